@@ -1,33 +1,112 @@
+//! Rebuilds the contract WASM in `wasm/` from source.
+//!
+//! The tests load the contracts through `contractimport!`, which reads the
+//! files in `wasm/`, so this script compiles each contract crate for
+//! `wasm32-unknown-unknown` and copies the result there whenever a contract
+//! changes. It runs a nested cargo in its own target directory, so it does not
+//! wait on the lock of the outer build.
+//!
+//! Set `TRUSTFUL_SKIP_WASM_BUILD=1` to use the committed WASM as they are.
+//! When the `wasm32-unknown-unknown` target is not installed, the committed
+//! WASM are used with a warning.
+use std::env;
 use std::fs;
-use std::path::Path;
-
-fn copy_wasm(contract_name: &str) {
-    let source = format!(
-        "target/wasm32-unknown-unknown/release/{}.wasm",
-        contract_name
-    );
-    let dest = format!("wasm/{}.wasm", contract_name);
-    
-    if Path::new(&source).exists() {
-        fs::copy(&source, &dest).unwrap();
-    }
-}
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const TARGET: &str = "wasm32-unknown-unknown";
+
+// Contracts built into `wasm/`
+const CONTRACTS: &[&str] = &["scorer", "deployer", "scorer_factory", "mock_issuer", "issuer_registry", "quests", "badge_token", "reward_distributor", "staking", "price_adapter", "delegation_registry", "analytics", "policy_account", "passkey_wallet", "timelock", "multisig", "upgrade_coordinator", "attestation_bridge", "membership", "disputes", "badge_governance", "scoreboard", "factory_governance"];
+
+// Crates the contracts are built from, watched for changes
+const SOURCES: &[&str] = &["scorer", "deployer", "scorer_factory", "mock_issuer", "issuer_registry", "quests", "badge_token", "reward_distributor", "staking", "price_adapter", "delegation_registry", "analytics", "policy_account", "passkey_wallet", "timelock", "multisig", "upgrade_coordinator", "attestation_bridge", "membership", "disputes", "badge_governance", "scoreboard", "factory_governance", "scorer_interface"];
 
 fn main() {
-    println!("cargo:rerun-if-changed=contracts/");
-    
-    // Cria a pasta wasm se não existir
+    for source in SOURCES {
+        println!("cargo:rerun-if-changed=contracts/{}/src", source);
+        println!("cargo:rerun-if-changed=contracts/{}/Cargo.toml", source);
+    }
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-env-changed=TRUSTFUL_SKIP_WASM_BUILD");
+
     fs::create_dir_all("wasm").unwrap();
-    
-    // Lista de contratos para build
-    let contracts = vec![
-        "scorer",
-        "deployer",
-        "scorer_factory"
-    ];
-    
-    // Copia o WASM de cada contrato
-    for contract in contracts {
-        copy_wasm(contract);
+
+    if env::var_os("TRUSTFUL_SKIP_WASM_BUILD").is_some() {
+        check_committed_wasm("TRUSTFUL_SKIP_WASM_BUILD is set");
+        return;
+    }
+    if !target_installed() {
+        check_committed_wasm(&format!("the {} target is not installed", TARGET));
+        return;
+    }
+
+    let target_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("target/wasm-build");
+    for contract in CONTRACTS {
+        build_wasm(contract, &target_dir);
+        let source = target_dir.join(TARGET).join("release").join(format!("{}.wasm", contract));
+        copy_if_changed(&source, &wasm_path(contract));
+    }
+}
+
+// Compiles `contract` the same way as the `build-wasm` script in `Cargo.toml`.
+fn build_wasm(contract: &str, target_dir: &Path) {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["build", "--release", "--target", TARGET, "-p", contract, "--target-dir"])
+        .arg(target_dir)
+        // The outer build's flags and wrappers are meant for the host target
+        .env_remove("RUSTFLAGS")
+        .env_remove("CARGO_TARGET_DIR")
+        .env_remove("CARGO_BUILD_TARGET")
+        .env_remove("RUSTC_WORKSPACE_WRAPPER")
+        .env("CARGO_ENCODED_RUSTFLAGS", "-Ctarget-feature=-reference-types")
+        .output()
+        .unwrap_or_else(|error| panic!("cannot run cargo to build {}: {}", contract, error));
+
+    if !output.status.success() {
+        panic!(
+            "building the {} contract for {} failed:\n{}",
+            contract,
+            TARGET,
+            String::from_utf8_lossy(&output.stderr)
+        );
     }
 }
+
+fn target_installed() -> bool {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let sysroot = Command::new(rustc)
+        .args(["--print", "sysroot"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    match sysroot {
+        Some(sysroot) => Path::new(&sysroot).join("lib/rustlib").join(TARGET).exists(),
+        None => false,
+    }
+}
+
+// Falls back to the WASM in `wasm/`, which may be stale.
+fn check_committed_wasm(reason: &str) {
+    println!("cargo:warning=not rebuilding the contract WASM ({}); tests use the files in wasm/", reason);
+    for contract in CONTRACTS {
+        let path = wasm_path(contract);
+        if !path.exists() {
+            panic!("{} is missing and cannot be built because {}", path.display(), reason);
+        }
+    }
+}
+
+// Leaves unchanged files untouched, so their timestamps do not trigger rebuilds.
+fn copy_if_changed(source: &Path, dest: &Path) {
+    let wasm = fs::read(source).unwrap_or_else(|error| panic!("cannot read {}: {}", source.display(), error));
+    if fs::read(dest).ok().as_deref() != Some(wasm.as_slice()) {
+        fs::write(dest, wasm).unwrap();
+    }
+}
+
+fn wasm_path(contract: &str) -> PathBuf {
+    Path::new("wasm").join(format!("{}.wasm", contract))
+}
@@ -0,0 +1,65 @@
+//! Conversions from command-line values to contract arguments.
+
+use std::str::FromStr;
+
+use trustful_client::{scval, BadgeId, ScAddress};
+
+use crate::error::Error;
+
+/// A badge given as `NAME:ISSUER:SCORE`, e.g. `SQL0001:GDQM...:3`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BadgeSpec {
+    pub name: String,
+    pub issuer: ScAddress,
+    pub score: u32,
+}
+
+impl BadgeSpec {
+    /// The badge and its score.
+    pub fn badge(&self) -> (BadgeId, u32) {
+        (BadgeId { name: self.name.clone(), issuer: self.issuer.clone() }, self.score)
+    }
+}
+
+impl FromStr for BadgeSpec {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        // Badge names may contain `:`, so the issuer and score are taken from the end
+        let mut parts = value.rsplitn(3, ':');
+        let (Some(score), Some(issuer), Some(name)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(Error::InvalidBadge(value.to_string()));
+        };
+        let score = score.parse().map_err(|_| Error::InvalidBadge(value.to_string()))?;
+
+        Ok(BadgeSpec {
+            name: name.to_string(),
+            issuer: parse_address(issuer)?,
+            score,
+        })
+    }
+}
+
+/// Parses a `G...` account or `C...` contract strkey.
+pub fn parse_address(value: &str) -> Result<ScAddress, Error> {
+    Ok(scval::parse_address(value)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ACCOUNT: &str = "GDQMNGUDOSMCCN6MD52DPXX4ACECXVODFK2NQQGFXYLGXJFZ2LEEIY35";
+
+    #[test]
+    fn test_parse_badge() {
+        let badge: BadgeSpec = format!("SQL:0001:{}:3", ACCOUNT).parse().unwrap();
+        assert_eq!(badge.name, "SQL:0001");
+        assert_eq!(scval::format_address(&badge.issuer), ACCOUNT);
+        assert_eq!(badge.score, 3);
+
+        assert!(format!("SQL0001:{}", ACCOUNT).parse::<BadgeSpec>().is_err());
+        assert!("SQL0001:not-an-address:3".parse::<BadgeSpec>().is_err());
+        assert!(format!("SQL0001:{}:high", ACCOUNT).parse::<BadgeSpec>().is_err());
+    }
+}
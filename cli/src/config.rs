@@ -0,0 +1,164 @@
+//! Network configuration.
+//!
+//! Each network has an env file under `config/` holding its RPC URL, network
+//! passphrase and known contract ids. The files are embedded as defaults and
+//! can be replaced by another file with `--config`; `TRUSTFUL_*` environment
+//! variables override the values of the file. The deploy scripts read the same
+//! files through `scripts/config.sh`.
+
+use std::{collections::BTreeMap, path::Path};
+
+use clap::ValueEnum;
+use trustful_client::ScAddress;
+
+use crate::{args, error::Error};
+
+const RPC_URL: &str = "TRUSTFUL_RPC_URL";
+const NETWORK_PASSPHRASE: &str = "TRUSTFUL_NETWORK_PASSPHRASE";
+const DEPLOYER_ID: &str = "TRUSTFUL_DEPLOYER_ID";
+const FACTORY_ID: &str = "TRUSTFUL_FACTORY_ID";
+const SCORER_ID: &str = "TRUSTFUL_SCORER_ID";
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Network {
+    Local,
+    Testnet,
+    Mainnet,
+}
+
+impl Network {
+    fn default_config(self) -> &'static str {
+        match self {
+            Network::Local => include_str!("../../config/local.env"),
+            Network::Testnet => include_str!("../../config/testnet.env"),
+            Network::Mainnet => include_str!("../../config/mainnet.env"),
+        }
+    }
+}
+
+/// The resolved configuration of a network.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub rpc_url: String,
+    pub network_passphrase: String,
+    deployer_id: Option<ScAddress>,
+    factory_id: Option<ScAddress>,
+    scorer_id: Option<ScAddress>,
+}
+
+impl Config {
+    /// Resolves the configuration of `network` from the env file at `file`, or
+    /// the network's default file, overridden by the `TRUSTFUL_*` environment
+    /// variables.
+    pub fn load(network: Network, file: Option<&Path>) -> Result<Self, Error> {
+        let contents = match file {
+            Some(file) => std::fs::read_to_string(file)?,
+            None => network.default_config().to_string(),
+        };
+        Self::resolve(&contents, |key| std::env::var(key).ok())
+    }
+
+    fn resolve(contents: &str, env: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        let file = parse_env_file(contents);
+        let value = |key: &str| {
+            env(key)
+                .or_else(|| file.get(key).cloned())
+                .filter(|value| !value.is_empty())
+        };
+        let required = |key: &'static str| value(key).ok_or(Error::MissingConfig(key));
+        let address = |key: &str| value(key).map(|value| args::parse_address(&value)).transpose();
+
+        Ok(Config {
+            rpc_url: required(RPC_URL)?,
+            network_passphrase: required(NETWORK_PASSPHRASE)?,
+            deployer_id: address(DEPLOYER_ID)?,
+            factory_id: address(FACTORY_ID)?,
+            scorer_id: address(SCORER_ID)?,
+        })
+    }
+
+    /// Returns `id`, or the configured deployer id when `id` is not given.
+    pub fn deployer_id(&self, id: Option<ScAddress>) -> Result<ScAddress, Error> {
+        id.or_else(|| self.deployer_id.clone()).ok_or(Error::MissingConfig(DEPLOYER_ID))
+    }
+
+    /// Returns `id`, or the configured factory id when `id` is not given.
+    pub fn factory_id(&self, id: Option<ScAddress>) -> Result<ScAddress, Error> {
+        id.or_else(|| self.factory_id.clone()).ok_or(Error::MissingConfig(FACTORY_ID))
+    }
+
+    /// Returns `id`, or the configured scorer id when `id` is not given.
+    pub fn scorer_id(&self, id: Option<ScAddress>) -> Result<ScAddress, Error> {
+        id.or_else(|| self.scorer_id.clone()).ok_or(Error::MissingConfig(SCORER_ID))
+    }
+}
+
+// Parses `KEY=VALUE` lines, skipping blank lines and `#` comments. Values may
+// be double-quoted so the files can also be sourced by the shell scripts.
+fn parse_env_file(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ACCOUNT: &str = "GDQMNGUDOSMCCN6MD52DPXX4ACECXVODFK2NQQGFXYLGXJFZ2LEEIY35";
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn test_default_configs() {
+        let testnet = Config::resolve(Network::Testnet.default_config(), no_env).unwrap();
+        assert_eq!(testnet.rpc_url, "https://soroban-testnet.stellar.org");
+        assert_eq!(testnet.network_passphrase, "Test SDF Network ; September 2015");
+        assert_eq!(testnet.deployer_id, None);
+
+        let local = Config::resolve(Network::Local.default_config(), no_env).unwrap();
+        assert_eq!(local.network_passphrase, "Standalone Network ; February 2017");
+
+        // Mainnet has no default RPC server
+        let mainnet = Config::resolve(Network::Mainnet.default_config(), no_env);
+        assert!(matches!(mainnet, Err(Error::MissingConfig(RPC_URL))));
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let contents = format!("# comment\nTRUSTFUL_RPC_URL=http://file\n\nTRUSTFUL_DEPLOYER_ID={}\n", ACCOUNT);
+        let env = |key: &str| match key {
+            RPC_URL => Some("http://env".to_string()),
+            NETWORK_PASSPHRASE => Some("Env Network".to_string()),
+            _ => None,
+        };
+
+        let config = Config::resolve(&contents, env).unwrap();
+        assert_eq!(config.rpc_url, "http://env");
+        assert_eq!(config.network_passphrase, "Env Network");
+        assert_eq!(config.deployer_id, Some(args::parse_address(ACCOUNT).unwrap()));
+        assert_eq!(config.factory_id, None);
+    }
+
+    #[test]
+    fn test_invalid_contract_id() {
+        let contents = "TRUSTFUL_RPC_URL=http://file\nTRUSTFUL_NETWORK_PASSPHRASE=x\nTRUSTFUL_FACTORY_ID=nope\n";
+        assert!(matches!(
+            Config::resolve(contents, no_env),
+            Err(Error::Client(trustful_client::Error::InvalidAddress(_)))
+        ));
+    }
+}
@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid badge `{0}`, expected NAME:ISSUER:SCORE")]
+    InvalidBadge(String),
+    #[error("missing `{0}`, set it in the network config file or the environment")]
+    MissingConfig(&'static str),
+    #[error("unexpected result {0:?}")]
+    UnexpectedResult(stellar_xdr::curr::ScVal),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Client(#[from] trustful_client::Error),
+}
@@ -0,0 +1,236 @@
+//! Admin CLI for the Trustful contracts.
+//!
+//! Every command is signed by the source account given by `TRUSTFUL_SECRET_KEY`
+//! and submitted to the RPC server of the network selected with `--network`,
+//! as configured in `config/<network>.env`.
+
+mod args;
+mod config;
+mod error;
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use rand::RngCore;
+use stellar_xdr::curr::ScVal;
+use trustful_client::{scval, BadgeId, NewScorer, ScAddress, TrustfulClient};
+
+use args::BadgeSpec;
+use config::{Config, Network};
+use error::Error;
+
+#[derive(Parser)]
+#[command(name = "trustful-cli", about = "Administer Trustful deployments on Soroban")]
+struct Cli {
+    #[command(flatten)]
+    network: NetworkArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct NetworkArgs {
+    /// Network to use
+    #[arg(long, env = "TRUSTFUL_NETWORK", value_enum, default_value = "testnet")]
+    network: Network,
+    /// Config file to use instead of the network's default config
+    #[arg(long, env = "TRUSTFUL_CONFIG")]
+    config: Option<PathBuf>,
+    /// Soroban RPC server URL, overriding the config
+    #[arg(long)]
+    rpc_url: Option<String>,
+    /// Passphrase of the network the RPC server belongs to, overriding the config
+    #[arg(long)]
+    network_passphrase: Option<String>,
+    /// Secret key (S...) of the source account signing the transactions
+    #[arg(long, env = "TRUSTFUL_SECRET_KEY", hide_env_values = true)]
+    secret_key: String,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Upload the scorer and factory WASM and deploy a factory through the deployer
+    DeployFactory {
+        /// Deployer contract id [default: the configured deployer]
+        #[arg(long, value_parser = args::parse_address)]
+        deployer: Option<ScAddress>,
+        #[arg(long, default_value = "wasm/scorer.wasm")]
+        scorer_wasm: PathBuf,
+        #[arg(long, default_value = "wasm/scorer_factory.wasm")]
+        factory_wasm: PathBuf,
+    },
+    /// Create a scorer through the factory
+    CreateScorer {
+        /// Factory contract id [default: the configured factory]
+        #[arg(long, value_parser = args::parse_address)]
+        factory: Option<ScAddress>,
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        description: String,
+        #[arg(long)]
+        icon: String,
+        /// Initial badge as NAME:ISSUER:SCORE, may be repeated
+        #[arg(long = "badge")]
+        badges: Vec<BadgeSpec>,
+    },
+    /// Add a badge to a scorer
+    AddBadge {
+        #[command(flatten)]
+        badge: BadgeArgs,
+        #[arg(long)]
+        score: u32,
+    },
+    /// Remove a badge from a scorer
+    RemoveBadge {
+        #[command(flatten)]
+        badge: BadgeArgs,
+    },
+    /// Register the source account as a user of a scorer
+    AddUser {
+        /// Scorer contract id [default: the configured scorer]
+        #[arg(long, value_parser = args::parse_address)]
+        scorer: Option<ScAddress>,
+    },
+    /// Deactivate the source account as a user of a scorer
+    RemoveUser {
+        /// Scorer contract id [default: the configured scorer]
+        #[arg(long, value_parser = args::parse_address)]
+        scorer: Option<ScAddress>,
+    },
+    /// Attest a badge issued by the source account to a user, by minting its token
+    Attest {
+        /// Badge token contract id of the scorer
+        #[arg(long, value_parser = args::parse_address)]
+        badge_token: ScAddress,
+        #[arg(long, value_parser = args::parse_address)]
+        user: ScAddress,
+        /// Name of the badge, whose issuer is the source account
+        #[arg(long)]
+        name: String,
+    },
+    /// Add a manager to a scorer
+    AddManager {
+        #[command(flatten)]
+        manager: ManagerArgs,
+    },
+    /// Remove a manager from a scorer
+    RemoveManager {
+        #[command(flatten)]
+        manager: ManagerArgs,
+    },
+}
+
+#[derive(Args)]
+struct BadgeArgs {
+    /// Scorer contract id [default: the configured scorer]
+    #[arg(long, value_parser = args::parse_address)]
+    scorer: Option<ScAddress>,
+    #[arg(long)]
+    name: String,
+    #[arg(long, value_parser = args::parse_address)]
+    issuer: ScAddress,
+}
+
+impl BadgeArgs {
+    fn id(&self) -> BadgeId {
+        BadgeId { name: self.name.clone(), issuer: self.issuer.clone() }
+    }
+}
+
+#[derive(Args)]
+struct ManagerArgs {
+    /// Scorer contract id [default: the configured scorer]
+    #[arg(long, value_parser = args::parse_address)]
+    scorer: Option<ScAddress>,
+    #[arg(long, value_parser = args::parse_address)]
+    manager: ScAddress,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    if let Err(error) = run(cli).await {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Error> {
+    let network = cli.network;
+    let mut config = Config::load(network.network, network.config.as_deref())?;
+    if let Some(rpc_url) = network.rpc_url {
+        config.rpc_url = rpc_url;
+    }
+    if let Some(network_passphrase) = network.network_passphrase {
+        config.network_passphrase = network_passphrase;
+    }
+    let client = TrustfulClient::new(&config.rpc_url, &config.network_passphrase, &network.secret_key)?;
+    let source = scval::address(&client.source());
+
+    match cli.command {
+        Command::DeployFactory { deployer, scorer_wasm, factory_wasm } => {
+            let scorer_hash = client.upload_wasm(&std::fs::read(scorer_wasm)?).await?;
+            println!("scorer wasm hash: {}", hex::encode(scorer_hash));
+            let factory_hash = client.upload_wasm(&std::fs::read(factory_wasm)?).await?;
+            println!("factory wasm hash: {}", hex::encode(factory_hash));
+
+            let init_args = scval::vec(vec![source.clone(), scval::bytes(&scorer_hash)?])?;
+            let args = vec![
+                source,
+                scval::bytes(&factory_hash)?,
+                scval::bytes(&salt())?,
+                scval::symbol("initialize")?,
+                init_args,
+            ];
+            let result = client.invoke(&config.deployer_id(deployer)?, "deploy", args).await?;
+            match result {
+                ScVal::Address(factory) => println!("factory: {}", scval::format_address(&factory)),
+                _ => return Err(Error::UnexpectedResult(result)),
+            }
+        }
+        Command::CreateScorer { factory, name, description, icon, badges } => {
+            let scorer = NewScorer {
+                name,
+                description,
+                icon,
+                badges: badges.iter().map(BadgeSpec::badge).collect(),
+            };
+            let scorer = client.create_scorer(&config.factory_id(factory)?, &scorer).await?;
+            println!("scorer: {}", scval::format_address(&scorer));
+        }
+        Command::AddBadge { badge, score } => {
+            let id = badge.id();
+            client.add_badge(&config.scorer_id(badge.scorer)?, &id, score).await?;
+        }
+        Command::RemoveBadge { badge } => {
+            let id = badge.id();
+            client.remove_badge(&config.scorer_id(badge.scorer)?, &id).await?;
+        }
+        Command::AddUser { scorer } => {
+            client.add_user(&config.scorer_id(scorer)?).await?;
+        }
+        Command::RemoveUser { scorer } => {
+            client.remove_user(&config.scorer_id(scorer)?).await?;
+        }
+        Command::Attest { badge_token, user, name } => {
+            let badge = BadgeId { name, issuer: client.source() };
+            client.attest_badge(&badge_token, &user, &badge).await?;
+        }
+        Command::AddManager { manager } => {
+            client.add_manager(&config.scorer_id(manager.scorer)?, &manager.manager).await?;
+        }
+        Command::RemoveManager { manager } => {
+            client.remove_manager(&config.scorer_id(manager.scorer)?, &manager.manager).await?;
+        }
+    }
+
+    Ok(())
+}
+
+// Random salt so every deployment gets a fresh contract address
+fn salt() -> [u8; 32] {
+    let mut salt = [0; 32];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
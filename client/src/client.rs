@@ -0,0 +1,231 @@
+//! Builds, signs and submits contract invocations through Soroban RPC.
+
+use ed25519_dalek::{Signer, SigningKey};
+use rand::RngCore;
+use stellar_rpc_client::Client;
+use stellar_strkey::ed25519;
+use stellar_xdr::curr::{
+    DecoratedSignature, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Memo, MuxedAccount, Operation,
+    OperationBody, Preconditions, ScAddress, ScSymbol, ScVal, SequenceNumber, Signature, SignatureHint, Transaction,
+    TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256, VecM,
+};
+
+use crate::{error::Error, scval, BadgeId, NewScorer};
+
+// Inclusion fee in stroops; the resource fee is added from the simulation
+const BASE_FEE: u32 = 100;
+
+/// Calls the Trustful contracts on behalf of a single source account.
+pub struct TrustfulClient {
+    client: Client,
+    network_passphrase: String,
+    key: SigningKey,
+}
+
+impl TrustfulClient {
+    /// Creates a client for the RPC server at `rpc_url` signing with `secret_key` (an `S...` strkey).
+    pub fn new(rpc_url: &str, network_passphrase: &str, secret_key: &str) -> Result<Self, Error> {
+        let ed25519::PrivateKey(seed) =
+            ed25519::PrivateKey::from_string(secret_key).map_err(|_| Error::InvalidSecretKey)?;
+
+        Ok(TrustfulClient {
+            client: Client::new(rpc_url)?,
+            network_passphrase: network_passphrase.to_string(),
+            key: SigningKey::from_bytes(&seed),
+        })
+    }
+
+    /// The address of the source account, which authorizes the invocations.
+    pub fn source(&self) -> ScAddress {
+        scval::account_address(self.key.verifying_key().to_bytes())
+    }
+
+    /// Creates a scorer through `factory`, with the source account as its creator,
+    /// and returns its address.
+    pub async fn create_scorer(&self, factory: &ScAddress, scorer: &NewScorer) -> Result<ScAddress, Error> {
+        let source = scval::address(&self.source());
+        let init_args = scval::vec(vec![
+            source.clone(),
+            scval::badge_map(&scorer.badges)?,
+            scval::string(&scorer.name)?,
+            scval::string(&scorer.description)?,
+            scval::string(&scorer.icon)?,
+        ])?;
+        let args = vec![source, scval::bytes(&salt())?, scval::symbol("initialize")?, init_args];
+        scval::parse_address_val(&self.invoke(factory, "create_scorer", args).await?)
+    }
+
+    /// Returns the badges of `scorer` and their scores.
+    pub async fn get_badges(&self, scorer: &ScAddress) -> Result<Vec<(BadgeId, u32)>, Error> {
+        scval::parse_badge_map(&self.simulate(scorer, "get_badges", vec![]).await?)
+    }
+
+    /// Attests `badge` to `user` by minting its token from `badge_token`, with
+    /// the source account as the badge issuer.
+    pub async fn attest_badge(&self, badge_token: &ScAddress, user: &ScAddress, badge: &BadgeId) -> Result<(), Error> {
+        let args = vec![scval::address(&self.source()), scval::address(user), scval::badge_id(badge)?];
+        self.invoke(badge_token, "mint_badge", args).await?;
+        Ok(())
+    }
+
+    /// Adds `badge` to `scorer` with `score`, authorized by the source account as a manager.
+    pub async fn add_badge(&self, scorer: &ScAddress, badge: &BadgeId, score: u32) -> Result<(), Error> {
+        let args = vec![
+            scval::address(&self.source()),
+            scval::string(&badge.name)?,
+            scval::address(&badge.issuer),
+            ScVal::U32(score),
+        ];
+        self.invoke(scorer, "add_badge", args).await?;
+        Ok(())
+    }
+
+    /// Removes `badge` from `scorer`, authorized by the source account as a manager.
+    pub async fn remove_badge(&self, scorer: &ScAddress, badge: &BadgeId) -> Result<(), Error> {
+        let args = vec![scval::address(&self.source()), scval::string(&badge.name)?, scval::address(&badge.issuer)];
+        self.invoke(scorer, "remove_badge", args).await?;
+        Ok(())
+    }
+
+    /// Registers the source account as a user of `scorer`.
+    pub async fn add_user(&self, scorer: &ScAddress) -> Result<(), Error> {
+        self.invoke(scorer, "add_user", vec![scval::address(&self.source())]).await?;
+        Ok(())
+    }
+
+    /// Deactivates the source account as a user of `scorer`.
+    pub async fn remove_user(&self, scorer: &ScAddress) -> Result<(), Error> {
+        self.invoke(scorer, "remove_user", vec![scval::address(&self.source())]).await?;
+        Ok(())
+    }
+
+    /// Extends the storage entries of `user` in `scorer` once they were restored,
+    /// and returns how many were extended.
+    pub async fn restore_user(&self, scorer: &ScAddress, user: &ScAddress) -> Result<u32, Error> {
+        let entry = scval::vec(vec![scval::symbol("User")?, scval::address(user)])?;
+        match self.invoke(scorer, "restore_entry", vec![entry]).await? {
+            ScVal::U32(restored) => Ok(restored),
+            result => Err(Error::UnexpectedResult(result)),
+        }
+    }
+
+    /// Extends the storage entries of `badge` in `scorer` once they were restored,
+    /// and returns how many were extended.
+    pub async fn restore_badge(&self, scorer: &ScAddress, badge: &BadgeId) -> Result<u32, Error> {
+        let entry = scval::vec(vec![scval::symbol("Badge")?, scval::badge_id(badge)?])?;
+        match self.invoke(scorer, "restore_entry", vec![entry]).await? {
+            ScVal::U32(restored) => Ok(restored),
+            result => Err(Error::UnexpectedResult(result)),
+        }
+    }
+
+    /// Adds `manager` to `scorer`, authorized by the source account as the scorer creator.
+    pub async fn add_manager(&self, scorer: &ScAddress, manager: &ScAddress) -> Result<(), Error> {
+        let args = vec![scval::address(&self.source()), scval::address(manager)];
+        self.invoke(scorer, "add_manager", args).await?;
+        Ok(())
+    }
+
+    /// Removes `manager` from `scorer`, authorized by the source account as the scorer creator.
+    pub async fn remove_manager(&self, scorer: &ScAddress, manager: &ScAddress) -> Result<(), Error> {
+        let args = vec![scval::address(&self.source()), scval::address(manager)];
+        self.invoke(scorer, "remove_manager", args).await?;
+        Ok(())
+    }
+
+    /// Uploads `wasm` and returns its hash.
+    pub async fn upload_wasm(&self, wasm: &[u8]) -> Result<[u8; 32], Error> {
+        match self.submit(HostFunction::UploadContractWasm(wasm.try_into()?)).await? {
+            ScVal::Bytes(hash) => hash.as_slice().try_into().map_err(|_| Error::UnexpectedResult(ScVal::Bytes(hash))),
+            result => Err(Error::UnexpectedResult(result)),
+        }
+    }
+
+    /// Invokes `function` on `contract` and returns its result.
+    pub async fn invoke(&self, contract: &ScAddress, function: &str, args: Vec<ScVal>) -> Result<ScVal, Error> {
+        self.submit(invoke_contract(contract, function, args)?).await
+    }
+
+    /// Simulates calling `function` on `contract` and returns its result, without
+    /// submitting a transaction. Meant for read-only functions. Fails with
+    /// `Error::ArchivedState` when the call reads archived storage entries.
+    pub async fn simulate(&self, contract: &ScAddress, function: &str, args: Vec<ScVal>) -> Result<ScVal, Error> {
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: self.transaction(invoke_contract(contract, function, args)?).await?,
+            signatures: VecM::default(),
+        });
+        let response = self.client.simulate_transaction_envelope(&envelope).await?;
+        if let Some(error) = response.error {
+            return Err(Error::SimulationFailed(error));
+        }
+        // Reads of archived entries need a RestoreFootprint operation first
+        if response.restore_preamble.is_some() {
+            return Err(Error::ArchivedState);
+        }
+        match response.results()?.pop() {
+            Some(result) => Ok(result.xdr),
+            None => Err(Error::SimulationFailed("no result".to_string())),
+        }
+    }
+
+    // Simulates the call to fill in its footprint, auth and fees, then signs and sends it.
+    // Addresses required to authorize the call other than the source account are not supported.
+    async fn submit(&self, host_function: HostFunction) -> Result<ScVal, Error> {
+        let tx = self.transaction(host_function).await?;
+        let assembled = self.client.simulate_and_assemble_transaction(&tx).await?;
+        let hash = assembled.hash(&self.network_passphrase)?;
+        let public_key = self.key.verifying_key().to_bytes();
+        let signature = DecoratedSignature {
+            hint: SignatureHint(public_key[28..].try_into().unwrap()),
+            signature: Signature(self.key.sign(&hash).to_bytes().try_into()?),
+        };
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: assembled.transaction().clone(),
+            signatures: vec![signature].try_into()?,
+        });
+
+        let response = self.client.send_transaction_polling(&envelope).await?;
+        Ok(response.return_value()?)
+    }
+
+    // Builds an unsigned transaction of the source account performing `host_function`
+    async fn transaction(&self, host_function: HostFunction) -> Result<Transaction, Error> {
+        let public_key = self.key.verifying_key().to_bytes();
+        let account = self
+            .client
+            .get_account(&ed25519::PublicKey(public_key).to_string())
+            .await?;
+
+        Ok(Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256(public_key)),
+            fee: BASE_FEE,
+            seq_num: SequenceNumber(account.seq_num.0 + 1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                    host_function,
+                    auth: VecM::default(),
+                }),
+            }]
+            .try_into()?,
+            ext: TransactionExt::V0,
+        })
+    }
+}
+
+fn invoke_contract(contract: &ScAddress, function: &str, args: Vec<ScVal>) -> Result<HostFunction, Error> {
+    Ok(HostFunction::InvokeContract(InvokeContractArgs {
+        contract_address: contract.clone(),
+        function_name: ScSymbol(function.try_into()?),
+        args: args.try_into()?,
+    }))
+}
+
+// Random salt so every deployment gets a fresh contract address
+fn salt() -> [u8; 32] {
+    let mut salt = [0; 32];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
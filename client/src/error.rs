@@ -0,0 +1,26 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid address `{0}`, expected a G... account or C... contract")]
+    InvalidAddress(String),
+    #[error("invalid secret key, expected an S... strkey")]
+    InvalidSecretKey,
+    #[error("unexpected result {0:?}")]
+    UnexpectedResult(stellar_xdr::curr::ScVal),
+    #[error("simulation failed: {0}")]
+    SimulationFailed(String),
+    #[error("the call reads archived storage entries; restore them with a RestoreFootprint operation, then call `restore_entry` on the scorer to extend them")]
+    ArchivedState,
+    #[error(transparent)]
+    Rpc(Box<stellar_rpc_client::Error>),
+    #[error(transparent)]
+    Xdr(#[from] stellar_xdr::curr::Error),
+}
+
+// RPC errors are boxed to keep results small
+impl From<stellar_rpc_client::Error> for Error {
+    fn from(error: stellar_rpc_client::Error) -> Self {
+        Error::Rpc(Box::new(error))
+    }
+}
@@ -0,0 +1,65 @@
+//! The event catalog of the contracts, from `scorer_interface::events`.
+//!
+//! The contracts publish their events through the same topic constants and
+//! payload structs, so indexers decoding with this module read exactly what
+//! the deployed code emits.
+
+pub use scorer_interface::events::*;
+use soroban_sdk::{xdr, Env, TryFromVal, Val, Vec};
+use stellar_xdr::curr::{Limits, ScVal, WriteXdr};
+
+/// Decodes an event from its XDR topics and data, as returned by the RPC
+/// `getEvents` method. Returns `None` for events outside the catalog.
+///
+/// Filter events by the ids of the Trustful contracts first: the data of another
+/// contract publishing the same topics with a map of other fields makes the
+/// host panic.
+pub fn decode(topics: &[ScVal], data: &ScVal) -> Option<Event> {
+    let env = Env::default();
+    let mut values = Vec::new(&env);
+    for topic in topics {
+        values.push_back(Val::try_from_val(&env, &sdk_value(topic)?).ok()?);
+    }
+    Event::decode(&env, &values, Val::try_from_val(&env, &sdk_value(data)?).ok()?)
+}
+
+// Converts a value to the XDR version of the SDK, which can differ from the RPC client's
+fn sdk_value(value: &ScVal) -> Option<xdr::ScVal> {
+    let bytes = value.to_xdr(Limits::none()).ok()?;
+    xdr::ReadXdr::from_xdr(bytes, xdr::Limits::none()).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scval;
+    use stellar_xdr::curr::{ScMap, ScMapEntry};
+
+    const ACCOUNT: &str = "GDQMNGUDOSMCCN6MD52DPXX4ACECXVODFK2NQQGFXYLGXJFZ2LEEIY35";
+
+    fn user_joined() -> ScVal {
+        let user = scval::address(&scval::parse_address(ACCOUNT).unwrap());
+        ScVal::Map(Some(ScMap(vec![ScMapEntry { key: scval::symbol("user").unwrap(), val: user }].try_into().unwrap())))
+    }
+
+    #[test]
+    fn test_decode_scorer_event() {
+        let topics = [ScVal::U32(EVENT_SCHEMA_VERSION), scval::symbol("user").unwrap(), scval::symbol("add").unwrap()];
+        let Some(Event::UserJoined(event)) = decode(&topics, &user_joined()) else {
+            panic!("not decoded as UserJoined");
+        };
+        assert_eq!(event.user.to_string().to_string(), ACCOUNT);
+
+        // Scorer events of other schema versions, or without their version, are not decoded
+        let topics = [ScVal::U32(EVENT_SCHEMA_VERSION - 1), topics[1].clone(), topics[2].clone()];
+        assert_eq!(decode(&topics, &user_joined()), None);
+        assert_eq!(decode(&topics[1..], &user_joined()), None);
+    }
+
+    #[test]
+    fn test_decode_rejects_mismatched_data() {
+        let topics = [scval::symbol("badge").unwrap(), scval::symbol("issue").unwrap()];
+        assert_eq!(decode(&topics, &ScVal::U32(1)), None);
+        assert_eq!(decode(&topics[..1], &user_joined()), None);
+    }
+}
@@ -0,0 +1,34 @@
+//! Typed client for the Trustful contracts, for backend services.
+//!
+//! `TrustfulClient` signs with a single source account and talks to a Soroban
+//! RPC server: state-changing calls are simulated, signed and submitted, and
+//! read-only calls are only simulated. Contract values are converted from and
+//! to XDR by the `scval` module, so callers work with `ScAddress`es and the
+//! types below instead of building `ScVal`s by hand. Contract events are
+//! decoded with the `events` module, the catalog the contracts publish with.
+
+mod client;
+mod error;
+pub mod events;
+pub mod scval;
+
+pub use client::TrustfulClient;
+pub use error::Error;
+pub use stellar_xdr::curr::ScAddress;
+
+/// Identifier of a badge: its name and the address of its issuer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeId {
+    pub name: String,
+    pub issuer: ScAddress,
+}
+
+/// The metadata and initial badges of a scorer created through the factory.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NewScorer {
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    /// The badges and their scores.
+    pub badges: Vec<(BadgeId, u32)>,
+}
@@ -0,0 +1,150 @@
+//! Conversions between Rust values and contract values.
+
+use stellar_strkey::{ed25519, Contract, Strkey};
+use stellar_xdr::curr::{
+    AccountId, Hash, PublicKey, ScAddress, ScBytes, ScMap, ScMapEntry, ScString, ScSymbol, ScVal, ScVec, Uint256,
+};
+
+use crate::{error::Error, BadgeId};
+
+/// Parses a `G...` account or `C...` contract strkey.
+pub fn parse_address(value: &str) -> Result<ScAddress, Error> {
+    match Strkey::from_string(value) {
+        Ok(Strkey::PublicKeyEd25519(ed25519::PublicKey(key))) => Ok(account_address(key)),
+        Ok(Strkey::Contract(Contract(id))) => Ok(ScAddress::Contract(Hash(id))),
+        _ => Err(Error::InvalidAddress(value.to_string())),
+    }
+}
+
+/// Formats an address as its strkey.
+pub fn format_address(address: &ScAddress) -> String {
+    match address {
+        ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key)))) => {
+            ed25519::PublicKey(*key).to_string()
+        }
+        ScAddress::Contract(Hash(id)) => Contract(*id).to_string(),
+    }
+}
+
+pub fn account_address(key: [u8; 32]) -> ScAddress {
+    ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key))))
+}
+
+pub fn address(address: &ScAddress) -> ScVal {
+    ScVal::Address(address.clone())
+}
+
+pub fn string(value: &str) -> Result<ScVal, Error> {
+    Ok(ScVal::String(ScString(value.try_into()?)))
+}
+
+pub fn symbol(value: &str) -> Result<ScVal, Error> {
+    Ok(ScVal::Symbol(ScSymbol(value.try_into()?)))
+}
+
+pub fn bytes(value: &[u8]) -> Result<ScVal, Error> {
+    Ok(ScVal::Bytes(ScBytes(value.try_into()?)))
+}
+
+pub fn vec(values: Vec<ScVal>) -> Result<ScVal, Error> {
+    Ok(ScVal::Vec(Some(ScVec(values.try_into()?))))
+}
+
+/// Encodes badges as the scorer's `Map<BadgeId, u32>`.
+pub fn badge_map(badges: &[(BadgeId, u32)]) -> Result<ScVal, Error> {
+    let entries = badges
+        .iter()
+        .map(|(badge, score)| Ok((badge_id(badge)?, ScVal::U32(*score))))
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(ScVal::Map(Some(ScMap::sorted_from_pairs(entries.into_iter())?)))
+}
+
+// Contract structs are encoded as maps keyed by their field names.
+pub fn badge_id(badge: &BadgeId) -> Result<ScVal, Error> {
+    let fields = vec![
+        ScMapEntry { key: symbol("issuer")?, val: address(&badge.issuer) },
+        ScMapEntry { key: symbol("name")?, val: string(&badge.name)? },
+    ];
+    Ok(ScVal::Map(Some(ScMap(fields.try_into()?))))
+}
+
+/// Decodes the scorer's `Map<BadgeId, u32>`.
+pub fn parse_badge_map(value: &ScVal) -> Result<Vec<(BadgeId, u32)>, Error> {
+    let ScVal::Map(Some(map)) = value else {
+        return Err(unexpected(value));
+    };
+    map.iter()
+        .map(|entry| match &entry.val {
+            ScVal::U32(score) => Ok((parse_badge_id(&entry.key)?, *score)),
+            _ => Err(unexpected(value)),
+        })
+        .collect()
+}
+
+pub fn parse_badge_id(value: &ScVal) -> Result<BadgeId, Error> {
+    let ScVal::Map(Some(fields)) = value else {
+        return Err(unexpected(value));
+    };
+    match fields.as_slice() {
+        [ScMapEntry { val: ScVal::Address(issuer), .. }, ScMapEntry { val: ScVal::String(name), .. }] => Ok(BadgeId {
+            name: name.to_utf8_string_lossy(),
+            issuer: issuer.clone(),
+        }),
+        _ => Err(unexpected(value)),
+    }
+}
+
+pub fn parse_address_val(value: &ScVal) -> Result<ScAddress, Error> {
+    match value {
+        ScVal::Address(address) => Ok(address.clone()),
+        _ => Err(unexpected(value)),
+    }
+}
+
+fn unexpected(value: &ScVal) -> Error {
+    Error::UnexpectedResult(value.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ACCOUNT: &str = "GDQMNGUDOSMCCN6MD52DPXX4ACECXVODFK2NQQGFXYLGXJFZ2LEEIY35";
+
+    #[test]
+    fn test_parse_address() {
+        let contract = Contract([7; 32]).to_string();
+        assert_eq!(parse_address(&contract).unwrap(), ScAddress::Contract(Hash([7; 32])));
+        assert_eq!(format_address(&parse_address(ACCOUNT).unwrap()), ACCOUNT);
+        assert!(parse_address("SDJHRQF4GCMIIKAAAQ6IHY42X73FQFLHUULAPSKKD4DFDM7UXWWCRHBE").is_err());
+    }
+
+    #[test]
+    fn test_badge_map_is_sorted() {
+        let issuer = parse_address(ACCOUNT).unwrap();
+        let badges = [
+            (BadgeId { name: "B".to_string(), issuer: issuer.clone() }, 2),
+            (BadgeId { name: "A".to_string(), issuer }, 1),
+        ];
+
+        let ScVal::Map(Some(map)) = badge_map(&badges).unwrap() else {
+            panic!("badges are not encoded as a map");
+        };
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[0].val, ScVal::U32(1));
+        assert_eq!(map[1].val, ScVal::U32(2));
+    }
+
+    #[test]
+    fn test_parse_badge_map() {
+        let issuer = parse_address(ACCOUNT).unwrap();
+        let badges = vec![
+            (BadgeId { name: "A".to_string(), issuer: issuer.clone() }, 1),
+            (BadgeId { name: "B".to_string(), issuer: issuer.clone() }, 2),
+        ];
+        assert_eq!(parse_badge_map(&badge_map(&badges).unwrap()).unwrap(), badges);
+
+        assert!(matches!(parse_badge_map(&ScVal::U32(1)), Err(Error::UnexpectedResult(_))));
+        assert!(matches!(parse_badge_id(&address(&issuer)), Err(Error::UnexpectedResult(_))));
+    }
+}
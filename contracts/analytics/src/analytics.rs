@@ -0,0 +1,263 @@
+#![no_std]
+use scorer_interface::{AnalyticsTrait, BadgeId, ScorerClient};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Env, Map, Vec};
+
+// Ledgers per epoch, about a day at 5 seconds per ledger
+const EPOCH_LEDGERS: u32 = 17_280;
+
+// Most entries returned by a paginated getter
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// The activity of a scorer during an epoch.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EpochStats {
+    /// The epoch, the ledger sequence divided by the epoch length.
+    pub epoch: u32,
+    /// The number of users who joined.
+    pub joins: u32,
+    /// The number of members who left.
+    pub leaves: u32,
+}
+
+#[contracttype]
+enum DataKey {
+    Epoch(Address, u32),
+    Attestations(Address),
+}
+
+/// Errors raised by the analytics contract.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The badge is not part of the scorer.
+    BadgeNotFound = 1,
+}
+
+/// Counters of scorer activity, read by dashboards without an indexer.
+///
+/// Scorers report joins and departures once they register this contract as
+/// their `Extension::Analytics`, and issuers report the attestations of their
+/// badges. Joins and departures are counted per epoch of `EPOCH_LEDGERS`
+/// ledgers, so growth and churn can be charted over time; attestations are
+/// counted per badge. Every report is authorized by the scorer or the badge
+/// issuer, so counters cannot be inflated by third parties. Memberships that
+/// expire are not reported as departures.
+#[contract]
+pub struct AnalyticsContract;
+
+#[contractimpl]
+impl AnalyticsContract {
+    /// Returns the current epoch
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    pub fn get_epoch(env: Env) -> u32 {
+        env.ledger().sequence() / EPOCH_LEDGERS
+    }
+
+    /// Returns the activity of a scorer over consecutive epochs
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer to read
+    /// * `start` - The first epoch
+    /// * `limit` - The number of epochs, at most `MAX_PAGE_SIZE`
+    ///
+    /// # Returns
+    /// * `Vec<EpochStats>` - The activity of each epoch from `start`, up to the current epoch
+    pub fn get_epoch_stats(env: Env, scorer: Address, start: u32, limit: u32) -> Vec<EpochStats> {
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(Self::get_epoch(env.clone()) + 1);
+        let mut stats = Vec::new(&env);
+        for epoch in start..end {
+            stats.push_back(Self::epoch_stats(&env, &scorer, epoch));
+        }
+        stats
+    }
+
+    /// Returns the number of attestations of the badges of a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer to read
+    /// * `start` - The index of the first badge, in the order of the badge ids
+    /// * `limit` - The number of badges, at most `MAX_PAGE_SIZE`
+    ///
+    /// # Returns
+    /// * `Vec<(BadgeId, u32)>` - The badges with at least one attestation and their count
+    pub fn get_attestations(env: Env, scorer: Address, start: u32, limit: u32) -> Vec<(BadgeId, u32)> {
+        let attestations = Self::attestations(&env, &scorer);
+        let badges = attestations.keys();
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(badges.len());
+        let mut page = Vec::new(&env);
+        for index in start..end {
+            let badge = badges.get_unchecked(index);
+            page.push_back((badge.clone(), attestations.get_unchecked(badge)));
+        }
+        page
+    }
+
+    /// Returns the number of attestations of a badge of a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer to read
+    /// * `badge` - The badge to read
+    pub fn get_attestation_count(env: Env, scorer: Address, badge: BadgeId) -> u32 {
+        Self::attestations(&env, &scorer).get(badge).unwrap_or(0)
+    }
+
+    fn epoch_stats(env: &Env, scorer: &Address, epoch: u32) -> EpochStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Epoch(scorer.clone(), epoch))
+            .unwrap_or(EpochStats { epoch, ..Default::default() })
+    }
+
+    fn attestations(env: &Env, scorer: &Address) -> Map<BadgeId, u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attestations(scorer.clone()))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn update_epoch(env: &Env, scorer: &Address, update: impl FnOnce(&mut EpochStats)) {
+        let epoch = Self::get_epoch(env.clone());
+        let mut stats = Self::epoch_stats(env, scorer, epoch);
+        update(&mut stats);
+        env.storage().persistent().set(&DataKey::Epoch(scorer.clone(), epoch), &stats);
+    }
+}
+
+#[contractimpl]
+impl AnalyticsTrait for AnalyticsContract {
+    /// Counts a user joining a scorer in the current epoch
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer the user joined
+    ///
+    /// # Panics
+    /// * When the scorer fails authentication
+    fn record_join(env: Env, scorer: Address) {
+        scorer.require_auth();
+        Self::update_epoch(&env, &scorer, |stats| stats.joins += 1);
+    }
+
+    /// Counts a member leaving a scorer in the current epoch
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer the member left
+    ///
+    /// # Panics
+    /// * When the scorer fails authentication
+    fn record_leave(env: Env, scorer: Address) {
+        scorer.require_auth();
+        Self::update_epoch(&env, &scorer, |stats| stats.leaves += 1);
+    }
+
+    /// Counts an attestation of a badge of a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer the badge is part of
+    /// * `badge` - The attested badge
+    ///
+    /// # Panics
+    /// * When the badge issuer fails authentication
+    /// * When the badge is not part of the scorer (`Error::BadgeNotFound`)
+    fn record_attestation(env: Env, scorer: Address, badge: BadgeId) {
+        badge.issuer.require_auth();
+
+        if !ScorerClient::new(&env, &scorer).get_badges().contains_key(badge.clone()) {
+            panic_with_error!(&env, Error::BadgeNotFound);
+        }
+
+        let mut attestations = Self::attestations(&env, &scorer);
+        attestations.set(badge.clone(), attestations.get(badge).unwrap_or(0) + 1);
+        env.storage().persistent().set(&DataKey::Attestations(scorer), &attestations);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{Extension, ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::String;
+
+    struct Setup {
+        env: Env,
+        manager: Address,
+        badge: BadgeId,
+        scorer: ScorerContractClient<'static>,
+        client: AnalyticsContractClient<'static>,
+    }
+
+    // A scorer with one badge, reporting to the analytics contract, on the
+    // last ledger of an epoch
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|ledger| ledger.sequence_number = EPOCH_LEDGERS - 1);
+
+        let manager = Address::generate(&env);
+        let badge = BadgeId { name: String::from_str(&env, "Speaker"), issuer: Address::generate(&env) };
+        let scorer = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        scorer.initialize(
+            &manager,
+            &Map::from_array(&env, [(badge.clone(), 50)]),
+            &String::from_str(&env, "Community"),
+            &String::from_str(&env, "A community"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        let client = AnalyticsContractClient::new(&env, &env.register(AnalyticsContract, ()));
+        scorer.set_extension(&manager, &Extension::Analytics, &Some(client.address.clone()));
+
+        Setup { env, manager, badge, scorer, client }
+    }
+
+    #[test]
+    fn test_joins_and_leaves_per_epoch() {
+        let Setup { env, scorer, client, .. } = setup();
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        scorer.add_user(&alice);
+        scorer.add_user(&bob);
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 1);
+        scorer.remove_user(&alice);
+
+        let epoch = client.get_epoch();
+        assert_eq!(
+            client.get_epoch_stats(&scorer.address, &(epoch - 1), &10),
+            Vec::from_array(&env, [
+                EpochStats { epoch: epoch - 1, joins: 2, leaves: 0 },
+                EpochStats { epoch, joins: 0, leaves: 1 },
+            ])
+        );
+        assert_eq!(client.get_epoch_stats(&scorer.address, &(epoch - 1), &1).len(), 1);
+    }
+
+    #[test]
+    fn test_attestations_per_badge() {
+        let Setup { env, manager, badge, scorer, client } = setup();
+        let other_badge = BadgeId { name: String::from_str(&env, "Mentor"), issuer: badge.issuer.clone() };
+        scorer.add_badge(&manager, &other_badge.name, &other_badge.issuer, &20);
+
+        client.record_attestation(&scorer.address, &badge);
+        client.record_attestation(&scorer.address, &badge);
+        client.record_attestation(&scorer.address, &other_badge);
+
+        assert_eq!(client.get_attestation_count(&scorer.address, &badge), 2);
+        assert_eq!(client.get_attestations(&scorer.address, &0, &10).len(), 2);
+        assert_eq!(client.get_attestations(&scorer.address, &1, &10).len(), 1);
+        assert_eq!(client.get_attestations(&scorer.address, &5, &10).len(), 0);
+
+        let unknown = BadgeId { name: String::from_str(&env, "Unknown"), issuer: badge.issuer };
+        assert_eq!(client.try_record_attestation(&scorer.address, &unknown), Err(Ok(Error::BadgeNotFound.into())));
+    }
+}
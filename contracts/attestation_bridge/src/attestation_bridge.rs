@@ -0,0 +1,451 @@
+#![no_std]
+use scorer_interface::events::{self, TOPIC_BADGE, TOPIC_BRIDGE};
+use scorer_interface::BadgeId;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address, BytesN, Env, String};
+
+/// An attestation made on an EVM deployment of Trustful, through the Ethereum
+/// Attestation Service.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EvmAttestation {
+    /// The chain id of the EVM network.
+    pub chain_id: u64,
+    /// The UID of the attestation in the EAS contract.
+    pub uid: BytesN<32>,
+    /// The UID of the EAS schema of the attestation.
+    pub schema: BytesN<32>,
+    /// The EVM address the attestation was made to.
+    pub recipient: BytesN<20>,
+}
+
+/// An attestation imported as a badge, until it is revoked.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportedAttestation {
+    pub user: Address,
+    pub badge: String,
+}
+
+#[contracttype]
+enum DataKey {
+    Admin,
+    Relayer(Address),
+    SchemaBadge(u64, BytesN<32>),
+    Imported(u64, BytesN<32>),
+    Held(Address, String),
+}
+
+/// Errors raised by the attestation bridge.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    /// The contract is not initialized.
+    NotInitialized = 2,
+    /// The sender is not a registered relayer.
+    RelayerNotRegistered = 3,
+    /// The badge name is empty.
+    EmptyArg = 4,
+    /// The schema of the attestation is not mapped to a badge.
+    SchemaNotMapped = 5,
+    /// The attestation was already imported.
+    AttestationAlreadyImported = 6,
+    /// The attestation was not imported, or was revoked.
+    AttestationNotFound = 7,
+}
+
+/// Imports attestations from EVM deployments of Trustful, so users keep the
+/// reputation they earned on other chains.
+///
+/// The admin maps EAS schemas to badges issued by this contract, which scorer
+/// managers add to their scorers like any other badge, and registers the
+/// relayers trusted to submit attestations. A relayer checks an attestation on
+/// its chain and that the Stellar user controls its recipient, then imports it
+/// for the user; the bridge reports the badge through `has_badge` until a
+/// relayer reports the attestation revoked. A contract verifying light-client
+/// proofs can be registered as a relayer to remove the trust in off-chain ones.
+#[contract]
+pub struct AttestationBridgeContract;
+
+#[contractimpl]
+impl AttestationBridgeContract {
+    /// Initializes the bridge
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `admin` - The address allowed to register relayers and map schemas
+    ///
+    /// # Panics
+    /// * When the contract is already initialized (`Error::ContractAlreadyInitialized`)
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().persistent().has(&DataKey::Admin) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+    }
+
+    /// Returns the admin
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
+    }
+
+    /// Registers or unregisters a relayer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `relayer` - The relayer
+    /// * `enabled` - Whether the relayer can import and revoke attestations
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    /// * When the admin fails authentication
+    pub fn set_relayer(env: Env, relayer: Address, enabled: bool) {
+        let admin = Self::get_admin(env.clone());
+        admin.require_auth();
+
+        let key = DataKey::Relayer(relayer.clone());
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events().publish((TOPIC_BRIDGE, symbol_short!("relayer")), events::RelayerSet { admin, relayer, enabled });
+    }
+
+    /// Returns whether a relayer is registered
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `relayer` - The address to check
+    pub fn is_relayer(env: Env, relayer: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Relayer(relayer))
+    }
+
+    /// Maps an EAS schema of an EVM chain to a badge issued by the bridge, or unmaps it.
+    /// Badges already imported are kept
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `chain_id` - The chain id of the EVM network
+    /// * `schema` - The UID of the EAS schema
+    /// * `badge` - The name of the badge its attestations grant, or None
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    /// * When the admin fails authentication
+    /// * When the badge name is empty (`Error::EmptyArg`)
+    pub fn set_schema_badge(env: Env, chain_id: u64, schema: BytesN<32>, badge: Option<String>) {
+        let admin = Self::get_admin(env.clone());
+        admin.require_auth();
+
+        let key = DataKey::SchemaBadge(chain_id, schema.clone());
+        match &badge {
+            Some(name) if name.is_empty() => panic_with_error!(&env, Error::EmptyArg),
+            Some(name) => env.storage().persistent().set(&key, name),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events().publish(
+            (TOPIC_BRIDGE, symbol_short!("schema")),
+            events::SchemaMapped { admin, chain_id, schema, badge },
+        );
+    }
+
+    /// Returns the badge the attestations of an EAS schema grant, if it is mapped
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `chain_id` - The chain id of the EVM network
+    /// * `schema` - The UID of the EAS schema
+    pub fn get_schema_badge(env: Env, chain_id: u64, schema: BytesN<32>) -> Option<BadgeId> {
+        let name: String = env.storage().persistent().get(&DataKey::SchemaBadge(chain_id, schema))?;
+        Some(BadgeId { name, issuer: env.current_contract_address() })
+    }
+
+    /// Imports an EVM attestation, granting the badge of its schema to a user
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `relayer` - A registered relayer, vouching for the attestation and for the user controlling its recipient
+    /// * `attestation` - The attestation
+    /// * `user` - The Stellar address of the recipient
+    ///
+    /// # Returns
+    /// * `BadgeId` - The badge granted
+    ///
+    /// # Panics
+    /// * When the relayer fails authentication or is not registered (`Error::RelayerNotRegistered`)
+    /// * When the schema is not mapped to a badge (`Error::SchemaNotMapped`)
+    /// * When the attestation was already imported (`Error::AttestationAlreadyImported`)
+    pub fn import_attestation(env: Env, relayer: Address, attestation: EvmAttestation, user: Address) -> BadgeId {
+        Self::require_relayer(&env, &relayer);
+
+        let badge = Self::get_schema_badge(env.clone(), attestation.chain_id, attestation.schema.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::SchemaNotMapped));
+
+        let key = DataKey::Imported(attestation.chain_id, attestation.uid.clone());
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(&env, Error::AttestationAlreadyImported);
+        }
+        env.storage().persistent().set(&key, &ImportedAttestation { user: user.clone(), badge: badge.name.clone() });
+
+        // Several attestations can grant the same badge, which is held until all are revoked
+        let held_key = DataKey::Held(user.clone(), badge.name.clone());
+        let held: u32 = env.storage().persistent().get(&held_key).unwrap_or(0);
+        env.storage().persistent().set(&held_key, &(held + 1));
+
+        env.events().publish(
+            (TOPIC_BRIDGE, symbol_short!("import")),
+            events::AttestationImported {
+                relayer,
+                chain_id: attestation.chain_id,
+                uid: attestation.uid,
+                recipient: attestation.recipient,
+                user: user.clone(),
+                badge: badge.name.clone(),
+            },
+        );
+        if held == 0 {
+            env.events().publish(
+                (TOPIC_BADGE, symbol_short!("issue")),
+                events::BadgeIssued { user, badge: badge.name.clone() },
+            );
+        }
+        badge
+    }
+
+    /// Revokes an imported attestation, after it was revoked on its EVM chain
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `relayer` - A registered relayer
+    /// * `chain_id` - The chain id of the EVM network
+    /// * `uid` - The UID of the attestation
+    ///
+    /// # Panics
+    /// * When the relayer fails authentication or is not registered (`Error::RelayerNotRegistered`)
+    /// * When the attestation was not imported (`Error::AttestationNotFound`)
+    pub fn revoke_attestation(env: Env, relayer: Address, chain_id: u64, uid: BytesN<32>) {
+        Self::require_relayer(&env, &relayer);
+
+        let key = DataKey::Imported(chain_id, uid.clone());
+        let imported = Self::get_imported(env.clone(), chain_id, uid.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::AttestationNotFound));
+        env.storage().persistent().remove(&key);
+
+        let held_key = DataKey::Held(imported.user.clone(), imported.badge.clone());
+        let held: u32 = env.storage().persistent().get(&held_key).unwrap_or(0);
+        if held > 1 {
+            env.storage().persistent().set(&held_key, &(held - 1));
+        } else {
+            env.storage().persistent().remove(&held_key);
+        }
+
+        env.events().publish(
+            (TOPIC_BRIDGE, symbol_short!("revoke")),
+            events::ImportRevoked {
+                relayer,
+                chain_id,
+                uid,
+                user: imported.user.clone(),
+                badge: imported.badge.clone(),
+            },
+        );
+        if held <= 1 {
+            env.events().publish(
+                (TOPIC_BADGE, symbol_short!("revoke")),
+                events::BadgeRevoked { user: imported.user, badge: imported.badge },
+            );
+        }
+    }
+
+    /// Returns the user and badge of an imported attestation, if it was imported and not revoked
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `chain_id` - The chain id of the EVM network
+    /// * `uid` - The UID of the attestation
+    pub fn get_imported(env: Env, chain_id: u64, uid: BytesN<32>) -> Option<ImportedAttestation> {
+        env.storage().persistent().get(&DataKey::Imported(chain_id, uid))
+    }
+
+    /// Returns whether a user holds a badge through an imported attestation
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The address to check
+    /// * `badge` - The badge name
+    pub fn has_badge(env: Env, user: Address, badge: String) -> bool {
+        env.storage().persistent().has(&DataKey::Held(user, badge))
+    }
+
+    fn require_relayer(env: &Env, relayer: &Address) {
+        relayer.require_auth();
+
+        if !Self::is_relayer(env.clone(), relayer.clone()) {
+            panic_with_error!(env, Error::RelayerNotRegistered);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use test_utils::{EventRecord, EventSnapshot};
+
+    const CHAIN_ID: u64 = 10;
+
+    struct Setup {
+        env: Env,
+        admin: Address,
+        relayer: Address,
+        schema: BytesN<32>,
+        badge: String,
+        client: AttestationBridgeContractClient<'static>,
+    }
+
+    // A bridge with a registered relayer and a schema mapped to the "Contributor" badge
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let client = AttestationBridgeContractClient::new(&env, &env.register(AttestationBridgeContract, ()));
+        client.initialize(&admin);
+
+        let relayer = Address::generate(&env);
+        client.set_relayer(&relayer, &true);
+        let schema = BytesN::from_array(&env, &[1; 32]);
+        let badge = String::from_str(&env, "Contributor");
+        client.set_schema_badge(&CHAIN_ID, &schema, &Some(badge.clone()));
+
+        Setup { env, admin, relayer, schema, badge, client }
+    }
+
+    fn attestation(env: &Env, schema: &BytesN<32>, uid: u8) -> EvmAttestation {
+        EvmAttestation {
+            chain_id: CHAIN_ID,
+            uid: BytesN::from_array(env, &[uid; 32]),
+            schema: schema.clone(),
+            recipient: BytesN::from_array(env, &[7; 20]),
+        }
+    }
+
+    #[test]
+    fn test_import_attestation() {
+        let Setup { env, relayer, schema, badge, client, .. } = setup();
+        let user = Address::generate(&env);
+        let first = attestation(&env, &schema, 1);
+
+        let badge_id = client.import_attestation(&relayer, &first, &user);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BRIDGE, symbol_short!("import")),
+                events::AttestationImported {
+                    relayer: relayer.clone(),
+                    chain_id: CHAIN_ID,
+                    uid: first.uid.clone(),
+                    recipient: first.recipient.clone(),
+                    user: user.clone(),
+                    badge: badge.clone(),
+                },
+            ),
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BADGE, symbol_short!("issue")),
+                events::BadgeIssued { user: user.clone(), badge: badge.clone() },
+            ),
+        ]);
+        assert_eq!(badge_id, BadgeId { name: badge.clone(), issuer: client.address.clone() });
+        assert!(client.has_badge(&user, &badge));
+        assert_eq!(
+            client.get_imported(&CHAIN_ID, &first.uid),
+            Some(ImportedAttestation { user: user.clone(), badge: badge.clone() })
+        );
+
+        assert_eq!(client.try_import_attestation(&relayer, &first, &user), Err(Ok(Error::AttestationAlreadyImported.into())));
+        let unmapped = attestation(&env, &BytesN::from_array(&env, &[2; 32]), 2);
+        assert_eq!(client.try_import_attestation(&relayer, &unmapped, &user), Err(Ok(Error::SchemaNotMapped.into())));
+        let other_chain = EvmAttestation { chain_id: 1, ..attestation(&env, &schema, 2) };
+        assert_eq!(client.try_import_attestation(&relayer, &other_chain, &user), Err(Ok(Error::SchemaNotMapped.into())));
+        let stranger = Address::generate(&env);
+        let second = attestation(&env, &schema, 2);
+        assert_eq!(client.try_import_attestation(&stranger, &second, &user), Err(Ok(Error::RelayerNotRegistered.into())));
+
+        // The badge is held until every attestation granting it is revoked
+        client.import_attestation(&relayer, &second, &user);
+        client.revoke_attestation(&relayer, &CHAIN_ID, &first.uid);
+        assert!(client.has_badge(&user, &badge));
+        assert_eq!(client.get_imported(&CHAIN_ID, &first.uid), None);
+        client.revoke_attestation(&relayer, &CHAIN_ID, &second.uid);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BRIDGE, symbol_short!("revoke")),
+                events::ImportRevoked {
+                    relayer: relayer.clone(),
+                    chain_id: CHAIN_ID,
+                    uid: second.uid.clone(),
+                    user: user.clone(),
+                    badge: badge.clone(),
+                },
+            ),
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BADGE, symbol_short!("revoke")),
+                events::BadgeRevoked { user: user.clone(), badge: badge.clone() },
+            ),
+        ]);
+        assert!(!client.has_badge(&user, &badge));
+
+        assert_eq!(client.try_revoke_attestation(&relayer, &CHAIN_ID, &first.uid), Err(Ok(Error::AttestationNotFound.into())));
+    }
+
+    #[test]
+    fn test_admin_settings() {
+        let Setup { env, admin, relayer, schema, badge, client } = setup();
+        assert_eq!(client.get_admin(), admin);
+        assert!(client.is_relayer(&relayer));
+        assert_eq!(client.get_schema_badge(&CHAIN_ID, &schema), Some(BadgeId { name: badge, issuer: client.address.clone() }));
+        assert_eq!(client.try_set_schema_badge(&CHAIN_ID, &schema, &Some(String::from_str(&env, ""))), Err(Ok(Error::EmptyArg.into())));
+        assert_eq!(client.try_initialize(&admin), Err(Ok(Error::ContractAlreadyInitialized.into())));
+
+        client.set_relayer(&relayer, &false);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_BRIDGE, symbol_short!("relayer")),
+            events::RelayerSet { admin: admin.clone(), relayer: relayer.clone(), enabled: false },
+        )]);
+        assert!(!client.is_relayer(&relayer));
+        client.set_schema_badge(&CHAIN_ID, &schema, &None);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BRIDGE, symbol_short!("schema")),
+                events::SchemaMapped { admin: admin.clone(), chain_id: CHAIN_ID, schema: schema.clone(), badge: None },
+            ),
+        ]);
+        assert_eq!(client.get_schema_badge(&CHAIN_ID, &schema), None);
+    }
+}
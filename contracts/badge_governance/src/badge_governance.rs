@@ -0,0 +1,609 @@
+#![no_std]
+use scorer_interface::{events, BadgeChange, BadgeChangeKind, BadgeId, GovernanceConfig, ScorerClient, MAX_BADGE_NAME_LENGTH};
+use scorer_interface::events::{EVENT_SCHEMA_VERSION, TOPIC_BADGE, TOPIC_GOVERNANCE};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, vec, Address, Env, Map, String, Vec};
+
+/// Maximum number of badges an issuer can have proposed to a scorer at a time,
+/// so proposals can't grow the contract's storage without a manager's decision.
+pub const MAX_BADGE_PROPOSALS: u32 = 5;
+
+/// A proposed badge change and the managers who voted for it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub proposer: Address,
+    pub change: BadgeChange,
+    pub votes: Vec<Address>,
+    /// Last ledger at which the proposal accepts votes.
+    pub deadline: u32,
+    pub executed: bool,
+}
+
+#[contracttype]
+enum DataKey {
+    Scorer,
+    Config,
+    ProposalCount,
+    Proposal(u32),
+    BadgeProposal(BadgeId),
+    IssuerProposals(Address),
+}
+
+// Errors raised by the badge governance contract. They keep the codes the
+// scorer raised them with before governance moved to this contract.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    // The sender is not a manager, or not the scorer creator.
+    Unauthorized = 2,
+    // The proposed badge is already part of the scorer.
+    BadgeAlreadyExists = 9,
+    // The badge was not proposed.
+    BadgeNotFound = 10,
+    // The proposed score is greater than 10000.
+    InvalidScoreRange = 11,
+    // The badge name is empty.
+    EmptyArg = 12,
+    // The scorer is archived.
+    ScorerArchived = 17,
+    // The quorum or voting period is zero, or the quorum exceeds the number of managers.
+    InvalidGovernanceConfig = 25,
+    // No proposal with this id exists.
+    ProposalNotFound = 26,
+    // The proposal was executed or its voting period ended.
+    ProposalClosed = 27,
+    // The manager already voted for the proposal.
+    AlreadyVoted = 28,
+    // The badge name is longer than `MAX_BADGE_NAME_LENGTH` bytes.
+    BadgeNameTooLong = 47,
+    // The badge was already proposed and awaits a manager's decision, or its
+    // issuer has `MAX_BADGE_PROPOSALS` pending proposals.
+    BadgeProposalPending = 50,
+    // The scorer is missing from storage.
+    ScorerNotFound = 51,
+}
+
+/// Manager votes on the badges of a scorer, and badges proposed by issuers.
+///
+/// Once the scorer registers the contract as its `Extension::Governance`,
+/// managers can no longer add, remove or rescore badges directly. They propose
+/// a change here instead, and the vote that brings it to the quorum applies it
+/// to the scorer. Issuers propose their own badges, which a manager approves,
+/// opening a proposal to add the badge with the manager's vote, or rejects.
+#[contract]
+pub struct BadgeGovernanceContract;
+
+#[contractimpl]
+impl BadgeGovernanceContract {
+    // Initializes the governance of a scorer's badges
+    pub fn initialize(env: Env, scorer: Address, config: GovernanceConfig) {
+        if env.storage().persistent().has(&DataKey::Scorer) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        // Only the scorer owner can hand its badge changes to these votes
+        ScorerClient::new(&env, &scorer).get_contract_owner().require_auth();
+
+        env.storage().persistent().set(&DataKey::Scorer, &scorer);
+        Self::store_config(&env, &config);
+    }
+
+    // Returns the scorer whose badges are governed
+    pub fn get_scorer(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scorer)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    // Changes the quorum and voting period. Open proposals keep their deadline
+    // and are executed by the next vote that reaches the new quorum
+    pub fn set_config(env: Env, sender: Address, config: GovernanceConfig) {
+        sender.require_auth();
+
+        if Self::scorer(&env).get_contract_owner() != sender {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        Self::store_config(&env, &config);
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_GOVERNANCE, symbol_short!("config")),
+            events::GovernanceSet { sender, quorum: config.quorum, voting_period: config.voting_period },
+        );
+    }
+
+    // Returns the quorum and voting period
+    pub fn get_config(env: Env) -> GovernanceConfig {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Config)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    // Proposes a badge change to the other managers and returns the id of the
+    // proposal. The proposer's vote is counted, so the change is applied at
+    // once when the quorum is 1
+    pub fn propose_badge_change(env: Env, sender: Address, change: BadgeChange) -> u32 {
+        Self::require_manager(&env, &sender);
+
+        if change.badge_id.name.len() > MAX_BADGE_NAME_LENGTH {
+            panic_with_error!(&env, Error::BadgeNameTooLong);
+        }
+
+        Self::open_proposal(&env, sender, change)
+    }
+
+    // Votes for a proposal. The vote that brings it to the quorum applies the
+    // badge change to the scorer
+    pub fn vote(env: Env, sender: Address, proposal_id: u32) {
+        Self::require_manager(&env, &sender);
+
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ProposalNotFound));
+
+        if proposal.executed || env.ledger().sequence() > proposal.deadline {
+            panic_with_error!(&env, Error::ProposalClosed);
+        }
+
+        if proposal.votes.contains(&sender) {
+            panic_with_error!(&env, Error::AlreadyVoted);
+        }
+
+        proposal.votes.push_back(sender.clone());
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_GOVERNANCE, symbol_short!("vote")),
+            events::ProposalVoted { proposal_id, voter: sender, votes: proposal.votes.len() },
+        );
+
+        Self::store_proposal(&env, proposal_id, proposal);
+    }
+
+    // Returns a proposal with its votes, or None if it does not exist
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Option<Proposal> {
+        env.storage().persistent().get(&DataKey::Proposal(proposal_id))
+    }
+
+    // Proposes a badge of the issuer, stored until a manager approves or rejects it
+    pub fn propose_badge(env: Env, issuer: Address, name: String, score: u32) {
+        issuer.require_auth();
+
+        let scorer = Self::scorer(&env);
+        if scorer.is_archived() {
+            panic_with_error!(&env, Error::ScorerArchived);
+        }
+        if name.is_empty() {
+            panic_with_error!(&env, Error::EmptyArg);
+        }
+        if name.len() > MAX_BADGE_NAME_LENGTH {
+            panic_with_error!(&env, Error::BadgeNameTooLong);
+        }
+        if score > 10000 {
+            panic_with_error!(&env, Error::InvalidScoreRange);
+        }
+
+        let badge_id = BadgeId { name, issuer };
+        if scorer.get_badges().contains_key(badge_id.clone()) {
+            panic_with_error!(&env, Error::BadgeAlreadyExists);
+        }
+
+        let mut names = Self::issuer_proposals(&env, &badge_id.issuer);
+        if env.storage().persistent().has(&DataKey::BadgeProposal(badge_id.clone())) || names.len() >= MAX_BADGE_PROPOSALS {
+            panic_with_error!(&env, Error::BadgeProposalPending);
+        }
+        names.push_back(badge_id.name.clone());
+        env.storage().persistent().set(&DataKey::IssuerProposals(badge_id.issuer.clone()), &names);
+        env.storage().persistent().set(&DataKey::BadgeProposal(badge_id.clone()), &score);
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("propose")),
+            events::BadgeProposed { badge_id, score },
+        );
+    }
+
+    // Approves a proposed badge, opening a proposal to add it with the score
+    // its issuer proposed and the approver's vote. Returns the id of the proposal
+    pub fn approve_badge_proposal(env: Env, sender: Address, badge_id: BadgeId) -> u32 {
+        let score = Self::take_badge_proposal(&env, &sender, &badge_id);
+
+        Self::open_proposal(&env, sender, BadgeChange { kind: BadgeChangeKind::Add, badge_id, score })
+    }
+
+    // Discards a proposed badge
+    pub fn reject_badge_proposal(env: Env, sender: Address, badge_id: BadgeId) {
+        Self::take_badge_proposal(&env, &sender, &badge_id);
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("reject")),
+            events::BadgeProposalRejected { sender, badge_id },
+        );
+    }
+
+    // Returns the badges an issuer proposed that await a manager's decision,
+    // with the scores proposed for them
+    pub fn get_badge_proposals(env: Env, issuer: Address) -> Map<BadgeId, u32> {
+        let mut proposals = Map::new(&env);
+        for name in Self::issuer_proposals(&env, &issuer).iter() {
+            let badge_id = BadgeId { name, issuer: issuer.clone() };
+            if let Some(score) = env.storage().persistent().get(&DataKey::BadgeProposal(badge_id.clone())) {
+                proposals.set(badge_id, score);
+            }
+        }
+        proposals
+    }
+}
+
+impl BadgeGovernanceContract {
+    /// Returns a client of the scorer whose badges are governed
+    fn scorer(env: &Env) -> ScorerClient<'_> {
+        ScorerClient::new(env, &Self::get_scorer(env.clone()))
+    }
+
+    /// Requires the authorization of the sender and that it is a manager of the scorer
+    ///
+    /// # Panics
+    /// * When the sender is not a manager (`Error::Unauthorized`)
+    fn require_manager(env: &Env, sender: &Address) {
+        sender.require_auth();
+
+        if !Self::scorer(env).get_managers().contains(sender) {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    /// Validates and stores the quorum and voting period
+    ///
+    /// # Panics
+    /// * When the quorum or voting period is zero, or the quorum exceeds the number of managers (`Error::InvalidGovernanceConfig`)
+    fn store_config(env: &Env, config: &GovernanceConfig) {
+        let managers = Self::scorer(env).get_managers();
+        if config.quorum == 0 || config.voting_period == 0 || config.quorum > managers.len() {
+            panic_with_error!(env, Error::InvalidGovernanceConfig);
+        }
+        env.storage().persistent().set(&DataKey::Config, config);
+    }
+
+    /// Opens a proposal with the vote of its proposer and returns its id
+    fn open_proposal(env: &Env, proposer: Address, change: BadgeChange) -> u32 {
+        let config = Self::get_config(env.clone());
+
+        let proposal_id: u32 = env.storage().persistent().get(&DataKey::ProposalCount).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::ProposalCount, &(proposal_id + 1));
+
+        let deadline = env.ledger().sequence().saturating_add(config.voting_period);
+        let proposal = Proposal {
+            proposer: proposer.clone(),
+            change: change.clone(),
+            votes: vec![env, proposer.clone()],
+            deadline,
+            executed: false,
+        };
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_GOVERNANCE, symbol_short!("propose")),
+            events::ProposalCreated { proposal_id, proposer, change, deadline },
+        );
+
+        Self::store_proposal(env, proposal_id, proposal);
+        proposal_id
+    }
+
+    /// Stores a proposal, first applying its change to the scorer when its
+    /// votes reach the quorum. The scorer accepts the change because this
+    /// contract is its governance extension and the direct caller
+    fn store_proposal(env: &Env, proposal_id: u32, mut proposal: Proposal) {
+        if proposal.votes.len() >= Self::get_config(env.clone()).quorum {
+            let scorer = Self::scorer(env);
+            let governance = env.current_contract_address();
+            let BadgeChange { kind, badge_id, score } = proposal.change.clone();
+            match kind {
+                BadgeChangeKind::Add => scorer.add_badge(&governance, &badge_id.name, &badge_id.issuer, &score),
+                BadgeChangeKind::Remove => scorer.remove_badge(&governance, &badge_id.name, &badge_id.issuer),
+                BadgeChangeKind::SetScore => scorer.set_badge_score(&governance, &badge_id, &score),
+            }
+            proposal.executed = true;
+
+            env.events().publish(
+                (EVENT_SCHEMA_VERSION, TOPIC_GOVERNANCE, symbol_short!("execute")),
+                events::ProposalExecuted { proposal_id },
+            );
+        }
+
+        env.storage().persistent().set(&DataKey::Proposal(proposal_id), &proposal);
+    }
+
+    /// Removes a proposed badge and returns its score, once the sender is known to be a manager
+    ///
+    /// # Panics
+    /// * When the sender is not a manager (`Error::Unauthorized`)
+    /// * When the badge was not proposed (`Error::BadgeNotFound`)
+    fn take_badge_proposal(env: &Env, sender: &Address, badge_id: &BadgeId) -> u32 {
+        Self::require_manager(env, sender);
+
+        let key = DataKey::BadgeProposal(badge_id.clone());
+        let score = env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(env, Error::BadgeNotFound));
+        env.storage().persistent().remove(&key);
+
+        let issuer_key = DataKey::IssuerProposals(badge_id.issuer.clone());
+        let mut names = Self::issuer_proposals(env, &badge_id.issuer);
+        if let Some(index) = names.first_index_of(&badge_id.name) {
+            names.remove(index);
+        }
+        if names.is_empty() {
+            env.storage().persistent().remove(&issuer_key);
+        } else {
+            env.storage().persistent().set(&issuer_key, &names);
+        }
+        score
+    }
+
+    /// Returns the names of the badges an issuer has pending proposals for
+    fn issuer_proposals(env: &Env, issuer: &Address) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::IssuerProposals(issuer.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{Extension, ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use test_utils::{scorer_with_extension, EventRecord, EventSnapshot, ScorerWithExtension};
+
+    struct Setup {
+        env: Env,
+        scorer_creator: Address,
+        manager: Address,
+        badge_id: BadgeId,
+        scorer: ScorerContractClient<'static>,
+        client: BadgeGovernanceContractClient<'static>,
+    }
+
+    // The scorer of `scorer_with_extension` with three managers, whose badges are
+    // voted with `quorum` over 100 ledgers; `badge_id` is not part of the scorer
+    fn setup(quorum: u32) -> Setup {
+        let ScorerWithExtension { env, scorer_creator, badges, scorer, extension, .. } =
+            scorer_with_extension(ScorerContract, Extension::Governance, BadgeGovernanceContract, |env, extension, scorer| {
+                let scorer = ScorerContractClient::new(env, scorer);
+                let owner = scorer.get_contract_owner();
+                scorer.add_manager(&owner, &Address::generate(env));
+                scorer.add_manager(&owner, &Address::generate(env));
+                BadgeGovernanceContractClient::new(env, extension).initialize(&scorer.address, &GovernanceConfig { quorum, voting_period: 100 })
+            });
+        let scorer = ScorerContractClient::new(&env, &scorer);
+        let client = BadgeGovernanceContractClient::new(&env, &extension);
+        let manager = scorer.get_managers().get_unchecked(1);
+        Setup { env, scorer_creator, manager, badge_id: badges.get_unchecked(2), scorer, client }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let Setup { scorer, client, .. } = setup(2);
+        assert_eq!(client.get_scorer(), scorer.address);
+        assert_eq!(client.get_config(), GovernanceConfig { quorum: 2, voting_period: 100 });
+        assert_eq!(
+            client.try_initialize(&scorer.address, &GovernanceConfig { quorum: 2, voting_period: 100 }),
+            Err(Ok(Error::ContractAlreadyInitialized.into()))
+        );
+    }
+
+    #[test]
+    fn test_set_config() {
+        let Setup { env, scorer_creator, manager, scorer, client, .. } = setup(2);
+
+        assert_eq!(
+            client.try_set_config(&manager, &GovernanceConfig { quorum: 1, voting_period: 100 }),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+        for config in [
+            GovernanceConfig { quorum: 0, voting_period: 100 },
+            GovernanceConfig { quorum: 1, voting_period: 0 },
+            GovernanceConfig { quorum: 4, voting_period: 100 },
+        ] {
+            assert_eq!(client.try_set_config(&scorer_creator, &config), Err(Ok(Error::InvalidGovernanceConfig.into())));
+        }
+
+        client.set_config(&scorer_creator, &GovernanceConfig { quorum: 3, voting_period: 50 });
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_GOVERNANCE, symbol_short!("config")),
+            events::GovernanceSet { sender: scorer_creator.clone(), quorum: 3, voting_period: 50 },
+        )]);
+        assert_eq!(client.get_config(), GovernanceConfig { quorum: 3, voting_period: 50 });
+
+        // The quorum is bounded by the managers of the scorer
+        let uninitialized = BadgeGovernanceContractClient::new(&env, &env.register(BadgeGovernanceContract, ()));
+        assert_eq!(
+            uninitialized.try_initialize(&scorer.address, &GovernanceConfig { quorum: 4, voting_period: 100 }),
+            Err(Ok(Error::InvalidGovernanceConfig.into()))
+        );
+    }
+
+    #[test]
+    fn test_governance_vote_adds_badge() {
+        let Setup { env, scorer_creator, manager, badge_id, scorer, client } = setup(2);
+
+        // Direct badge changes are rejected once the scorer is governed
+        assert!(scorer.try_add_badge(&scorer_creator, &badge_id.name, &badge_id.issuer, &40).is_err());
+        assert!(scorer.try_remove_badge(&scorer_creator, &String::from_str(&env, "Test Badge"), &scorer_creator).is_err());
+
+        let change = BadgeChange { kind: BadgeChangeKind::Add, badge_id: badge_id.clone(), score: 40 };
+        let proposal_id = client.propose_badge_change(&scorer_creator, &change);
+        let deadline = env.ledger().sequence() + 100;
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_GOVERNANCE, symbol_short!("propose")),
+            events::ProposalCreated { proposal_id, proposer: scorer_creator.clone(), change, deadline },
+        )]);
+        let proposal = client.get_proposal(&proposal_id).unwrap();
+        assert_eq!(proposal.votes, Vec::from_array(&env, [scorer_creator.clone()]));
+        assert_eq!(proposal.deadline, deadline);
+        assert!(!proposal.executed);
+        assert!(!scorer.get_badges().contains_key(badge_id.clone()));
+
+        client.vote(&manager, &proposal_id);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_GOVERNANCE, symbol_short!("vote")),
+                events::ProposalVoted { proposal_id, voter: manager.clone(), votes: 2 },
+            ),
+            EventRecord::new(
+                &env,
+                &scorer.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("add")),
+                events::BadgeAdded { badge_id: badge_id.clone(), score: 40, sender: client.address.clone() },
+            ),
+            EventRecord::new(
+                &env,
+                &client.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_GOVERNANCE, symbol_short!("execute")),
+                events::ProposalExecuted { proposal_id },
+            ),
+        ]);
+        assert!(client.get_proposal(&proposal_id).unwrap().executed);
+        assert_eq!(scorer.get_badges().get(badge_id.clone()), Some(40));
+
+        // An executed proposal no longer accepts votes
+        let result = client.try_vote(&scorer.get_managers().get_unchecked(2), &proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalClosed.into())));
+        assert_eq!(client.try_vote(&manager, &(proposal_id + 1)), Err(Ok(Error::ProposalNotFound.into())));
+    }
+
+    #[test]
+    fn test_governance_score_change_and_removal() {
+        let Setup { env, scorer_creator, scorer, client, .. } = setup(1);
+        let badge_id = BadgeId { name: String::from_str(&env, "Test Badge"), issuer: scorer_creator.clone() };
+
+        // With a quorum of 1 the proposer's vote applies the change at once
+        let proposal_id = client.propose_badge_change(&scorer_creator, &BadgeChange { kind: BadgeChangeKind::SetScore, badge_id: badge_id.clone(), score: 250 });
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("score")),
+            events::BadgeScoreSet { badge_id: badge_id.clone(), previous_score: 100, score: 250, sender: client.address.clone() },
+        )]);
+        assert!(client.get_proposal(&proposal_id).unwrap().executed);
+        assert_eq!(scorer.get_badges().get(badge_id.clone()), Some(250));
+
+        let proposal_id = client.propose_badge_change(&scorer_creator, &BadgeChange { kind: BadgeChangeKind::Remove, badge_id: badge_id.clone(), score: 0 });
+        assert_eq!(proposal_id, 1);
+        assert!(!scorer.get_badges().contains_key(badge_id.clone()));
+
+        // Once the scorer drops governance, managers change badges directly again
+        scorer.set_extension(&scorer_creator, &Extension::Governance, &None);
+        scorer.add_badge(&scorer_creator, &badge_id.name, &badge_id.issuer, &100);
+    }
+
+    #[test]
+    fn test_vote_rejections() {
+        let Setup { env, scorer_creator, manager, badge_id, client, .. } = setup(2);
+        let proposal_id = client.propose_badge_change(&scorer_creator, &BadgeChange { kind: BadgeChangeKind::Add, badge_id, score: 40 });
+
+        assert_eq!(client.try_vote(&Address::generate(&env), &proposal_id), Err(Ok(Error::Unauthorized.into())));
+        assert_eq!(client.try_vote(&scorer_creator, &proposal_id), Err(Ok(Error::AlreadyVoted.into())));
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 101);
+        assert_eq!(client.try_vote(&manager, &proposal_id), Err(Ok(Error::ProposalClosed.into())));
+    }
+
+    #[test]
+    fn test_proposal_without_governance_extension() {
+        let Setup { scorer_creator, manager, badge_id, scorer, client, .. } = setup(2);
+        scorer.set_extension(&scorer_creator, &Extension::Governance, &None);
+        let proposal_id = client.propose_badge_change(&scorer_creator, &BadgeChange { kind: BadgeChangeKind::Add, badge_id: badge_id.clone(), score: 40 });
+
+        // The scorer only applies the changes of its registered governance contract
+        assert!(client.try_vote(&manager, &proposal_id).is_err());
+        assert!(!client.get_proposal(&proposal_id).unwrap().executed);
+        assert_eq!(scorer.get_badges().get(badge_id), None);
+    }
+
+    #[test]
+    fn test_propose_badge_change_rejections() {
+        let Setup { env, scorer_creator, badge_id, client, .. } = setup(2);
+        let change = BadgeChange { kind: BadgeChangeKind::Add, badge_id: badge_id.clone(), score: 40 };
+
+        assert_eq!(client.try_propose_badge_change(&Address::generate(&env), &change), Err(Ok(Error::Unauthorized.into())));
+        let long_name = String::from_bytes(&env, &[b'a'; MAX_BADGE_NAME_LENGTH as usize + 1]);
+        let long_change = BadgeChange { badge_id: BadgeId { name: long_name, issuer: badge_id.issuer }, ..change };
+        assert_eq!(client.try_propose_badge_change(&scorer_creator, &long_change), Err(Ok(Error::BadgeNameTooLong.into())));
+    }
+
+    #[test]
+    fn test_propose_badge() {
+        let Setup { env, scorer_creator, scorer, client, .. } = setup(1);
+        let issuer = Address::generate(&env);
+        let speaker = BadgeId { name: String::from_str(&env, "Speaker"), issuer: issuer.clone() };
+        let mentor = BadgeId { name: String::from_str(&env, "Mentor"), issuer: issuer.clone() };
+
+        client.propose_badge(&issuer, &speaker.name, &40);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("propose")),
+            events::BadgeProposed { badge_id: speaker.clone(), score: 40 },
+        )]);
+        client.propose_badge(&issuer, &mentor.name, &60);
+        assert_eq!(client.get_badge_proposals(&issuer), Map::from_array(&env, [(speaker.clone(), 40), (mentor.clone(), 60)]));
+        assert!(!scorer.get_badges().contains_key(speaker.clone()));
+        assert_eq!(client.try_propose_badge(&issuer, &speaker.name, &50), Err(Ok(Error::BadgeProposalPending.into())));
+        assert_eq!(client.try_propose_badge(&scorer_creator, &String::from_str(&env, "Test Badge"), &50), Err(Ok(Error::BadgeAlreadyExists.into())));
+        assert_eq!(client.try_propose_badge(&issuer, &String::from_str(&env, ""), &50), Err(Ok(Error::EmptyArg.into())));
+        assert_eq!(client.try_propose_badge(&issuer, &String::from_str(&env, "Judge"), &10001), Err(Ok(Error::InvalidScoreRange.into())));
+
+        // Only managers decide
+        assert_eq!(client.try_approve_badge_proposal(&issuer, &speaker), Err(Ok(Error::Unauthorized.into())));
+        assert_eq!(client.try_reject_badge_proposal(&issuer, &mentor), Err(Ok(Error::Unauthorized.into())));
+
+        // An approval opens a proposal with the approver's vote
+        let proposal_id = client.approve_badge_proposal(&scorer_creator, &speaker);
+        assert_eq!(client.get_proposal(&proposal_id).unwrap().change, BadgeChange { kind: BadgeChangeKind::Add, badge_id: speaker.clone(), score: 40 });
+        client.reject_badge_proposal(&scorer_creator, &mentor);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("reject")),
+            events::BadgeProposalRejected { sender: scorer_creator.clone(), badge_id: mentor.clone() },
+        )]);
+        assert!(client.get_badge_proposals(&issuer).is_empty());
+        assert_eq!(scorer.get_badges().get(speaker.clone()), Some(40));
+        assert!(!scorer.get_badges().contains_key(mentor.clone()));
+        assert_eq!(client.try_approve_badge_proposal(&scorer_creator, &mentor), Err(Ok(Error::BadgeNotFound.into())));
+
+        // Archived scorers take no proposals
+        scorer.set_archived(&scorer_creator, &true);
+        assert_eq!(client.try_propose_badge(&issuer, &String::from_str(&env, "Judge"), &50), Err(Ok(Error::ScorerArchived.into())));
+    }
+
+    #[test]
+    fn test_badge_proposals_bounded_per_issuer() {
+        let Setup { env, scorer_creator, client, .. } = setup(2);
+        let issuer = Address::generate(&env);
+        let names = ["A", "B", "C", "D", "E", "F"].map(|name| String::from_str(&env, name));
+        for name in names.iter().take(MAX_BADGE_PROPOSALS as usize) {
+            client.propose_badge(&issuer, name, &10);
+        }
+        assert_eq!(client.get_badge_proposals(&issuer).len(), MAX_BADGE_PROPOSALS);
+
+        // The issuer has to wait for a decision before proposing more
+        assert_eq!(client.try_propose_badge(&issuer, &names[5], &10), Err(Ok(Error::BadgeProposalPending.into())));
+        client.propose_badge(&Address::generate(&env), &names[5], &10);
+
+        client.reject_badge_proposal(&scorer_creator, &BadgeId { name: names[0].clone(), issuer: issuer.clone() });
+        client.propose_badge(&issuer, &names[5], &10);
+        assert_eq!(client.get_badge_proposals(&issuer).len(), MAX_BADGE_PROPOSALS);
+    }
+}
@@ -0,0 +1,583 @@
+#![no_std]
+use scorer_interface::{events, merkle, BadgeId, BadgeTokenTrait, Extension, ScorerClient};
+use scorer_interface::events::{TOPIC_BADGE};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token::TokenInterface, Address, BytesN, Env, String, Vec};
+
+/// A leaf of the Merkle tree of legacy badge holders: the badges `user` held
+/// in the previous badge system. `token` is the badge token the tree is
+/// published to, so a proof cannot be replayed on the token of another scorer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyClaim {
+    pub token: Address,
+    pub user: Address,
+    pub badges: Vec<BadgeId>,
+}
+
+#[contracttype]
+enum DataKey {
+    Scorer,
+    Name,
+    Symbol,
+    Badges(Address),
+    HoldersCount(BadgeId),
+    LegacyRoot,
+    LegacyClaimed(BytesN<32>, Address),
+}
+
+/// Errors raised by the badge token.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    /// The scorer is missing from storage.
+    ScorerNotFound = 2,
+    /// The caller is neither the scorer nor the badge issuer, nor for burns the
+    /// disputes contract of the scorer.
+    Unauthorized = 3,
+    /// The badge is not part of the scorer.
+    BadgeNotFound = 4,
+    /// The user already holds the token of the badge.
+    BadgeAlreadyMinted = 5,
+    /// The user does not hold the token of the badge.
+    BadgeNotMinted = 6,
+    /// The scorer revoked the attestation of the badge to the user.
+    AttestationRevoked = 7,
+    /// Badge tokens cannot be transferred, approved or burned by their holder.
+    NonTransferable = 8,
+    /// A manager of the scorer disabled the badge.
+    BadgeDisabled = 9,
+    /// No legacy root was published.
+    LegacyRootNotFound = 10,
+    /// The proof does not lead from the claim to the legacy root.
+    InvalidProof = 11,
+    /// The user already claimed their legacy badges under this root.
+    AlreadyClaimed = 12,
+}
+
+/// A non-transferable token mirroring the badges attested to the members of a scorer.
+///
+/// Each token is a badge of the scorer: the scorer or the badge issuer mints it
+/// when the badge is attested to a user and burns it when the attestation is
+/// revoked. The contract implements the read side of the token interface, so
+/// wallets and other contracts see a user's balance as the number of badges the
+/// user holds, while transfers, approvals and holder burns fail.
+///
+/// Holders of the previous badge system migrate themselves: a manager publishes
+/// the Merkle root of the legacy holders and their badges, and each holder
+/// claims their tokens with a proof of inclusion.
+#[contract]
+pub struct BadgeTokenContract;
+
+#[contractimpl]
+impl BadgeTokenContract {
+    /// Initializes the badge token of a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer whose badges the token mirrors
+    /// * `name` - The token name shown in wallets
+    /// * `symbol` - The token symbol shown in wallets
+    ///
+    /// # Panics
+    /// * When the scorer owner fails authentication
+    /// * When the contract is already initialized (`Error::ContractAlreadyInitialized`)
+    pub fn initialize(env: Env, scorer: Address, name: String, symbol: String) {
+        if env.storage().persistent().has(&DataKey::Scorer) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        // Only the scorer owner can mirror its badges in a token
+        ScorerClient::new(&env, &scorer).get_contract_owner().require_auth();
+
+        env.storage().persistent().set(&DataKey::Scorer, &scorer);
+        env.storage().persistent().set(&DataKey::Name, &name);
+        env.storage().persistent().set(&DataKey::Symbol, &symbol);
+    }
+
+    /// Returns the scorer whose badges the token mirrors
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::ScorerNotFound`)
+    pub fn get_scorer(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scorer)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    /// Publishes the Merkle root of the legacy badge holders, replacing the previous root, or removes it
+    ///
+    /// The leaves of the tree are the `LegacyClaim`s of the holders, hashed with
+    /// the `merkle` module of `scorer_interface`.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `manager` - A manager of the scorer
+    /// * `root` - The root of the tree of legacy claims, or None to stop legacy claims
+    ///
+    /// # Panics
+    /// * When the manager fails authentication or is not a manager of the scorer (`Error::Unauthorized`)
+    pub fn set_legacy_root(env: Env, manager: Address, root: Option<BytesN<32>>) {
+        manager.require_auth();
+
+        if !Self::scorer(&env).get_managers().contains(&manager) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        match root {
+            Some(root) => {
+                env.storage().persistent().set(&DataKey::LegacyRoot, &root);
+                env.events().publish((TOPIC_BADGE, symbol_short!("legacy")), events::LegacyRootSet { manager, root });
+            }
+            None => {
+                env.storage().persistent().remove(&DataKey::LegacyRoot);
+                env.events().publish((TOPIC_BADGE, symbol_short!("legclear")), events::LegacyRootCleared { manager });
+            }
+        }
+    }
+
+    /// Returns the Merkle root of the legacy badge holders, if any
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    pub fn get_legacy_root(env: Env) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::LegacyRoot)
+    }
+
+    /// Mints to a legacy holder the tokens of the badges they held in the previous badge system
+    ///
+    /// Badges the user already holds, that are no longer part of the scorer,
+    /// disabled or whose attestation the scorer revoked are skipped, so they do
+    /// not block the migration of the others.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The legacy holder
+    /// * `badges` - The badges of the user's leaf in the legacy tree
+    /// * `proof` - The sibling hashes from the leaf up to the root
+    ///
+    /// # Returns
+    /// * `u32` - The number of tokens minted
+    ///
+    /// # Panics
+    /// * When the user fails authentication
+    /// * When no legacy root was published (`Error::LegacyRootNotFound`)
+    /// * When the user already claimed under this root (`Error::AlreadyClaimed`)
+    /// * When the proof does not match the root (`Error::InvalidProof`)
+    pub fn claim_legacy_badges(env: Env, user: Address, badges: Vec<BadgeId>, proof: Vec<BytesN<32>>) -> u32 {
+        user.require_auth();
+
+        let root = Self::get_legacy_root(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::LegacyRootNotFound));
+        let claimed = DataKey::LegacyClaimed(root.clone(), user.clone());
+        if env.storage().persistent().has(&claimed) {
+            panic_with_error!(&env, Error::AlreadyClaimed);
+        }
+
+        let claim = LegacyClaim { token: env.current_contract_address(), user: user.clone(), badges: badges.clone() };
+        if !merkle::verify(&env, merkle::leaf(&env, claim), &proof, &root) {
+            panic_with_error!(&env, Error::InvalidProof);
+        }
+        env.storage().persistent().set(&claimed, &());
+
+        let scorer = Self::scorer(&env);
+        let mut minted = 0;
+        for badge_id in badges.iter() {
+            if Self::check_mint(&env, &scorer, &user, &badge_id).is_ok() {
+                Self::mint(&env, env.current_contract_address(), user.clone(), badge_id);
+                minted += 1;
+            }
+        }
+        minted
+    }
+
+    fn scorer(env: &Env) -> ScorerClient<'static> {
+        ScorerClient::new(env, &Self::get_scorer(env.clone()))
+    }
+
+    // Checks that the token of a badge can be minted to a user
+    fn check_mint(env: &Env, scorer: &ScorerClient, user: &Address, badge_id: &BadgeId) -> Result<(), Error> {
+        if !scorer.get_badges().contains_key(badge_id.clone()) {
+            return Err(Error::BadgeNotFound);
+        }
+        if !scorer.is_badge_enabled(badge_id) {
+            return Err(Error::BadgeDisabled);
+        }
+        if scorer.is_attestation_revoked(badge_id, user) {
+            return Err(Error::AttestationRevoked);
+        }
+        if Self::get_badges(env.clone(), user.clone()).contains(badge_id) {
+            return Err(Error::BadgeAlreadyMinted);
+        }
+        Ok(())
+    }
+
+    fn mint(env: &Env, caller: Address, user: Address, badge_id: BadgeId) {
+        let mut badges = Self::get_badges(env.clone(), user.clone());
+        badges.push_back(badge_id.clone());
+        env.storage().persistent().set(&DataKey::Badges(user.clone()), &badges);
+        let holders = Self::get_holders_count(env.clone(), badge_id.clone());
+        env.storage().persistent().set(&DataKey::HoldersCount(badge_id.clone()), &(holders + 1));
+
+        env.events().publish((TOPIC_BADGE, symbol_short!("mint")), events::BadgeMinted { caller, user, badge_id });
+    }
+
+    fn require_minter(env: &Env, caller: &Address, badge_id: &BadgeId) -> ScorerClient<'static> {
+        caller.require_auth();
+
+        let scorer = Self::scorer(env);
+        if *caller != scorer.address && *caller != badge_id.issuer {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+        scorer
+    }
+}
+
+#[contractimpl]
+impl BadgeTokenTrait for BadgeTokenContract {
+    /// Mints the token of a badge to a user
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The scorer or the badge issuer
+    /// * `user` - The user the badge was attested to
+    /// * `badge_id` - The badge
+    ///
+    /// # Panics
+    /// * When the caller fails authentication or is neither the scorer nor the issuer (`Error::Unauthorized`)
+    /// * When the badge is not part of the scorer (`Error::BadgeNotFound`)
+    /// * When a manager of the scorer disabled the badge (`Error::BadgeDisabled`)
+    /// * When the scorer revoked the attestation (`Error::AttestationRevoked`)
+    /// * When the user already holds the token (`Error::BadgeAlreadyMinted`)
+    fn mint_badge(env: Env, caller: Address, user: Address, badge_id: BadgeId) {
+        let scorer = Self::require_minter(&env, &caller, &badge_id);
+
+        if let Err(error) = Self::check_mint(&env, &scorer, &user, &badge_id) {
+            panic_with_error!(&env, error);
+        }
+        Self::mint(&env, caller, user, badge_id);
+    }
+
+    /// Burns the token of a badge from a user
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The scorer, its disputes contract or the badge issuer
+    /// * `user` - The holder
+    /// * `badge_id` - The badge
+    ///
+    /// # Panics
+    /// * When the caller fails authentication or is neither the scorer, its disputes contract nor the issuer (`Error::Unauthorized`)
+    /// * When the user does not hold the token (`Error::BadgeNotMinted`)
+    fn burn_badge(env: Env, caller: Address, user: Address, badge_id: BadgeId) {
+        // The disputes contract burns the tokens of the attestations it revokes
+        let scorer = Self::scorer(&env);
+        if scorer.get_extension(&Extension::Disputes) == Some(caller.clone()) {
+            caller.require_auth();
+        } else {
+            Self::require_minter(&env, &caller, &badge_id);
+        }
+
+        let mut badges = Self::get_badges(env.clone(), user.clone());
+        let index = badges
+            .first_index_of(&badge_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::BadgeNotMinted));
+        badges.remove(index);
+        env.storage().persistent().set(&DataKey::Badges(user.clone()), &badges);
+        // Tokens minted before holders were counted are not part of the count
+        let holders = Self::get_holders_count(env.clone(), badge_id.clone());
+        env.storage().persistent().set(&DataKey::HoldersCount(badge_id.clone()), &holders.saturating_sub(1));
+
+        env.events().publish((TOPIC_BADGE, symbol_short!("burn")), events::BadgeBurned { caller, user, badge_id });
+    }
+
+    /// Returns whether a user holds the token of a badge
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The holder
+    /// * `badge_id` - The badge
+    fn holds_badge(env: Env, user: Address, badge_id: BadgeId) -> bool {
+        Self::get_badges(env, user).contains(&badge_id)
+    }
+
+    /// Returns the badges whose tokens a user holds
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The holder
+    fn get_badges(env: Env, user: Address) -> Vec<BadgeId> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Badges(user))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the number of users holding the token of a badge
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `badge_id` - The badge
+    fn get_holders_count(env: Env, badge_id: BadgeId) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::HoldersCount(badge_id))
+            .unwrap_or(0)
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for BadgeTokenContract {
+    /// Returns zero, as badge tokens cannot be approved
+    fn allowance(_env: Env, _from: Address, _spender: Address) -> i128 {
+        0
+    }
+
+    /// Fails, as badge tokens cannot be approved (`Error::NonTransferable`)
+    fn approve(env: Env, _from: Address, _spender: Address, _amount: i128, _expiration_ledger: u32) {
+        panic_with_error!(&env, Error::NonTransferable)
+    }
+
+    /// Returns the number of badges whose tokens `id` holds
+    fn balance(env: Env, id: Address) -> i128 {
+        Self::get_badges(env, id).len() as i128
+    }
+
+    /// Fails, as badge tokens cannot be transferred (`Error::NonTransferable`)
+    fn transfer(env: Env, _from: Address, _to: Address, _amount: i128) {
+        panic_with_error!(&env, Error::NonTransferable)
+    }
+
+    /// Fails, as badge tokens cannot be transferred (`Error::NonTransferable`)
+    fn transfer_from(env: Env, _spender: Address, _from: Address, _to: Address, _amount: i128) {
+        panic_with_error!(&env, Error::NonTransferable)
+    }
+
+    /// Fails, as badge tokens are only burned when their attestation is revoked (`Error::NonTransferable`)
+    fn burn(env: Env, _from: Address, _amount: i128) {
+        panic_with_error!(&env, Error::NonTransferable)
+    }
+
+    /// Fails, as badge tokens are only burned when their attestation is revoked (`Error::NonTransferable`)
+    fn burn_from(env: Env, _spender: Address, _from: Address, _amount: i128) {
+        panic_with_error!(&env, Error::NonTransferable)
+    }
+
+    /// Returns zero, as each badge is one indivisible token
+    fn decimals(_env: Env) -> u32 {
+        0
+    }
+
+    /// Returns the token name
+    fn name(env: Env) -> String {
+        env.storage().persistent().get(&DataKey::Name).unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    /// Returns the token symbol
+    fn symbol(env: Env) -> String {
+        env.storage().persistent().get(&DataKey::Symbol).unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::{token, Map};
+    use test_utils::{EventRecord, EventSnapshot};
+
+    struct Setup {
+        env: Env,
+        manager: Address,
+        user: Address,
+        badge_id: BadgeId,
+        scorer: ScorerContractClient<'static>,
+        client: BadgeTokenContractClient<'static>,
+    }
+
+    // A scorer with a "Speaker" badge and its badge token
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let manager = Address::generate(&env);
+        let badge_id = BadgeId { name: String::from_str(&env, "Speaker"), issuer: Address::generate(&env) };
+        let mut badges = Map::new(&env);
+        badges.set(badge_id.clone(), 40);
+        let scorer = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        scorer.initialize(
+            &manager,
+            &badges,
+            &String::from_str(&env, "Community"),
+            &String::from_str(&env, "A community"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        let client = BadgeTokenContractClient::new(&env, &env.register(BadgeTokenContract, ()));
+        client.initialize(&scorer.address, &String::from_str(&env, "Community badges"), &String::from_str(&env, "BADGE"));
+        scorer.set_extension(&manager, &Extension::BadgeToken, &Some(client.address.clone()));
+
+        let user = Address::generate(&env);
+        scorer.add_user(&user);
+
+        Setup { env, manager, user, badge_id, scorer, client }
+    }
+
+    #[test]
+    fn test_mint_and_burn_badge() {
+        let Setup { env, user, badge_id, client, .. } = setup();
+        let token = token::TokenClient::new(&env, &client.address);
+
+        client.mint_badge(&badge_id.issuer, &user, &badge_id);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BADGE, symbol_short!("mint")),
+                events::BadgeMinted { caller: badge_id.issuer.clone(), user: user.clone(), badge_id: badge_id.clone() },
+            ),
+        ]);
+        assert!(client.holds_badge(&user, &badge_id));
+        assert_eq!(token.balance(&user), 1);
+        assert_eq!(client.get_holders_count(&badge_id), 1);
+        assert_eq!(token.decimals(), 0);
+        assert_eq!(token.symbol(), String::from_str(&env, "BADGE"));
+        assert_eq!(
+            client.try_mint_badge(&badge_id.issuer, &user, &badge_id),
+            Err(Ok(Error::BadgeAlreadyMinted.into()))
+        );
+
+        client.burn_badge(&badge_id.issuer, &user, &badge_id);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BADGE, symbol_short!("burn")),
+                events::BadgeBurned { caller: badge_id.issuer.clone(), user: user.clone(), badge_id: badge_id.clone() },
+            ),
+        ]);
+        assert!(!client.holds_badge(&user, &badge_id));
+        assert_eq!(token.balance(&user), 0);
+        assert_eq!(client.get_holders_count(&badge_id), 0);
+    }
+
+    #[test]
+    fn test_tokens_are_not_transferable() {
+        let Setup { env, user, badge_id, client, .. } = setup();
+        let token = token::TokenClient::new(&env, &client.address);
+        client.mint_badge(&badge_id.issuer, &user, &badge_id);
+
+        let other = Address::generate(&env);
+        assert!(token.try_transfer(&user, &other, &1).is_err());
+        assert!(token.try_approve(&user, &other, &1, &100).is_err());
+        assert!(token.try_burn(&user, &1).is_err());
+        assert_eq!(token.allowance(&user, &other), 0);
+        assert_eq!(token.balance(&user), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_mint_unauthorized() {
+        let Setup { env, user, badge_id, client, .. } = setup();
+
+        client.mint_badge(&Address::generate(&env), &user, &badge_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #4)")]
+    fn test_mint_unknown_badge() {
+        let Setup { env, scorer, user, client, .. } = setup();
+        let unknown = BadgeId { name: String::from_str(&env, "Unknown"), issuer: Address::generate(&env) };
+
+        client.mint_badge(&scorer.address, &user, &unknown);
+    }
+
+    #[test]
+    fn test_mint_disabled_badge() {
+        let Setup { manager, user, badge_id, scorer, client, .. } = setup();
+
+        scorer.set_badge_enabled(&manager, &badge_id, &false);
+        assert_eq!(
+            client.try_mint_badge(&badge_id.issuer, &user, &badge_id),
+            Err(Ok(Error::BadgeDisabled.into()))
+        );
+
+        scorer.set_badge_enabled(&manager, &badge_id, &true);
+        client.mint_badge(&badge_id.issuer, &user, &badge_id);
+        assert!(client.holds_badge(&user, &badge_id));
+    }
+
+    #[test]
+    fn test_claim_legacy_badges() {
+        let Setup { env, manager, user, badge_id, client, .. } = setup();
+        let other = Address::generate(&env);
+        let removed = BadgeId { name: String::from_str(&env, "Retired"), issuer: badge_id.issuer.clone() };
+        let user_badges = Vec::from_array(&env, [badge_id.clone(), removed]);
+        let other_badges = Vec::from_array(&env, [badge_id.clone()]);
+
+        let leaf = |user: &Address, badges: &Vec<BadgeId>| {
+            merkle::leaf(&env, LegacyClaim { token: client.address.clone(), user: user.clone(), badges: badges.clone() })
+        };
+        let user_leaf = leaf(&user, &user_badges);
+        let other_leaf = leaf(&other, &other_badges);
+        let root = merkle::hash_pair(&env, &user_leaf, &other_leaf);
+        let proof = Vec::from_array(&env, [other_leaf]);
+
+        assert_eq!(
+            client.try_claim_legacy_badges(&user, &user_badges, &proof),
+            Err(Ok(Error::LegacyRootNotFound.into()))
+        );
+        assert_eq!(client.try_set_legacy_root(&user, &Some(root.clone())), Err(Ok(Error::Unauthorized.into())));
+        client.set_legacy_root(&manager, &Some(root.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BADGE, symbol_short!("legacy")),
+                events::LegacyRootSet { manager: manager.clone(), root: root.clone() },
+            ),
+        ]);
+        assert_eq!(client.get_legacy_root(), Some(root.clone()));
+
+        assert_eq!(
+            client.try_claim_legacy_badges(&user, &other_badges, &proof),
+            Err(Ok(Error::InvalidProof.into()))
+        );
+
+        // Badges no longer part of the scorer are skipped
+        assert_eq!(client.claim_legacy_badges(&user, &user_badges, &proof), 1);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BADGE, symbol_short!("mint")),
+                events::BadgeMinted { caller: client.address.clone(), user: user.clone(), badge_id: badge_id.clone() },
+            ),
+        ]);
+        assert_eq!(client.get_badges(&user), other_badges);
+        assert_eq!(
+            client.try_claim_legacy_badges(&user, &user_badges, &proof),
+            Err(Ok(Error::AlreadyClaimed.into()))
+        );
+
+        client.set_legacy_root(&manager, &None);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_BADGE, symbol_short!("legclear")),
+            events::LegacyRootCleared { manager: manager.clone() },
+        )]);
+        assert_eq!(client.get_legacy_root(), None);
+        assert_eq!(
+            client.try_claim_legacy_badges(&other, &other_badges, &Vec::from_array(&env, [user_leaf])),
+            Err(Ok(Error::LegacyRootNotFound.into()))
+        );
+    }
+}
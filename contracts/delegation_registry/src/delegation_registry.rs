@@ -0,0 +1,272 @@
+#![no_std]
+use scorer_interface::{events, DelegationRegistryTrait};
+use scorer_interface::events::{TOPIC_DELEGATE};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, vec, Address, Env};
+
+// Longest chain of delegations followed when resolving a user
+const MAX_DEPTH: u32 = 8;
+
+#[contracttype]
+enum DataKey {
+    Global(Address),
+    Scoped(Address, Address),
+}
+
+/// Errors raised by the delegation registry.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The user tried to delegate to itself.
+    SelfDelegation = 1,
+    /// The delegation would make the chain lead back to the delegator.
+    DelegationLoop = 2,
+    /// The delegation would make the chain longer than the maximum depth.
+    ChainTooLong = 3,
+    /// The user has no delegation for the scorer.
+    DelegationNotFound = 4,
+}
+
+/// Delegations of score and votes, shared by every scorer.
+///
+/// A user delegates either for a single scorer or globally, for every scorer
+/// it has no delegation of its own for. Delegations chain: a delegatee who
+/// delegates passes on what it was delegated, up to `MAX_DEPTH` hops. A
+/// delegation closing a loop in its own scope is rejected; a loop mixing a
+/// global delegation with scorer ones is ignored when resolving, leaving the
+/// user as its own representative.
+#[contract]
+pub struct DelegationRegistryContract;
+
+#[contractimpl]
+impl DelegationRegistryContract {
+    /// Delegates the score and votes of a user
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `delegator` - The user delegating
+    /// * `scorer` - The scorer the delegation applies to, or `None` for every scorer
+    /// * `delegatee` - The address receiving the delegation
+    ///
+    /// # Panics
+    /// * When the delegator fails authentication
+    /// * When the delegator is the delegatee (`Error::SelfDelegation`)
+    /// * When the chain from the delegatee leads back to the delegator (`Error::DelegationLoop`)
+    /// * When the chain from the delegatee is too long (`Error::ChainTooLong`)
+    pub fn delegate(env: Env, delegator: Address, scorer: Option<Address>, delegatee: Address) {
+        delegator.require_auth();
+
+        if delegator == delegatee {
+            panic_with_error!(&env, Error::SelfDelegation);
+        }
+
+        let mut current = delegatee.clone();
+        let mut depth = 1;
+        while let Some(next) = Self::get_scoped_delegate(&env, &current, &scorer) {
+            if next == delegator {
+                panic_with_error!(&env, Error::DelegationLoop);
+            }
+            depth += 1;
+            if depth > MAX_DEPTH {
+                panic_with_error!(&env, Error::ChainTooLong);
+            }
+            current = next;
+        }
+
+        env.storage().persistent().set(&Self::key(&delegator, &scorer), &delegatee);
+
+        env.events().publish(
+            (TOPIC_DELEGATE, symbol_short!("set")),
+            events::DelegationSet { delegator, scorer, delegatee },
+        );
+    }
+
+    /// Removes a delegation of a user
+    ///
+    /// Removing a delegation for a scorer falls back to the global delegation,
+    /// if any.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `delegator` - The user removing its delegation
+    /// * `scorer` - The scorer of the delegation, or `None` for the global one
+    ///
+    /// # Panics
+    /// * When the delegator fails authentication
+    /// * When the delegator has no such delegation (`Error::DelegationNotFound`)
+    pub fn undelegate(env: Env, delegator: Address, scorer: Option<Address>) {
+        delegator.require_auth();
+
+        let key = Self::key(&delegator, &scorer);
+        if !env.storage().persistent().has(&key) {
+            panic_with_error!(&env, Error::DelegationNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (TOPIC_DELEGATE, symbol_short!("remove")),
+            events::DelegationRemoved { delegator, scorer },
+        );
+    }
+
+    /// Returns the global delegation of a user, ignoring its scorer delegations
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The user to look up
+    pub fn get_global_delegate(env: Env, user: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Global(user))
+    }
+
+    fn key(delegator: &Address, scorer: &Option<Address>) -> DataKey {
+        match scorer {
+            Some(scorer) => DataKey::Scoped(delegator.clone(), scorer.clone()),
+            None => DataKey::Global(delegator.clone()),
+        }
+    }
+
+    // The delegation followed in a scope: for a scorer, its own delegation or
+    // else the global one; globally, only the global one.
+    fn get_scoped_delegate(env: &Env, user: &Address, scorer: &Option<Address>) -> Option<Address> {
+        match scorer {
+            Some(scorer) => Self::get_delegate(env.clone(), user.clone(), scorer.clone()),
+            None => Self::get_global_delegate(env.clone(), user.clone()),
+        }
+    }
+}
+
+#[contractimpl]
+impl DelegationRegistryTrait for DelegationRegistryContract {
+    /// Returns the address a user delegated to for a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The user to look up
+    /// * `scorer` - The scorer to look up
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The delegation for the scorer, else the global delegation, if any
+    fn get_delegate(env: Env, user: Address, scorer: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scoped(user.clone(), scorer))
+            .or_else(|| Self::get_global_delegate(env, user))
+    }
+
+    /// Returns the representative of a user for a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The user to resolve
+    /// * `scorer` - The scorer to resolve for
+    ///
+    /// # Returns
+    /// * `Address` - The last address of the chain of delegations from the user,
+    ///   or the user itself when it did not delegate or the chain loops
+    fn resolve(env: Env, user: Address, scorer: Address) -> Address {
+        let mut visited = vec![&env, user.clone()];
+        let mut current = user.clone();
+        while let Some(next) = Self::get_delegate(env.clone(), current.clone(), scorer.clone()) {
+            if visited.contains(&next) || visited.len() > MAX_DEPTH {
+                return user;
+            }
+            visited.push_back(next.clone());
+            current = next;
+        }
+        current
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use test_utils::{EventRecord, EventSnapshot};
+
+    fn setup() -> (Env, DelegationRegistryContractClient<'static>) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = DelegationRegistryContractClient::new(&env, &env.register(DelegationRegistryContract, ()));
+        (env, client)
+    }
+
+    #[test]
+    fn test_delegate() {
+        let (env, client) = setup();
+        let scorer = Address::generate(&env);
+        let other_scorer = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+
+        client.delegate(&alice, &None, &bob);
+        client.delegate(&alice, &Some(scorer.clone()), &carol);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_DELEGATE, symbol_short!("set")),
+            events::DelegationSet { delegator: alice.clone(), scorer: Some(scorer.clone()), delegatee: carol.clone() },
+        )]);
+
+        // The scorer delegation overrides the global one
+        assert_eq!(client.get_delegate(&alice, &scorer), Some(carol.clone()));
+        assert_eq!(client.get_delegate(&alice, &other_scorer), Some(bob.clone()));
+        assert_eq!(client.get_delegate(&bob, &scorer), None);
+
+        client.undelegate(&alice, &Some(scorer.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_DELEGATE, symbol_short!("remove")),
+            events::DelegationRemoved { delegator: alice.clone(), scorer: Some(scorer.clone()) },
+        )]);
+        assert_eq!(client.get_delegate(&alice, &scorer), Some(bob));
+
+        assert_eq!(client.try_undelegate(&alice, &Some(scorer)), Err(Ok(Error::DelegationNotFound.into())));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let (env, client) = setup();
+        let scorer = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+
+        assert_eq!(client.resolve(&alice, &scorer), alice);
+
+        client.delegate(&alice, &None, &bob);
+        client.delegate(&bob, &Some(scorer.clone()), &carol);
+        assert_eq!(client.resolve(&alice, &scorer), carol);
+        assert_eq!(client.resolve(&alice, &Address::generate(&env)), bob);
+
+        // A loop through global and scorer delegations is ignored
+        client.delegate(&carol, &None, &alice);
+        assert_eq!(client.resolve(&alice, &scorer), alice);
+    }
+
+    #[test]
+    fn test_delegate_rejections() {
+        let (env, client) = setup();
+        let scorer = Some(Address::generate(&env));
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+
+        assert_eq!(client.try_delegate(&alice, &None, &alice), Err(Ok(Error::SelfDelegation.into())));
+
+        client.delegate(&alice, &None, &bob);
+        client.delegate(&bob, &scorer, &carol);
+        assert_eq!(client.try_delegate(&carol, &scorer, &alice), Err(Ok(Error::DelegationLoop.into())));
+        assert_eq!(client.try_delegate(&bob, &None, &alice), Err(Ok(Error::DelegationLoop.into())));
+
+        // A chain of MAX_DEPTH delegations cannot grow further
+        let mut users = vec![&env, Address::generate(&env)];
+        for _ in 0..MAX_DEPTH {
+            let user = Address::generate(&env);
+            client.delegate(&user, &None, &users.last().unwrap());
+            users.push_back(user);
+        }
+        assert_eq!(client.try_delegate(&Address::generate(&env), &None, &users.last().unwrap()), Err(Ok(Error::ChainTooLong.into())));
+    }
+}
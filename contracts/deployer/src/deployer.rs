@@ -1,8 +1,78 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, Address, BytesN, Env, Symbol, Val, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal,
+    String, Symbol, Val, Vec,
 };
 
+use scorer_interface::events::{TOPIC_ALLOWLIST, TOPIC_DEPLOY};
+
+pub use scorer_interface::events;
+
+// Number of deployments returned per page by `get_deployments`
+const PAGE_SIZE: u32 = 10;
+
+#[contracttype]
+enum DataKey {
+    DeploymentCount,
+    Deployment(u32),
+    DeployedAddress(Address),
+    AllowlistAdmin,
+    AllowedDeployers,
+}
+
+/// Errors returned by the deployer, so callers can tell deployment failures
+/// apart from initialization failures.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The deployer already deployed a contract with this salt.
+    DuplicateSalt = 1,
+    /// The init function of the deployed contract failed.
+    InitFailed = 2,
+    /// The ownership transfer on the deployed contract failed.
+    AdminTransferFailed = 3,
+    /// The allowlist is enabled and the deployer is not on it.
+    DeployerNotAllowed = 4,
+    /// The allowlist mode was already enabled.
+    AllowlistAlreadyEnabled = 5,
+    /// The allowlist mode is not enabled.
+    AllowlistNotEnabled = 6,
+    /// The caller is not the allowlist admin.
+    NotAllowlistAdmin = 7,
+    /// The init function returned a different value than expected.
+    UnexpectedInitResult = 8,
+}
+
+/// A deployment recorded in the deployer's audit log.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeploymentRecord {
+    pub address: Address,
+    pub wasm_hash: BytesN<32>,
+    pub deployer: Address,
+    pub ledger: u32,
+}
+
+/// The contracts of an environment set up by `bootstrap`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BootstrappedEnvironment {
+    pub factory: Address,
+    pub factory_wasm_hash: BytesN<32>,
+    pub scorer_wasm_hash: BytesN<32>,
+}
+
+/// Parameters of a single deployment in a `deploy_many` batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeploymentSpec {
+    pub wasm_hash: BytesN<32>,
+    pub salt: BytesN<32>,
+    pub init_fn: Symbol,
+    pub init_args: Vec<Val>,
+}
+
 #[contract]
 pub struct Deployer;
 
@@ -15,7 +85,12 @@ impl Deployer {
     /// itself is used as deployer). This way the whole operation is atomic
     /// and it's not possible to frontrun the contract initialization.
     ///
-    /// Returns the contract address and result of the init function.
+    /// The init function must return nothing, like the scorer's and the
+    /// factory's `initialize`; use `deploy_checked` for init functions with a
+    /// result.
+    ///
+    /// Returns the contract address, or `Error::DuplicateSalt` /
+    /// `Error::InitFailed` / `Error::UnexpectedInitResult`.
     pub fn deploy(
         env: Env,
         deployer: Address,
@@ -23,24 +98,483 @@ impl Deployer {
         salt: BytesN<32>,
         init_fn: Symbol,
         init_args: Vec<Val>,
-    ) -> (Address, Val) {
+    ) -> Result<Address, Error> {
+        // Skip authorization if deployer is the current contract.
+        if deployer != env.current_contract_address() {
+            deployer.require_auth();
+        }
+
+        Self::deploy_void(&env, deployer, wasm_hash, salt, init_fn, init_args)
+    }
+
+    /// Deploy the contract Wasm and run its constructor with the given
+    /// arguments as part of the deployment.
+    ///
+    /// Unlike `deploy`, there is no separate init call: the contract is set up
+    /// when it is created, so no one can initialize it first. The Wasm must
+    /// export a `__constructor` taking `constructor_args`; a failing
+    /// constructor aborts the transaction. This has to be authorized by
+    /// `deployer` (unless the `Deployer` instance itself is used as deployer),
+    /// and in allowlist mode `deployer` must be approved.
+    ///
+    /// Returns the contract address, or `Error::DuplicateSalt` /
+    /// `Error::DeployerNotAllowed`.
+    pub fn deploy_v2(
+        env: Env,
+        deployer: Address,
+        wasm_hash: BytesN<32>,
+        salt: BytesN<32>,
+        constructor_args: Vec<Val>,
+    ) -> Result<Address, Error> {
+        // Skip authorization if deployer is the current contract.
+        if deployer != env.current_contract_address() {
+            deployer.require_auth();
+        }
+
+        let deployed_address = Self::check_deployment(&env, &deployer, &salt)?;
+        env.deployer()
+            .with_address(deployer.clone(), salt.clone())
+            .deploy_v2(wasm_hash.clone(), constructor_args);
+
+        Self::record_deployment(&env, deployer, wasm_hash, salt, &deployed_address, Symbol::new(&env, "__constructor"));
+        Ok(deployed_address)
+    }
+
+    /// Deploy the contract Wasm like `deploy`, and check that the init function
+    /// returned `expected_result`.
+    ///
+    /// Values are compared by their XDR encoding, so for example a `()` init
+    /// function is checked with `().into_val(&env)`. On mismatch the whole
+    /// deployment is rolled back, so misconfigured init arguments fail loudly
+    /// instead of leaving an unexpectedly initialized contract behind.
+    ///
+    /// Returns the contract address, or `Error::UnexpectedInitResult` and the
+    /// errors of `deploy`.
+    pub fn deploy_checked(
+        env: Env,
+        deployer: Address,
+        wasm_hash: BytesN<32>,
+        salt: BytesN<32>,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+        expected_result: Val,
+    ) -> Result<Address, Error> {
+        // Skip authorization if deployer is the current contract.
+        if deployer != env.current_contract_address() {
+            deployer.require_auth();
+        }
+
+        let (deployed_address, res) = Self::deploy_contract(&env, deployer, wasm_hash, salt, init_fn, init_args)?;
+        if res.to_xdr(&env) != expected_result.to_xdr(&env) {
+            return Err(Error::UnexpectedInitResult);
+        }
+
+        Ok(deployed_address)
+    }
+
+    /// Deploy and initialize several contracts in a single atomic call, in
+    /// the given order.
+    ///
+    /// This has to be authorized by `deployer` (unless the `Deployer` instance
+    /// itself is used as deployer). If any deployment or init call fails, the
+    /// whole batch is rolled back.
+    ///
+    /// Returns the addresses of the deployed contracts, in the order of `specs`,
+    /// or the error of the first failing deployment.
+    pub fn deploy_many(env: Env, deployer: Address, specs: Vec<DeploymentSpec>) -> Result<Vec<Address>, Error> {
+        // Skip authorization if deployer is the current contract.
+        if deployer != env.current_contract_address() {
+            deployer.require_auth();
+        }
+
+        let mut deployed_addresses = Vec::new(&env);
+        for spec in specs.iter() {
+            let (deployed_address, _) = Self::deploy_contract(
+                &env,
+                deployer.clone(),
+                spec.wasm_hash,
+                spec.salt,
+                spec.init_fn,
+                spec.init_args,
+            )?;
+            deployed_addresses.push_back(deployed_address);
+        }
+
+        Ok(deployed_addresses)
+    }
+
+    /// Deploy the contract Wasm, invoke its init function and then hand its
+    /// ownership to `new_admin`.
+    ///
+    /// After initialization the deployed contract's `transfer_ownership(sender,
+    /// new_owner)` function is invoked with `deployer` as sender, so a relayer
+    /// can deploy a community on behalf of its owner without keeping control.
+    /// The init arguments must therefore make `deployer` the initial owner.
+    ///
+    /// This has to be authorized by `deployer` (unless the `Deployer` instance
+    /// itself is used as deployer).
+    ///
+    /// Returns the contract address and result of the init function, or
+    /// `Error::DuplicateSalt` / `Error::InitFailed` / `Error::AdminTransferFailed`.
+    pub fn deploy_and_transfer_admin(
+        env: Env,
+        deployer: Address,
+        wasm_hash: BytesN<32>,
+        salt: BytesN<32>,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+        new_admin: Address,
+    ) -> Result<(Address, Val), Error> {
+        // Skip authorization if deployer is the current contract.
+        if deployer != env.current_contract_address() {
+            deployer.require_auth();
+        }
+
+        let (deployed_address, res) = Self::deploy_contract(&env, deployer.clone(), wasm_hash, salt, init_fn, init_args)?;
+
+        // Hand control of the new contract to the designated admin.
+        let transfer = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &deployed_address,
+            &Symbol::new(&env, "transfer_ownership"),
+            (deployer, new_admin).into_val(&env),
+        );
+        if !matches!(transfer, Ok(Ok(()))) {
+            return Err(Error::AdminTransferFailed);
+        }
+
+        Ok((deployed_address, res))
+    }
+
+    /// Set up a new environment in a single transaction: upload the factory and
+    /// scorer Wasm, deploy the factory with `deployer_salt` and initialize it
+    /// with `admin` as its creator and the uploaded scorer hash.
+    ///
+    /// This has to be authorized by `admin`, who is the deployer of the factory
+    /// and must be approved in allowlist mode. Validating the uploads takes most
+    /// of a transaction's budget, so the transaction needs the network limits.
+    ///
+    /// Returns the factory address and both Wasm hashes, or the errors of
+    /// `deploy`.
+    pub fn bootstrap(
+        env: Env,
+        admin: Address,
+        factory_wasm: Bytes,
+        scorer_wasm: Bytes,
+        deployer_salt: BytesN<32>,
+    ) -> Result<BootstrappedEnvironment, Error> {
+        admin.require_auth();
+
+        let factory_wasm_hash = env.deployer().upload_contract_wasm(factory_wasm);
+        let scorer_wasm_hash = env.deployer().upload_contract_wasm(scorer_wasm);
+
+        let (factory, _) = Self::deploy_contract(
+            &env,
+            admin.clone(),
+            factory_wasm_hash.clone(),
+            deployer_salt,
+            Symbol::new(&env, "initialize"),
+            (admin.clone(), scorer_wasm_hash.clone()).into_val(&env),
+        )?;
+
+        let environment = BootstrappedEnvironment { factory, factory_wasm_hash, scorer_wasm_hash };
+        env.events().publish(
+            (TOPIC_DEPLOY, symbol_short!("bootstrap")),
+            events::EnvironmentBootstrapped {
+                admin,
+                factory: environment.factory.clone(),
+                factory_wasm_hash: environment.factory_wasm_hash.clone(),
+                scorer_wasm_hash: environment.scorer_wasm_hash.clone(),
+            },
+        );
+
+        Ok(environment)
+    }
+
+    /// Deploy the Stellar Asset Contract (SAC) wrapping a classic Stellar
+    /// asset, so badge assets issued on classic Stellar can be used from
+    /// Soroban as part of the same bootstrap flow as the scorer stack.
+    ///
+    /// `asset` is the XDR serialized `Asset`. This has to be authorized by
+    /// `deployer` (unless the `Deployer` instance itself is used as deployer),
+    /// and in allowlist mode `deployer` must be approved. The SAC address only
+    /// depends on the asset, so each asset can be wrapped once.
+    ///
+    /// Returns the SAC address, or `Error::DeployerNotAllowed`.
+    pub fn deploy_sac(env: Env, deployer: Address, asset: Bytes) -> Result<Address, Error> {
+        // Skip authorization if deployer is the current contract.
+        if deployer != env.current_contract_address() {
+            deployer.require_auth();
+        }
+
+        if !Self::is_deployer_allowed(env.clone(), deployer.clone()) {
+            return Err(Error::DeployerNotAllowed);
+        }
+
+        let deployed_address = env.deployer().with_stellar_asset(asset.clone()).deploy();
+
+        env.events().publish(
+            (TOPIC_DEPLOY, symbol_short!("sac")),
+            events::AssetContractDeployed { deployer, asset, contract: deployed_address.clone() },
+        );
+
+        Ok(deployed_address)
+    }
+
+    /// Deploy the contract Wasm like `deploy`, with the salt derived from
+    /// `label` by `derive_salt`.
+    ///
+    /// A label like "factory-v2" gives the same address whenever the same
+    /// `deployer` deploys it, so scripts get human-meaningful addresses that
+    /// survive testnet resets without managing 32-byte salts.
+    ///
+    /// Returns the contract address, or the errors of `deploy`.
+    pub fn deploy_labeled(
+        env: Env,
+        deployer: Address,
+        wasm_hash: BytesN<32>,
+        label: String,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+    ) -> Result<Address, Error> {
         // Skip authorization if deployer is the current contract.
         if deployer != env.current_contract_address() {
             deployer.require_auth();
         }
 
+        let salt = Self::derive_salt(env.clone(), deployer.clone(), label);
+        Self::deploy_void(&env, deployer, wasm_hash, salt, init_fn, init_args)
+    }
+
+    /// Returns the salt `deploy_labeled` uses for `label`, the SHA-256 hash of
+    /// the XDR encodings of `deployer` and `label`.
+    ///
+    /// Salts are namespaced by the deployer, so different deployers can use
+    /// the same labels.
+    pub fn derive_salt(env: Env, deployer: Address, label: String) -> BytesN<32> {
+        let mut preimage = deployer.to_xdr(&env);
+        preimage.append(&label.to_xdr(&env));
+        env.crypto().sha256(&preimage).into()
+    }
+
+    /// Returns the address `deploy` would use for a contract deployed by
+    /// `deployer` with `salt`, so callers can compute target addresses ahead
+    /// of time and verify them after deployment.
+    pub fn predict_address(env: Env, deployer: Address, salt: BytesN<32>) -> Address {
+        env.deployer().with_address(deployer, salt).deployed_address()
+    }
+
+    /// Returns the address `deploy_labeled` would use for a contract deployed
+    /// by `deployer` with `label`.
+    pub fn predict_labeled_address(env: Env, deployer: Address, label: String) -> Address {
+        let salt = Self::derive_salt(env.clone(), deployer.clone(), label);
+        Self::predict_address(env, deployer, salt)
+    }
+
+    /// Switch the deployer to allowlist mode, where only approved addresses
+    /// may deploy contracts through it.
+    ///
+    /// This is a one-way operation that has to be authorized by `admin`, who
+    /// becomes the allowlist admin and is approved as the first deployer.
+    /// Without it anyone can deploy through this instance.
+    ///
+    /// Returns `Error::AllowlistAlreadyEnabled` if the mode was already enabled.
+    pub fn enable_allowlist(env: Env, admin: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        if env.storage().persistent().has(&DataKey::AllowlistAdmin) {
+            return Err(Error::AllowlistAlreadyEnabled);
+        }
+
+        let mut allowed_deployers = Vec::new(&env);
+        allowed_deployers.push_back(admin.clone());
+        env.storage().persistent().set(&DataKey::AllowlistAdmin, &admin);
+        env.storage().persistent().set(&DataKey::AllowedDeployers, &allowed_deployers);
+
+        env.events().publish((TOPIC_ALLOWLIST, symbol_short!("enable")), events::AllowlistEnabled { admin });
+        Ok(())
+    }
+
+    /// Approve `deployer` to deploy contracts in allowlist mode.
+    ///
+    /// This has to be authorized by the allowlist admin.
+    pub fn allow_deployer(env: Env, admin: Address, deployer: Address) -> Result<(), Error> {
+        let mut allowed_deployers = Self::check_allowlist_admin(&env, &admin)?;
+
+        if !allowed_deployers.contains(&deployer) {
+            allowed_deployers.push_back(deployer.clone());
+            env.storage().persistent().set(&DataKey::AllowedDeployers, &allowed_deployers);
+        }
+
+        env.events().publish((TOPIC_ALLOWLIST, symbol_short!("add")), events::DeployerAllowed { deployer });
+        Ok(())
+    }
+
+    /// Revoke the approval of `deployer` in allowlist mode.
+    ///
+    /// This has to be authorized by the allowlist admin.
+    pub fn disallow_deployer(env: Env, admin: Address, deployer: Address) -> Result<(), Error> {
+        let mut allowed_deployers = Self::check_allowlist_admin(&env, &admin)?;
+
+        if let Some(index) = allowed_deployers.first_index_of(&deployer) {
+            allowed_deployers.remove(index);
+            env.storage().persistent().set(&DataKey::AllowedDeployers, &allowed_deployers);
+        }
+
+        env.events().publish((TOPIC_ALLOWLIST, symbol_short!("remove")), events::DeployerDisallowed { deployer });
+        Ok(())
+    }
+
+    /// Returns the allowlist admin, if the allowlist mode is enabled.
+    pub fn get_allowlist_admin(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::AllowlistAdmin)
+    }
+
+    /// Returns whether `deployer` may deploy contracts. Always true when the
+    /// allowlist mode is not enabled.
+    pub fn is_deployer_allowed(env: Env, deployer: Address) -> bool {
+        match env.storage().persistent().get::<DataKey, Vec<Address>>(&DataKey::AllowedDeployers) {
+            Some(allowed_deployers) => allowed_deployers.contains(&deployer),
+            None => true,
+        }
+    }
+
+    /// Extend the TTL of a deployed contract's instance and code, so freshly
+    /// deployed contracts are not archived if unused for a while.
+    ///
+    /// Anyone can pay for the extension, so no authorization is required.
+    /// The TTLs are only extended if they are below `threshold` ledgers, and
+    /// then become `extend_to` ledgers.
+    pub fn extend_ttl(env: Env, contract_address: Address, threshold: u32, extend_to: u32) {
+        env.deployer().extend_ttl(contract_address, threshold, extend_to);
+    }
+
+    /// Returns the number of deployments recorded by this deployer.
+    pub fn get_deployment_count(env: Env) -> u32 {
+        env.storage().persistent().get(&DataKey::DeploymentCount).unwrap_or(0)
+    }
+
+    /// Returns one page of the deployment audit log, oldest first.
+    ///
+    /// Pages are zero-based and hold up to `PAGE_SIZE` records; pages past the
+    /// end of the log are empty.
+    pub fn get_deployments(env: Env, page: u32) -> Vec<DeploymentRecord> {
+        let count = Self::get_deployment_count(env.clone());
+        let start = page.saturating_mul(PAGE_SIZE).min(count);
+        let end = start.saturating_add(PAGE_SIZE).min(count);
+
+        let mut records = Vec::new(&env);
+        for index in start..end {
+            if let Some(record) = env.storage().persistent().get(&DataKey::Deployment(index)) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
+
+    fn check_allowlist_admin(env: &Env, admin: &Address) -> Result<Vec<Address>, Error> {
+        admin.require_auth();
+
+        let current_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AllowlistAdmin)
+            .ok_or(Error::AllowlistNotEnabled)?;
+        if current_admin != *admin {
+            return Err(Error::NotAllowlistAdmin);
+        }
+
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::AllowedDeployers)
+            .unwrap_or(Vec::new(env)))
+    }
+
+    fn deploy_contract(
+        env: &Env,
+        deployer: Address,
+        wasm_hash: BytesN<32>,
+        salt: BytesN<32>,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+    ) -> Result<(Address, Val), Error> {
+        let deployed_address = Self::check_deployment(env, &deployer, &salt)?;
+
         // Deploy the contract using the uploaded Wasm with given hash.
-        let deployed_address = env
-            .deployer()
-            .with_address(deployer, salt)
-            .deploy(wasm_hash);
+        env.deployer()
+            .with_address(deployer.clone(), salt.clone())
+            .deploy_v2(wasm_hash.clone(), ());
 
         // Invoke the init function with the given arguments.
-        let res: Val = env.invoke_contract(&deployed_address, &init_fn, init_args);
-        
+        let res = match env.try_invoke_contract::<Val, soroban_sdk::Error>(&deployed_address, &init_fn, init_args) {
+            Ok(Ok(res)) => res,
+            _ => return Err(Error::InitFailed),
+        };
+
+        Self::record_deployment(env, deployer, wasm_hash, salt, &deployed_address, init_fn);
+
         // Return the contract ID of the deployed contract and the result of
         // invoking the init result.
-        (deployed_address, res)
+        Ok((deployed_address, res))
+    }
+
+    // Deploys the contract like `deploy_contract` and checks that the init
+    // function returned nothing
+    fn deploy_void(
+        env: &Env,
+        deployer: Address,
+        wasm_hash: BytesN<32>,
+        salt: BytesN<32>,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+    ) -> Result<Address, Error> {
+        let (deployed_address, res) = Self::deploy_contract(env, deployer, wasm_hash, salt, init_fn, init_args)?;
+        if !res.is_void() {
+            return Err(Error::UnexpectedInitResult);
+        }
+
+        Ok(deployed_address)
+    }
+
+    // Checks that `deployer` may deploy with `salt`, and returns the address
+    // the contract will get
+    fn check_deployment(env: &Env, deployer: &Address, salt: &BytesN<32>) -> Result<Address, Error> {
+        if !Self::is_deployer_allowed(env.clone(), deployer.clone()) {
+            return Err(Error::DeployerNotAllowed);
+        }
+
+        // Reject salts this deployer already used for the same deployer address.
+        let deployed_address = Self::predict_address(env.clone(), deployer.clone(), salt.clone());
+        if env.storage().persistent().has(&DataKey::DeployedAddress(deployed_address.clone())) {
+            return Err(Error::DuplicateSalt);
+        }
+        Ok(deployed_address)
+    }
+
+    // Records the deployment in the audit log and publishes it
+    fn record_deployment(
+        env: &Env,
+        deployer: Address,
+        wasm_hash: BytesN<32>,
+        salt: BytesN<32>,
+        deployed_address: &Address,
+        init_fn: Symbol,
+    ) {
+        let count = Self::get_deployment_count(env.clone());
+        let record = DeploymentRecord {
+            address: deployed_address.clone(),
+            wasm_hash: wasm_hash.clone(),
+            deployer: deployer.clone(),
+            ledger: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&DataKey::Deployment(count), &record);
+        env.storage().persistent().set(&DataKey::DeploymentCount, &(count + 1));
+        env.storage().persistent().set(&DataKey::DeployedAddress(deployed_address.clone()), &count);
+
+        env.events().publish(
+            (TOPIC_DEPLOY, symbol_short!("contract")),
+            events::ContractDeployed { deployer, wasm_hash, salt, contract: deployed_address.clone(), init_fn },
+        );
     }
 }
 
@@ -48,13 +582,23 @@ impl Deployer {
 mod test {
     use super::*;
     use scorer_contract::BadgeId;
-    use soroban_sdk::{testutils::Address as _, String, Map, Vec, testutils::BytesN as _, IntoVal};
+    use soroban_sdk::{testutils::Address as _, String, Map, Vec, testutils::BytesN as _, testutils::Deployer as _, IntoVal};
+    use test_utils::{EventRecord, EventSnapshot};
     mod scorer_contract {
         soroban_sdk::contractimport!(
             file = "../../wasm/scorer.wasm"
         );
     }
 
+    fn upload_scorer_wasm(env: &Env) -> BytesN<32> {
+        // Validating the upload takes about the whole default budget, which on
+        // the network would be spent by a transaction of its own
+        env.cost_estimate().budget().reset_unlimited();
+        let wasm_hash = env.deployer().upload_contract_wasm(scorer_contract::WASM);
+        env.cost_estimate().budget().reset_default();
+        wasm_hash
+    }
+
     #[test]
     fn test_deploy_scorer() {
         let env = Env::default();
@@ -69,10 +613,10 @@ mod test {
             issuer: scorer_creator.clone()
         };
 
-        scorer_badges.set(badge_id, 100);
+        scorer_badges.set(badge_id, 100u32);
 
         // Deploy the generic deployer contract
-        let deployer_address = env.register_contract(None, Deployer);
+        let deployer_address = env.register(Deployer, ());
         let deployer = DeployerClient::new(&env, &deployer_address);
 
         // Prepare initialization arguments
@@ -87,17 +631,445 @@ mod test {
         let init_fn = Symbol::new(&env, "initialize");
         
         // Get the WASM hash of the Scorer contract
-        let wasm_hash = env.deployer().upload_contract_wasm(scorer_contract::WASM);
+        let wasm_hash = upload_scorer_wasm(&env);
         let salt = BytesN::random(&env);
+        let predicted_address = deployer.predict_address(&scorer_creator, &salt);
 
         // Deploy and initialize the scorer contract atomically
-        let (_scorer_address, _) = deployer.deploy(
+        let scorer_address = deployer.deploy(
             &scorer_creator,
             &wasm_hash,
             &salt,
             &init_fn,
             &init_args,
         );
-        
+        assert_eq!(scorer_address, predicted_address);
+
+        // Verify event emission
+        let expected_event = EventRecord::new(
+            &env,
+            &deployer_address,
+            (TOPIC_DEPLOY, symbol_short!("contract")),
+            events::ContractDeployed { deployer: scorer_creator, wasm_hash, salt, contract: scorer_address, init_fn },
+        );
+
+        EventSnapshot::capture(&env).assert_contains(&[expected_event]);
+    }
+
+    #[test]
+    fn test_deploy_v2() {
+        mod policy_account_contract {
+            // The imported `__check_auth` refers to the auth context type by name
+            use soroban_sdk::auth::Context;
+            soroban_sdk::contractimport!(
+                file = "../../wasm/policy_account.wasm"
+            );
+        }
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let creator = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let wasm_hash = env.deployer().upload_contract_wasm(policy_account_contract::WASM);
+        let salt = BytesN::random(&env);
+
+        // The account gets its signers from the constructor, with no init call to race
+        let signers = Vec::from_array(&env, [BytesN::<32>::random(&env), BytesN::<32>::random(&env)]);
+        let constructor_args: Vec<Val> = (signers.clone(), 2_u32).into_val(&env);
+        let account_address = deployer.deploy_v2(&creator, &wasm_hash, &salt, &constructor_args);
+        let snapshot = EventSnapshot::capture(&env);
+        assert_eq!(account_address, deployer.predict_address(&creator, &salt));
+
+        let account = policy_account_contract::Client::new(&env, &account_address);
+        assert_eq!(account.get_signers(), (signers, 2));
+        assert_eq!(deployer.get_deployment_count(), 1);
+
+        let expected_event = EventRecord::new(
+            &env,
+            &deployer_address,
+            (TOPIC_DEPLOY, symbol_short!("contract")),
+            events::ContractDeployed {
+                deployer: creator.clone(),
+                wasm_hash: wasm_hash.clone(),
+                salt: salt.clone(),
+                contract: account_address,
+                init_fn: Symbol::new(&env, "__constructor"),
+            },
+        );
+        snapshot.assert_contains(&[expected_event]);
+
+        let result = deployer.try_deploy_v2(&creator, &wasm_hash, &salt, &constructor_args);
+        assert_eq!(result.err(), Some(Ok(Error::DuplicateSalt)));
+    }
+
+    #[test]
+    fn test_deploy_many_scorers() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let scorer_creator = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let wasm_hash = upload_scorer_wasm(&env);
+
+        let mut specs = Vec::new(&env);
+        for name in ["First Scorer", "Second Scorer"] {
+            let mut init_args: Vec<Val> = Vec::new(&env);
+            init_args.push_back(scorer_creator.clone().into_val(&env));
+            init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+            init_args.push_back(String::from_str(&env, name).into_val(&env));
+            init_args.push_back(String::from_str(&env, "A test scorer contract").into_val(&env));
+            init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+            specs.push_back(DeploymentSpec {
+                wasm_hash: wasm_hash.clone(),
+                salt: BytesN::random(&env),
+                init_fn: Symbol::new(&env, "initialize"),
+                init_args,
+            });
+        }
+
+        let addresses = deployer.deploy_many(&scorer_creator, &specs);
+        assert_eq!(addresses.len(), 2);
+
+        // Both deployments are recorded in the audit log
+        assert_eq!(deployer.get_deployment_count(), 2);
+        let records = deployer.get_deployments(&0);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records.get(0).unwrap(), DeploymentRecord {
+            address: addresses.get(0).unwrap(),
+            wasm_hash: wasm_hash.clone(),
+            deployer: scorer_creator.clone(),
+            ledger: env.ledger().sequence(),
+        });
+        assert!(deployer.get_deployments(&1).is_empty());
+
+        let second_scorer = scorer_contract::Client::new(&env, &addresses.get(1).unwrap());
+        assert_eq!(second_scorer.get_metadata().0, String::from_str(&env, "Second Scorer"));
+    }
+
+    #[test]
+    fn test_deploy_labeled() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let scorer_creator = Address::generate(&env);
+        let other_creator = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let wasm_hash = upload_scorer_wasm(&env);
+        let label = String::from_str(&env, "scorer-v2");
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(scorer_creator.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer contract").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        // Salts are reproducible and namespaced by the deployer
+        let salt = deployer.derive_salt(&scorer_creator, &label);
+        assert_eq!(salt, deployer.derive_salt(&scorer_creator, &label));
+        assert_ne!(salt, deployer.derive_salt(&other_creator, &label));
+        assert_ne!(salt, deployer.derive_salt(&scorer_creator, &String::from_str(&env, "scorer-v3")));
+
+        let predicted_address = deployer.predict_labeled_address(&scorer_creator, &label);
+        let scorer_address =
+            deployer.deploy_labeled(&scorer_creator, &wasm_hash, &label, &Symbol::new(&env, "initialize"), &init_args);
+        assert_eq!(scorer_address, predicted_address);
+        assert_eq!(scorer_address, deployer.predict_address(&scorer_creator, &salt));
+
+        let result =
+            deployer.try_deploy_labeled(&scorer_creator, &wasm_hash, &label, &Symbol::new(&env, "initialize"), &init_args);
+        assert_eq!(result.err(), Some(Ok(Error::DuplicateSalt)));
+    }
+
+    #[test]
+    fn test_extend_ttl() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let scorer_creator = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let wasm_hash = upload_scorer_wasm(&env);
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(scorer_creator.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer contract").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        let scorer_address = deployer.deploy(
+            &scorer_creator,
+            &wasm_hash,
+            &BytesN::random(&env),
+            &Symbol::new(&env, "initialize"),
+            &init_args,
+        );
+
+        let extend_to = 100_000;
+        deployer.extend_ttl(&scorer_address, &extend_to, &extend_to);
+
+        assert_eq!(env.deployer().get_contract_instance_ttl(&scorer_address), extend_to);
+        assert_eq!(env.deployer().get_contract_code_ttl(&scorer_address), extend_to);
+    }
+
+    #[test]
+    fn test_deploy_and_transfer_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let relayer = Address::generate(&env);
+        let community_owner = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let wasm_hash = upload_scorer_wasm(&env);
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(relayer.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer contract").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        let (scorer_address, _) = deployer.deploy_and_transfer_admin(
+            &relayer,
+            &wasm_hash,
+            &BytesN::random(&env),
+            &Symbol::new(&env, "initialize"),
+            &init_args,
+            &community_owner,
+        );
+
+        let scorer = scorer_contract::Client::new(&env, &scorer_address);
+        assert_eq!(scorer.get_contract_owner(), community_owner);
+        assert!(!scorer.get_managers().contains(relayer));
+    }
+
+    #[test]
+    fn test_deploy_and_transfer_admin_from_deployer_contract() {
+        let env = Env::default();
+
+        let community_owner = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let wasm_hash = upload_scorer_wasm(&env);
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(deployer_address.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer contract").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        // The deployer contract needs no authorization to deploy as itself
+        let (scorer_address, _) = deployer.deploy_and_transfer_admin(
+            &deployer_address,
+            &wasm_hash,
+            &BytesN::random(&env),
+            &Symbol::new(&env, "initialize"),
+            &init_args,
+            &community_owner,
+        );
+
+        let scorer = scorer_contract::Client::new(&env, &scorer_address);
+        assert_eq!(scorer.get_contract_owner(), community_owner);
+    }
+
+    #[test]
+    fn test_deploy_errors() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let scorer_creator = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let wasm_hash = upload_scorer_wasm(&env);
+        let salt = BytesN::random(&env);
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(scorer_creator.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer contract").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        // Init arguments that do not match the init function
+        let result = deployer.try_deploy(&scorer_creator, &wasm_hash, &salt, &Symbol::new(&env, "initialize"), &Vec::new(&env));
+        assert_eq!(result.err(), Some(Ok(Error::InitFailed)));
+
+        // An init function with a result, here the scorer's version
+        let result = deployer.try_deploy(&scorer_creator, &wasm_hash, &salt, &Symbol::new(&env, "contract_version"), &Vec::new(&env));
+        assert_eq!(result.err(), Some(Ok(Error::UnexpectedInitResult)));
+        assert_eq!(deployer.get_deployment_count(), 0);
+
+        // Reusing a salt
+        deployer.deploy(&scorer_creator, &wasm_hash, &salt, &Symbol::new(&env, "initialize"), &init_args);
+        let result = deployer.try_deploy(&scorer_creator, &wasm_hash, &salt, &Symbol::new(&env, "initialize"), &init_args);
+        assert_eq!(result.err(), Some(Ok(Error::DuplicateSalt)));
+    }
+
+    #[test]
+    fn test_deploy_allowlist() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let scorer_creator = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let wasm_hash = upload_scorer_wasm(&env);
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(scorer_creator.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer contract").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        // Everyone is allowed until the allowlist is enabled
+        assert!(deployer.is_deployer_allowed(&scorer_creator));
+        assert_eq!(deployer.get_allowlist_admin(), None);
+
+        deployer.enable_allowlist(&admin);
+        assert_eq!(deployer.get_allowlist_admin(), Some(admin.clone()));
+        assert!(deployer.is_deployer_allowed(&admin));
+        assert!(!deployer.is_deployer_allowed(&scorer_creator));
+        assert_eq!(deployer.try_enable_allowlist(&scorer_creator), Err(Ok(Error::AllowlistAlreadyEnabled)));
+
+        // Unapproved deployers are rejected
+        let result = deployer.try_deploy(&scorer_creator, &wasm_hash, &BytesN::random(&env), &Symbol::new(&env, "initialize"), &init_args);
+        assert_eq!(result.err(), Some(Ok(Error::DeployerNotAllowed)));
+
+        // Only the admin manages the allowlist
+        assert_eq!(deployer.try_allow_deployer(&scorer_creator, &scorer_creator), Err(Ok(Error::NotAllowlistAdmin)));
+
+        deployer.allow_deployer(&admin, &scorer_creator);
+        deployer.deploy(&scorer_creator, &wasm_hash, &BytesN::random(&env), &Symbol::new(&env, "initialize"), &init_args);
+        assert_eq!(deployer.get_deployment_count(), 1);
+
+        deployer.disallow_deployer(&admin, &scorer_creator);
+        assert!(!deployer.is_deployer_allowed(&scorer_creator));
+    }
+
+    #[test]
+    fn test_deploy_checked() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let scorer_creator = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let wasm_hash = upload_scorer_wasm(&env);
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(scorer_creator.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer contract").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        // The scorer's initialize function returns nothing
+        let result = deployer.try_deploy_checked(
+            &scorer_creator,
+            &wasm_hash,
+            &BytesN::random(&env),
+            &Symbol::new(&env, "initialize"),
+            &init_args,
+            &true.into_val(&env),
+        );
+        assert_eq!(result, Err(Ok(Error::UnexpectedInitResult)));
+        assert_eq!(deployer.get_deployment_count(), 0);
+
+        let salt = BytesN::random(&env);
+        let scorer_address = deployer.deploy_checked(
+            &scorer_creator,
+            &wasm_hash,
+            &salt,
+            &Symbol::new(&env, "initialize"),
+            &init_args,
+            &().into_val(&env),
+        );
+        assert_eq!(scorer_address, deployer.predict_address(&scorer_creator, &salt));
+        assert_eq!(deployer.get_deployment_count(), 1);
+    }
+
+    #[test]
+    fn test_bootstrap() {
+        mod factory_contract {
+            soroban_sdk::contractimport!(
+                file = "../../wasm/scorer_factory.wasm"
+            );
+        }
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+        let salt = BytesN::random(&env);
+
+        // Validating the uploads takes more than the default budget
+        env.cost_estimate().budget().reset_unlimited();
+        let environment = deployer.bootstrap(
+            &admin,
+            &Bytes::from_slice(&env, factory_contract::WASM),
+            &Bytes::from_slice(&env, scorer_contract::WASM),
+            &salt,
+        );
+        env.cost_estimate().budget().reset_default();
+        let snapshot = EventSnapshot::capture(&env);
+
+        assert_eq!(environment.factory, deployer.predict_address(&admin, &salt));
+        assert_eq!(environment.scorer_wasm_hash, upload_scorer_wasm(&env));
+
+        let factory = factory_contract::Client::new(&env, &environment.factory);
+        assert!(factory.is_scorer_factory_creator(&admin));
+        assert_eq!(factory.get_scorer_wasm_hash(), environment.scorer_wasm_hash);
+
+        let expected_event = EventRecord::new(
+            &env,
+            &deployer_address,
+            (TOPIC_DEPLOY, symbol_short!("bootstrap")),
+            events::EnvironmentBootstrapped {
+                admin,
+                factory: environment.factory,
+                factory_wasm_hash: environment.factory_wasm_hash,
+                scorer_wasm_hash: environment.scorer_wasm_hash,
+            },
+        );
+        snapshot.assert_contains(&[expected_event]);
+    }
+
+    #[test]
+    fn test_deploy_sac() {
+        use soroban_sdk::xdr::{Asset, Limits, WriteXdr};
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let creator = Address::generate(&env);
+        let deployer_address = env.register(Deployer, ());
+        let deployer = DeployerClient::new(&env, &deployer_address);
+
+        let asset = Bytes::from_slice(&env, &Asset::Native.to_xdr(Limits::none()).unwrap());
+        let sac_address = deployer.deploy_sac(&creator, &asset);
+        let snapshot = EventSnapshot::capture(&env);
+
+        let token = soroban_sdk::token::TokenClient::new(&env, &sac_address);
+        assert_eq!(token.name(), String::from_str(&env, "native"));
+
+        let expected_event = EventRecord::new(
+            &env,
+            &deployer_address,
+            (TOPIC_DEPLOY, symbol_short!("sac")),
+            events::AssetContractDeployed { deployer: creator, asset, contract: sac_address },
+        );
+        snapshot.assert_contains(&[expected_event]);
     }
 }
@@ -0,0 +1,388 @@
+#![no_std]
+use scorer_interface::{events, BadgeId, BadgeTokenClient, DisputesTrait, Extension, ScorerClient, UserStatus, MAX_REASON_LENGTH};
+use scorer_interface::events::{EVENT_SCHEMA_VERSION, TOPIC_DISPUTE};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address, Env, String};
+
+/// Status of a challenge against the attestation of a badge to a user.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ChallengeStatus {
+    /// Waiting for a manager to resolve it.
+    Pending = 0,
+    /// A manager kept the attestation.
+    Upheld = 1,
+    /// A manager revoked the attestation, which no longer counts towards scores.
+    Revoked = 2,
+}
+
+/// A challenge against the attestation of a badge to a user.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Challenge {
+    pub challenger: Address,
+    pub reason: String,
+    pub status: ChallengeStatus,
+}
+
+#[contracttype]
+enum DataKey {
+    Scorer,
+    Challenge(BadgeId, Address),
+}
+
+// Errors raised by the disputes contract. They keep the codes the scorer
+// raised them with before challenges moved to this contract.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    // The challenger is not an active member, or the resolver not a manager.
+    Unauthorized = 2,
+    // The badge is not part of the scorer.
+    BadgeNotFound = 10,
+    // The reason is empty.
+    EmptyArg = 12,
+    // The scorer is archived.
+    ScorerArchived = 17,
+    // The attestation already has a pending challenge.
+    ChallengeAlreadyPending = 20,
+    // The attestation has no pending challenge.
+    ChallengeNotFound = 21,
+    // The attestation was revoked.
+    AttestationRevoked = 22,
+    // The reason is longer than `MAX_REASON_LENGTH` bytes.
+    ReasonTooLong = 48,
+    // The scorer is missing from storage.
+    ScorerNotFound = 51,
+}
+
+/// Challenges of the attestations of a scorer's badges.
+///
+/// Active members of the scorer challenge the attestation of a badge to a
+/// user, giving a reason, and a manager upholds or revokes it. Once the scorer
+/// registers the contract as its `Extension::Disputes`, revoked attestations
+/// count for nothing in its scores, and the badge token of the scorer, if
+/// any, burns their token. An upheld attestation can be challenged again, a
+/// revoked one cannot.
+#[contract]
+pub struct DisputesContract;
+
+#[contractimpl]
+impl DisputesContract {
+    // Initializes the disputes of a scorer
+    pub fn initialize(env: Env, scorer: Address) {
+        if env.storage().persistent().has(&DataKey::Scorer) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        // Only the scorer owner can let this contract revoke its attestations
+        ScorerClient::new(&env, &scorer).get_contract_owner().require_auth();
+
+        env.storage().persistent().set(&DataKey::Scorer, &scorer);
+    }
+
+    // Returns the scorer whose attestations are challenged
+    pub fn get_scorer(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scorer)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    // Challenges the attestation of a badge to a user, for a manager to resolve
+    pub fn challenge_attestation(env: Env, challenger: Address, badge_id: BadgeId, user: Address, reason: String) {
+        challenger.require_auth();
+
+        let scorer = Self::scorer(&env);
+        if scorer.is_archived() {
+            panic_with_error!(&env, Error::ScorerArchived);
+        }
+        if scorer.get_user_status(&challenger) != Some(UserStatus::Active) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        if reason.is_empty() {
+            panic_with_error!(&env, Error::EmptyArg);
+        }
+        if reason.len() > MAX_REASON_LENGTH {
+            panic_with_error!(&env, Error::ReasonTooLong);
+        }
+
+        if !scorer.get_badges().contains_key(badge_id.clone()) {
+            panic_with_error!(&env, Error::BadgeNotFound);
+        }
+
+        match Self::get_challenge(env.clone(), badge_id.clone(), user.clone()).map(|challenge| challenge.status) {
+            Some(ChallengeStatus::Pending) => panic_with_error!(&env, Error::ChallengeAlreadyPending),
+            Some(ChallengeStatus::Revoked) => panic_with_error!(&env, Error::AttestationRevoked),
+            Some(ChallengeStatus::Upheld) | None => {}
+        }
+
+        let challenge = Challenge {
+            challenger: challenger.clone(),
+            reason: reason.clone(),
+            status: ChallengeStatus::Pending,
+        };
+        env.storage().persistent().set(&DataKey::Challenge(badge_id.clone(), user.clone()), &challenge);
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_DISPUTE, symbol_short!("open")),
+            events::AttestationChallenged { challenger, badge_id, user, reason },
+        );
+    }
+
+    // Resolves the pending challenge of an attestation
+    pub fn resolve_challenge(env: Env, sender: Address, badge_id: BadgeId, user: Address, revoke: bool) {
+        sender.require_auth();
+
+        let scorer = Self::scorer(&env);
+        if !scorer.get_managers().contains(&sender) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        let mut challenge = Self::get_challenge(env.clone(), badge_id.clone(), user.clone())
+            .filter(|challenge| challenge.status == ChallengeStatus::Pending)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ChallengeNotFound));
+
+        challenge.status = if revoke { ChallengeStatus::Revoked } else { ChallengeStatus::Upheld };
+        env.storage().persistent().set(&DataKey::Challenge(badge_id.clone(), user.clone()), &challenge);
+
+        // Users without the token, such as attestations never minted, have nothing to burn
+        if let (true, Some(token)) = (revoke, scorer.get_extension(&Extension::BadgeToken)) {
+            let token = BadgeTokenClient::new(&env, &token);
+            if token.holds_badge(&user, &badge_id) {
+                token.burn_badge(&env.current_contract_address(), &user, &badge_id);
+            }
+        }
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_DISPUTE, symbol_short!("resolve")),
+            events::ChallengeResolved { sender, badge_id, user, revoked: revoke },
+        );
+    }
+
+    // Returns the latest challenge of an attestation, or None if it was never challenged
+    pub fn get_challenge(env: Env, badge_id: BadgeId, user: Address) -> Option<Challenge> {
+        env.storage().persistent().get(&DataKey::Challenge(badge_id, user))
+    }
+}
+
+#[contractimpl]
+impl DisputesTrait for DisputesContract {
+    // Returns whether a manager revoked an attestation after a challenge
+    fn is_attestation_revoked(env: Env, badge_id: BadgeId, user: Address) -> bool {
+        Self::get_challenge(env, badge_id, user)
+            .is_some_and(|challenge| challenge.status == ChallengeStatus::Revoked)
+    }
+}
+
+impl DisputesContract {
+    /// Returns a client of the scorer whose attestations are challenged
+    fn scorer(env: &Env) -> ScorerClient<'_> {
+        ScorerClient::new(env, &Self::get_scorer(env.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use badge_token::{BadgeTokenContract, BadgeTokenContractClient};
+    use scorer::{ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Vec;
+    use test_utils::{scorer_with_extension, EventRecord, EventSnapshot, ScorerWithExtension};
+
+    struct Setup {
+        env: Env,
+        manager: Address,
+        user: Address,
+        badges: Vec<BadgeId>,
+        scorer: ScorerContractClient<'static>,
+        client: DisputesContractClient<'static>,
+    }
+
+    // The scorer of `scorer_with_extension`, counting disputes, whose creator is
+    // the manager
+    fn setup() -> Setup {
+        let ScorerWithExtension { env, scorer_creator, user, badges, scorer, extension } =
+            scorer_with_extension(ScorerContract, Extension::Disputes, DisputesContract, |env, extension, scorer| {
+                DisputesContractClient::new(env, extension).initialize(scorer)
+            });
+        let scorer = ScorerContractClient::new(&env, &scorer);
+        let client = DisputesContractClient::new(&env, &extension);
+        Setup { env, manager: scorer_creator, user, badges, scorer, client }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let Setup { scorer, client, .. } = setup();
+        assert_eq!(client.get_scorer(), scorer.address);
+        assert_eq!(client.try_initialize(&scorer.address), Err(Ok(Error::ContractAlreadyInitialized.into())));
+    }
+
+    #[test]
+    fn test_challenge_revokes_attestation() {
+        let Setup { env, manager, user, badges, scorer, client } = setup();
+        let challenger = Address::generate(&env);
+        scorer.add_user(&challenger);
+        let badge_id = badges.get_unchecked(0);
+        let reason = String::from_str(&env, "Badge was never earned");
+
+        client.challenge_attestation(&challenger, &badge_id, &user, &reason);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_DISPUTE, symbol_short!("open")),
+            events::AttestationChallenged {
+                challenger: challenger.clone(),
+                badge_id: badge_id.clone(),
+                user: user.clone(),
+                reason: reason.clone(),
+            },
+        )]);
+        let challenge = client.get_challenge(&badge_id, &user).unwrap();
+        assert_eq!(challenge.challenger, challenger);
+        assert_eq!(challenge.status, ChallengeStatus::Pending);
+        assert_eq!(scorer.get_score(&user, &badges), 140);
+
+        client.resolve_challenge(&manager, &badge_id, &user, &true);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_DISPUTE, symbol_short!("resolve")),
+            events::ChallengeResolved { sender: manager, badge_id: badge_id.clone(), user: user.clone(), revoked: true },
+        )]);
+        assert_eq!(client.get_challenge(&badge_id, &user).unwrap().status, ChallengeStatus::Revoked);
+        assert!(client.is_attestation_revoked(&badge_id, &user));
+        assert_eq!(scorer.get_score(&user, &badges), 40);
+
+        // A revoked attestation cannot be challenged again
+        assert_eq!(
+            client.try_challenge_attestation(&challenger, &badge_id, &user, &reason),
+            Err(Ok(Error::AttestationRevoked.into()))
+        );
+    }
+
+    #[test]
+    fn test_challenge_upheld() {
+        let Setup { env, manager, user, badges, scorer, client } = setup();
+        let badge_id = badges.get_unchecked(1);
+        let reason = String::from_str(&env, "Suspicious");
+
+        client.challenge_attestation(&user, &badge_id, &user, &reason);
+        client.resolve_challenge(&manager, &badge_id, &user, &false);
+        assert_eq!(client.get_challenge(&badge_id, &user).unwrap().status, ChallengeStatus::Upheld);
+        assert!(!client.is_attestation_revoked(&badge_id, &user));
+        assert_eq!(scorer.get_score(&user, &badges), 140);
+
+        // An upheld attestation can be challenged anew
+        client.challenge_attestation(&user, &badge_id, &user, &reason);
+        assert_eq!(client.get_challenge(&badge_id, &user).unwrap().status, ChallengeStatus::Pending);
+    }
+
+    #[test]
+    fn test_challenge_rejections() {
+        let Setup { env, manager, user, badges, scorer, client } = setup();
+        let badge_id = badges.get_unchecked(0);
+        let reason = String::from_str(&env, "Spam");
+
+        assert_eq!(
+            client.try_challenge_attestation(&Address::generate(&env), &badge_id, &user, &reason),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+        assert_eq!(
+            client.try_challenge_attestation(&user, &badge_id, &user, &String::from_str(&env, "")),
+            Err(Ok(Error::EmptyArg.into()))
+        );
+        assert_eq!(
+            client.try_challenge_attestation(&user, &badges.get_unchecked(2), &user, &reason),
+            Err(Ok(Error::BadgeNotFound.into()))
+        );
+
+        client.challenge_attestation(&user, &badge_id, &user, &reason);
+        assert_eq!(
+            client.try_challenge_attestation(&user, &badge_id, &user, &reason),
+            Err(Ok(Error::ChallengeAlreadyPending.into()))
+        );
+
+        scorer.set_archived(&manager, &true);
+        assert_eq!(
+            client.try_challenge_attestation(&user, &badges.get_unchecked(1), &user, &reason),
+            Err(Ok(Error::ScorerArchived.into()))
+        );
+    }
+
+    #[test]
+    fn test_reason_length_cap() {
+        let Setup { env, user, badges, client, .. } = setup();
+        let text = |len: u32| String::from_bytes(&env, &[b'a'; 2_048][..len as usize]);
+        let badge_id = badges.get_unchecked(0);
+
+        assert_eq!(
+            client.try_challenge_attestation(&user, &badge_id, &user, &text(MAX_REASON_LENGTH + 1)),
+            Err(Ok(Error::ReasonTooLong.into()))
+        );
+        client.challenge_attestation(&user, &badge_id, &user, &text(MAX_REASON_LENGTH));
+    }
+
+    #[test]
+    fn test_resolve_challenge_rejections() {
+        let Setup { env, manager, user, badges, client, .. } = setup();
+        let badge_id = badges.get_unchecked(0);
+
+        assert_eq!(client.try_resolve_challenge(&manager, &badge_id, &user, &true), Err(Ok(Error::ChallengeNotFound.into())));
+
+        client.challenge_attestation(&user, &badge_id, &user, &String::from_str(&env, "Spam"));
+        assert_eq!(client.try_resolve_challenge(&user, &badge_id, &user, &true), Err(Ok(Error::Unauthorized.into())));
+        client.resolve_challenge(&manager, &badge_id, &user, &false);
+        assert_eq!(client.try_resolve_challenge(&manager, &badge_id, &user, &true), Err(Ok(Error::ChallengeNotFound.into())));
+    }
+
+    #[test]
+    fn test_revoking_burns_badge_token() {
+        let Setup { env, manager, user, badges, scorer, client } = setup();
+        let badge_token = BadgeTokenContractClient::new(&env, &env.register(BadgeTokenContract, ()));
+        badge_token.initialize(&scorer.address, &String::from_str(&env, "Community badges"), &String::from_str(&env, "BADGE"));
+        scorer.set_extension(&manager, &Extension::BadgeToken, &Some(badge_token.address.clone()));
+        let (minted, unminted) = (badges.get_unchecked(1), badges.get_unchecked(0));
+        badge_token.mint_badge(&minted.issuer, &user, &minted);
+
+        client.challenge_attestation(&user, &minted, &user, &String::from_str(&env, "Never spoke"));
+        client.resolve_challenge(&manager, &minted, &user, &true);
+        assert!(!badge_token.holds_badge(&user, &minted));
+
+        // A revoked attestation cannot be minted again
+        assert_eq!(
+            badge_token.try_mint_badge(&minted.issuer, &user, &minted),
+            Err(Ok(badge_token::Error::AttestationRevoked.into()))
+        );
+
+        // A user without the token does not block the revocation
+        client.challenge_attestation(&user, &unminted, &user, &String::from_str(&env, "Never organized"));
+        client.resolve_challenge(&manager, &unminted, &user, &true);
+        assert!(client.is_attestation_revoked(&unminted, &user));
+    }
+
+    #[test]
+    fn test_failed_burn_fails_revocation() {
+        let Setup { env, manager, user, badges, scorer, client } = setup();
+        let badge_token = BadgeTokenContractClient::new(&env, &env.register(BadgeTokenContract, ()));
+        badge_token.initialize(&scorer.address, &String::from_str(&env, "Community badges"), &String::from_str(&env, "BADGE"));
+        scorer.set_extension(&manager, &Extension::BadgeToken, &Some(badge_token.address.clone()));
+        let minted = badges.get_unchecked(1);
+        badge_token.mint_badge(&minted.issuer, &user, &minted);
+        client.challenge_attestation(&user, &minted, &user, &String::from_str(&env, "Never spoke"));
+
+        // Once the scorer drops the contract, the token no longer lets it burn
+        scorer.set_extension(&manager, &Extension::Disputes, &None);
+        assert_eq!(
+            client.try_resolve_challenge(&manager, &minted, &user, &true),
+            Err(Ok(badge_token::Error::Unauthorized.into()))
+        );
+        assert!(badge_token.holds_badge(&user, &minted));
+        assert_eq!(client.get_challenge(&minted, &user).unwrap().status, ChallengeStatus::Pending);
+    }
+}
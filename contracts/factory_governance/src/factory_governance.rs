@@ -0,0 +1,477 @@
+#![no_std]
+use scorer_interface::{events, FactoryChange, GovernanceConfig, ScorerFactoryClient};
+use scorer_interface::events::TOPIC_GOVERNANCE;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, vec, Address, Env, Vec};
+
+/// A proposed factory change and the managers who voted for it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub proposer: Address,
+    pub change: FactoryChange,
+    pub votes: Vec<Address>,
+    /// Last ledger at which the proposal accepts votes.
+    pub deadline: u32,
+    pub executed: bool,
+}
+
+#[contracttype]
+enum DataKey {
+    Factory,
+    Config,
+    ProposalCount,
+    Proposal(u32),
+}
+
+// Errors raised by the factory governance contract. They keep the codes the
+// factory raised them with before WASM hash votes moved to this contract.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    // The sender is not a factory manager, or not the factory creator.
+    Unauthorized = 2,
+    // The quorum or voting period is zero, or the quorum exceeds the number of managers.
+    InvalidGovernanceConfig = 22,
+    // No proposal with this id exists.
+    ProposalNotFound = 25,
+    // The proposal was executed or its voting period ended.
+    ProposalClosed = 26,
+    // The manager already voted for the proposal.
+    AlreadyVoted = 27,
+    // The factory is missing from storage.
+    FactoryNotFound = 30,
+    // The votes of current managers for the proposal are below the quorum.
+    QuorumNotReached = 31,
+}
+
+/// Manager votes on the scorer WASM hash a factory deploys.
+///
+/// Once the factory registers the contract with `set_wasm_hash_governance`,
+/// its `set_scorer_wasm_hash` and `set_wasm_hash_governance` only accept
+/// changes from here. A manager proposes a new hash or governance contract,
+/// and the vote that brings the proposal to the quorum within the voting
+/// period applies it to the factory.
+#[contract]
+pub struct FactoryGovernanceContract;
+
+#[contractimpl]
+impl FactoryGovernanceContract {
+    // Initializes the governance of a factory's scorer WASM hash
+    pub fn initialize(env: Env, factory: Address, config: GovernanceConfig) {
+        if env.storage().persistent().has(&DataKey::Factory) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        // Only the factory creator can hand its WASM hash changes to these votes
+        ScorerFactoryClient::new(&env, &factory).get_contract_creator().require_auth();
+
+        env.storage().persistent().set(&DataKey::Factory, &factory);
+        Self::store_config(&env, &config);
+    }
+
+    // Returns the factory whose scorer WASM hash is governed
+    pub fn get_factory(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Factory)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::FactoryNotFound))
+    }
+
+    // Changes the quorum and voting period. Open proposals keep their deadline
+    // and are executed by the next vote that reaches the new quorum
+    pub fn set_config(env: Env, sender: Address, config: GovernanceConfig) {
+        sender.require_auth();
+
+        if Self::factory(&env).get_contract_creator() != sender {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        Self::store_config(&env, &config);
+
+        env.events().publish(
+            (TOPIC_GOVERNANCE, symbol_short!("config")),
+            events::GovernanceSet { sender, quorum: config.quorum, voting_period: config.voting_period },
+        );
+    }
+
+    // Returns the quorum and voting period
+    pub fn get_config(env: Env) -> GovernanceConfig {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Config)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::FactoryNotFound))
+    }
+
+    // Proposes a factory change to the other managers and returns the id of
+    // the proposal. The proposer's vote is counted, so the change is applied
+    // at once when the quorum is 1
+    pub fn propose_factory_change(env: Env, sender: Address, change: FactoryChange) -> u32 {
+        Self::require_manager(&env, &sender);
+
+        let config = Self::get_config(env.clone());
+
+        let proposal_id: u32 = env.storage().persistent().get(&DataKey::ProposalCount).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::ProposalCount, &(proposal_id + 1));
+
+        let deadline = env.ledger().sequence().saturating_add(config.voting_period);
+        let proposal = Proposal {
+            proposer: sender.clone(),
+            change: change.clone(),
+            votes: vec![&env, sender.clone()],
+            deadline,
+            executed: false,
+        };
+
+        env.events().publish(
+            (TOPIC_GOVERNANCE, symbol_short!("propose")),
+            events::FactoryChangeProposed { proposal_id, proposer: sender, change, deadline },
+        );
+
+        Self::store_proposal(&env, proposal_id, proposal);
+        proposal_id
+    }
+
+    // Votes for a proposal. The vote that brings it to the quorum applies the
+    // change to the factory
+    pub fn vote(env: Env, sender: Address, proposal_id: u32) {
+        Self::require_manager(&env, &sender);
+
+        let mut proposal = Self::open_proposal(&env, proposal_id);
+
+        if proposal.votes.contains(&sender) {
+            panic_with_error!(&env, Error::AlreadyVoted);
+        }
+
+        proposal.votes.push_back(sender.clone());
+
+        env.events().publish(
+            (TOPIC_GOVERNANCE, symbol_short!("vote")),
+            events::ProposalVoted { proposal_id, voter: sender, votes: proposal.votes.len() },
+        );
+
+        Self::store_proposal(&env, proposal_id, proposal);
+    }
+
+    // Applies an open proposal whose votes already reach the quorum, which
+    // happens when managers are removed after voting lowers the quorum below
+    // the votes cast. Anyone can call it
+    pub fn execute(env: Env, proposal_id: u32) {
+        let proposal = Self::open_proposal(&env, proposal_id);
+
+        if !Self::store_proposal(&env, proposal_id, proposal) {
+            panic_with_error!(&env, Error::QuorumNotReached);
+        }
+    }
+
+    // Returns a proposal with its votes, or None if it does not exist
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Option<Proposal> {
+        env.storage().persistent().get(&DataKey::Proposal(proposal_id))
+    }
+
+    // Returns the number of proposals, the id the next one will get
+    pub fn get_proposal_count(env: Env) -> u32 {
+        env.storage().persistent().get(&DataKey::ProposalCount).unwrap_or(0)
+    }
+}
+
+impl FactoryGovernanceContract {
+    /// Returns a client of the factory whose scorer WASM hash is governed
+    fn factory(env: &Env) -> ScorerFactoryClient<'_> {
+        ScorerFactoryClient::new(env, &Self::get_factory(env.clone()))
+    }
+
+    /// Requires the authorization of the sender and that it is a manager of the factory
+    ///
+    /// # Panics
+    /// * When the sender is not a manager (`Error::Unauthorized`)
+    fn require_manager(env: &Env, sender: &Address) {
+        sender.require_auth();
+
+        if !Self::factory(env).get_managers().contains(sender) {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    /// Validates and stores the quorum and voting period
+    ///
+    /// # Panics
+    /// * When the quorum or voting period is zero, or the quorum exceeds the number of managers (`Error::InvalidGovernanceConfig`)
+    fn store_config(env: &Env, config: &GovernanceConfig) {
+        let managers = Self::factory(env).get_managers();
+        if config.quorum == 0 || config.voting_period == 0 || config.quorum > managers.len() {
+            panic_with_error!(env, Error::InvalidGovernanceConfig);
+        }
+        env.storage().persistent().set(&DataKey::Config, config);
+    }
+
+    /// Returns a proposal that accepts votes
+    ///
+    /// # Panics
+    /// * When no proposal with this id exists (`Error::ProposalNotFound`)
+    /// * When the proposal was executed or its voting period ended (`Error::ProposalClosed`)
+    fn open_proposal(env: &Env, proposal_id: u32) -> Proposal {
+        let proposal = Self::get_proposal(env.clone(), proposal_id)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ProposalNotFound));
+
+        if proposal.executed || env.ledger().sequence() > proposal.deadline {
+            panic_with_error!(env, Error::ProposalClosed);
+        }
+        proposal
+    }
+
+    /// Stores a proposal, first applying its change to the factory when the
+    /// votes of current managers reach the quorum, and returns whether it was
+    /// applied. The quorum is capped at the number of managers, at least one,
+    /// so removals can't leave a proposal unpassable. The factory accepts the
+    /// change because this contract is its WASM hash governance and the direct caller
+    fn store_proposal(env: &Env, proposal_id: u32, mut proposal: Proposal) -> bool {
+        let factory = Self::factory(env);
+        let managers = factory.get_managers();
+        let votes = proposal.votes.iter().filter(|voter| managers.contains(voter)).count() as u32;
+        let quorum = Self::get_config(env.clone()).quorum.min(managers.len()).max(1);
+
+        if votes >= quorum {
+            let governance = env.current_contract_address();
+            match &proposal.change {
+                FactoryChange::WasmHash(wasm_hash) => factory.set_scorer_wasm_hash(&governance, wasm_hash),
+                FactoryChange::Governance(next) => factory.set_wasm_hash_governance(&governance, next),
+            }
+            proposal.executed = true;
+
+            env.events().publish(
+                (TOPIC_GOVERNANCE, symbol_short!("execute")),
+                events::ProposalExecuted { proposal_id },
+            );
+        }
+
+        env.storage().persistent().set(&DataKey::Proposal(proposal_id), &proposal);
+        proposal.executed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer_factory::{ScorerFactoryContract, ScorerFactoryContractClient};
+    use scorer_interface::events::TOPIC_WASM_HASH;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::BytesN;
+    use test_utils::{EventRecord, EventSnapshot};
+
+    struct Setup {
+        env: Env,
+        creator: Address,
+        manager: Address,
+        factory: ScorerFactoryContractClient<'static>,
+        client: FactoryGovernanceContractClient<'static>,
+    }
+
+    // A factory with three managers, its creator included, whose scorer WASM
+    // hash is voted with `quorum` over 100 ledgers
+    fn setup(quorum: u32) -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let creator = Address::generate(&env);
+        let factory = ScorerFactoryContractClient::new(&env, &env.register(ScorerFactoryContract, ()));
+        factory.initialize(&creator, &BytesN::from_array(&env, &[1; 32]));
+        let manager = Address::generate(&env);
+        factory.add_manager(&creator, &manager);
+        factory.add_manager(&creator, &Address::generate(&env));
+
+        let client = FactoryGovernanceContractClient::new(&env, &env.register(FactoryGovernanceContract, ()));
+        client.initialize(&factory.address, &GovernanceConfig { quorum, voting_period: 100 });
+        factory.set_wasm_hash_governance(&creator, &Some(client.address.clone()));
+
+        Setup { env, creator, manager, factory, client }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let Setup { factory, client, .. } = setup(2);
+        assert_eq!(client.get_factory(), factory.address);
+        assert_eq!(client.get_config(), GovernanceConfig { quorum: 2, voting_period: 100 });
+        assert_eq!(client.get_proposal_count(), 0);
+        assert_eq!(
+            client.try_initialize(&factory.address, &GovernanceConfig { quorum: 2, voting_period: 100 }),
+            Err(Ok(Error::ContractAlreadyInitialized.into()))
+        );
+    }
+
+    #[test]
+    fn test_set_config() {
+        let Setup { env, creator, manager, factory, client } = setup(2);
+
+        assert_eq!(
+            client.try_set_config(&manager, &GovernanceConfig { quorum: 1, voting_period: 100 }),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+        for config in [
+            GovernanceConfig { quorum: 0, voting_period: 100 },
+            GovernanceConfig { quorum: 1, voting_period: 0 },
+            GovernanceConfig { quorum: 4, voting_period: 100 },
+        ] {
+            assert_eq!(client.try_set_config(&creator, &config), Err(Ok(Error::InvalidGovernanceConfig.into())));
+        }
+
+        client.set_config(&creator, &GovernanceConfig { quorum: 3, voting_period: 50 });
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_GOVERNANCE, symbol_short!("config")),
+            events::GovernanceSet { sender: creator.clone(), quorum: 3, voting_period: 50 },
+        )]);
+        assert_eq!(client.get_config(), GovernanceConfig { quorum: 3, voting_period: 50 });
+
+        // The quorum is bounded by the managers of the factory
+        let uninitialized = FactoryGovernanceContractClient::new(&env, &env.register(FactoryGovernanceContract, ()));
+        assert_eq!(
+            uninitialized.try_initialize(&factory.address, &GovernanceConfig { quorum: 4, voting_period: 100 }),
+            Err(Ok(Error::InvalidGovernanceConfig.into()))
+        );
+    }
+
+    #[test]
+    fn test_vote_replaces_wasm_hash() {
+        let Setup { env, creator, manager, factory, client } = setup(2);
+        let previous_wasm_hash = factory.get_scorer_wasm_hash();
+        let wasm_hash = BytesN::from_array(&env, &[2; 32]);
+
+        let change = FactoryChange::WasmHash(wasm_hash.clone());
+        let proposal_id = client.propose_factory_change(&creator, &change);
+        let deadline = env.ledger().sequence() + 100;
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_GOVERNANCE, symbol_short!("propose")),
+            events::FactoryChangeProposed { proposal_id, proposer: creator.clone(), change, deadline },
+        )]);
+        let proposal = client.get_proposal(&proposal_id).unwrap();
+        assert_eq!(proposal.votes, Vec::from_array(&env, [creator.clone()]));
+        assert_eq!(proposal.deadline, deadline);
+        assert!(!proposal.executed);
+        assert_eq!(factory.get_scorer_wasm_hash(), previous_wasm_hash);
+
+        client.vote(&manager, &proposal_id);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_GOVERNANCE, symbol_short!("vote")),
+                events::ProposalVoted { proposal_id, voter: manager.clone(), votes: 2 },
+            ),
+            EventRecord::new(
+                &env,
+                &factory.address,
+                (TOPIC_WASM_HASH, symbol_short!("set")),
+                events::ScorerWasmHashSet { caller: client.address.clone(), previous_wasm_hash, wasm_hash: wasm_hash.clone() },
+            ),
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_GOVERNANCE, symbol_short!("execute")),
+                events::ProposalExecuted { proposal_id },
+            ),
+        ]);
+        assert!(client.get_proposal(&proposal_id).unwrap().executed);
+        assert_eq!(factory.get_scorer_wasm_hash(), wasm_hash);
+        assert_eq!(factory.get_template_version(), 2);
+
+        // An executed proposal no longer accepts votes
+        let result = client.try_vote(&factory.get_managers().get_unchecked(2), &proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalClosed.into())));
+        assert_eq!(client.try_vote(&manager, &(proposal_id + 1)), Err(Ok(Error::ProposalNotFound.into())));
+        assert_eq!(client.get_proposal_count(), 1);
+    }
+
+    #[test]
+    fn test_quorum_of_one() {
+        let Setup { env, creator, manager, factory, client } = setup(1);
+        let wasm_hash = BytesN::from_array(&env, &[2; 32]);
+
+        // The proposer's vote applies the hash at once
+        let proposal_id = client.propose_factory_change(&manager, &FactoryChange::WasmHash(wasm_hash.clone()));
+        assert!(client.get_proposal(&proposal_id).unwrap().executed);
+        assert_eq!(factory.get_scorer_wasm_hash(), wasm_hash);
+
+        // Once the managers hand the hash back to the creator, it changes it directly again
+        client.propose_factory_change(&manager, &FactoryChange::Governance(None));
+        assert_eq!(factory.get_wasm_hash_governance(), None);
+        factory.set_scorer_wasm_hash(&creator, &BytesN::from_array(&env, &[3; 32]));
+        assert!(client.try_propose_factory_change(&creator, &FactoryChange::WasmHash(wasm_hash)).is_err());
+    }
+
+    #[test]
+    fn test_vote_rejections() {
+        let Setup { env, creator, manager, client, .. } = setup(2);
+        let change = FactoryChange::WasmHash(BytesN::from_array(&env, &[2; 32]));
+        let proposal_id = client.propose_factory_change(&creator, &change);
+
+        assert_eq!(client.try_propose_factory_change(&Address::generate(&env), &change), Err(Ok(Error::Unauthorized.into())));
+        assert_eq!(client.try_vote(&Address::generate(&env), &proposal_id), Err(Ok(Error::Unauthorized.into())));
+        assert_eq!(client.try_vote(&creator, &proposal_id), Err(Ok(Error::AlreadyVoted.into())));
+        assert_eq!(client.try_execute(&proposal_id), Err(Ok(Error::QuorumNotReached.into())));
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 101);
+        assert_eq!(client.try_vote(&manager, &proposal_id), Err(Ok(Error::ProposalClosed.into())));
+        assert_eq!(client.try_execute(&proposal_id), Err(Ok(Error::ProposalClosed.into())));
+    }
+
+    #[test]
+    fn test_votes_of_removed_managers() {
+        let Setup { env, creator, manager, factory, client } = setup(3);
+        let third = factory.get_managers().get_unchecked(2);
+        let previous_wasm_hash = factory.get_scorer_wasm_hash();
+        let wasm_hash = BytesN::from_array(&env, &[2; 32]);
+
+        // The vote of a removed manager no longer counts toward the quorum,
+        // which drops to the two managers left
+        let proposal_id = client.propose_factory_change(&creator, &FactoryChange::WasmHash(wasm_hash.clone()));
+        client.vote(&manager, &proposal_id);
+        factory.remove_manager(&creator, &manager);
+        assert_eq!(client.try_execute(&proposal_id), Err(Ok(Error::QuorumNotReached.into())));
+        assert_eq!(factory.get_scorer_wasm_hash(), previous_wasm_hash);
+
+        client.vote(&third, &proposal_id);
+        assert!(client.get_proposal(&proposal_id).unwrap().executed);
+        assert_eq!(factory.get_scorer_wasm_hash(), wasm_hash);
+
+        // A proposal whose voters are all left once the quorum drops is executed without another vote
+        factory.add_manager(&creator, &manager);
+        let proposal_id = client.propose_factory_change(&creator, &FactoryChange::WasmHash(previous_wasm_hash.clone()));
+        client.vote(&manager, &proposal_id);
+        factory.remove_manager(&creator, &third);
+        client.execute(&proposal_id);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_GOVERNANCE, symbol_short!("execute")),
+            events::ProposalExecuted { proposal_id },
+        )]);
+        assert_eq!(factory.get_scorer_wasm_hash(), previous_wasm_hash);
+    }
+
+    #[test]
+    fn test_replaced_governance() {
+        let Setup { env, creator, manager, factory, client } = setup(2);
+        let previous_wasm_hash = factory.get_scorer_wasm_hash();
+        let next = FactoryGovernanceContractClient::new(&env, &env.register(FactoryGovernanceContract, ()));
+        next.initialize(&factory.address, &GovernanceConfig { quorum: 1, voting_period: 100 });
+
+        let proposal_id = client.propose_factory_change(&creator, &FactoryChange::Governance(Some(next.address.clone())));
+        assert_eq!(factory.get_wasm_hash_governance(), Some(client.address.clone()));
+        client.vote(&manager, &proposal_id);
+        assert_eq!(factory.get_wasm_hash_governance(), Some(next.address.clone()));
+
+        // The factory only applies the changes of its current governance contract
+        let proposal_id = client.propose_factory_change(&creator, &FactoryChange::WasmHash(BytesN::from_array(&env, &[2; 32])));
+        assert!(client.try_vote(&manager, &proposal_id).is_err());
+        assert!(!client.get_proposal(&proposal_id).unwrap().executed);
+        assert_eq!(factory.get_scorer_wasm_hash(), previous_wasm_hash);
+
+        next.propose_factory_change(&manager, &FactoryChange::WasmHash(BytesN::from_array(&env, &[2; 32])));
+        assert_eq!(factory.get_scorer_wasm_hash(), BytesN::from_array(&env, &[2; 32]));
+    }
+}
@@ -0,0 +1,374 @@
+#![no_std]
+use scorer_interface::{events, IssuerRegistryTrait};
+use scorer_interface::events::{TOPIC_ISSUER};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token, Address, Env, String};
+
+/// How an issuer got listed in the registry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    /// The token staked by the issuer, or None when the admin listed it.
+    pub stake_token: Option<Address>,
+    /// The amount staked, zero when the admin listed the issuer.
+    pub stake: i128,
+    /// The ledger at which the issuer was listed.
+    pub listed_at: u32,
+}
+
+#[contracttype]
+enum DataKey {
+    Admin,
+    StakeRequirement,
+    Listing(Address),
+}
+
+/// Errors raised by the issuer registry.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    /// The admin is missing from storage.
+    AdminNotFound = 2,
+    /// The issuer is already listed.
+    IssuerAlreadyListed = 3,
+    /// The issuer is not listed.
+    IssuerNotListed = 4,
+    /// No stake requirement is set, so issuers cannot list themselves.
+    StakingDisabled = 5,
+    /// The issuer was listed by the admin and has no stake to withdraw.
+    NotStaked = 6,
+    /// The stake amount is not positive.
+    InvalidStake = 7,
+}
+
+/// A registry of verified badge issuers shared by scorers and factories.
+///
+/// Issuers are listed by the admin, or list themselves by staking the amount
+/// of the token set as stake requirement. The admin can revoke any issuer, in
+/// which case its stake is forfeited to the admin, and staked issuers can leave
+/// with their stake. Scorers register the registry as their
+/// `Extension::IssuerRegistry` to only accept badges of listed issuers.
+#[contract]
+pub struct IssuerRegistryContract;
+
+#[contractimpl]
+impl IssuerRegistryTrait for IssuerRegistryContract {
+    /// Returns whether an issuer is listed
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `issuer` - The issuer address to check
+    fn is_verified_issuer(env: Env, issuer: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Listing(issuer))
+    }
+}
+
+#[contractimpl]
+impl IssuerRegistryContract {
+    /// Initializes the registry
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `admin` - The address curating the listed issuers
+    ///
+    /// # Panics
+    /// * When the contract is already initialized (`Error::ContractAlreadyInitialized`)
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().persistent().has(&DataKey::Admin) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+    }
+
+    /// Returns the admin
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::AdminNotFound`)
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::AdminNotFound))
+    }
+
+    /// Sets or clears the stake that lets issuers list themselves. Issuers
+    /// already listed keep the stake they deposited
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `requirement` - The token and amount to stake, or None to disable staking
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the amount is not positive (`Error::InvalidStake`)
+    pub fn set_stake_requirement(env: Env, requirement: Option<(Address, i128)>) {
+        Self::get_admin(env.clone()).require_auth();
+
+        match requirement {
+            Some((_, amount)) if amount <= 0 => panic_with_error!(&env, Error::InvalidStake),
+            Some(requirement) => env.storage().persistent().set(&DataKey::StakeRequirement, &requirement),
+            None => env.storage().persistent().remove(&DataKey::StakeRequirement),
+        }
+    }
+
+    /// Returns the token and amount issuers stake to list themselves, if staking is enabled
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    pub fn get_stake_requirement(env: Env) -> Option<(Address, i128)> {
+        env.storage().persistent().get(&DataKey::StakeRequirement)
+    }
+
+    /// Lists an issuer without a stake
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `issuer` - The issuer address to list
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the issuer is already listed (`Error::IssuerAlreadyListed`)
+    pub fn list_issuer(env: Env, issuer: Address) {
+        let admin = Self::get_admin(env.clone());
+        admin.require_auth();
+
+        Self::add_listing(&env, admin, issuer, None, 0);
+    }
+
+    /// Lists the issuer by transferring the required stake to the registry
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `issuer` - The issuer listing itself
+    ///
+    /// # Panics
+    /// * When the issuer fails authentication
+    /// * When staking is disabled (`Error::StakingDisabled`)
+    /// * When the issuer is already listed (`Error::IssuerAlreadyListed`)
+    /// * When the issuer cannot pay the stake
+    pub fn stake(env: Env, issuer: Address) {
+        issuer.require_auth();
+
+        let (stake_token, stake) = Self::get_stake_requirement(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::StakingDisabled));
+
+        Self::add_listing(&env, issuer.clone(), issuer.clone(), Some(stake_token.clone()), stake);
+        token::Client::new(&env, &stake_token).transfer(&issuer, &env.current_contract_address(), &stake);
+    }
+
+    /// Delists a staked issuer and returns its stake
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `issuer` - The issuer leaving the registry
+    ///
+    /// # Panics
+    /// * When the issuer fails authentication
+    /// * When the issuer is not listed (`Error::IssuerNotListed`)
+    /// * When the admin listed the issuer (`Error::NotStaked`)
+    pub fn unstake(env: Env, issuer: Address) {
+        issuer.require_auth();
+
+        let listing = Self::take_listing(&env, &issuer);
+        let stake_token = listing.stake_token.unwrap_or_else(|| panic_with_error!(&env, Error::NotStaked));
+        token::Client::new(&env, &stake_token).transfer(&env.current_contract_address(), &issuer, &listing.stake);
+
+        env.events().publish(
+            (TOPIC_ISSUER, symbol_short!("unstake")),
+            events::IssuerUnstaked { issuer, stake: listing.stake },
+        );
+    }
+
+    /// Revokes an issuer, transferring its stake, if any, to the admin
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `issuer` - The issuer to revoke
+    /// * `reason` - Why the issuer is revoked, published in the event
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the issuer is not listed (`Error::IssuerNotListed`)
+    pub fn revoke_issuer(env: Env, issuer: Address, reason: String) {
+        let admin = Self::get_admin(env.clone());
+        admin.require_auth();
+
+        let listing = Self::take_listing(&env, &issuer);
+        if let Some(stake_token) = listing.stake_token {
+            token::Client::new(&env, &stake_token).transfer(&env.current_contract_address(), &admin, &listing.stake);
+        }
+
+        env.events().publish(
+            (TOPIC_ISSUER, symbol_short!("revoke")),
+            events::IssuerRevoked { caller: admin, issuer, slashed: listing.stake, reason },
+        );
+    }
+
+    /// Returns how an issuer was listed, if it is
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `issuer` - The issuer address
+    pub fn get_listing(env: Env, issuer: Address) -> Option<Listing> {
+        env.storage().persistent().get(&DataKey::Listing(issuer))
+    }
+
+    fn add_listing(env: &Env, caller: Address, issuer: Address, stake_token: Option<Address>, stake: i128) {
+        let key = DataKey::Listing(issuer.clone());
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(env, Error::IssuerAlreadyListed);
+        }
+
+        let listing = Listing { stake_token, stake, listed_at: env.ledger().sequence() };
+        env.storage().persistent().set(&key, &listing);
+
+        env.events().publish((TOPIC_ISSUER, symbol_short!("list")), events::IssuerListed { caller, issuer, stake });
+    }
+
+    fn take_listing(env: &Env, issuer: &Address) -> Listing {
+        let key = DataKey::Listing(issuer.clone());
+        let listing = env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(env, Error::IssuerNotListed));
+        env.storage().persistent().remove(&key);
+        listing
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{Extension, ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Map;
+    use test_utils::{EventRecord, EventSnapshot};
+
+    fn setup() -> (Env, Address, IssuerRegistryContractClient<'static>) {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let client = IssuerRegistryContractClient::new(&env, &env.register(IssuerRegistryContract, ()));
+        client.initialize(&admin);
+
+        (env, admin, client)
+    }
+
+    // Requires a stake of 100 of a new token, and funds `issuer` with 150
+    fn setup_staking(env: &Env, client: &IssuerRegistryContractClient, issuer: &Address) -> token::Client<'static> {
+        let stake_token = env.register_stellar_asset_contract_v2(Address::generate(env)).address();
+        token::StellarAssetClient::new(env, &stake_token).mint(issuer, &150);
+        client.set_stake_requirement(&Some((stake_token.clone(), 100)));
+        token::Client::new(env, &stake_token)
+    }
+
+    #[test]
+    fn test_list_and_revoke_issuer() {
+        let (env, admin, client) = setup();
+        let issuer = Address::generate(&env);
+        let reason = String::from_str(&env, "Issued badges without checks");
+
+        client.list_issuer(&issuer);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_ISSUER, symbol_short!("list")),
+                events::IssuerListed { caller: admin.clone(), issuer: issuer.clone(), stake: 0 },
+            ),
+        ]);
+        assert!(client.is_verified_issuer(&issuer));
+        assert_eq!(client.get_listing(&issuer).unwrap().stake_token, None);
+        assert_eq!(client.try_list_issuer(&issuer), Err(Ok(Error::IssuerAlreadyListed.into())));
+        assert_eq!(client.try_unstake(&issuer), Err(Ok(Error::NotStaked.into())));
+
+        client.revoke_issuer(&issuer, &reason);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_ISSUER, symbol_short!("revoke")),
+                events::IssuerRevoked { caller: admin, issuer: issuer.clone(), slashed: 0, reason: reason.clone() },
+            ),
+        ]);
+        assert!(!client.is_verified_issuer(&issuer));
+
+        assert_eq!(client.try_revoke_issuer(&issuer, &reason), Err(Ok(Error::IssuerNotListed.into())));
+    }
+
+    #[test]
+    fn test_stake_and_unstake() {
+        let (env, _, client) = setup();
+        let issuer = Address::generate(&env);
+        assert_eq!(client.try_stake(&issuer), Err(Ok(Error::StakingDisabled.into())));
+
+        let stake_token = setup_staking(&env, &client, &issuer);
+        client.stake(&issuer);
+        assert!(client.is_verified_issuer(&issuer));
+        assert_eq!(client.get_listing(&issuer).unwrap().stake, 100);
+        assert_eq!(stake_token.balance(&issuer), 50);
+        assert_eq!(stake_token.balance(&client.address), 100);
+
+        client.unstake(&issuer);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_ISSUER, symbol_short!("unstake")),
+            events::IssuerUnstaked { issuer: issuer.clone(), stake: 100 },
+        )]);
+        assert!(!client.is_verified_issuer(&issuer));
+        assert_eq!(stake_token.balance(&issuer), 150);
+    }
+
+    #[test]
+    fn test_revoke_slashes_stake() {
+        let (env, admin, client) = setup();
+        let issuer = Address::generate(&env);
+        let stake_token = setup_staking(&env, &client, &issuer);
+
+        client.stake(&issuer);
+        client.revoke_issuer(&issuer, &String::from_str(&env, "Spam"));
+        assert!(!client.is_verified_issuer(&issuer));
+        assert_eq!(stake_token.balance(&issuer), 50);
+        assert_eq!(stake_token.balance(&admin), 100);
+        assert_eq!(stake_token.balance(&client.address), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #7)")]
+    fn test_set_invalid_stake_requirement() {
+        let (env, _, client) = setup();
+
+        client.set_stake_requirement(&Some((Address::generate(&env), 0)));
+    }
+
+    #[test]
+    fn test_scorer_checks_issuers_in_registry() {
+        let (env, _, client) = setup();
+        let scorer_creator = Address::generate(&env);
+        let scorer = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        scorer.initialize(
+            &scorer_creator,
+            &Map::new(&env),
+            &String::from_str(&env, "Community"),
+            &String::from_str(&env, "A community"),
+            &String::from_str(&env, "icon.png"),
+        );
+        scorer.set_extension(&scorer_creator, &Extension::IssuerRegistry, &Some(client.address.clone()));
+
+        let issuer = Address::generate(&env);
+        let badge = String::from_str(&env, "Speaker");
+        assert!(scorer.try_add_badge(&scorer_creator, &badge, &issuer, &40).is_err());
+
+        client.list_issuer(&issuer);
+        scorer.add_badge(&scorer_creator, &badge, &issuer, &40);
+        assert_eq!(scorer.get_badges().len(), 1);
+    }
+}
@@ -0,0 +1,99 @@
+//! Escrow of the fees paid with join requests and of join deposits.
+//!
+//! The membership contract holds the fee of a pending request until a manager
+//! decides on it: the fee is released to the scorer creator when the request
+//! is approved, and refunded to the user when it is rejected or expires. Join
+//! deposits are held while users are members, refunded when they leave and
+//! released to the scorer creator when they are banned. Fees paid in another
+//! asset are swapped to the fee token before they are held, so the escrow
+//! always holds the fee token. Each movement is published under the `escrow`
+//! topic, after the event schema version. Zero fees move nothing and publish
+//! no event.
+use scorer_interface::events::{self, EVENT_SCHEMA_VERSION, TOPIC_ESCROW};
+use scorer_interface::SwapClient;
+use soroban_sdk::{panic_with_error, symbol_short, token, Address, Env};
+
+use crate::Error;
+
+/// Transfers the fee of `user` to the membership contract
+pub(crate) fn hold(env: &Env, user: &Address, token: &Address, amount: i128) {
+    if amount == 0 {
+        return;
+    }
+    token::Client::new(env, token).transfer(user, &env.current_contract_address(), &amount);
+
+    env.events().publish(
+        (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("hold")),
+        events::EscrowHeld { user: user.clone(), token: token.clone(), amount },
+    );
+}
+
+/// Swaps at most `max_amount_in` of `asset` from `user` to the fee through
+/// `router`, delivered to the membership contract, and returns the amount of `asset` spent
+///
+/// The fee is only held once the contract's own balance shows it was delivered,
+/// since the router's report can't be trusted. Fails with
+/// `Error::InvalidJoinApproval` when the router delivers less.
+pub(crate) fn hold_swapped(
+    env: &Env,
+    user: &Address,
+    router: &Address,
+    asset: &Address,
+    max_amount_in: i128,
+    token: &Address,
+    amount: i128,
+) -> i128 {
+    if amount == 0 {
+        return 0;
+    }
+    let escrow = env.current_contract_address();
+    let fee_token = token::Client::new(env, token);
+    let balance_before = fee_token.balance(&escrow);
+    let amount_in = SwapClient::new(env, router).swap_exact_out(
+        user,
+        asset,
+        token,
+        &amount,
+        &max_amount_in,
+        &escrow,
+    );
+    if fee_token.balance(&escrow).saturating_sub(balance_before) < amount {
+        panic_with_error!(env, Error::InvalidJoinApproval);
+    }
+
+    env.events().publish(
+        (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("swap")),
+        events::EscrowSwapped { user: user.clone(), asset: asset.clone(), amount_in, token: token.clone(), amount },
+    );
+    env.events().publish(
+        (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("hold")),
+        events::EscrowHeld { user: user.clone(), token: token.clone(), amount },
+    );
+    amount_in
+}
+
+/// Pays the escrowed fee of `user` to `recipient`
+pub(crate) fn release(env: &Env, user: &Address, token: &Address, amount: i128, recipient: &Address) {
+    if amount == 0 {
+        return;
+    }
+    token::Client::new(env, token).transfer(&env.current_contract_address(), recipient, &amount);
+
+    env.events().publish(
+        (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("release")),
+        events::EscrowReleased { user: user.clone(), token: token.clone(), amount, recipient: recipient.clone() },
+    );
+}
+
+/// Returns the escrowed fee to `user`
+pub(crate) fn refund(env: &Env, user: &Address, token: &Address, amount: i128) {
+    if amount == 0 {
+        return;
+    }
+    token::Client::new(env, token).transfer(&env.current_contract_address(), user, &amount);
+
+    env.events().publish(
+        (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("refund")),
+        events::EscrowRefunded { user: user.clone(), token: token.clone(), amount },
+    );
+}
@@ -0,0 +1,1343 @@
+#![no_std]
+use scorer_interface::{events, passkey, MembershipTrait, PasskeySignature, PersonhoodClient, ScorerClient, UserStatus};
+use scorer_interface::events::{EVENT_SCHEMA_VERSION, TOPIC_STATUS, TOPIC_USER};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token, Address, Env, Symbol, Vec};
+
+mod escrow;
+
+/// Rules of join approval: the fee escrowed with each join request, in
+/// `token`, and for how many ledgers a request waits for a manager's decision.
+/// A zero fee requires approval without a fee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JoinApproval {
+    pub token: Address,
+    pub fee: i128,
+    pub ttl: u32,
+}
+
+/// A request to join a scorer and the fee escrowed with it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JoinRequest {
+    pub token: Address,
+    pub fee: i128,
+    /// Last ledger at which a manager can approve the request.
+    pub expires_at: u32,
+}
+
+/// A refundable deposit of `amount` in `token` locked by each user who joins.
+/// It is returned when the user leaves and forfeited to the scorer creator when
+/// the user is banned.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JoinDeposit {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// The AMM join fees are swapped through, and the assets users can pay them
+/// in besides the fee token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSwap {
+    pub router: Address,
+    pub assets: Vec<Address>,
+}
+
+/// Rules of membership renewals: members stay active for `period` ledgers
+/// after joining or renewing, and pay `fee` in `token` to the scorer creator
+/// for each renewal. A zero fee only requires members to renew.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MembershipTerms {
+    pub period: u32,
+    pub token: Address,
+    pub fee: i128,
+}
+
+#[contracttype]
+enum DataKey {
+    Scorer,
+    JoinApproval,
+    JoinRequest(Address),
+    JoinDeposit,
+    Deposit(Address),
+    FeeSwap,
+    MembershipTerms,
+    Personhood,
+    RejoinCooldown,
+    LeftAt(Address),
+    PasskeyNonce(Address),
+}
+
+// Errors raised by the membership contract. They keep the codes the scorer
+// raised them with before joins moved to this contract.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    // The sender is not allowed to perform the operation.
+    Unauthorized = 2,
+    // The user is already active.
+    UserAlreadyExist = 7,
+    // The user does not exist or is inactive.
+    UserDoesNotExist = 8,
+    // The scorer is archived.
+    ScorerArchived = 17,
+    // Joining requires a manager's approval, through `request_join`.
+    ApprovalRequired = 29,
+    // Joining does not require approval, so users join with `add_user`.
+    JoinApprovalDisabled = 30,
+    // The join fee or deposit is negative, or the request lifetime is zero.
+    InvalidJoinApproval = 31,
+    // The user already has a join request.
+    JoinRequestPending = 32,
+    // The user has no join request.
+    JoinRequestNotFound = 33,
+    // The join request expired.
+    JoinRequestExpired = 34,
+    // The join request has not expired.
+    JoinRequestNotExpired = 35,
+    // Memberships do not expire, so they cannot be renewed.
+    MembershipTermsDisabled = 36,
+    // The membership period is zero or the renewal fee is negative.
+    InvalidMembershipTerms = 37,
+    // The personhood contract has not verified the user.
+    PersonhoodNotVerified = 39,
+    // The user was banned by a manager.
+    UserBanned = 40,
+    // The user is not banned.
+    UserNotBanned = 41,
+    // The user left less than the rejoin cooldown ago.
+    RejoinCooldownActive = 42,
+    // The scorer is missing from storage.
+    ScorerNotFound = 51,
+}
+
+/// Gates, charges and expires the memberships of a scorer.
+///
+/// The scorer registers the contract as its `Extension::Membership`, after
+/// which users join through it: it checks the rejoin cooldown, the
+/// personhood contract and, when approval is required, a manager's decision
+/// on a join request, escrowing its fee. It locks the join deposit, then
+/// admits the user in the scorer with `admit_user`, with the expiry of the
+/// membership when memberships expire. Bans go through `ban_user`, which
+/// forfeits the deposit, and the scorer reports departures with
+/// `record_departure`, which refunds it.
+#[contract]
+pub struct MembershipContract;
+
+#[contractimpl]
+impl MembershipContract {
+    // Initializes the membership contract of a scorer
+    pub fn initialize(env: Env, scorer: Address) {
+        if env.storage().persistent().has(&DataKey::Scorer) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        // Only the scorer owner can put its memberships under this contract
+        ScorerClient::new(&env, &scorer).get_contract_owner().require_auth();
+
+        env.storage().persistent().set(&DataKey::Scorer, &scorer);
+    }
+
+    // Returns the scorer users join
+    pub fn get_scorer(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scorer)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    // Adds a user to the scorer, locking its join deposit
+    pub fn add_user(env: Env, user: Address) {
+        user.require_auth();
+
+        Self::join(&env, user);
+    }
+
+    // Adds a passkey wallet to the scorer, so users of passkey-based smart
+    // wallets can join through a relayer
+    pub fn add_user_with_passkey(env: Env, wallet: Address, signature: PasskeySignature) {
+        let nonce_key = DataKey::PasskeyNonce(wallet.clone());
+        let nonce: u32 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
+        let challenge = passkey::challenge(&env, Symbol::new(&env, "add_user"), Vec::new(&env), nonce);
+        if env.storage().persistent().has(&DataKey::JoinDeposit) || !passkey::verify_wallet(&env, &wallet, &challenge, &signature) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        env.storage().persistent().set(&nonce_key, &(nonce + 1));
+
+        Self::join(&env, wallet);
+    }
+
+    // Returns the nonce the next challenge signed by a wallet's passkey commits to
+    pub fn get_passkey_nonce(env: Env, wallet: Address) -> u32 {
+        env.storage().persistent().get(&DataKey::PasskeyNonce(wallet)).unwrap_or(0)
+    }
+
+    // Requires join requests to be approved by a manager, or lets users join
+    // freely. Pending requests keep the fee and expiry they were made with
+    pub fn set_join_approval(env: Env, sender: Address, approval: Option<JoinApproval>) {
+        Self::require_owner(&env, &sender);
+
+        let event = match approval {
+            Some(approval) => {
+                if approval.fee < 0 || approval.ttl == 0 {
+                    panic_with_error!(&env, Error::InvalidJoinApproval);
+                }
+                env.storage().persistent().set(&DataKey::JoinApproval, &approval);
+                events::JoinApprovalSet { sender, token: Some(approval.token), fee: approval.fee, ttl: approval.ttl }
+            }
+            None => {
+                env.storage().persistent().remove(&DataKey::JoinApproval);
+                events::JoinApprovalSet { sender, token: None, fee: 0, ttl: 0 }
+            }
+        };
+
+        env.events().publish((EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("approval")), event);
+    }
+
+    // Returns the join approval rules, or None if users join freely
+    pub fn get_join_approval(env: Env) -> Option<JoinApproval> {
+        env.storage().persistent().get(&DataKey::JoinApproval)
+    }
+
+    // Requests to join the scorer, escrowing the join fee until a manager decides.
+    // The join deposit, if any, is locked with the request
+    pub fn request_join(env: Env, user: Address) {
+        Self::open_join_request(&env, user, None);
+    }
+
+    // Accepts join fees in other assets, swapped to the fee token through an
+    // AMM when users request to join, or only in the fee token
+    pub fn set_fee_swap(env: Env, sender: Address, swap: Option<FeeSwap>) {
+        Self::require_owner(&env, &sender);
+
+        let event = match swap {
+            Some(swap) => {
+                env.storage().persistent().set(&DataKey::FeeSwap, &swap);
+                events::FeeSwapSet { sender, router: Some(swap.router), assets: swap.assets }
+            }
+            None => {
+                env.storage().persistent().remove(&DataKey::FeeSwap);
+                events::FeeSwapSet { sender, router: None, assets: Vec::new(&env) }
+            }
+        };
+
+        env.events().publish((EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("feeswap")), event);
+    }
+
+    // Returns the AMM and assets join fees can be paid in, or None if fees
+    // are only paid in the fee token
+    pub fn get_fee_swap(env: Env) -> Option<FeeSwap> {
+        env.storage().persistent().get(&DataKey::FeeSwap)
+    }
+
+    // Requests to join the scorer like `request_join`, paying the join fee in
+    // `asset`, swapped to the fee token through the AMM at call time. The fee
+    // token is escrowed, so rejected and expired requests are refunded in it
+    pub fn request_join_with_swap(env: Env, user: Address, asset: Address, max_amount_in: i128) -> i128 {
+        let swap = Self::get_fee_swap(env.clone())
+            .filter(|swap| swap.assets.contains(&asset))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::Unauthorized));
+
+        Self::open_join_request(&env, user, Some((swap.router, asset, max_amount_in)))
+    }
+
+    // Approves a join request, admitting the user and paying its fee to the scorer creator
+    pub fn approve_join(env: Env, sender: Address, user: Address) {
+        let request = Self::take_join_request(&env, &sender, &user);
+        if env.ledger().sequence() > request.expires_at {
+            panic_with_error!(&env, Error::JoinRequestExpired);
+        }
+
+        let scorer = Self::scorer(&env);
+        escrow::release(&env, &user, &request.token, request.fee, &scorer.get_contract_owner());
+        Self::admit(&env, &scorer, &user);
+    }
+
+    // Rejects a join request, refunding its fee and the deposit locked with it
+    pub fn reject_join(env: Env, sender: Address, user: Address) {
+        let request = Self::take_join_request(&env, &sender, &user);
+        escrow::refund(&env, &user, &request.token, request.fee);
+        Self::refund_pending_deposit(&env, &user);
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("reject")),
+            events::JoinRejected { sender, user },
+        );
+    }
+
+    // Refunds the fee and deposit of an expired join request. Anyone can call it, so
+    // keepers can return the fees of requests managers left undecided
+    pub fn refund_expired_join(env: Env, user: Address) {
+        let request = Self::get_join_request(env.clone(), user.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::JoinRequestNotFound));
+        if env.ledger().sequence() <= request.expires_at {
+            panic_with_error!(&env, Error::JoinRequestNotExpired);
+        }
+
+        env.storage().persistent().remove(&DataKey::JoinRequest(user.clone()));
+        escrow::refund(&env, &user, &request.token, request.fee);
+        Self::refund_pending_deposit(&env, &user);
+    }
+
+    // Returns the join request of a user, if any
+    pub fn get_join_request(env: Env, user: Address) -> Option<JoinRequest> {
+        env.storage().persistent().get(&DataKey::JoinRequest(user))
+    }
+
+    // Requires users to lock a refundable deposit to join, or lets them join
+    // without one. Deposits already locked are kept until their users leave
+    pub fn set_join_deposit(env: Env, sender: Address, deposit: Option<JoinDeposit>) {
+        Self::require_owner(&env, &sender);
+
+        let event = match deposit {
+            Some(deposit) => {
+                if deposit.amount <= 0 {
+                    panic_with_error!(&env, Error::InvalidJoinApproval);
+                }
+                env.storage().persistent().set(&DataKey::JoinDeposit, &deposit);
+                events::JoinDepositSet { sender, token: Some(deposit.token), amount: deposit.amount }
+            }
+            None => {
+                env.storage().persistent().remove(&DataKey::JoinDeposit);
+                events::JoinDepositSet { sender, token: None, amount: 0 }
+            }
+        };
+
+        env.events().publish((EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("deposit")), event);
+    }
+
+    // Returns the deposit users lock to join, or None if users join without one
+    pub fn get_join_deposit(env: Env) -> Option<JoinDeposit> {
+        env.storage().persistent().get(&DataKey::JoinDeposit)
+    }
+
+    // Returns the deposit locked by a user, if any
+    pub fn get_deposit(env: Env, user: Address) -> Option<JoinDeposit> {
+        env.storage().persistent().get(&DataKey::Deposit(user))
+    }
+
+    // Bans a user, removing it from the scorer and refunding its join request.
+    // Its join deposit is forfeited to the scorer creator. Banned users cannot
+    // join or request to join until unbanned
+    pub fn ban_user(env: Env, sender: Address, user: Address) {
+        let scorer = Self::require_manager(&env, &sender);
+        if scorer.get_user_status(&user) == Some(UserStatus::Banned) {
+            panic_with_error!(&env, Error::UserBanned);
+        }
+
+        if let Some(request) = Self::get_join_request(env.clone(), user.clone()) {
+            env.storage().persistent().remove(&DataKey::JoinRequest(user.clone()));
+            escrow::refund(&env, &user, &request.token, request.fee);
+        }
+        if let Some(deposit) = Self::take_deposit(&env, &user) {
+            escrow::release(&env, &user, &deposit.token, deposit.amount, &scorer.get_contract_owner());
+        }
+        scorer.dismiss_user(&env.current_contract_address(), &user, &true);
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("ban")),
+            events::UserBanned { sender, user },
+        );
+    }
+
+    // Lifts the ban of a user, who can then join again
+    pub fn unban_user(env: Env, sender: Address, user: Address) {
+        let scorer = Self::require_manager(&env, &sender);
+        if scorer.get_user_status(&user) != Some(UserStatus::Banned) {
+            panic_with_error!(&env, Error::UserNotBanned);
+        }
+
+        scorer.dismiss_user(&env.current_contract_address(), &user, &false);
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("unban")),
+            events::UserUnbanned { sender, user },
+        );
+    }
+
+    // Sets the number of ledgers after leaving during which a user cannot
+    // join or request to join again, discouraging join and leave spam
+    pub fn set_rejoin_cooldown(env: Env, sender: Address, ledgers: u32) {
+        Self::require_owner(&env, &sender);
+
+        if ledgers > 0 {
+            env.storage().persistent().set(&DataKey::RejoinCooldown, &ledgers);
+        } else {
+            env.storage().persistent().remove(&DataKey::RejoinCooldown);
+        }
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("cooldown")),
+            events::RejoinCooldownSet { sender, ledgers },
+        );
+    }
+
+    // Returns the number of ledgers after leaving during which a user cannot
+    // join again, 0 if users can rejoin at once
+    pub fn get_rejoin_cooldown(env: Env) -> u32 {
+        env.storage().persistent().get(&DataKey::RejoinCooldown).unwrap_or(0)
+    }
+
+    // Requires users to be verified by a personhood contract to join, or
+    // stops requiring it. Members who joined before keep their membership
+    pub fn set_personhood(env: Env, sender: Address, personhood: Option<Address>) {
+        Self::require_owner(&env, &sender);
+
+        match personhood.clone() {
+            Some(personhood) => env.storage().persistent().set(&DataKey::Personhood, &personhood),
+            None => env.storage().persistent().remove(&DataKey::Personhood),
+        }
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_STATUS, symbol_short!("person")),
+            events::PersonhoodSet { sender, personhood },
+        );
+    }
+
+    // Returns the personhood contract users must be verified by, if any
+    pub fn get_personhood(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Personhood)
+    }
+
+    // Makes the memberships of users admitted from now on expire unless
+    // renewed, or lets them last. Members admitted before memberships expired
+    // keep theirs until they renew
+    pub fn set_membership_terms(env: Env, sender: Address, terms: Option<MembershipTerms>) {
+        Self::require_owner(&env, &sender);
+
+        let event = match terms {
+            Some(terms) => {
+                if terms.period == 0 || terms.fee < 0 {
+                    panic_with_error!(&env, Error::InvalidMembershipTerms);
+                }
+                env.storage().persistent().set(&DataKey::MembershipTerms, &terms);
+                events::MembershipTermsSet { sender, period: terms.period, token: Some(terms.token), fee: terms.fee }
+            }
+            None => {
+                env.storage().persistent().remove(&DataKey::MembershipTerms);
+                events::MembershipTermsSet { sender, period: 0, token: None, fee: 0 }
+            }
+        };
+
+        env.events().publish((EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("terms")), event);
+    }
+
+    // Returns the membership period and renewal fee, or None if memberships
+    // do not expire
+    pub fn get_membership_terms(env: Env) -> Option<MembershipTerms> {
+        env.storage().persistent().get(&DataKey::MembershipTerms)
+    }
+
+    // Renews a membership for one period, from its expiry or from now if it
+    // already expired, paying the renewal fee to the scorer creator. A member
+    // whose membership expired is activated again
+    pub fn renew(env: Env, user: Address) {
+        user.require_auth();
+
+        let scorer = Self::scorer(&env);
+        if scorer.is_archived() {
+            panic_with_error!(&env, Error::ScorerArchived);
+        }
+
+        // Members whose membership expired are reported as having left, but
+        // keep their expiry until a keeper sweeps them
+        let expiry = scorer.get_membership_expiry(&user);
+        if expiry.is_none() && scorer.get_user_status(&user) != Some(UserStatus::Active) {
+            panic_with_error!(&env, Error::UserDoesNotExist);
+        }
+
+        let Some(terms) = Self::get_membership_terms(env.clone()) else {
+            if expiry.is_none() {
+                panic_with_error!(&env, Error::MembershipTermsDisabled);
+            }
+            scorer.admit_user(&env.current_contract_address(), &user, &None);
+            return;
+        };
+
+        if terms.fee > 0 {
+            token::Client::new(&env, &terms.token).transfer(&user, &scorer.get_contract_owner(), &terms.fee);
+        }
+        let expires_at = expiry
+            .unwrap_or(0)
+            .max(env.ledger().sequence())
+            .saturating_add(terms.period);
+        scorer.admit_user(&env.current_contract_address(), &user, &Some(expires_at));
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("renew")),
+            events::MembershipRenewed { user, fee: terms.fee, expires_at },
+        );
+    }
+}
+
+#[contractimpl]
+impl MembershipTrait for MembershipContract {
+    // Refunds the join deposit of a user who left the scorer, and starts its
+    // rejoin cooldown
+    fn record_departure(env: Env, user: Address) {
+        Self::get_scorer(env.clone()).require_auth();
+
+        if let Some(deposit) = Self::take_deposit(&env, &user) {
+            escrow::refund(&env, &user, &deposit.token, deposit.amount);
+        }
+        if Self::get_rejoin_cooldown(env.clone()) > 0 {
+            env.storage().persistent().set(&DataKey::LeftAt(user), &env.ledger().sequence());
+        }
+    }
+}
+
+impl MembershipContract {
+    /// Returns a client of the scorer users join
+    fn scorer(env: &Env) -> ScorerClient<'_> {
+        ScorerClient::new(env, &Self::get_scorer(env.clone()))
+    }
+
+    /// Requires the authorization of the sender and that it is the scorer creator
+    ///
+    /// # Panics
+    /// * When the sender is not the scorer creator (`Error::Unauthorized`)
+    fn require_owner(env: &Env, sender: &Address) {
+        sender.require_auth();
+
+        if Self::scorer(env).get_contract_owner() != *sender {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    /// Requires the authorization of the sender and that it is a manager of
+    /// the scorer, which is returned
+    ///
+    /// # Panics
+    /// * When the sender is not a manager (`Error::Unauthorized`)
+    fn require_manager<'a>(env: &'a Env, sender: &Address) -> ScorerClient<'a> {
+        sender.require_auth();
+
+        let scorer = Self::scorer(env);
+        if !scorer.get_managers().contains(sender) {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+        scorer
+    }
+
+    /// Checks that the user can join: the scorer is not archived, the user is
+    /// neither active nor banned, its rejoin cooldown is over and the
+    /// personhood contract, if any, verified it
+    fn check_joinable(env: &Env, scorer: &ScorerClient, user: &Address) {
+        if scorer.is_archived() {
+            panic_with_error!(env, Error::ScorerArchived);
+        }
+
+        match scorer.get_user_status(user) {
+            Some(UserStatus::Active) => panic_with_error!(env, Error::UserAlreadyExist),
+            Some(UserStatus::Banned) => panic_with_error!(env, Error::UserBanned),
+            _ => {}
+        }
+
+        let cooldown = Self::get_rejoin_cooldown(env.clone());
+        let left_at: Option<u32> = env.storage().persistent().get(&DataKey::LeftAt(user.clone()));
+        if cooldown > 0 && left_at.is_some_and(|left_at| env.ledger().sequence() < left_at.saturating_add(cooldown)) {
+            panic_with_error!(env, Error::RejoinCooldownActive);
+        }
+
+        if let Some(personhood) = Self::get_personhood(env.clone()) {
+            if !PersonhoodClient::new(env, &personhood).is_verified_person(user) {
+                panic_with_error!(env, Error::PersonhoodNotVerified);
+            }
+        }
+    }
+
+    /// Adds a user whose authorization was checked, locking its join deposit
+    fn join(env: &Env, user: Address) {
+        let scorer = Self::scorer(env);
+        Self::check_joinable(env, &scorer, &user);
+        if env.storage().persistent().has(&DataKey::JoinApproval) {
+            panic_with_error!(env, Error::ApprovalRequired);
+        }
+
+        Self::lock_deposit(env, &user);
+        Self::admit(env, &scorer, &user);
+    }
+
+    /// Admits a user in the scorer, for one period when memberships expire
+    fn admit(env: &Env, scorer: &ScorerClient, user: &Address) {
+        let expires_at = Self::get_membership_terms(env.clone())
+            .map(|terms| env.ledger().sequence().saturating_add(terms.period));
+        scorer.admit_user(&env.current_contract_address(), user, &expires_at);
+    }
+
+    /// Opens the join request of `user`, paying the fee in the fee token or, with
+    /// a swap of (router, asset, max amount in), in another asset. Returns the
+    /// amount paid, in the asset of the payment
+    fn open_join_request(env: &Env, user: Address, swap: Option<(Address, Address, i128)>) -> i128 {
+        user.require_auth();
+
+        let approval = Self::get_join_approval(env.clone())
+            .unwrap_or_else(|| panic_with_error!(env, Error::JoinApprovalDisabled));
+        let key = DataKey::JoinRequest(user.clone());
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(env, Error::JoinRequestPending);
+        }
+        Self::check_joinable(env, &Self::scorer(env), &user);
+
+        let paid = match swap {
+            Some((router, asset, max_amount_in)) => {
+                escrow::hold_swapped(env, &user, &router, &asset, max_amount_in, &approval.token, approval.fee)
+            }
+            None => {
+                escrow::hold(env, &user, &approval.token, approval.fee);
+                approval.fee
+            }
+        };
+        Self::lock_deposit(env, &user);
+
+        let expires_at = env.ledger().sequence().saturating_add(approval.ttl);
+        env.storage().persistent().set(&key, &JoinRequest { token: approval.token, fee: approval.fee, expires_at });
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("request")),
+            events::JoinRequested { user, fee: approval.fee, expires_at },
+        );
+        paid
+    }
+
+    /// Removes the join request of a user, once the sender is known to be a manager
+    ///
+    /// # Panics
+    /// * When the sender is not a manager (`Error::Unauthorized`)
+    /// * When the user has no join request (`Error::JoinRequestNotFound`)
+    fn take_join_request(env: &Env, sender: &Address, user: &Address) -> JoinRequest {
+        Self::require_manager(env, sender);
+
+        let key = DataKey::JoinRequest(user.clone());
+        let request = env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(env, Error::JoinRequestNotFound));
+        env.storage().persistent().remove(&key);
+        request
+    }
+
+    /// Locks the join deposit of a user, unless it already holds one
+    fn lock_deposit(env: &Env, user: &Address) {
+        let Some(deposit) = Self::get_join_deposit(env.clone()) else {
+            return;
+        };
+        let key = DataKey::Deposit(user.clone());
+        if env.storage().persistent().has(&key) {
+            return;
+        }
+
+        escrow::hold(env, user, &deposit.token, deposit.amount);
+        env.storage().persistent().set(&key, &deposit);
+    }
+
+    /// Removes the deposit locked by a user and returns it
+    fn take_deposit(env: &Env, user: &Address) -> Option<JoinDeposit> {
+        let key = DataKey::Deposit(user.clone());
+        let deposit = env.storage().persistent().get(&key);
+        if deposit.is_some() {
+            env.storage().persistent().remove(&key);
+        }
+        deposit
+    }
+
+    /// Refunds the deposit locked with a join request that did not go through.
+    /// Members whose membership expired keep theirs
+    fn refund_pending_deposit(env: &Env, user: &Address) {
+        if Self::scorer(env).get_membership_expiry(user).is_some() {
+            return;
+        }
+        if let Some(deposit) = Self::take_deposit(env, user) {
+            escrow::refund(env, user, &deposit.token, deposit.amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use passkey_wallet::PasskeyWalletContract;
+    use scorer::{Extension, MaintenanceTask, ScorerContract, ScorerContractClient};
+    use scorer_interface::events::TOPIC_ESCROW;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::vec;
+    use test_utils::{scorer_with_extension, EventRecord, EventSnapshot, Passkey, ScorerWithExtension};
+
+    struct Setup {
+        env: Env,
+        scorer_creator: Address,
+        scorer: ScorerContractClient<'static>,
+        client: MembershipContractClient<'static>,
+    }
+
+    // The scorer of `scorer_with_extension`, which users join through the
+    // membership contract
+    fn setup() -> Setup {
+        let ScorerWithExtension { env, scorer_creator, scorer, extension, .. } =
+            scorer_with_extension(ScorerContract, Extension::Membership, MembershipContract, |env, extension, scorer| {
+                MembershipContractClient::new(env, extension).initialize(scorer)
+            });
+        let scorer = ScorerContractClient::new(&env, &scorer);
+        let client = MembershipContractClient::new(&env, &extension);
+        Setup { env, scorer_creator, scorer, client }
+    }
+
+    // Join requests escrow a fee of 50, expiring after 100 ledgers, and the
+    // returned user holds 500 of the fee token
+    fn setup_join_approval(setup: &Setup) -> (Address, token::Client<'static>) {
+        let Setup { env, scorer_creator, client, .. } = setup;
+        let token = env.register_stellar_asset_contract_v2(Address::generate(env)).address();
+        client.set_join_approval(scorer_creator, &Some(JoinApproval { token: token.clone(), fee: 50, ttl: 100 }));
+
+        let user = Address::generate(env);
+        token::StellarAssetClient::new(env, &token).mint(&user, &500);
+        (user, token::Client::new(env, &token))
+    }
+
+    mod fixed_rate_amm {
+        use soroban_sdk::{contract, contractimpl, token, Address, Env};
+
+        // AMM selling any asset it holds for twice the amount of any other asset
+        #[contract]
+        pub struct FixedRateAmm;
+
+        #[contractimpl]
+        impl FixedRateAmm {
+            pub fn swap_exact_out(
+                env: Env,
+                from: Address,
+                asset_in: Address,
+                asset_out: Address,
+                amount_out: i128,
+                max_amount_in: i128,
+                to: Address,
+            ) -> i128 {
+                from.require_auth();
+                let amount_in = amount_out * 2;
+                assert!(amount_in <= max_amount_in, "excessive input amount");
+                token::Client::new(&env, &asset_in).transfer(&from, &env.current_contract_address(), &amount_in);
+                token::Client::new(&env, &asset_out).transfer(&env.current_contract_address(), &to, &amount_out);
+                amount_in
+            }
+        }
+    }
+
+    mod short_changing_amm {
+        use soroban_sdk::{contract, contractimpl, token, Address, Env};
+
+        // AMM reporting a full swap while delivering half of the output
+        #[contract]
+        pub struct ShortChangingAmm;
+
+        #[contractimpl]
+        impl ShortChangingAmm {
+            pub fn swap_exact_out(
+                env: Env,
+                from: Address,
+                asset_in: Address,
+                asset_out: Address,
+                amount_out: i128,
+                max_amount_in: i128,
+                to: Address,
+            ) -> i128 {
+                from.require_auth();
+                token::Client::new(&env, &asset_in).transfer(&from, &env.current_contract_address(), &max_amount_in);
+                token::Client::new(&env, &asset_out).transfer(&env.current_contract_address(), &to, &(amount_out / 2));
+                max_amount_in
+            }
+        }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let Setup { scorer, client, .. } = setup();
+        assert_eq!(client.get_scorer(), scorer.address);
+        assert_eq!(client.try_initialize(&scorer.address), Err(Ok(Error::ContractAlreadyInitialized.into())));
+    }
+
+    #[test]
+    fn test_initialize_requires_scorer_owner() {
+        let Setup { env, scorer, .. } = setup();
+        let client = MembershipContractClient::new(&env, &env.register(MembershipContract, ()));
+
+        // Without the owner's authorization, the contract cannot be attached to the scorer
+        env.mock_auths(&[]);
+        assert!(client.try_initialize(&scorer.address).is_err());
+    }
+
+    #[test]
+    fn test_add_user() {
+        let Setup { env, scorer, client, .. } = setup();
+        let user = Address::generate(&env);
+
+        client.add_user(&user);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("add")),
+            events::UserJoined { user: user.clone() },
+        )]);
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Active));
+        assert_eq!(scorer.get_membership_expiry(&user), None);
+        assert_eq!(client.try_add_user(&user), Err(Ok(Error::UserAlreadyExist.into())));
+
+        // Users can leave the scorer directly and join again
+        scorer.remove_user(&user);
+        client.add_user(&user);
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Active));
+    }
+
+    #[test]
+    fn test_add_user_with_passkey() {
+        let Setup { env, scorer, client, .. } = setup();
+        let passkey = Passkey::new(7);
+        let wallet = env.register(PasskeyWalletContract, (passkey.public_key(&env), true));
+        let challenge = |nonce| {
+            env.as_contract(&client.address, || passkey::challenge(&env, Symbol::new(&env, "add_user"), Vec::new(&env), nonce))
+        };
+
+        let signature = passkey.sign(&env, &challenge(0));
+        client.add_user_with_passkey(&wallet, &signature);
+
+        assert_eq!(scorer.get_user_status(&wallet), Some(UserStatus::Active));
+        assert_eq!(client.get_passkey_nonce(&wallet), 1);
+
+        // Signatures can't be replayed, nor made by another passkey
+        scorer.remove_user(&wallet);
+        assert_eq!(client.try_add_user_with_passkey(&wallet, &signature), Err(Ok(Error::Unauthorized.into())));
+        let other_signature = Passkey::new(8).sign(&env, &challenge(1));
+        assert!(client.try_add_user_with_passkey(&wallet, &other_signature).is_err());
+
+        // The wallet requires the user verified flag
+        let client_data = Passkey::client_data_json(&challenge(1));
+        let unverified = passkey.sign_assertion(&env, &client_data, passkey::FLAG_USER_PRESENT);
+        assert_eq!(client.try_add_user_with_passkey(&wallet, &unverified), Err(Ok(Error::Unauthorized.into())));
+
+        client.add_user_with_passkey(&wallet, &passkey.sign(&env, &challenge(1)));
+        assert_eq!(scorer.get_user_status(&wallet), Some(UserStatus::Active));
+    }
+
+    #[test]
+    fn test_personhood_gates_joining() {
+        mod personhood {
+            use soroban_sdk::{contract, contractimpl, Address, Env};
+
+            // Verifies the users it was told about
+            #[contract]
+            pub struct MockPersonhood;
+
+            #[contractimpl]
+            impl MockPersonhood {
+                pub fn verify(env: Env, user: Address) {
+                    env.storage().persistent().set(&user, &true);
+                }
+
+                pub fn is_verified_person(env: Env, user: Address) -> bool {
+                    env.storage().persistent().has(&user)
+                }
+            }
+        }
+
+        let Setup { env, scorer_creator, scorer, client } = setup();
+        let personhood = personhood::MockPersonhoodClient::new(&env, &env.register(personhood::MockPersonhood, ()));
+        let member = Address::generate(&env);
+        client.add_user(&member);
+
+        assert_eq!(client.try_set_personhood(&member, &Some(personhood.address.clone())), Err(Ok(Error::Unauthorized.into())));
+        client.set_personhood(&scorer_creator, &Some(personhood.address.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_STATUS, symbol_short!("person")),
+            events::PersonhoodSet { sender: scorer_creator.clone(), personhood: Some(personhood.address.clone()) },
+        )]);
+        assert_eq!(client.get_personhood(), Some(personhood.address.clone()));
+
+        let user = Address::generate(&env);
+        assert_eq!(client.try_add_user(&user), Err(Ok(Error::PersonhoodNotVerified.into())));
+        personhood.verify(&user);
+        client.add_user(&user);
+
+        // Members who joined before the gate stay active
+        assert_eq!(scorer.get_user_status(&member), Some(UserStatus::Active));
+
+        client.set_join_approval(
+            &scorer_creator,
+            &Some(JoinApproval { token: Address::generate(&env), fee: 0, ttl: 100 }),
+        );
+        assert_eq!(client.try_request_join(&Address::generate(&env)), Err(Ok(Error::PersonhoodNotVerified.into())));
+    }
+
+    #[test]
+    fn test_approve_join_releases_fee() {
+        let setup = setup();
+        let (user, token) = setup_join_approval(&setup);
+        let Setup { env, scorer_creator, scorer, client } = setup;
+        assert_eq!(client.try_add_user(&user), Err(Ok(Error::ApprovalRequired.into())));
+
+        client.request_join(&user);
+        let expires_at = env.ledger().sequence() + 100;
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("hold")),
+                events::EscrowHeld { user: user.clone(), token: token.address.clone(), amount: 50 },
+            ),
+            EventRecord::new(
+                &env,
+                &client.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("request")),
+                events::JoinRequested { user: user.clone(), fee: 50, expires_at },
+            ),
+        ]);
+        assert_eq!(client.get_join_request(&user), Some(JoinRequest { token: token.address.clone(), fee: 50, expires_at }));
+        assert_eq!(token.balance(&client.address), 50);
+        assert_eq!(scorer.get_user_status(&user), None);
+        assert_eq!(client.try_request_join(&user), Err(Ok(Error::JoinRequestPending.into())));
+
+        assert_eq!(client.try_approve_join(&user, &user), Err(Ok(Error::Unauthorized.into())));
+        client.approve_join(&scorer_creator, &user);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("release")),
+                events::EscrowReleased {
+                    user: user.clone(),
+                    token: token.address.clone(),
+                    amount: 50,
+                    recipient: scorer_creator.clone(),
+                },
+            ),
+            EventRecord::new(&env, &scorer.address, (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("add")), events::UserJoined { user: user.clone() }),
+        ]);
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Active));
+        assert_eq!(client.get_join_request(&user), None);
+        assert_eq!(token.balance(&scorer_creator), 50);
+
+        assert_eq!(client.try_request_join(&user), Err(Ok(Error::UserAlreadyExist.into())));
+    }
+
+    #[test]
+    fn test_request_join_with_swap() {
+        let setup = setup();
+        let (user, token) = setup_join_approval(&setup);
+        let Setup { env, scorer_creator, scorer, client } = setup;
+        let amm = env.register(fixed_rate_amm::FixedRateAmm, ());
+        token::StellarAssetClient::new(&env, &token.address).mint(&amm, &1000);
+        let asset = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        token::StellarAssetClient::new(&env, &asset).mint(&user, &500);
+        let asset_client = token::Client::new(&env, &asset);
+
+        let swap = FeeSwap { router: amm.clone(), assets: vec![&env, asset.clone()] };
+        assert_eq!(client.try_set_fee_swap(&user, &Some(swap.clone())), Err(Ok(Error::Unauthorized.into())));
+        assert_eq!(client.try_request_join_with_swap(&user, &asset, &100), Err(Ok(Error::Unauthorized.into())));
+        client.set_fee_swap(&scorer_creator, &Some(swap.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("feeswap")),
+            events::FeeSwapSet { sender: scorer_creator.clone(), router: Some(amm.clone()), assets: vec![&env, asset.clone()] },
+        )]);
+        assert_eq!(client.get_fee_swap(), Some(swap));
+
+        // Only accepted assets are swapped, for at most the amount the user allows
+        let other = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        assert_eq!(client.try_request_join_with_swap(&user, &other, &100), Err(Ok(Error::Unauthorized.into())));
+        assert!(client.try_request_join_with_swap(&user, &asset, &99).is_err());
+
+        assert_eq!(client.request_join_with_swap(&user, &asset, &100), 100);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("swap")),
+                events::EscrowSwapped {
+                    user: user.clone(),
+                    asset: asset.clone(),
+                    amount_in: 100,
+                    token: token.address.clone(),
+                    amount: 50,
+                },
+            ),
+            EventRecord::new(
+                &env,
+                &client.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("hold")),
+                events::EscrowHeld { user: user.clone(), token: token.address.clone(), amount: 50 },
+            ),
+        ]);
+        assert_eq!(asset_client.balance(&user), 400);
+        assert_eq!(token.balance(&client.address), 50);
+        assert!(client.get_join_request(&user).is_some());
+
+        // The escrow holds the fee token, which rejected requests are refunded in
+        client.reject_join(&scorer_creator, &user);
+        assert_eq!(token.balance(&user), 550);
+        assert_eq!(scorer.get_user_status(&user), None);
+
+        client.set_fee_swap(&scorer_creator, &None);
+        assert_eq!(client.get_fee_swap(), None);
+        assert_eq!(client.try_request_join_with_swap(&user, &asset, &100), Err(Ok(Error::Unauthorized.into())));
+    }
+
+    #[test]
+    fn test_request_join_with_short_swap() {
+        let setup = setup();
+        let (user, token) = setup_join_approval(&setup);
+        let Setup { env, scorer_creator, client, .. } = setup;
+        let amm = env.register(short_changing_amm::ShortChangingAmm, ());
+        token::StellarAssetClient::new(&env, &token.address).mint(&amm, &1000);
+        let asset = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        token::StellarAssetClient::new(&env, &asset).mint(&user, &500);
+        client.set_fee_swap(&scorer_creator, &Some(FeeSwap { router: amm, assets: vec![&env, asset.clone()] }));
+
+        // The request is refused when the contract receives less than the fee
+        assert_eq!(client.try_request_join_with_swap(&user, &asset, &100), Err(Ok(Error::InvalidJoinApproval.into())));
+        assert_eq!(token.balance(&client.address), 0);
+        assert_eq!(client.get_join_request(&user), None);
+    }
+
+    #[test]
+    fn test_reject_join_refunds_fee() {
+        let setup = setup();
+        let (user, token) = setup_join_approval(&setup);
+        let Setup { env, scorer_creator, scorer, client } = setup;
+
+        client.request_join(&user);
+        assert_eq!(client.try_reject_join(&user, &user), Err(Ok(Error::Unauthorized.into())));
+        client.reject_join(&scorer_creator, &user);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("refund")),
+                events::EscrowRefunded { user: user.clone(), token: token.address.clone(), amount: 50 },
+            ),
+            EventRecord::new(
+                &env,
+                &client.address,
+                (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("reject")),
+                events::JoinRejected { sender: scorer_creator.clone(), user: user.clone() },
+            ),
+        ]);
+
+        assert_eq!(token.balance(&user), 500);
+        assert_eq!(scorer.get_user_status(&user), None);
+        assert_eq!(client.try_approve_join(&scorer_creator, &user), Err(Ok(Error::JoinRequestNotFound.into())));
+    }
+
+    #[test]
+    fn test_refund_expired_join() {
+        let setup = setup();
+        let (user, token) = setup_join_approval(&setup);
+        let Setup { env, scorer_creator, scorer, client } = setup;
+
+        client.request_join(&user);
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 100);
+        assert_eq!(client.try_refund_expired_join(&user), Err(Ok(Error::JoinRequestNotExpired.into())));
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 1);
+        assert_eq!(client.try_approve_join(&scorer_creator, &user), Err(Ok(Error::JoinRequestExpired.into())));
+        client.refund_expired_join(&user);
+        assert_eq!(token.balance(&user), 500);
+        assert_eq!(client.get_join_request(&user), None);
+        assert_eq!(client.try_refund_expired_join(&user), Err(Ok(Error::JoinRequestNotFound.into())));
+
+        // The user can request again, and joins freely once approval is disabled
+        client.request_join(&user);
+        client.set_join_approval(&scorer_creator, &None);
+        assert_eq!(client.get_join_approval(), None);
+        assert_eq!(client.try_request_join(&Address::generate(&env)), Err(Ok(Error::JoinApprovalDisabled.into())));
+        client.add_user(&user);
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Active));
+    }
+
+    #[test]
+    fn test_set_join_approval_invalid() {
+        let Setup { env, scorer_creator, scorer, client } = setup();
+        let token = Address::generate(&env);
+
+        let negative_fee = Some(JoinApproval { token: token.clone(), fee: -1, ttl: 100 });
+        assert_eq!(client.try_set_join_approval(&scorer_creator, &negative_fee), Err(Ok(Error::InvalidJoinApproval.into())));
+        let no_ttl = Some(JoinApproval { token: token.clone(), fee: 0, ttl: 0 });
+        assert_eq!(client.try_set_join_approval(&scorer_creator, &no_ttl), Err(Ok(Error::InvalidJoinApproval.into())));
+
+        let manager = Address::generate(&env);
+        scorer.add_manager(&scorer_creator, &manager);
+        let approval = Some(JoinApproval { token, fee: 0, ttl: 100 });
+        assert_eq!(client.try_set_join_approval(&manager, &approval), Err(Ok(Error::Unauthorized.into())));
+    }
+
+    #[test]
+    fn test_ban_user() {
+        let setup = setup();
+        let (user, token) = setup_join_approval(&setup);
+        let Setup { env, scorer_creator, scorer, client } = setup;
+
+        // Banning a user with a join request refunds it
+        client.request_join(&user);
+        assert_eq!(client.try_ban_user(&user, &user), Err(Ok(Error::Unauthorized.into())));
+        client.ban_user(&scorer_creator, &user);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("ban")),
+            events::UserBanned { sender: scorer_creator.clone(), user: user.clone() },
+        )]);
+        assert_eq!(token.balance(&user), 500);
+        assert_eq!(client.get_join_request(&user), None);
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Banned));
+        assert_eq!(client.try_ban_user(&scorer_creator, &user), Err(Ok(Error::UserBanned.into())));
+        assert_eq!(client.try_request_join(&user), Err(Ok(Error::UserBanned.into())));
+
+        // Once unbanned the user can join again, and a ban removes a member
+        client.unban_user(&scorer_creator, &user);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("unban")),
+            events::UserUnbanned { sender: scorer_creator.clone(), user: user.clone() },
+        )]);
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Left));
+        assert_eq!(client.try_unban_user(&scorer_creator, &user), Err(Ok(Error::UserNotBanned.into())));
+
+        client.request_join(&user);
+        client.approve_join(&scorer_creator, &user);
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Active));
+        client.ban_user(&scorer_creator, &user);
+        assert_eq!(scorer.get_users().get(user.clone()), Some(UserStatus::Banned));
+        assert!(scorer.try_remove_user(&user).is_err());
+    }
+
+    #[test]
+    fn test_join_deposit() {
+        let Setup { env, scorer_creator, scorer, client } = setup();
+        let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let deposit = JoinDeposit { token: token.clone(), amount: 20 };
+
+        assert_eq!(client.try_set_join_deposit(&Address::generate(&env), &Some(deposit.clone())), Err(Ok(Error::Unauthorized.into())));
+        assert_eq!(
+            client.try_set_join_deposit(&scorer_creator, &Some(JoinDeposit { token: token.clone(), amount: 0 })),
+            Err(Ok(Error::InvalidJoinApproval.into()))
+        );
+        client.set_join_deposit(&scorer_creator, &Some(deposit.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("deposit")),
+            events::JoinDepositSet { sender: scorer_creator.clone(), token: Some(token.clone()), amount: 20 },
+        )]);
+        assert_eq!(client.get_join_deposit(), Some(deposit.clone()));
+
+        let user = Address::generate(&env);
+        let banned = Address::generate(&env);
+        let asset = token::StellarAssetClient::new(&env, &token);
+        asset.mint(&user, &100);
+        asset.mint(&banned, &100);
+        let token = token::Client::new(&env, &token);
+
+        // Joining locks the deposit and leaving the scorer returns it
+        client.add_user(&user);
+        assert_eq!(client.get_deposit(&user), Some(deposit.clone()));
+        assert_eq!(token.balance(&user), 80);
+        scorer.remove_user(&user);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_ESCROW, symbol_short!("refund")),
+            events::EscrowRefunded { user: user.clone(), token: token.address.clone(), amount: 20 },
+        )]);
+        assert_eq!(client.get_deposit(&user), None);
+        assert_eq!(token.balance(&user), 100);
+
+        // A ban forfeits the deposit to the scorer creator
+        client.add_user(&banned);
+        client.ban_user(&scorer_creator, &banned);
+        assert_eq!(client.get_deposit(&banned), None);
+        assert_eq!(token.balance(&banned), 80);
+        assert_eq!(token.balance(&scorer_creator), 20);
+
+        // Rejected join requests return the deposit with the fee
+        client.set_join_approval(&scorer_creator, &Some(JoinApproval { token: token.address.clone(), fee: 10, ttl: 100 }));
+        client.request_join(&user);
+        assert_eq!(token.balance(&user), 70);
+        client.reject_join(&scorer_creator, &user);
+        assert_eq!(client.get_deposit(&user), None);
+        assert_eq!(token.balance(&user), 100);
+
+        // Once the deposit is no longer required, users join without locking one
+        client.set_join_deposit(&scorer_creator, &None);
+        assert_eq!(client.get_join_deposit(), None);
+        client.request_join(&user);
+        client.approve_join(&scorer_creator, &user);
+        assert_eq!(client.get_deposit(&user), None);
+        assert_eq!(token.balance(&user), 90);
+    }
+
+    #[test]
+    fn test_passkey_wallets_cannot_lock_deposits() {
+        let Setup { env, scorer_creator, client, .. } = setup();
+        let passkey = Passkey::new(7);
+        let wallet = env.register(PasskeyWalletContract, (passkey.public_key(&env), true));
+        let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        client.set_join_deposit(&scorer_creator, &Some(JoinDeposit { token, amount: 20 }));
+
+        let challenge = env.as_contract(&client.address, || passkey::challenge(&env, Symbol::new(&env, "add_user"), Vec::new(&env), 0));
+        assert_eq!(
+            client.try_add_user_with_passkey(&wallet, &passkey.sign(&env, &challenge)),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+        assert_eq!(client.get_passkey_nonce(&wallet), 0);
+    }
+
+    #[test]
+    fn test_rejoin_cooldown() {
+        let Setup { env, scorer_creator, scorer, client } = setup();
+        let user = Address::generate(&env);
+
+        assert_eq!(client.try_set_rejoin_cooldown(&user, &100), Err(Ok(Error::Unauthorized.into())));
+        client.set_rejoin_cooldown(&scorer_creator, &100);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("cooldown")),
+            events::RejoinCooldownSet { sender: scorer_creator.clone(), ledgers: 100 },
+        )]);
+        assert_eq!(client.get_rejoin_cooldown(), 100);
+
+        client.add_user(&user);
+        scorer.remove_user(&user);
+        assert_eq!(client.try_add_user(&user), Err(Ok(Error::RejoinCooldownActive.into())));
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 99);
+        assert_eq!(client.try_add_user(&user), Err(Ok(Error::RejoinCooldownActive.into())));
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 1);
+        client.add_user(&user);
+
+        // Without a cooldown users rejoin at once
+        client.set_rejoin_cooldown(&scorer_creator, &0);
+        assert_eq!(client.get_rejoin_cooldown(), 0);
+        scorer.remove_user(&user);
+        client.add_user(&user);
+    }
+
+    #[test]
+    fn test_record_departure_unauthorized() {
+        let Setup { env, client, .. } = setup();
+        env.set_auths(&[]);
+
+        assert!(client.try_record_departure(&Address::generate(&env)).is_err());
+    }
+
+    #[test]
+    fn test_membership_expires_until_renewed() {
+        let Setup { env, scorer_creator, scorer, client } = setup();
+        let member = Address::generate(&env);
+        client.add_user(&member);
+
+        let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let terms = MembershipTerms { period: 100, token: token.clone(), fee: 10 };
+        assert_eq!(client.try_set_membership_terms(&member, &Some(terms.clone())), Err(Ok(Error::Unauthorized.into())));
+        client.set_membership_terms(&scorer_creator, &Some(terms.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("terms")),
+            events::MembershipTermsSet { sender: scorer_creator.clone(), period: 100, token: Some(token.clone()), fee: 10 },
+        )]);
+        assert_eq!(client.get_membership_terms(), Some(terms));
+
+        // Members admitted before memberships expired keep theirs
+        let user = Address::generate(&env);
+        client.add_user(&user);
+        token::StellarAssetClient::new(&env, &token).mint(&user, &100);
+        let start = env.ledger().sequence();
+        assert_eq!(scorer.get_membership_expiry(&user), Some(start + 100));
+        assert_eq!(scorer.get_membership_expiry(&member), None);
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 101);
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Left));
+
+        client.renew(&user);
+        let expires_at = env.ledger().sequence() + 100;
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("renew")),
+            events::MembershipRenewed { user: user.clone(), fee: 10, expires_at },
+        )]);
+        assert_eq!(scorer.get_membership_expiry(&user), Some(expires_at));
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Active));
+        assert_eq!(token::Client::new(&env, &token).balance(&scorer_creator), 10);
+
+        // Renewing before the expiry extends it
+        client.renew(&user);
+        assert_eq!(scorer.get_membership_expiry(&user), Some(expires_at + 100));
+
+        // Once memberships no longer expire, renewing makes them last
+        client.set_membership_terms(&scorer_creator, &None);
+        assert_eq!(client.get_membership_terms(), None);
+        client.renew(&user);
+        assert_eq!(scorer.get_membership_expiry(&user), None);
+        assert_eq!(client.try_renew(&user), Err(Ok(Error::MembershipTermsDisabled.into())));
+    }
+
+    #[test]
+    fn test_expired_member_rejoins() {
+        let setup = setup();
+        let (user, token) = setup_join_approval(&setup);
+        let Setup { env, scorer_creator, scorer, client } = setup;
+        let terms = MembershipTerms { period: 100, token: token.address.clone(), fee: 0 };
+        client.set_membership_terms(&scorer_creator, &Some(terms));
+        client.request_join(&user);
+        client.approve_join(&scorer_creator, &user);
+        assert_eq!(client.try_request_join(&user), Err(Ok(Error::UserAlreadyExist.into())));
+
+        // An expired member keeps its stored status until a new request is approved
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 101);
+        client.request_join(&user);
+        client.approve_join(&scorer_creator, &user);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(&env, &scorer.address, (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("add")), events::UserJoined { user: user.clone() })]);
+        assert_eq!(scorer.get_membership_expiry(&user), Some(env.ledger().sequence() + 100));
+        assert_eq!(scorer.get_user_status(&user), Some(UserStatus::Active));
+        assert_eq!(token.balance(&scorer_creator), 100);
+
+        // Once swept, it can no longer renew but joins again
+        client.set_join_approval(&scorer_creator, &None);
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 101);
+        scorer.maintenance(&MaintenanceTask::SweepExpired, &0, &10);
+        assert_eq!(client.try_renew(&user), Err(Ok(Error::UserDoesNotExist.into())));
+        client.add_user(&user);
+        assert_eq!(scorer.get_membership_expiry(&user), Some(env.ledger().sequence() + 100));
+    }
+
+    #[test]
+    fn test_renew_rejections() {
+        let Setup { env, scorer_creator, scorer, client } = setup();
+        let token = Address::generate(&env);
+
+        let no_period = Some(MembershipTerms { period: 0, token: token.clone(), fee: 0 });
+        assert_eq!(client.try_set_membership_terms(&scorer_creator, &no_period), Err(Ok(Error::InvalidMembershipTerms.into())));
+        let negative_fee = Some(MembershipTerms { period: 100, token: token.clone(), fee: -1 });
+        assert_eq!(client.try_set_membership_terms(&scorer_creator, &negative_fee), Err(Ok(Error::InvalidMembershipTerms.into())));
+
+        let user = Address::generate(&env);
+        client.add_user(&user);
+        assert_eq!(client.try_renew(&user), Err(Ok(Error::MembershipTermsDisabled.into())));
+
+        client.set_membership_terms(&scorer_creator, &Some(MembershipTerms { period: 100, token, fee: 0 }));
+        assert_eq!(client.try_renew(&Address::generate(&env)), Err(Ok(Error::UserDoesNotExist.into())));
+
+        scorer.set_archived(&scorer_creator, &true);
+        assert_eq!(client.try_renew(&user), Err(Ok(Error::ScorerArchived.into())));
+        assert_eq!(client.try_add_user(&Address::generate(&env)), Err(Ok(Error::ScorerArchived.into())));
+    }
+}
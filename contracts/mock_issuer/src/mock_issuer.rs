@@ -0,0 +1,824 @@
+#![no_std]
+use scorer_interface::{events, merkle, passkey, PasskeySignature, ProofVerifierClient, ScorerClient};
+use scorer_interface::events::{TOPIC_BADGE, TOPIC_SESSION, TOPIC_CLAIM, TOPIC_PRIVATE};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, String, Vec};
+
+/// A badge claim signed off-chain with the issuer's signing key.
+///
+/// The signed message is the XDR encoding of the claim, which includes the
+/// issuer contract address so a signature cannot be replayed on another issuer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub issuer: Address,
+    pub user: Address,
+    pub badge: String,
+}
+
+/// A temporary key the admin allows to issue and revoke some badges, such as
+/// the key of a device used by event staff.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionKey {
+    /// The badges the key may issue and revoke.
+    pub badges: Vec<String>,
+    /// The last ledger the key can be used at.
+    pub expires_at: u32,
+}
+
+#[contracttype]
+enum DataKey {
+    Admin,
+    SigningKey,
+    Badges(Address),
+    SessionKey(Address),
+    ClaimRoot(String),
+    ProofClaimed(BytesN<32>, Address),
+    ProofVerifier(String),
+    Nullifier(BytesN<32>),
+    PrivateBadge(String, BytesN<32>),
+    PasskeyNonce(Address),
+}
+
+/// Errors raised by the mock issuer.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The admin is missing from storage.
+    AdminNotFound = 2,
+    /// The signing key is missing from storage.
+    SigningKeyNotFound = 3,
+    /// The user already holds the badge.
+    BadgeAlreadyIssued = 4,
+    /// The user does not hold the badge.
+    BadgeNotIssued = 5,
+    /// The scope of the session key is empty or its expiry ledger has passed.
+    InvalidSessionKey = 6,
+    /// The session key is unknown or was removed.
+    SessionKeyNotFound = 7,
+    /// The session key expired.
+    SessionKeyExpired = 8,
+    /// The badge is not in the scope of the session key.
+    BadgeOutOfScope = 9,
+    /// No claim root was published for the badge.
+    ClaimRootNotFound = 10,
+    /// The proof does not lead from the claim to the claim root.
+    InvalidProof = 11,
+    /// The user already claimed the badge under this claim root.
+    AlreadyClaimed = 12,
+    /// No proof verifier is set for the badge.
+    ProofVerifierNotFound = 13,
+    /// The proof verifier rejected the proof.
+    ProofRejected = 14,
+    /// A badge was already claimed with this nullifier.
+    NullifierUsed = 15,
+    /// The passkey signature does not match the claim or was already used.
+    InvalidPasskeySignature = 16,
+}
+
+/// A minimal badge issuer for tests and local development.
+///
+/// Badges are issued to users directly by the admin or claimed by users with a
+/// signature of the issuer's signing key, either with their account or with a
+/// passkey, and the issuer can register its badges
+/// in scorers, where it appears as the badge issuer. The admin can also hand a
+/// session key, limited to some badges until an expiry ledger, to staff who
+/// issue badges from a device that should not hold the admin key.
+///
+/// For large cohorts, the admin publishes the Merkle root of their claims for
+/// a badge and each user claims the badge with a proof of inclusion. Badges
+/// with a proof verifier can also be claimed privately: the verifier checks a
+/// zero-knowledge proof that the claimant qualifies, and the badge is attested
+/// to a commitment instead of an account.
+#[contract]
+pub struct MockIssuerContract;
+
+#[contractimpl]
+impl MockIssuerContract {
+    /// Sets up the issuer when it is deployed, so no one can claim an issuer
+    /// deployed but not yet initialized
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `admin` - The address allowed to issue and revoke badges
+    /// * `signing_key` - The ed25519 public key whose signatures make claims valid
+    pub fn __constructor(env: Env, admin: Address, signing_key: BytesN<32>) {
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+        env.storage().persistent().set(&DataKey::SigningKey, &signing_key);
+    }
+
+    /// Issues a badge to a user
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The address receiving the badge
+    /// * `badge` - The badge name
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the user already holds the badge (`Error::BadgeAlreadyIssued`)
+    pub fn issue(env: Env, user: Address, badge: String) {
+        Self::admin(&env).require_auth();
+        Self::record_badge(&env, user, badge);
+    }
+
+    /// Issues a badge to a user from a claim signed with the issuer's signing key
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The address claiming the badge
+    /// * `badge` - The badge name
+    /// * `signature` - The ed25519 signature of the XDR-encoded `Claim`
+    ///
+    /// # Panics
+    /// * When the user fails authentication
+    /// * When the signature does not match the claim
+    /// * When the user already holds the badge (`Error::BadgeAlreadyIssued`)
+    pub fn claim(env: Env, user: Address, badge: String, signature: BytesN<64>) {
+        user.require_auth();
+
+        let signing_key = env
+            .storage()
+            .persistent()
+            .get::<DataKey, BytesN<32>>(&DataKey::SigningKey)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::SigningKeyNotFound));
+        let claim = Claim {
+            issuer: env.current_contract_address(),
+            user: user.clone(),
+            badge: badge.clone(),
+        };
+        env.crypto().ed25519_verify(&signing_key, &claim.to_xdr(&env), &signature);
+
+        Self::record_badge(&env, user, badge);
+    }
+
+    /// Issues a badge to a passkey wallet from a claim signed with the issuer's
+    /// signing key, authorized by a signature of the wallet's passkey instead of
+    /// the wallet itself
+    ///
+    /// The passkey signs the challenge of the `claim` action with the badge as its
+    /// only argument and the wallet's current nonce in this issuer. The assertion
+    /// is checked against the public key the wallet reports.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `wallet` - The address of the passkey wallet contract
+    /// * `badge` - The badge name
+    /// * `signature` - The ed25519 signature of the XDR-encoded `Claim` for the wallet
+    /// * `passkey_signature` - The WebAuthn assertion of the wallet's passkey over the challenge
+    ///
+    /// # Panics
+    /// * When the passkey signature does not match the challenge (`Error::InvalidPasskeySignature`)
+    /// * When either signature is invalid, or `wallet` is not a passkey wallet
+    /// * When the wallet already holds the badge (`Error::BadgeAlreadyIssued`)
+    pub fn claim_with_passkey(
+        env: Env,
+        wallet: Address,
+        badge: String,
+        signature: BytesN<64>,
+        passkey_signature: PasskeySignature,
+    ) {
+        let nonce = Self::get_passkey_nonce(env.clone(), wallet.clone());
+        let challenge = passkey::challenge(&env, symbol_short!("claim"), vec![&env, badge.into_val(&env)], nonce);
+        if !passkey::verify_wallet(&env, &wallet, &challenge, &passkey_signature) {
+            panic_with_error!(&env, Error::InvalidPasskeySignature);
+        }
+        env.storage().persistent().set(&DataKey::PasskeyNonce(wallet.clone()), &(nonce + 1));
+
+        let signing_key = env
+            .storage()
+            .persistent()
+            .get::<DataKey, BytesN<32>>(&DataKey::SigningKey)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::SigningKeyNotFound));
+        let claim = Claim {
+            issuer: env.current_contract_address(),
+            user: wallet.clone(),
+            badge: badge.clone(),
+        };
+        env.crypto().ed25519_verify(&signing_key, &claim.to_xdr(&env), &signature);
+
+        Self::record_badge(&env, wallet, badge);
+    }
+
+    /// Returns the nonce the next signature of a wallet's passkey must commit to
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `wallet` - The address of the passkey wallet contract
+    pub fn get_passkey_nonce(env: Env, wallet: Address) -> u32 {
+        env.storage().persistent().get(&DataKey::PasskeyNonce(wallet)).unwrap_or(0)
+    }
+
+    /// Publishes the Merkle root of the claims of a badge, replacing the previous root
+    ///
+    /// The leaves are the SHA-256 hashes of the XDR-encoded `Claim`s, and each node
+    /// hashes its two children in ascending order.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `badge` - The badge name
+    /// * `merkle_root` - The root of the tree of claims
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    pub fn set_claim_root(env: Env, badge: String, merkle_root: BytesN<32>) {
+        Self::admin(&env).require_auth();
+        env.storage().persistent().set(&DataKey::ClaimRoot(badge.clone()), &merkle_root);
+
+        env.events().publish((TOPIC_CLAIM, symbol_short!("root")), events::ClaimRootSet { badge, merkle_root });
+    }
+
+    /// Returns the claim root of a badge, if any
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `badge` - The badge name
+    pub fn get_claim_root(env: Env, badge: String) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::ClaimRoot(badge))
+    }
+
+    /// Issues a badge to a user whose claim is in the claim root of the badge
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The address claiming the badge
+    /// * `badge` - The badge name
+    /// * `proof` - The sibling hashes from the leaf of the claim up to the root
+    ///
+    /// # Panics
+    /// * When the user fails authentication
+    /// * When the badge has no claim root (`Error::ClaimRootNotFound`)
+    /// * When the user already claimed the badge under this root (`Error::AlreadyClaimed`)
+    /// * When the proof does not match the root (`Error::InvalidProof`)
+    /// * When the user already holds the badge (`Error::BadgeAlreadyIssued`)
+    pub fn claim_with_proof(env: Env, user: Address, badge: String, proof: Vec<BytesN<32>>) {
+        user.require_auth();
+
+        let root = Self::get_claim_root(env.clone(), badge.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ClaimRootNotFound));
+        // A revoked badge cannot be claimed again with the same proof
+        let claimed = DataKey::ProofClaimed(root.clone(), user.clone());
+        if env.storage().persistent().has(&claimed) {
+            panic_with_error!(&env, Error::AlreadyClaimed);
+        }
+
+        let claim = Claim {
+            issuer: env.current_contract_address(),
+            user: user.clone(),
+            badge: badge.clone(),
+        };
+        if !merkle::verify(&env, merkle::leaf(&env, claim), &proof, &root) {
+            panic_with_error!(&env, Error::InvalidProof);
+        }
+        env.storage().persistent().set(&claimed, &());
+
+        Self::record_badge(&env, user, badge);
+    }
+
+    /// Sets the contract verifying the proofs of private claims of a badge, or removes it
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `badge` - The badge name
+    /// * `verifier` - The address of a `ProofVerifierTrait` contract, or `None` to disable private claims
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    pub fn set_proof_verifier(env: Env, badge: String, verifier: Option<Address>) {
+        Self::admin(&env).require_auth();
+
+        let key = DataKey::ProofVerifier(badge.clone());
+        match &verifier {
+            Some(verifier) => env.storage().persistent().set(&key, verifier),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events().publish((TOPIC_PRIVATE, symbol_short!("verifier")), events::ProofVerifierSet { badge, verifier });
+    }
+
+    /// Returns the proof verifier of a badge, if any
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `badge` - The badge name
+    pub fn get_proof_verifier(env: Env, badge: String) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::ProofVerifier(badge))
+    }
+
+    /// Attests a badge to a commitment with a proof accepted by the proof verifier of the badge
+    ///
+    /// The claim needs no account authorization, so it can be submitted by a relayer
+    /// without linking the claimant's account to the commitment.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `badge` - The badge name
+    /// * `commitment` - The commitment the badge is attested to
+    /// * `nullifier` - The nullifier of the claimant's secret, usable once
+    /// * `proof` - The proof, in the verifier's encoding
+    ///
+    /// # Panics
+    /// * When the badge has no proof verifier (`Error::ProofVerifierNotFound`)
+    /// * When the nullifier was already used (`Error::NullifierUsed`)
+    /// * When the verifier rejects the proof (`Error::ProofRejected`)
+    pub fn claim_private(env: Env, badge: String, commitment: BytesN<32>, nullifier: BytesN<32>, proof: Bytes) {
+        let verifier = Self::get_proof_verifier(env.clone(), badge.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ProofVerifierNotFound));
+        if env.storage().persistent().has(&DataKey::Nullifier(nullifier.clone())) {
+            panic_with_error!(&env, Error::NullifierUsed);
+        }
+
+        let issuer = env.current_contract_address();
+        if !ProofVerifierClient::new(&env, &verifier).verify(&issuer, &badge, &commitment, &nullifier, &proof) {
+            panic_with_error!(&env, Error::ProofRejected);
+        }
+        env.storage().persistent().set(&DataKey::Nullifier(nullifier), &());
+        env.storage().persistent().set(&DataKey::PrivateBadge(badge.clone(), commitment.clone()), &());
+
+        env.events().publish((TOPIC_PRIVATE, symbol_short!("claim")), events::PrivateBadgeClaimed { badge, commitment });
+    }
+
+    /// Returns whether a badge was attested to a commitment
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `badge` - The badge name
+    /// * `commitment` - The commitment to check
+    pub fn has_private_badge(env: Env, badge: String, commitment: BytesN<32>) -> bool {
+        env.storage().persistent().has(&DataKey::PrivateBadge(badge, commitment))
+    }
+
+    /// Revokes a badge from a user
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The address holding the badge
+    /// * `badge` - The badge name
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the user does not hold the badge (`Error::BadgeNotIssued`)
+    pub fn revoke(env: Env, user: Address, badge: String) {
+        Self::admin(&env).require_auth();
+        Self::remove_badge(&env, user, badge);
+    }
+
+    /// Allows a session key to issue and revoke some badges until an expiry ledger,
+    /// replacing its previous scope and expiry
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `key` - The address of the session key
+    /// * `badges` - The badge names the key may issue and revoke
+    /// * `expires_at` - The last ledger the key can be used at
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the scope is empty or the expiry ledger has passed (`Error::InvalidSessionKey`)
+    pub fn add_session_key(env: Env, key: Address, badges: Vec<String>, expires_at: u32) {
+        Self::admin(&env).require_auth();
+
+        if badges.is_empty() || expires_at < env.ledger().sequence() {
+            panic_with_error!(&env, Error::InvalidSessionKey);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::SessionKey(key.clone()), &SessionKey { badges: badges.clone(), expires_at });
+
+        env.events().publish(
+            (TOPIC_SESSION, symbol_short!("add")),
+            events::SessionKeyAdded { key, badges, expires_at },
+        );
+    }
+
+    /// Removes a session key before it expires
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `key` - The address of the session key
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the key is unknown (`Error::SessionKeyNotFound`)
+    pub fn remove_session_key(env: Env, key: Address) {
+        Self::admin(&env).require_auth();
+
+        if !env.storage().persistent().has(&DataKey::SessionKey(key.clone())) {
+            panic_with_error!(&env, Error::SessionKeyNotFound);
+        }
+        env.storage().persistent().remove(&DataKey::SessionKey(key.clone()));
+
+        env.events().publish((TOPIC_SESSION, symbol_short!("remove")), events::SessionKeyRemoved { key });
+    }
+
+    /// Returns the scope and expiry of a session key, if any
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `key` - The address of the session key
+    pub fn get_session_key(env: Env, key: Address) -> Option<SessionKey> {
+        env.storage().persistent().get(&DataKey::SessionKey(key))
+    }
+
+    /// Issues a badge to a user with a session key
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `key` - The address of the session key
+    /// * `user` - The address receiving the badge
+    /// * `badge` - The badge name
+    ///
+    /// # Panics
+    /// * When the session key fails authentication
+    /// * When the session key is unknown (`Error::SessionKeyNotFound`)
+    /// * When the session key expired (`Error::SessionKeyExpired`)
+    /// * When the badge is not in the scope of the session key (`Error::BadgeOutOfScope`)
+    /// * When the user already holds the badge (`Error::BadgeAlreadyIssued`)
+    pub fn session_issue(env: Env, key: Address, user: Address, badge: String) {
+        Self::check_session_key(&env, &key, &badge);
+        Self::record_badge(&env, user, badge);
+    }
+
+    /// Revokes a badge from a user with a session key
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `key` - The address of the session key
+    /// * `user` - The address holding the badge
+    /// * `badge` - The badge name
+    ///
+    /// # Panics
+    /// * When the session key fails authentication
+    /// * When the session key is unknown (`Error::SessionKeyNotFound`)
+    /// * When the session key expired (`Error::SessionKeyExpired`)
+    /// * When the badge is not in the scope of the session key (`Error::BadgeOutOfScope`)
+    /// * When the user does not hold the badge (`Error::BadgeNotIssued`)
+    pub fn session_revoke(env: Env, key: Address, user: Address, badge: String) {
+        Self::check_session_key(&env, &key, &badge);
+        Self::remove_badge(&env, user, badge);
+    }
+
+    /// Returns whether a user holds a badge
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The address to check
+    /// * `badge` - The badge name
+    pub fn has_badge(env: Env, user: Address, badge: String) -> bool {
+        Self::get_badges(env, user).contains(&badge)
+    }
+
+    /// Returns the badges held by a user
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The address holding the badges
+    pub fn get_badges(env: Env, user: Address) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Badges(user))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Adds a badge of this issuer to a scorer, with this contract as the badge issuer
+    ///
+    /// The issuer must be a manager of the scorer, and verified by the scorer's issuer
+    /// registry when one is set.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The address of the scorer contract
+    /// * `badge` - The badge name
+    /// * `score` - The score of the badge in the scorer
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the scorer rejects the badge
+    pub fn register_badge(env: Env, scorer: Address, badge: String, score: u32) {
+        Self::admin(&env).require_auth();
+
+        let issuer = env.current_contract_address();
+        ScorerClient::new(&env, &scorer).add_badge(&issuer, &badge, &issuer, &score);
+    }
+
+    fn admin(env: &Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, Error::AdminNotFound))
+    }
+
+    // Authenticates a session key allowed to issue and revoke the badge now.
+    fn check_session_key(env: &Env, key: &Address, badge: &String) {
+        key.require_auth();
+
+        let session: SessionKey = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SessionKey(key.clone()))
+            .unwrap_or_else(|| panic_with_error!(env, Error::SessionKeyNotFound));
+        if env.ledger().sequence() > session.expires_at {
+            panic_with_error!(env, Error::SessionKeyExpired);
+        }
+        if !session.badges.contains(badge) {
+            panic_with_error!(env, Error::BadgeOutOfScope);
+        }
+    }
+
+    fn remove_badge(env: &Env, user: Address, badge: String) {
+        let mut badges = Self::get_badges(env.clone(), user.clone());
+        let index = badges
+            .first_index_of(&badge)
+            .unwrap_or_else(|| panic_with_error!(env, Error::BadgeNotIssued));
+        badges.remove(index);
+        env.storage().persistent().set(&DataKey::Badges(user.clone()), &badges);
+
+        env.events().publish((TOPIC_BADGE, symbol_short!("revoke")), events::BadgeRevoked { user, badge });
+    }
+
+    fn record_badge(env: &Env, user: Address, badge: String) {
+        let mut badges = Self::get_badges(env.clone(), user.clone());
+        if badges.contains(&badge) {
+            panic_with_error!(env, Error::BadgeAlreadyIssued);
+        }
+        badges.push_back(badge.clone());
+        env.storage().persistent().set(&DataKey::Badges(user.clone()), &badges);
+
+        env.events().publish((TOPIC_BADGE, symbol_short!("issue")), events::BadgeIssued { user, badge });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use passkey_wallet::PasskeyWalletContract;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use test_utils::{EventRecord, EventSnapshot, Passkey};
+
+    fn setup() -> (Env, Address, SigningKey, MockIssuerContractClient<'static>) {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let signing_public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+        let client = MockIssuerContractClient::new(&env, &env.register(MockIssuerContract, (&admin, signing_public_key)));
+
+        (env, admin, signing_key, client)
+    }
+
+    fn sign_claim(env: &Env, signing_key: &SigningKey, claim: &Claim) -> BytesN<64> {
+        let xdr = claim.clone().to_xdr(env);
+        let mut message = [0; 256];
+        let message = &mut message[..xdr.len() as usize];
+        xdr.copy_into_slice(message);
+        BytesN::from_array(env, &signing_key.sign(message).to_bytes())
+    }
+
+    #[test]
+    fn test_issue_and_revoke() {
+        let (env, _, _, client) = setup();
+        let user = Address::generate(&env);
+        let badge = String::from_str(&env, "Quest");
+
+        client.issue(&user, &badge);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_BADGE, symbol_short!("issue")),
+            events::BadgeIssued { user: user.clone(), badge: badge.clone() },
+        )]);
+        assert!(client.has_badge(&user, &badge));
+
+        client.revoke(&user, &badge);
+        assert!(!client.has_badge(&user, &badge));
+        assert!(client.get_badges(&user).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #4)")]
+    fn test_issue_twice() {
+        let (env, _, _, client) = setup();
+        let user = Address::generate(&env);
+        let badge = String::from_str(&env, "Quest");
+
+        client.issue(&user, &badge);
+        client.issue(&user, &badge);
+    }
+
+    #[test]
+    fn test_claim_with_signature() {
+        let (env, _, signing_key, client) = setup();
+        let user = Address::generate(&env);
+        let badge = String::from_str(&env, "Quest");
+        let claim = Claim { issuer: client.address.clone(), user: user.clone(), badge: badge.clone() };
+
+        client.claim(&user, &badge, &sign_claim(&env, &signing_key, &claim));
+        assert!(client.has_badge(&user, &badge));
+    }
+
+    #[test]
+    fn test_claim_rejects_other_claims() {
+        let (env, _, signing_key, client) = setup();
+        let user = Address::generate(&env);
+        let badge = String::from_str(&env, "Quest");
+
+        // Signed for another user
+        let other_user = Claim { issuer: client.address.clone(), user: Address::generate(&env), badge: badge.clone() };
+        assert!(client.try_claim(&user, &badge, &sign_claim(&env, &signing_key, &other_user)).is_err());
+
+        // Signed for another issuer
+        let other_issuer = Claim { issuer: Address::generate(&env), user: user.clone(), badge: badge.clone() };
+        assert!(client.try_claim(&user, &badge, &sign_claim(&env, &signing_key, &other_issuer)).is_err());
+
+        // Signed with another key
+        let claim = Claim { issuer: client.address.clone(), user: user.clone(), badge: badge.clone() };
+        let other_key = SigningKey::from_bytes(&[8; 32]);
+        assert!(client.try_claim(&user, &badge, &sign_claim(&env, &other_key, &claim)).is_err());
+
+        assert!(!client.has_badge(&user, &badge));
+    }
+
+    #[test]
+    fn test_claim_with_passkey() {
+        let (env, _, signing_key, client) = setup();
+        let passkey = Passkey::new(7);
+        let user = env.register(PasskeyWalletContract, (passkey.public_key(&env), true));
+        let badge = String::from_str(&env, "Quest");
+        let challenge = |badge: &String, nonce| {
+            env.as_contract(&client.address, || {
+                passkey::challenge(&env, symbol_short!("claim"), vec![&env, badge.into_val(&env)], nonce)
+            })
+        };
+        let claim = Claim { issuer: client.address.clone(), user: user.clone(), badge: badge.clone() };
+        let signature = sign_claim(&env, &signing_key, &claim);
+
+        // Signed for another badge
+        let other_badge = passkey.sign(&env, &challenge(&String::from_str(&env, "Speaker"), 0));
+        assert_eq!(
+            client.try_claim_with_passkey(&user, &badge, &signature, &other_badge),
+            Err(Ok(Error::InvalidPasskeySignature.into()))
+        );
+
+        let passkey_signature = passkey.sign(&env, &challenge(&badge, 0));
+        client.claim_with_passkey(&user, &badge, &signature, &passkey_signature);
+        assert!(client.has_badge(&user, &badge));
+        assert_eq!(client.get_passkey_nonce(&user), 1);
+
+        client.revoke(&user, &badge);
+        assert_eq!(
+            client.try_claim_with_passkey(&user, &badge, &signature, &passkey_signature),
+            Err(Ok(Error::InvalidPasskeySignature.into()))
+        );
+    }
+
+    #[test]
+    fn test_session_key() {
+        let (env, _, _, client) = setup();
+        let key = Address::generate(&env);
+        let user = Address::generate(&env);
+        let speaker = String::from_str(&env, "Speaker");
+        let badges = Vec::from_array(&env, [speaker.clone()]);
+
+        client.add_session_key(&key, &badges, &100);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_SESSION, symbol_short!("add")),
+            events::SessionKeyAdded { key: key.clone(), badges: badges.clone(), expires_at: 100 },
+        )]);
+        assert_eq!(client.get_session_key(&key), Some(SessionKey { badges: badges.clone(), expires_at: 100 }));
+
+        client.session_issue(&key, &user, &speaker);
+        assert!(client.has_badge(&user, &speaker));
+        assert_eq!(
+            client.try_session_issue(&key, &user, &String::from_str(&env, "Organizer")),
+            Err(Ok(Error::BadgeOutOfScope.into()))
+        );
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 101);
+        assert_eq!(client.try_session_revoke(&key, &user, &speaker), Err(Ok(Error::SessionKeyExpired.into())));
+
+        client.remove_session_key(&key);
+        assert_eq!(client.try_session_revoke(&key, &user, &speaker), Err(Ok(Error::SessionKeyNotFound.into())));
+        assert_eq!(client.try_add_session_key(&key, &Vec::new(&env), &200), Err(Ok(Error::InvalidSessionKey.into())));
+        assert_eq!(
+            client.try_add_session_key(&key, &Vec::from_array(&env, [speaker]), &100),
+            Err(Ok(Error::InvalidSessionKey.into()))
+        );
+    }
+
+    #[test]
+    fn test_claim_with_proof() {
+        let (env, _, _, client) = setup();
+        let badge = String::from_str(&env, "Quest");
+        let users = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+        let leaves: [BytesN<32>; 3] = core::array::from_fn(|i| {
+            merkle::leaf(&env, Claim { issuer: client.address.clone(), user: users[i].clone(), badge: badge.clone() })
+        });
+        // A tree of three claims, the last one paired with itself
+        let left = merkle::hash_pair(&env, &leaves[0], &leaves[1]);
+        let right = merkle::hash_pair(&env, &leaves[2], &leaves[2]);
+        let root = merkle::hash_pair(&env, &left, &right);
+
+        assert_eq!(
+            client.try_claim_with_proof(&users[0], &badge, &Vec::new(&env)),
+            Err(Ok(Error::ClaimRootNotFound.into()))
+        );
+        client.set_claim_root(&badge, &root);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_CLAIM, symbol_short!("root")),
+            events::ClaimRootSet { badge: badge.clone(), merkle_root: root.clone() },
+        )]);
+        assert_eq!(client.get_claim_root(&badge), Some(root.clone()));
+
+        client.claim_with_proof(&users[1], &badge, &Vec::from_array(&env, [leaves[0].clone(), right.clone()]));
+        client.claim_with_proof(&users[2], &badge, &Vec::from_array(&env, [leaves[2].clone(), left.clone()]));
+        assert!(client.has_badge(&users[1], &badge));
+        assert!(client.has_badge(&users[2], &badge));
+
+        // The proof of another user, or of a user outside the cohort
+        assert_eq!(
+            client.try_claim_with_proof(&users[0], &badge, &Vec::from_array(&env, [leaves[0].clone(), right.clone()])),
+            Err(Ok(Error::InvalidProof.into()))
+        );
+        assert_eq!(
+            client.try_claim_with_proof(&Address::generate(&env), &badge, &Vec::from_array(&env, [leaves[1].clone(), right.clone()])),
+            Err(Ok(Error::InvalidProof.into()))
+        );
+
+        client.revoke(&users[1], &badge);
+        assert_eq!(
+            client.try_claim_with_proof(&users[1], &badge, &Vec::from_array(&env, [leaves[0].clone(), right])),
+            Err(Ok(Error::AlreadyClaimed.into()))
+        );
+    }
+
+    // Accepts the proofs equal to the commitment followed by the nullifier
+    #[contract]
+    struct MockVerifier;
+
+    #[contractimpl]
+    impl MockVerifier {
+        pub fn verify(env: Env, _issuer: Address, _badge: String, commitment: BytesN<32>, nullifier: BytesN<32>, proof: Bytes) -> bool {
+            let mut expected = Bytes::from_array(&env, &commitment.to_array());
+            expected.append(&Bytes::from_array(&env, &nullifier.to_array()));
+            proof == expected
+        }
+    }
+
+    #[test]
+    fn test_claim_private() {
+        let (env, _, _, client) = setup();
+        let badge = String::from_str(&env, "Whale");
+        let commitment = BytesN::from_array(&env, &[1; 32]);
+        let nullifier = BytesN::from_array(&env, &[2; 32]);
+        let mut proof = Bytes::from_array(&env, &[1; 32]);
+        proof.append(&Bytes::from_array(&env, &[2; 32]));
+
+        assert_eq!(
+            client.try_claim_private(&badge, &commitment, &nullifier, &proof),
+            Err(Ok(Error::ProofVerifierNotFound.into()))
+        );
+        let verifier = env.register(MockVerifier, ());
+        client.set_proof_verifier(&badge, &Some(verifier.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_PRIVATE, symbol_short!("verifier")),
+            events::ProofVerifierSet { badge: badge.clone(), verifier: Some(verifier.clone()) },
+        )]);
+        assert_eq!(client.get_proof_verifier(&badge), Some(verifier.clone()));
+
+        let other_commitment = BytesN::from_array(&env, &[3; 32]);
+        assert_eq!(
+            client.try_claim_private(&badge, &other_commitment, &nullifier, &proof),
+            Err(Ok(Error::ProofRejected.into()))
+        );
+
+        // No account authorizes the claim
+        env.set_auths(&[]);
+        client.claim_private(&badge, &commitment, &nullifier, &proof);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_PRIVATE, symbol_short!("claim")),
+            events::PrivateBadgeClaimed { badge: badge.clone(), commitment: commitment.clone() },
+        )]);
+        assert!(client.has_private_badge(&badge, &commitment));
+        assert!(!client.has_private_badge(&badge, &other_commitment));
+
+        assert_eq!(
+            client.try_claim_private(&badge, &commitment, &nullifier, &proof),
+            Err(Ok(Error::NullifierUsed.into()))
+        );
+
+        env.mock_all_auths();
+        client.set_proof_verifier(&badge, &None);
+        assert_eq!(client.get_proof_verifier(&badge), None);
+    }
+}
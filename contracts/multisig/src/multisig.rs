@@ -0,0 +1,312 @@
+#![no_std]
+use scorer_interface::events::{self, TOPIC_MULTISIG};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address, Env, Symbol, Val, Vec};
+
+/// What a proposal does once approved.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Calls a function of a contract with arguments.
+    Call(Address, Symbol, Vec<Val>),
+    /// Replaces the owners and the threshold of the multisig.
+    SetOwners(Vec<Address>, u32),
+}
+
+/// A proposed action and the owners who approved it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub action: Action,
+    pub approvals: Vec<Address>,
+}
+
+#[contracttype]
+enum DataKey {
+    Owners,
+    Threshold,
+    ProposalCount,
+    Proposal(u32),
+}
+
+/// Errors raised by the multisig.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    /// The contract is not initialized.
+    NotInitialized = 2,
+    /// The threshold is zero or exceeds the number of owners.
+    InvalidThreshold = 3,
+    /// The address is not an owner.
+    NotAnOwner = 4,
+    /// No pending proposal with this id exists.
+    ProposalNotFound = 5,
+    /// The owner already approved the proposal.
+    AlreadyApproved = 6,
+    /// Fewer owners approved the proposal than the threshold.
+    NotEnoughApprovals = 7,
+}
+
+/// An M-of-N multisig holding privileged roles such as the scorer creator or
+/// the factory creator.
+///
+/// An owner proposes an action, a call to any contract or a change of the
+/// owners, and it can be executed once `threshold` owners approved it. The
+/// proposer's approval is counted with the proposal, and approvals of owners
+/// removed since no longer count. The called contracts see the multisig as the
+/// invoker, so their `require_auth` passes without signatures, and calls
+/// taking a `sender` take the multisig's address.
+#[contract]
+pub struct MultisigContract;
+
+#[contractimpl]
+impl MultisigContract {
+    /// Initializes the multisig
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `owners` - The addresses allowed to propose and approve actions
+    /// * `threshold` - The number of approvals an action needs
+    ///
+    /// # Panics
+    /// * When the contract is already initialized (`Error::ContractAlreadyInitialized`)
+    /// * When the threshold is zero or exceeds the number of owners (`Error::InvalidThreshold`)
+    pub fn initialize(env: Env, owners: Vec<Address>, threshold: u32) {
+        if env.storage().persistent().has(&DataKey::Owners) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+        Self::set_owners(&env, owners, threshold);
+    }
+
+    /// Proposes an action, approved by the proposer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `proposer` - The owner proposing the action
+    /// * `action` - The action to execute once approved
+    ///
+    /// # Returns
+    /// * `u32` - The id of the proposal
+    ///
+    /// # Panics
+    /// * When the proposer fails authentication
+    /// * When the proposer is not an owner (`Error::NotAnOwner`)
+    /// * When the action sets a threshold that is zero or exceeds its owners (`Error::InvalidThreshold`)
+    pub fn propose(env: Env, proposer: Address, action: Action) -> u32 {
+        proposer.require_auth();
+        Self::check_owner(&env, &proposer);
+
+        if let Action::SetOwners(owners, threshold) = &action {
+            if *threshold == 0 || *threshold > owners.len() {
+                panic_with_error!(&env, Error::InvalidThreshold);
+            }
+        }
+
+        let id = env.storage().persistent().get(&DataKey::ProposalCount).unwrap_or(0u32) + 1;
+        env.storage().persistent().set(&DataKey::ProposalCount, &id);
+        let approvals = Vec::from_array(&env, [proposer.clone()]);
+        env.storage().persistent().set(&DataKey::Proposal(id), &Proposal { action, approvals });
+
+        env.events().publish((TOPIC_MULTISIG, symbol_short!("propose")), events::ActionProposed { id, proposer });
+        id
+    }
+
+    /// Approves a proposal
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `owner` - The owner approving the proposal
+    /// * `id` - The id of the proposal
+    ///
+    /// # Panics
+    /// * When the owner fails authentication
+    /// * When the address is not an owner (`Error::NotAnOwner`)
+    /// * When no pending proposal has this id (`Error::ProposalNotFound`)
+    /// * When the owner already approved the proposal (`Error::AlreadyApproved`)
+    pub fn approve(env: Env, owner: Address, id: u32) {
+        owner.require_auth();
+        Self::check_owner(&env, &owner);
+
+        let mut proposal = Self::get_proposal(env.clone(), id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ProposalNotFound));
+        if proposal.approvals.contains(&owner) {
+            panic_with_error!(&env, Error::AlreadyApproved);
+        }
+        proposal.approvals.push_back(owner.clone());
+        env.storage().persistent().set(&DataKey::Proposal(id), &proposal);
+
+        env.events().publish((TOPIC_MULTISIG, symbol_short!("approve")), events::ActionApproved { id, owner });
+    }
+
+    /// Executes an approved proposal
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the proposal
+    ///
+    /// # Returns
+    /// * `Val` - The value returned by the call, or void when the owners changed
+    ///
+    /// # Panics
+    /// * When no pending proposal has this id (`Error::ProposalNotFound`)
+    /// * When fewer current owners approved it than the threshold (`Error::NotEnoughApprovals`)
+    /// * When the call fails
+    pub fn execute(env: Env, id: u32) -> Val {
+        let proposal = Self::get_proposal(env.clone(), id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ProposalNotFound));
+        let (owners, threshold) = Self::get_owners(env.clone());
+        let approvals = proposal.approvals.iter().filter(|owner| owners.contains(owner)).count() as u32;
+        if approvals < threshold {
+            panic_with_error!(&env, Error::NotEnoughApprovals);
+        }
+
+        env.storage().persistent().remove(&DataKey::Proposal(id));
+        let result = match proposal.action {
+            Action::Call(contract, fn_name, args) => env.invoke_contract(&contract, &fn_name, args),
+            Action::SetOwners(owners, threshold) => {
+                Self::set_owners(&env, owners, threshold);
+                Val::VOID.to_val()
+            }
+        };
+
+        env.events().publish((TOPIC_MULTISIG, symbol_short!("execute")), events::ActionExecuted { id });
+        result
+    }
+
+    /// Returns a pending proposal, if any
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the proposal
+    pub fn get_proposal(env: Env, id: u32) -> Option<Proposal> {
+        env.storage().persistent().get(&DataKey::Proposal(id))
+    }
+
+    /// Returns the owners and the threshold
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    pub fn get_owners(env: Env) -> (Vec<Address>, u32) {
+        let owners = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Owners)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        (owners, env.storage().persistent().get(&DataKey::Threshold).unwrap_or(1))
+    }
+
+    fn check_owner(env: &Env, address: &Address) {
+        if !Self::get_owners(env.clone()).0.contains(address) {
+            panic_with_error!(env, Error::NotAnOwner);
+        }
+    }
+
+    fn set_owners(env: &Env, owners: Vec<Address>, threshold: u32) {
+        if threshold == 0 || threshold > owners.len() {
+            panic_with_error!(env, Error::InvalidThreshold);
+        }
+        env.storage().persistent().set(&DataKey::Owners, &owners);
+        env.storage().persistent().set(&DataKey::Threshold, &threshold);
+
+        env.events().publish((TOPIC_MULTISIG, symbol_short!("owners")), events::OwnersSet { owners, threshold });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::{vec, IntoVal, Map, String};
+    use test_utils::{EventRecord, EventSnapshot};
+
+    struct Setup {
+        env: Env,
+        owners: [Address; 3],
+        scorer: ScorerContractClient<'static>,
+        client: MultisigContractClient<'static>,
+    }
+
+    // A scorer whose creator is a 2-of-3 multisig
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let owners = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+        let client = MultisigContractClient::new(&env, &env.register(MultisigContract, ()));
+        client.initialize(&Vec::from_array(&env, owners.clone()), &2);
+
+        let scorer = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        scorer.initialize(
+            &client.address,
+            &Map::new(&env),
+            &String::from_str(&env, "Community"),
+            &String::from_str(&env, "A community"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        Setup { env, owners, scorer, client }
+    }
+
+    #[test]
+    fn test_propose_approve_execute() {
+        let Setup { env, owners, scorer, client } = setup();
+        let manager = Address::generate(&env);
+        let args = vec![&env, client.address.into_val(&env), manager.into_val(&env)];
+        let id = client.propose(&owners[0], &Action::Call(scorer.address.clone(), Symbol::new(&env, "add_manager"), args));
+
+        assert_eq!(client.try_execute(&id).err(), Some(Ok(Error::NotEnoughApprovals.into())));
+        assert_eq!(client.try_approve(&owners[0], &id), Err(Ok(Error::AlreadyApproved.into())));
+
+        client.approve(&owners[1], &id);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_MULTISIG, symbol_short!("approve")),
+            events::ActionApproved { id, owner: owners[1].clone() },
+        )]);
+
+        // The scorer authorizes the multisig as its invoker, without mocked auths
+        env.set_auths(&[]);
+        client.execute(&id);
+        assert!(scorer.get_managers().contains(&manager));
+        assert_eq!(client.get_proposal(&id), None);
+    }
+
+    #[test]
+    fn test_set_owners() {
+        let Setup { env, owners, client, .. } = setup();
+        let new_owners = Vec::from_array(&env, [owners[0].clone(), Address::generate(&env)]);
+
+        assert_eq!(
+            client.try_propose(&owners[0], &Action::SetOwners(new_owners.clone(), 3)),
+            Err(Ok(Error::InvalidThreshold.into()))
+        );
+        assert_eq!(
+            client.try_propose(&Address::generate(&env), &Action::SetOwners(new_owners.clone(), 2)),
+            Err(Ok(Error::NotAnOwner.into()))
+        );
+
+        let stale = client.propose(&owners[1], &Action::SetOwners(Vec::from_array(&env, [owners[1].clone()]), 1));
+        client.approve(&owners[2], &stale);
+
+        let id = client.propose(&owners[0], &Action::SetOwners(new_owners.clone(), 2));
+        client.approve(&owners[1], &id);
+        client.execute(&id);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_MULTISIG, symbol_short!("owners")),
+            events::OwnersSet { owners: new_owners.clone(), threshold: 2 },
+        )]);
+        assert_eq!(client.get_owners(), (new_owners.clone(), 2));
+
+        // The approvals of removed owners no longer count
+        assert_eq!(client.try_execute(&stale).err(), Some(Ok(Error::NotEnoughApprovals.into())));
+    }
+}
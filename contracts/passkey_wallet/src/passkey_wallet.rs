@@ -0,0 +1,132 @@
+#![no_std]
+use scorer_interface::{passkey, PasskeySignature, PasskeyWalletTrait};
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl, contracttype, crypto::Hash, BytesN, Env, Vec,
+};
+
+#[contracttype]
+enum DataKey {
+    PublicKey,
+    UserVerification,
+}
+
+/// Errors raised by the passkey wallet.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The assertion does not hold the authorization payload, or lacks the
+    /// user present or the required user verified flag.
+    InvalidAssertion = 1,
+}
+
+/// A smart wallet controlled by a WebAuthn passkey.
+///
+/// Users of passkey-based wallets join scorers and claim badges with the
+/// wallet's address. When a contract calls `require_auth` on it, the network
+/// calls `__check_auth`, which checks the passkey's secp256r1 assertion of the
+/// authorization payload, so the wallet can authorize calls and token transfers
+/// like any account. Scorers and issuers also check assertions of their own
+/// challenges against the key the wallet reports, so a relayer can submit them.
+#[contract]
+pub struct PasskeyWalletContract;
+
+#[contractimpl]
+impl PasskeyWalletContract {
+    /// Sets up the wallet when it is deployed
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `public_key` - The SEC-1 encoded uncompressed secp256r1 public key of the passkey
+    /// * `user_verification` - Whether assertions must have the user verified flag
+    pub fn __constructor(env: Env, public_key: BytesN<65>, user_verification: bool) {
+        env.storage().instance().set(&DataKey::PublicKey, &public_key);
+        env.storage().instance().set(&DataKey::UserVerification, &user_verification);
+    }
+}
+
+#[contractimpl]
+impl PasskeyWalletTrait for PasskeyWalletContract {
+    /// Returns the public key of the wallet's passkey
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    fn public_key(env: Env) -> BytesN<65> {
+        env.storage().instance().get(&DataKey::PublicKey).unwrap()
+    }
+
+    /// Returns whether the wallet only accepts assertions with the user verified flag
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    fn user_verification(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::UserVerification).unwrap_or(false)
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for PasskeyWalletContract {
+    type Signature = PasskeySignature;
+    type Error = Error;
+
+    /// Checks the passkey's assertion of the authorization payload
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `signature_payload` - The hash of the authorization payload, used as the WebAuthn challenge
+    /// * `signature` - The WebAuthn assertion of the passkey
+    /// * `auth_contexts` - The calls being authorized, all allowed
+    #[allow(non_snake_case)]
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signature: PasskeySignature,
+        _auth_contexts: Vec<Context>,
+    ) -> Result<(), Error> {
+        let public_key = Self::public_key(env.clone());
+        let user_verification = Self::user_verification(env.clone());
+        if !passkey::verify(&env, &public_key, &signature_payload.to_bytes(), &signature, user_verification) {
+            return Err(Error::InvalidAssertion);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{testutils::BytesN as _, IntoVal};
+    use test_utils::{passkey::USER_PRESENT_AND_VERIFIED, Passkey};
+
+    fn check_auth(env: &Env, wallet: &soroban_sdk::Address, payload: &BytesN<32>, signature: PasskeySignature) -> bool {
+        env.try_invoke_contract_check_auth::<Error>(wallet, payload, signature.into_val(env), &Vec::new(env)).is_ok()
+    }
+
+    #[test]
+    fn test_check_auth() {
+        let env = Env::default();
+        let passkey = Passkey::new(7);
+        let wallet = env.register(PasskeyWalletContract, (passkey.public_key(&env), true));
+        let client = PasskeyWalletContractClient::new(&env, &wallet);
+        assert_eq!(client.public_key(), passkey.public_key(&env));
+        assert!(client.user_verification());
+
+        let payload = BytesN::random(&env);
+        assert!(check_auth(&env, &wallet, &payload, passkey.sign(&env, &payload)));
+        assert!(!check_auth(&env, &wallet, &BytesN::random(&env), passkey.sign(&env, &payload)));
+
+        // The user must be present, and verified since the wallet requires it
+        let client_data = Passkey::client_data_json(&payload);
+        assert!(!check_auth(&env, &wallet, &payload, passkey.sign_assertion(&env, &client_data, passkey::FLAG_USER_VERIFIED)));
+        assert!(!check_auth(&env, &wallet, &payload, passkey.sign_assertion(&env, &client_data, passkey::FLAG_USER_PRESENT)));
+        let relaxed = env.register(PasskeyWalletContract, (passkey.public_key(&env), false));
+        assert!(check_auth(&env, &relaxed, &payload, passkey.sign_assertion(&env, &client_data, passkey::FLAG_USER_PRESENT)));
+
+        // The type and the challenge are read from their place, not found anywhere in the JSON
+        let moved = client_data.replacen(r#""type":"webauthn.get","#, r#""type":"webauthn.create","x":{"type":"webauthn.get"},"#, 1);
+        assert!(!check_auth(&env, &wallet, &payload, passkey.sign_assertion(&env, &moved, USER_PRESENT_AND_VERIFIED)));
+        let extended = client_data.replacen(r#"","origin""#, r#"x","origin""#, 1);
+        assert!(!check_auth(&env, &wallet, &payload, passkey.sign_assertion(&env, &extended, USER_PRESENT_AND_VERIFIED)));
+    }
+}
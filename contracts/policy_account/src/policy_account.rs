@@ -0,0 +1,423 @@
+#![no_std]
+use scorer_interface::events::{self, TOPIC_SIGNERS, TOPIC_POLICY};
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl, contracttype, crypto::Hash, panic_with_error, symbol_short, Bytes, BytesN, Env,
+    Symbol, Vec,
+};
+
+// Length of the windows daily limits are counted over, in seconds
+const DAY: u64 = 86_400;
+
+/// A signature of the authorization payload by one of the signers.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signature {
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+/// Rules applied to the calls of some functions authorized by the account.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Policy {
+    /// The names of the functions the policy applies to, in any contract.
+    pub functions: Vec<Symbol>,
+    /// The number of signers required to authorize a call.
+    pub threshold: u32,
+    /// The number of calls allowed per day, all functions together, or 0 for no limit.
+    pub daily_limit: u32,
+}
+
+#[contracttype]
+enum DataKey {
+    Signers,
+    Threshold,
+    Policies,
+    Policy(Symbol),
+    Usage(Symbol, u64),
+}
+
+/// Errors raised by the policy account.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized. Unused since the signers are set
+    /// by the constructor; kept so the other codes don't change.
+    ContractAlreadyInitialized = 1,
+    /// The contract is not initialized.
+    NotInitialized = 2,
+    /// A threshold is zero or exceeds the number of signers.
+    InvalidThreshold = 3,
+    /// A signature is by a key that is not a signer.
+    UnknownSigner = 4,
+    /// The signatures are not sorted by public key, or a signer signed twice.
+    SignaturesNotSorted = 5,
+    /// Fewer signers signed than the calls require.
+    NotEnoughSigners = 6,
+    /// A policy already allowed its daily number of calls.
+    DailyLimitExceeded = 7,
+    /// No policy with this name exists.
+    PolicyNotFound = 8,
+}
+
+/// A multisig account enforcing policies at the authorization layer.
+///
+/// Owners set the account's address as the scorer creator, or as a manager,
+/// and the scorer's `require_auth` calls are checked here. Every call needs
+/// `threshold` ed25519 signers; policies raise the threshold of some functions,
+/// such as `upgrade`, and cap how many times per day they can be called, such
+/// as `add_manager` and `remove_manager`. The account administers itself:
+/// changing its signers or policies is authorized, and policed, like any other
+/// call.
+#[contract]
+pub struct PolicyAccountContract;
+
+#[contractimpl]
+impl PolicyAccountContract {
+    /// Sets up the account when it is deployed
+    ///
+    /// The signers are set by the deployment itself, so nobody can take over
+    /// the account's address by initializing it first.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `signers` - The ed25519 public keys allowed to sign
+    /// * `threshold` - The number of signers required by calls without a stricter policy
+    ///
+    /// # Panics
+    /// * When the threshold is zero or exceeds the number of signers (`Error::InvalidThreshold`)
+    pub fn __constructor(env: Env, signers: Vec<BytesN<32>>, threshold: u32) {
+        Self::store_signers(&env, signers, threshold);
+    }
+
+    /// Replaces the signers and the default threshold
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `signers` - The ed25519 public keys allowed to sign
+    /// * `threshold` - The number of signers required by calls without a stricter policy
+    ///
+    /// # Panics
+    /// * When the account fails authentication
+    /// * When the threshold, or the threshold of a policy, is zero or exceeds the number
+    ///   of signers (`Error::InvalidThreshold`)
+    pub fn set_signers(env: Env, signers: Vec<BytesN<32>>, threshold: u32) {
+        env.current_contract_address().require_auth();
+
+        for name in Self::get_policies(env.clone()).iter() {
+            if Self::policy(&env, &name).threshold > signers.len() {
+                panic_with_error!(&env, Error::InvalidThreshold);
+            }
+        }
+        Self::store_signers(&env, signers, threshold);
+    }
+
+    /// Returns the signers and the default threshold
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    pub fn get_signers(env: Env) -> (Vec<BytesN<32>>, u32) {
+        let signers = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Signers)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        (signers, env.storage().persistent().get(&DataKey::Threshold).unwrap_or(1))
+    }
+
+    /// Adds or replaces a policy
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `name` - The name of the policy, which counts its daily calls
+    /// * `policy` - The functions the policy applies to and its rules
+    ///
+    /// # Panics
+    /// * When the account fails authentication
+    /// * When the threshold is zero or exceeds the number of signers (`Error::InvalidThreshold`)
+    pub fn set_policy(env: Env, name: Symbol, policy: Policy) {
+        env.current_contract_address().require_auth();
+
+        let (signers, _) = Self::get_signers(env.clone());
+        if policy.threshold == 0 || policy.threshold > signers.len() {
+            panic_with_error!(&env, Error::InvalidThreshold);
+        }
+
+        let mut policies = Self::get_policies(env.clone());
+        if !policies.contains(&name) {
+            policies.push_back(name.clone());
+            env.storage().persistent().set(&DataKey::Policies, &policies);
+        }
+        env.storage().persistent().set(&DataKey::Policy(name.clone()), &policy);
+
+        env.events().publish(
+            (TOPIC_POLICY, symbol_short!("set")),
+            events::PolicySet {
+                name,
+                functions: policy.functions,
+                threshold: policy.threshold,
+                daily_limit: policy.daily_limit,
+            },
+        );
+    }
+
+    /// Removes a policy
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `name` - The name of the policy
+    ///
+    /// # Panics
+    /// * When the account fails authentication
+    /// * When no policy has this name (`Error::PolicyNotFound`)
+    pub fn remove_policy(env: Env, name: Symbol) {
+        env.current_contract_address().require_auth();
+
+        let mut policies = Self::get_policies(env.clone());
+        let index = policies
+            .first_index_of(&name)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::PolicyNotFound));
+        policies.remove(index);
+        env.storage().persistent().set(&DataKey::Policies, &policies);
+        env.storage().persistent().remove(&DataKey::Policy(name.clone()));
+
+        env.events().publish((TOPIC_POLICY, symbol_short!("remove")), events::PolicyRemoved { name });
+    }
+
+    /// Returns the names of the policies
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    pub fn get_policies(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Policies)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns a policy, if any
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `name` - The name of the policy
+    pub fn get_policy(env: Env, name: Symbol) -> Option<Policy> {
+        env.storage().persistent().get(&DataKey::Policy(name))
+    }
+
+    /// Returns the number of calls a policy allowed today
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `name` - The name of the policy
+    pub fn get_usage(env: Env, name: Symbol) -> u32 {
+        let day = env.ledger().timestamp() / DAY;
+        env.storage().temporary().get(&DataKey::Usage(name, day)).unwrap_or(0)
+    }
+
+    fn store_signers(env: &Env, signers: Vec<BytesN<32>>, threshold: u32) {
+        if threshold == 0 || threshold > signers.len() {
+            panic_with_error!(env, Error::InvalidThreshold);
+        }
+        env.storage().persistent().set(&DataKey::Signers, &signers);
+        env.storage().persistent().set(&DataKey::Threshold, &threshold);
+
+        env.events().publish((TOPIC_SIGNERS, symbol_short!("set")), events::SignersSet { signers, threshold });
+    }
+
+    fn policy(env: &Env, name: &Symbol) -> Policy {
+        Self::get_policy(env.clone(), name.clone()).unwrap_or_else(|| panic_with_error!(env, Error::PolicyNotFound))
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for PolicyAccountContract {
+    type Signature = Vec<Signature>;
+    type Error = Error;
+
+    /// Checks the signatures against the signers, and the authorized calls against the policies
+    ///
+    /// Each call authorized counts towards the daily limit of the policies it falls under.
+    /// Usage is kept in temporary storage, which expires on its own once the day is over.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `signature_payload` - The hash of the authorization payload
+    /// * `signatures` - The signatures of the payload, sorted by public key
+    /// * `auth_contexts` - The calls being authorized
+    #[allow(non_snake_case)]
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signatures: Vec<Signature>,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), Error> {
+        let (signers, mut required) = Self::get_signers(env.clone());
+
+        let payload = Bytes::from_array(&env, &signature_payload.to_array());
+        let mut previous: Option<BytesN<32>> = None;
+        for signature in signatures.iter() {
+            if previous.is_some_and(|previous| previous >= signature.public_key) {
+                return Err(Error::SignaturesNotSorted);
+            }
+            if !signers.contains(&signature.public_key) {
+                return Err(Error::UnknownSigner);
+            }
+            env.crypto().ed25519_verify(&signature.public_key, &payload, &signature.signature);
+            previous = Some(signature.public_key);
+        }
+
+        let day = env.ledger().timestamp() / DAY;
+        for context in auth_contexts.iter() {
+            let Context::Contract(call) = context else {
+                continue;
+            };
+            for name in Self::get_policies(env.clone()).iter() {
+                let policy = Self::policy(&env, &name);
+                if !policy.functions.contains(&call.fn_name) {
+                    continue;
+                }
+                required = required.max(policy.threshold);
+                if policy.daily_limit > 0 {
+                    let key = DataKey::Usage(name, day);
+                    let usage: u32 = env.storage().temporary().get(&key).unwrap_or(0);
+                    if usage >= policy.daily_limit {
+                        return Err(Error::DailyLimitExceeded);
+                    }
+                    env.storage().temporary().set(&key, &(usage + 1));
+                }
+            }
+        }
+
+        if signatures.len() < required {
+            return Err(Error::NotEnoughSigners);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::auth::ContractContext;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{vec, Address, IntoVal};
+    use test_utils::{EventRecord, EventSnapshot};
+
+    struct Setup {
+        env: Env,
+        keys: [SigningKey; 3],
+        client: PolicyAccountContractClient<'static>,
+    }
+
+    // An account of three signers, any one of which authorizes calls without a policy;
+    // the keys are sorted by public key
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let mut keys = [SigningKey::from_bytes(&[1; 32]), SigningKey::from_bytes(&[2; 32]), SigningKey::from_bytes(&[3; 32])];
+        keys.sort_by_key(|key| key.verifying_key().to_bytes());
+        let signers = Vec::from_array(&env, keys.clone().map(|key| public_key(&env, &key)));
+
+        let client = PolicyAccountContractClient::new(&env, &env.register(PolicyAccountContract, (signers, 1_u32)));
+
+        Setup { env, keys, client }
+    }
+
+    fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+        BytesN::from_array(env, &key.verifying_key().to_bytes())
+    }
+
+    fn sign(env: &Env, payload: &BytesN<32>, keys: &[&SigningKey]) -> Vec<Signature> {
+        let mut signatures = Vec::new(env);
+        for key in keys {
+            signatures.push_back(Signature {
+                public_key: public_key(env, key),
+                signature: BytesN::from_array(env, &key.sign(&payload.to_array()).to_bytes()),
+            });
+        }
+        signatures
+    }
+
+    fn call(env: &Env, fn_name: &str) -> Vec<Context> {
+        vec![
+            env,
+            Context::Contract(ContractContext {
+                contract: Address::generate(env),
+                fn_name: Symbol::new(env, fn_name),
+                args: vec![env],
+            }),
+        ]
+    }
+
+    fn check_auth(client: &PolicyAccountContractClient, keys: &[&SigningKey], contexts: &Vec<Context>) -> Result<(), Error> {
+        let env = &client.env;
+        let payload = BytesN::from_array(env, &[9; 32]);
+        env.try_invoke_contract_check_auth::<Error>(&client.address, &payload, sign(env, &payload, keys).into_val(env), contexts)
+            .map_err(|error| error.unwrap())
+    }
+
+    #[test]
+    fn test_default_threshold() {
+        let Setup { env, keys, client } = setup();
+
+        assert_eq!(check_auth(&client, &[&keys[0]], &call(&env, "add_badge")), Ok(()));
+        assert_eq!(check_auth(&client, &[], &call(&env, "add_badge")), Err(Error::NotEnoughSigners));
+        assert_eq!(check_auth(&client, &[&keys[1], &keys[0]], &call(&env, "add_badge")), Err(Error::SignaturesNotSorted));
+        assert_eq!(check_auth(&client, &[&keys[0], &keys[0]], &call(&env, "add_badge")), Err(Error::SignaturesNotSorted));
+
+        let stranger = SigningKey::from_bytes(&[4; 32]);
+        assert_eq!(check_auth(&client, &[&stranger], &call(&env, "add_badge")), Err(Error::UnknownSigner));
+    }
+
+    #[test]
+    fn test_policy_threshold() {
+        let Setup { env, keys, client } = setup();
+        let upgrades = Policy { functions: vec![&env, symbol_short!("upgrade")], threshold: 2, daily_limit: 0 };
+
+        client.set_policy(&symbol_short!("upgrades"), &upgrades);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_POLICY, symbol_short!("set")),
+            events::PolicySet { name: symbol_short!("upgrades"), functions: upgrades.functions.clone(), threshold: 2, daily_limit: 0 },
+        )]);
+        assert_eq!(client.get_policy(&symbol_short!("upgrades")), Some(upgrades.clone()));
+
+        assert_eq!(check_auth(&client, &[&keys[0]], &call(&env, "upgrade")), Err(Error::NotEnoughSigners));
+        assert_eq!(check_auth(&client, &[&keys[0], &keys[2]], &call(&env, "upgrade")), Ok(()));
+        assert_eq!(check_auth(&client, &[&keys[0]], &call(&env, "add_badge")), Ok(()));
+
+        // The threshold cannot exceed the signers
+        let too_strict = Policy { functions: vec![&env, symbol_short!("upgrade")], threshold: 4, daily_limit: 0 };
+        assert_eq!(client.try_set_policy(&symbol_short!("upgrades"), &too_strict), Err(Ok(Error::InvalidThreshold.into())));
+        let signers = Vec::from_array(&env, [public_key(&env, &keys[0])]);
+        assert_eq!(client.try_set_signers(&signers, &1), Err(Ok(Error::InvalidThreshold.into())));
+
+        client.remove_policy(&symbol_short!("upgrades"));
+        assert_eq!(check_auth(&client, &[&keys[0]], &call(&env, "upgrade")), Ok(()));
+        assert_eq!(client.try_remove_policy(&symbol_short!("upgrades")), Err(Ok(Error::PolicyNotFound.into())));
+    }
+
+    #[test]
+    fn test_policy_daily_limit() {
+        let Setup { env, keys, client } = setup();
+        let functions = vec![&env, Symbol::new(&env, "add_manager"), Symbol::new(&env, "remove_manager")];
+        client.set_policy(&symbol_short!("managers"), &Policy { functions, threshold: 1, daily_limit: 2 });
+
+        assert_eq!(check_auth(&client, &[&keys[0]], &call(&env, "add_manager")), Ok(()));
+        assert_eq!(check_auth(&client, &[&keys[0]], &call(&env, "remove_manager")), Ok(()));
+        assert_eq!(client.get_usage(&symbol_short!("managers")), 2);
+        assert_eq!(check_auth(&client, &[&keys[0]], &call(&env, "add_manager")), Err(Error::DailyLimitExceeded));
+
+        env.ledger().with_mut(|ledger| ledger.timestamp += DAY);
+        assert_eq!(client.get_usage(&symbol_short!("managers")), 0);
+        assert_eq!(check_auth(&client, &[&keys[0]], &call(&env, "add_manager")), Ok(()));
+    }
+}
@@ -0,0 +1,320 @@
+#![no_std]
+use scorer_interface::{events, PriceAdapterTrait};
+use scorer_interface::events::{TOPIC_ORACLE, TOPIC_FEE};
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token, Address, Env, Symbol,
+};
+
+// Decimals of USD amounts, matching Stellar asset amounts
+const USD_DECIMALS: u32 = 7;
+
+/// An asset priced by the oracle, as defined by SEP-40.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// A price reported by the oracle, as defined by SEP-40.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// The part of the SEP-40 price feed interface the adapter uses, implemented
+/// by oracles such as Reflector quoting prices in USD.
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    /// Returns the number of decimals of the prices
+    fn decimals(env: Env) -> u32;
+
+    /// Returns the most recent price of `asset`, if any
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
+
+#[contracttype]
+enum DataKey {
+    Admin,
+    Oracle,
+    MaxAge,
+}
+
+/// Errors raised by the price adapter.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    /// The contract is not initialized.
+    NotInitialized = 2,
+    /// The oracle has no price for the asset.
+    PriceUnavailable = 3,
+    /// The oracle price is older than the maximum age.
+    StalePrice = 4,
+    /// The USD amount is negative.
+    InvalidAmount = 5,
+}
+
+/// Converts fees denominated in USD to the asset they are paid in.
+///
+/// Contracts charging fees, such as join or creation fees, store them in USD
+/// and call `quote` or `pay` with the asset the payer uses; the adapter prices
+/// it with a SEP-40 oracle at call time. USD amounts have 7 decimals, and the
+/// converted amount is rounded up so fees are never underpaid.
+#[contract]
+pub struct PriceAdapterContract;
+
+#[contractimpl]
+impl PriceAdapterContract {
+    /// Initializes the adapter
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `admin` - The address allowed to change the oracle
+    /// * `oracle` - A SEP-40 oracle quoting prices in USD
+    /// * `max_age` - The maximum age of a price, in seconds
+    ///
+    /// # Panics
+    /// * When the contract is already initialized (`Error::ContractAlreadyInitialized`)
+    pub fn initialize(env: Env, admin: Address, oracle: Address, max_age: u64) {
+        if env.storage().persistent().has(&DataKey::Admin) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+        env.storage().persistent().set(&DataKey::Oracle, &oracle);
+        env.storage().persistent().set(&DataKey::MaxAge, &max_age);
+    }
+
+    /// Changes the oracle and the maximum age of its prices
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `oracle` - A SEP-40 oracle quoting prices in USD
+    /// * `max_age` - The maximum age of a price, in seconds
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    /// * When the admin fails authentication
+    pub fn set_oracle(env: Env, oracle: Address, max_age: u64) {
+        let admin = Self::get_admin(env.clone());
+        admin.require_auth();
+
+        env.storage().persistent().set(&DataKey::Oracle, &oracle);
+        env.storage().persistent().set(&DataKey::MaxAge, &max_age);
+
+        env.events().publish((TOPIC_ORACLE, symbol_short!("set")), events::PriceOracleSet { admin, oracle, max_age });
+    }
+
+    /// Returns the admin
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
+    }
+
+    /// Returns the oracle and the maximum age of its prices, in seconds
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    pub fn get_oracle(env: Env) -> (Address, u64) {
+        let oracle = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Oracle)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized));
+        (oracle, env.storage().persistent().get(&DataKey::MaxAge).unwrap_or(0))
+    }
+}
+
+#[contractimpl]
+impl PriceAdapterTrait for PriceAdapterContract {
+    /// Returns the amount of an asset worth a USD amount at the current oracle price
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `asset` - The token the fee is paid in
+    /// * `usd_amount` - The fee, in units of 10^-7 USD
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    /// * When the USD amount is negative (`Error::InvalidAmount`)
+    /// * When the oracle has no positive price for the asset (`Error::PriceUnavailable`)
+    /// * When the price is older than the maximum age (`Error::StalePrice`)
+    fn quote(env: Env, asset: Address, usd_amount: i128) -> i128 {
+        if usd_amount < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+
+        let (oracle, max_age) = Self::get_oracle(env.clone());
+        let oracle = PriceOracleClient::new(&env, &oracle);
+        let price = match oracle.lastprice(&Asset::Stellar(asset.clone())) {
+            Some(price) if price.price > 0 => price,
+            _ => panic_with_error!(&env, Error::PriceUnavailable),
+        };
+        if price.timestamp.saturating_add(max_age) < env.ledger().timestamp() {
+            panic_with_error!(&env, Error::StalePrice);
+        }
+
+        // usd_amount / 10^7 USD at price / 10^oracle_decimals USD per whole token
+        let asset_decimals = token::Client::new(&env, &asset).decimals();
+        let numerator = usd_amount * 10i128.pow(oracle.decimals()) * 10i128.pow(asset_decimals);
+        let denominator = price.price * 10i128.pow(USD_DECIMALS);
+        (numerator + denominator - 1) / denominator
+    }
+
+    /// Transfers the amount of an asset worth a USD amount from a payer to a recipient
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `payer` - The address paying the fee
+    /// * `asset` - The token the fee is paid in
+    /// * `usd_amount` - The fee, in units of 10^-7 USD
+    /// * `recipient` - The address receiving the fee
+    ///
+    /// # Returns
+    /// * `i128` - The amount of the asset transferred
+    ///
+    /// # Panics
+    /// * When the payer fails authentication
+    /// * When the fee cannot be quoted (see `quote`)
+    fn pay(env: Env, payer: Address, asset: Address, usd_amount: i128, recipient: Address) -> i128 {
+        payer.require_auth();
+
+        let amount = Self::quote(env.clone(), asset.clone(), usd_amount);
+        token::Client::new(&env, &asset).transfer(&payer, &recipient, &amount);
+
+        env.events().publish(
+            (TOPIC_FEE, symbol_short!("pay")),
+            events::FeePaid { payer, asset, usd_amount, amount, recipient },
+        );
+        amount
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use test_utils::{EventRecord, EventSnapshot};
+
+    mod mock_oracle {
+        use super::super::{Asset, PriceData};
+        use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+        // Quotes every asset at the stored price, with 14 decimals like Reflector
+        #[contract]
+        pub struct MockOracle;
+
+        #[contractimpl]
+        impl MockOracle {
+            pub fn set_price(env: Env, price: PriceData) {
+                env.storage().instance().set(&symbol_short!("price"), &price);
+            }
+
+            pub fn decimals(_env: Env) -> u32 {
+                14
+            }
+
+            pub fn lastprice(env: Env, _asset: Asset) -> Option<PriceData> {
+                env.storage().instance().get(&symbol_short!("price"))
+            }
+        }
+    }
+
+    struct Setup {
+        env: Env,
+        admin: Address,
+        asset: Address,
+        oracle: mock_oracle::MockOracleClient<'static>,
+        client: PriceAdapterContractClient<'static>,
+    }
+
+    // An adapter accepting prices up to an hour old, and a Stellar asset worth 0.25 USD
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|ledger| ledger.timestamp = 10_000);
+
+        let oracle = mock_oracle::MockOracleClient::new(&env, &env.register(mock_oracle::MockOracle, ()));
+        oracle.set_price(&PriceData { price: 25_000_000_000_000, timestamp: 10_000 });
+
+        let admin = Address::generate(&env);
+        let client = PriceAdapterContractClient::new(&env, &env.register(PriceAdapterContract, ()));
+        client.initialize(&admin, &oracle.address, &3600);
+
+        let asset = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+        Setup { env, admin, asset, oracle, client }
+    }
+
+    #[test]
+    fn test_quote() {
+        let Setup { asset, client, .. } = setup();
+
+        // 5 USD at 0.25 USD per token is 20 tokens of 7 decimals
+        assert_eq!(client.quote(&asset, &50_000_000), 200_000_000);
+        assert_eq!(client.quote(&asset, &0), 0);
+        // Rounded up
+        assert_eq!(client.quote(&asset, &1), 4);
+    }
+
+    #[test]
+    fn test_pay() {
+        let Setup { env, asset, client, .. } = setup();
+        let payer = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &asset).mint(&payer, &1_000_000_000);
+
+        assert_eq!(client.pay(&payer, &asset, &50_000_000, &recipient), 200_000_000);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_FEE, symbol_short!("pay")),
+            events::FeePaid { payer, asset: asset.clone(), usd_amount: 50_000_000, amount: 200_000_000, recipient: recipient.clone() },
+        )]);
+        assert_eq!(token::Client::new(&env, &asset).balance(&recipient), 200_000_000);
+    }
+
+    #[test]
+    fn test_quote_without_valid_price() {
+        let Setup { env, asset, oracle, client, .. } = setup();
+
+        assert_eq!(client.try_quote(&asset, &-1), Err(Ok(Error::InvalidAmount.into())));
+
+        env.ledger().with_mut(|ledger| ledger.timestamp = 13_601);
+        assert_eq!(client.try_quote(&asset, &1), Err(Ok(Error::StalePrice.into())));
+
+        oracle.set_price(&PriceData { price: 0, timestamp: 13_601 });
+        assert_eq!(client.try_quote(&asset, &1), Err(Ok(Error::PriceUnavailable.into())));
+    }
+
+    #[test]
+    fn test_set_oracle() {
+        let Setup { env, admin, client, .. } = setup();
+        let oracle = Address::generate(&env);
+
+        client.set_oracle(&oracle, &60);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_ORACLE, symbol_short!("set")),
+            events::PriceOracleSet { admin, oracle: oracle.clone(), max_age: 60 },
+        )]);
+        assert_eq!(client.get_oracle(), (oracle.clone(), 60));
+    }
+}
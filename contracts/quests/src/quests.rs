@@ -0,0 +1,401 @@
+#![no_std]
+use scorer_interface::{events, BadgeId, ScorerClient, UserStatus};
+use scorer_interface::events::{TOPIC_QUEST, TOPIC_BADGE};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token, vec, Address, Env, IntoVal, String, Symbol, Vec};
+
+/// A condition a user must meet to complete a quest.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QuestCondition {
+    /// Holds at least the amount of the token.
+    TokenBalance(Address, i128),
+    /// Joined the scorer before the ledger. Members who joined before the
+    /// scorer recorded join ledgers count as having joined at ledger 0.
+    JoinedBefore(u32),
+    /// Holds the badge, as reported by the `has_badge` of its issuer, and the
+    /// scorer has not revoked its attestation.
+    HoldsBadge(BadgeId),
+}
+
+#[contracttype]
+enum DataKey {
+    Scorer,
+    Quest(String),
+    Completed(Address, String),
+}
+
+/// Errors raised by the quests contract.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    /// The scorer is missing from storage.
+    ScorerNotFound = 2,
+    /// The sender is not a manager of the scorer.
+    Unauthorized = 3,
+    /// The quest name is empty.
+    EmptyArg = 4,
+    /// No quest with this name exists.
+    QuestNotFound = 5,
+    /// The user already completed the quest.
+    QuestAlreadyCompleted = 6,
+    /// The scorer has no badge for the quest issued by this contract.
+    RewardBadgeNotFound = 7,
+    /// The user is not an active member of the scorer.
+    NotAMember = 8,
+    /// The user does not meet one of the quest conditions.
+    ConditionNotMet = 9,
+}
+
+/// Quests granting badges of a scorer when users meet on-chain conditions.
+///
+/// Each quest rewards the badge named after it and issued by this contract,
+/// which the scorer managers add to the scorer. Users complete quests
+/// themselves: the contract verifies the conditions and issues the badge, which
+/// it then reports through `has_badge` like any other issuer.
+#[contract]
+pub struct QuestsContract;
+
+#[contractimpl]
+impl QuestsContract {
+    /// Initializes the contract for a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer whose managers define the quests and whose badges reward them
+    ///
+    /// # Panics
+    /// * When the scorer owner fails authentication
+    /// * When the contract is already initialized (`Error::ContractAlreadyInitialized`)
+    pub fn initialize(env: Env, scorer: Address) {
+        if env.storage().persistent().has(&DataKey::Scorer) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        // Only the scorer owner can attach quests to its scorer
+        ScorerClient::new(&env, &scorer).get_contract_owner().require_auth();
+
+        env.storage().persistent().set(&DataKey::Scorer, &scorer);
+    }
+
+    /// Returns the scorer of the quests
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::ScorerNotFound`)
+    pub fn get_scorer(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scorer)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    /// Adds a quest, or replaces the conditions of an existing one
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `manager` - A manager of the scorer
+    /// * `quest` - The quest name, which is also the name of its reward badge
+    /// * `conditions` - The conditions users must all meet
+    ///
+    /// # Panics
+    /// * When the manager fails authentication or is not a manager of the scorer (`Error::Unauthorized`)
+    /// * When the quest name is empty (`Error::EmptyArg`)
+    pub fn set_quest(env: Env, manager: Address, quest: String, conditions: Vec<QuestCondition>) {
+        Self::require_manager(&env, &manager);
+
+        if quest.is_empty() {
+            panic_with_error!(&env, Error::EmptyArg);
+        }
+
+        env.storage().persistent().set(&DataKey::Quest(quest.clone()), &conditions);
+
+        env.events().publish((TOPIC_QUEST, symbol_short!("add")), events::QuestSet { manager, quest });
+    }
+
+    /// Removes a quest. Badges already granted by it are kept
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `manager` - A manager of the scorer
+    /// * `quest` - The quest name
+    ///
+    /// # Panics
+    /// * When the manager fails authentication or is not a manager of the scorer (`Error::Unauthorized`)
+    /// * When the quest does not exist (`Error::QuestNotFound`)
+    pub fn remove_quest(env: Env, manager: Address, quest: String) {
+        Self::require_manager(&env, &manager);
+
+        let key = DataKey::Quest(quest.clone());
+        if !env.storage().persistent().has(&key) {
+            panic_with_error!(&env, Error::QuestNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events().publish((TOPIC_QUEST, symbol_short!("remove")), events::QuestRemoved { manager, quest });
+    }
+
+    /// Returns the conditions of a quest, if it exists
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `quest` - The quest name
+    pub fn get_quest(env: Env, quest: String) -> Option<Vec<QuestCondition>> {
+        env.storage().persistent().get(&DataKey::Quest(quest))
+    }
+
+    /// Completes a quest, granting its reward badge to the user
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The address completing the quest
+    /// * `quest` - The quest name
+    ///
+    /// # Panics
+    /// * When the user fails authentication
+    /// * When the quest does not exist (`Error::QuestNotFound`)
+    /// * When the user already completed the quest (`Error::QuestAlreadyCompleted`)
+    /// * When the scorer has no reward badge for the quest (`Error::RewardBadgeNotFound`)
+    /// * When the user is not an active member of the scorer (`Error::NotAMember`)
+    /// * When the user does not meet a condition (`Error::ConditionNotMet`)
+    pub fn complete_quest(env: Env, user: Address, quest: String) {
+        user.require_auth();
+
+        let conditions = Self::get_quest(env.clone(), quest.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::QuestNotFound));
+
+        if Self::has_badge(env.clone(), user.clone(), quest.clone()) {
+            panic_with_error!(&env, Error::QuestAlreadyCompleted);
+        }
+
+        let scorer = ScorerClient::new(&env, &Self::get_scorer(env.clone()));
+        let reward = BadgeId { name: quest.clone(), issuer: env.current_contract_address() };
+        if !scorer.get_badges().contains_key(reward) {
+            panic_with_error!(&env, Error::RewardBadgeNotFound);
+        }
+
+        if scorer.get_user_status(&user) != Some(UserStatus::Active) {
+            panic_with_error!(&env, Error::NotAMember);
+        }
+
+        for condition in conditions.iter() {
+            if !Self::meets(&env, &scorer, &user, condition) {
+                panic_with_error!(&env, Error::ConditionNotMet);
+            }
+        }
+
+        env.storage().persistent().set(&DataKey::Completed(user.clone(), quest.clone()), &true);
+
+        env.events().publish((TOPIC_BADGE, symbol_short!("issue")), events::BadgeIssued { user, badge: quest });
+    }
+
+    /// Returns whether a user completed a quest, and so holds its reward badge
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The address to check
+    /// * `badge` - The quest name
+    pub fn has_badge(env: Env, user: Address, badge: String) -> bool {
+        env.storage().persistent().has(&DataKey::Completed(user, badge))
+    }
+
+    fn require_manager(env: &Env, manager: &Address) {
+        manager.require_auth();
+
+        let scorer = Self::get_scorer(env.clone());
+        if !ScorerClient::new(env, &scorer).get_managers().contains(manager) {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    fn meets(env: &Env, scorer: &ScorerClient, user: &Address, condition: QuestCondition) -> bool {
+        match condition {
+            QuestCondition::TokenBalance(token, amount) => token::Client::new(env, &token).balance(user) >= amount,
+            QuestCondition::JoinedBefore(ledger) => scorer.get_joined_at(user).unwrap_or(0) < ledger,
+            QuestCondition::HoldsBadge(badge) => {
+                // Badges of other quests are checked here, as a contract cannot call itself
+                let held = if badge.issuer == env.current_contract_address() {
+                    Self::has_badge(env.clone(), user.clone(), badge.name.clone())
+                } else {
+                    env.invoke_contract(
+                        &badge.issuer,
+                        &Symbol::new(env, "has_badge"),
+                        vec![env, user.into_val(env), badge.name.into_val(env)],
+                    )
+                };
+                held && !scorer.is_attestation_revoked(&badge, user)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mock_issuer::{MockIssuerContract, MockIssuerContractClient};
+    use scorer::{ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{BytesN, Map};
+    use test_utils::{EventRecord, EventSnapshot};
+
+    struct Setup {
+        env: Env,
+        manager: Address,
+        user: Address,
+        scorer: ScorerContractClient<'static>,
+        client: QuestsContractClient<'static>,
+    }
+
+    // A scorer with an active user, and a quests contract whose "Onboarding"
+    // reward badge is part of the scorer
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let manager = Address::generate(&env);
+        let scorer = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        scorer.initialize(
+            &manager,
+            &Map::new(&env),
+            &String::from_str(&env, "Community"),
+            &String::from_str(&env, "A community"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        let client = QuestsContractClient::new(&env, &env.register(QuestsContract, ()));
+        client.initialize(&scorer.address);
+        scorer.add_badge(&manager, &String::from_str(&env, "Onboarding"), &client.address, &50);
+
+        let user = Address::generate(&env);
+        scorer.add_user(&user);
+
+        Setup { env, manager, user, scorer, client }
+    }
+
+    #[test]
+    fn test_complete_quest() {
+        let Setup { env, manager, user, scorer, client } = setup();
+        let quest = String::from_str(&env, "Onboarding");
+
+        let token_admin = Address::generate(&env);
+        let token = env.register_stellar_asset_contract_v2(token_admin).address();
+        token::StellarAssetClient::new(&env, &token).mint(&user, &100);
+
+        let issuer = MockIssuerContractClient::new(&env, &env.register(MockIssuerContract, (&manager, BytesN::from_array(&env, &[0; 32]))));
+        issuer.issue(&user, &String::from_str(&env, "Speaker"));
+        let speaker = BadgeId { name: String::from_str(&env, "Speaker"), issuer: issuer.address.clone() };
+
+        let conditions = Vec::from_array(&env, [
+            QuestCondition::TokenBalance(token, 100),
+            QuestCondition::JoinedBefore(env.ledger().sequence() + 1),
+            QuestCondition::HoldsBadge(speaker),
+        ]);
+        client.set_quest(&manager, &quest, &conditions);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_QUEST, symbol_short!("add")),
+                events::QuestSet { manager, quest: quest.clone() },
+            ),
+        ]);
+        assert_eq!(client.get_quest(&quest), Some(conditions));
+
+        client.complete_quest(&user, &quest);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_BADGE, symbol_short!("issue")),
+                events::BadgeIssued { user: user.clone(), badge: quest.clone() },
+            ),
+        ]);
+        assert!(client.has_badge(&user, &quest));
+        let reward = BadgeId { name: quest.clone(), issuer: client.address.clone() };
+        assert_eq!(scorer.get_score(&user, &Vec::from_array(&env, [reward])), 50);
+
+        assert_eq!(client.try_complete_quest(&user, &quest), Err(Ok(Error::QuestAlreadyCompleted.into())));
+    }
+
+    #[test]
+    fn test_complete_quest_with_unmet_conditions() {
+        let Setup { env, manager, user, client, .. } = setup();
+        let quest = String::from_str(&env, "Onboarding");
+
+        let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        client.set_quest(&manager, &quest, &Vec::from_array(&env, [QuestCondition::TokenBalance(token, 1)]));
+        assert_eq!(client.try_complete_quest(&user, &quest), Err(Ok(Error::ConditionNotMet.into())));
+
+        let joined_at = env.ledger().sequence();
+        client.set_quest(&manager, &quest, &Vec::from_array(&env, [QuestCondition::JoinedBefore(joined_at)]));
+        assert_eq!(client.try_complete_quest(&user, &quest), Err(Ok(Error::ConditionNotMet.into())));
+
+        // Another quest's badge, which the user has not earned yet
+        let first_steps = BadgeId { name: String::from_str(&env, "First steps"), issuer: client.address.clone() };
+        client.set_quest(&manager, &quest, &Vec::from_array(&env, [QuestCondition::HoldsBadge(first_steps)]));
+        assert_eq!(client.try_complete_quest(&user, &quest), Err(Ok(Error::ConditionNotMet.into())));
+
+        assert!(!client.has_badge(&user, &quest));
+    }
+
+    #[test]
+    fn test_complete_chained_quests() {
+        let Setup { env, manager, user, scorer, client } = setup();
+        let first_steps = String::from_str(&env, "First steps");
+        let onboarding = String::from_str(&env, "Onboarding");
+        scorer.add_badge(&manager, &first_steps, &client.address, &10);
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 10);
+        client.set_quest(&manager, &first_steps, &Vec::from_array(&env, [QuestCondition::JoinedBefore(env.ledger().sequence())]));
+        let first_steps_badge = BadgeId { name: first_steps.clone(), issuer: client.address.clone() };
+        client.set_quest(&manager, &onboarding, &Vec::from_array(&env, [QuestCondition::HoldsBadge(first_steps_badge)]));
+
+        client.complete_quest(&user, &first_steps);
+        client.complete_quest(&user, &onboarding);
+        assert!(client.has_badge(&user, &onboarding));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #8)")]
+    fn test_complete_quest_by_non_member() {
+        let Setup { env, manager, client, .. } = setup();
+        let quest = String::from_str(&env, "Onboarding");
+
+        client.set_quest(&manager, &quest, &Vec::new(&env));
+        client.complete_quest(&Address::generate(&env), &quest);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #7)")]
+    fn test_complete_quest_without_reward_badge() {
+        let Setup { env, manager, user, client, .. } = setup();
+        let quest = String::from_str(&env, "Unlisted");
+
+        client.set_quest(&manager, &quest, &Vec::new(&env));
+        client.complete_quest(&user, &quest);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_set_quest_unauthorized() {
+        let Setup { env, user, client, .. } = setup();
+
+        client.set_quest(&user, &String::from_str(&env, "Onboarding"), &Vec::new(&env));
+    }
+
+    #[test]
+    fn test_remove_quest() {
+        let Setup { env, manager, user, client, .. } = setup();
+        let quest = String::from_str(&env, "Onboarding");
+
+        client.set_quest(&manager, &quest, &Vec::new(&env));
+        client.remove_quest(&manager, &quest);
+        assert_eq!(client.get_quest(&quest), None);
+        assert_eq!(client.try_complete_quest(&user, &quest), Err(Ok(Error::QuestNotFound.into())));
+        assert_eq!(client.try_remove_quest(&manager, &quest), Err(Ok(Error::QuestNotFound.into())));
+    }
+}
@@ -0,0 +1,696 @@
+#![no_std]
+use scorer_interface::{events, ScorerClient};
+use scorer_interface::events::{TOPIC_EPOCH, TOPIC_REWARD};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token, Address, Env, Map, Vec};
+
+/// A reward epoch of a scorer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Epoch {
+    /// The token the rewards are paid in.
+    pub token: Address,
+    /// The amount funded for the epoch.
+    pub pool: i128,
+    /// The sum of the snapshot scores.
+    pub total_score: u64,
+    /// Whether the epoch is closed to scores and funding, and open to claims.
+    pub finalized: bool,
+}
+
+/// A reward set aside for a user, who can claim it until it expires, like a
+/// Stellar claimable balance. The sponsor can take it back once expired.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingReward {
+    pub scorer: Address,
+    pub user: Address,
+    /// The manager who allocated the reward and paid for it.
+    pub sponsor: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// The ledger sequence from which the reward can no longer be claimed.
+    pub expires_at: u32,
+}
+
+#[contracttype]
+enum DataKey {
+    EpochCount(Address),
+    Epoch(Address, u32),
+    Score(Address, u32, Address),
+    Claimed(Address, u32, Address),
+    PendingCount,
+    Pending(u32),
+    UserPending(Address),
+}
+
+/// Errors raised by the reward distributor.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The sender is not a manager of the scorer.
+    Unauthorized = 1,
+    /// The scorer has no epoch with this id.
+    EpochNotFound = 2,
+    /// The epoch is finalized and no longer takes scores or funds.
+    EpochFinalized = 3,
+    /// The epoch is not finalized yet, so rewards cannot be claimed.
+    EpochNotFinalized = 4,
+    /// The funded amount is not positive.
+    InvalidAmount = 5,
+    /// The epoch has no funds or no scores to distribute.
+    NothingToDistribute = 6,
+    /// The user has no snapshot score in the epoch.
+    NoScore = 7,
+    /// The user already claimed the reward of the epoch.
+    AlreadyClaimed = 8,
+    /// There is no pending reward with this id, or it was claimed or reclaimed.
+    RewardNotFound = 9,
+    /// The pending reward expired and can only be reclaimed by its sponsor.
+    RewardExpired = 10,
+    /// The pending reward has not expired yet, so it cannot be reclaimed.
+    RewardNotExpired = 11,
+    /// The expiration ledger is not after the current one.
+    InvalidExpiration = 12,
+}
+
+/// Distributes token rewards to the members of scorers in proportion to their scores.
+///
+/// The managers of a scorer open an epoch, record a snapshot of the members'
+/// scores and finalize it once the community has funded it. Members then claim
+/// their share, `pool * score / total_score`, themselves, so payouts cost the
+/// scorer nothing however many members it has. Rounding leaves the remainder
+/// of the pool in the contract.
+///
+/// Managers can also allocate a reward to a single user, who claims it
+/// whenever they come online until it expires; the manager can then reclaim it.
+#[contract]
+pub struct RewardDistributorContract;
+
+#[contractimpl]
+impl RewardDistributorContract {
+    /// Opens a reward epoch for a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `manager` - A manager of the scorer
+    /// * `scorer` - The scorer whose members are rewarded
+    /// * `token` - The token the rewards are paid in
+    ///
+    /// # Returns
+    /// * `u32` - The id of the epoch, counting from 0 for each scorer
+    ///
+    /// # Panics
+    /// * When the manager fails authentication or is not a manager of the scorer (`Error::Unauthorized`)
+    pub fn create_epoch(env: Env, manager: Address, scorer: Address, token: Address) -> u32 {
+        Self::require_manager(&env, &manager, &scorer);
+
+        let count_key = DataKey::EpochCount(scorer.clone());
+        let epoch: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage().persistent().set(&count_key, &(epoch + 1));
+        env.storage().persistent().set(
+            &DataKey::Epoch(scorer.clone(), epoch),
+            &Epoch { token: token.clone(), pool: 0, total_score: 0, finalized: false },
+        );
+
+        env.events().publish((TOPIC_EPOCH, symbol_short!("create")), events::EpochCreated { scorer, epoch, manager, token });
+        epoch
+    }
+
+    /// Records snapshot scores of an epoch, replacing those already recorded for the same users
+    ///
+    /// Scores are computed off-chain from the attestations of the members, and
+    /// can be recorded in several batches before the epoch is finalized.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `manager` - A manager of the scorer
+    /// * `scorer` - The scorer of the epoch
+    /// * `epoch` - The epoch id
+    /// * `scores` - The snapshot score of each user
+    ///
+    /// # Panics
+    /// * When the manager fails authentication or is not a manager of the scorer (`Error::Unauthorized`)
+    /// * When the epoch does not exist (`Error::EpochNotFound`)
+    /// * When the epoch is finalized (`Error::EpochFinalized`)
+    pub fn record_scores(env: Env, manager: Address, scorer: Address, epoch: u32, scores: Map<Address, u32>) {
+        Self::require_manager(&env, &manager, &scorer);
+        let mut state = Self::open_epoch(&env, &scorer, epoch);
+
+        for (user, score) in scores.iter() {
+            let key = DataKey::Score(scorer.clone(), epoch, user);
+            let previous: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+            state.total_score = state.total_score - previous as u64 + score as u64;
+            env.storage().persistent().set(&key, &score);
+        }
+        env.storage().persistent().set(&DataKey::Epoch(scorer.clone(), epoch), &state);
+
+        env.events().publish(
+            (TOPIC_EPOCH, symbol_short!("scores")),
+            events::ScoresRecorded { scorer, epoch, manager, users: scores.len(), total_score: state.total_score },
+        );
+    }
+
+    /// Adds tokens to the pool of an epoch
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `funder` - The address transferring the tokens
+    /// * `scorer` - The scorer of the epoch
+    /// * `epoch` - The epoch id
+    /// * `amount` - The amount of the epoch token to transfer
+    ///
+    /// # Panics
+    /// * When the funder fails authentication
+    /// * When the amount is not positive (`Error::InvalidAmount`)
+    /// * When the epoch does not exist (`Error::EpochNotFound`)
+    /// * When the epoch is finalized (`Error::EpochFinalized`)
+    pub fn fund(env: Env, funder: Address, scorer: Address, epoch: u32, amount: i128) {
+        funder.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+
+        let mut state = Self::open_epoch(&env, &scorer, epoch);
+        token::Client::new(&env, &state.token).transfer(&funder, &env.current_contract_address(), &amount);
+        state.pool += amount;
+        env.storage().persistent().set(&DataKey::Epoch(scorer.clone(), epoch), &state);
+
+        env.events().publish((TOPIC_EPOCH, symbol_short!("fund")), events::EpochFunded { scorer, epoch, funder, amount });
+    }
+
+    /// Closes an epoch to scores and funding, and opens its claims
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `manager` - A manager of the scorer
+    /// * `scorer` - The scorer of the epoch
+    /// * `epoch` - The epoch id
+    ///
+    /// # Panics
+    /// * When the manager fails authentication or is not a manager of the scorer (`Error::Unauthorized`)
+    /// * When the epoch does not exist (`Error::EpochNotFound`)
+    /// * When the epoch is finalized (`Error::EpochFinalized`)
+    /// * When the epoch has no funds or no scores (`Error::NothingToDistribute`)
+    pub fn finalize(env: Env, manager: Address, scorer: Address, epoch: u32) {
+        Self::require_manager(&env, &manager, &scorer);
+        let mut state = Self::open_epoch(&env, &scorer, epoch);
+
+        if state.pool == 0 || state.total_score == 0 {
+            panic_with_error!(&env, Error::NothingToDistribute);
+        }
+
+        state.finalized = true;
+        env.storage().persistent().set(&DataKey::Epoch(scorer.clone(), epoch), &state);
+
+        env.events().publish(
+            (TOPIC_EPOCH, symbol_short!("finalize")),
+            events::EpochFinalized { scorer, epoch, pool: state.pool, total_score: state.total_score },
+        );
+    }
+
+    /// Transfers to a user its share of the pool of a finalized epoch
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The user claiming its reward
+    /// * `scorer` - The scorer of the epoch
+    /// * `epoch` - The epoch id
+    ///
+    /// # Returns
+    /// * `i128` - The amount transferred
+    ///
+    /// # Panics
+    /// * When the user fails authentication
+    /// * When the epoch does not exist (`Error::EpochNotFound`)
+    /// * When the epoch is not finalized (`Error::EpochNotFinalized`)
+    /// * When the user has no snapshot score in the epoch (`Error::NoScore`)
+    /// * When the user already claimed the reward (`Error::AlreadyClaimed`)
+    pub fn claim(env: Env, user: Address, scorer: Address, epoch: u32) -> i128 {
+        user.require_auth();
+
+        let state = Self::get_epoch(env.clone(), scorer.clone(), epoch)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::EpochNotFound));
+        if !state.finalized {
+            panic_with_error!(&env, Error::EpochNotFinalized);
+        }
+
+        let score = Self::get_snapshot_score(env.clone(), scorer.clone(), epoch, user.clone());
+        if score == 0 {
+            panic_with_error!(&env, Error::NoScore);
+        }
+
+        let claimed_key = DataKey::Claimed(scorer.clone(), epoch, user.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            panic_with_error!(&env, Error::AlreadyClaimed);
+        }
+        env.storage().persistent().set(&claimed_key, &true);
+
+        let amount = state.pool * score as i128 / state.total_score as i128;
+        token::Client::new(&env, &state.token).transfer(&env.current_contract_address(), &user, &amount);
+
+        env.events().publish((TOPIC_REWARD, symbol_short!("claim")), events::RewardClaimed { scorer, epoch, user, amount });
+        amount
+    }
+
+    /// Returns the amount a user can claim from an epoch, 0 once claimed or before the epoch is finalized
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer of the epoch
+    /// * `epoch` - The epoch id
+    /// * `user` - The user to check
+    pub fn get_claimable(env: Env, scorer: Address, epoch: u32, user: Address) -> i128 {
+        let state = match Self::get_epoch(env.clone(), scorer.clone(), epoch) {
+            Some(state) if state.finalized => state,
+            _ => return 0,
+        };
+        if env.storage().persistent().has(&DataKey::Claimed(scorer.clone(), epoch, user.clone())) {
+            return 0;
+        }
+        state.pool * Self::get_snapshot_score(env, scorer, epoch, user) as i128 / state.total_score as i128
+    }
+
+    /// Sets aside a reward for a user, paid by the manager, to be claimed before it expires
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `manager` - A manager of the scorer, who pays the reward and can reclaim it once expired
+    /// * `scorer` - The scorer the user is rewarded by
+    /// * `user` - The user who can claim the reward
+    /// * `token` - The token the reward is paid in
+    /// * `amount` - The amount of the reward
+    /// * `expires_at` - The ledger sequence from which the reward can no longer be claimed
+    ///
+    /// # Returns
+    /// * `u32` - The id of the pending reward
+    ///
+    /// # Panics
+    /// * When the manager fails authentication or is not a manager of the scorer (`Error::Unauthorized`)
+    /// * When the amount is not positive (`Error::InvalidAmount`)
+    /// * When the expiration ledger is not after the current one (`Error::InvalidExpiration`)
+    pub fn allocate(
+        env: Env,
+        manager: Address,
+        scorer: Address,
+        user: Address,
+        token: Address,
+        amount: i128,
+        expires_at: u32,
+    ) -> u32 {
+        Self::require_manager(&env, &manager, &scorer);
+
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        if expires_at <= env.ledger().sequence() {
+            panic_with_error!(&env, Error::InvalidExpiration);
+        }
+
+        token::Client::new(&env, &token).transfer(&manager, &env.current_contract_address(), &amount);
+
+        let id: u32 = env.storage().persistent().get(&DataKey::PendingCount).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::PendingCount, &(id + 1));
+        let reward = PendingReward {
+            scorer: scorer.clone(),
+            user: user.clone(),
+            sponsor: manager.clone(),
+            token: token.clone(),
+            amount,
+            expires_at,
+        };
+        env.storage().persistent().set(&DataKey::Pending(id), &reward);
+        let mut pending = Self::get_pending_rewards(env.clone(), user.clone());
+        pending.push_back(id);
+        env.storage().persistent().set(&DataKey::UserPending(user.clone()), &pending);
+
+        env.events().publish(
+            (TOPIC_REWARD, symbol_short!("allocate")),
+            events::RewardAllocated { id, scorer, user, sponsor: manager, token, amount, expires_at },
+        );
+        id
+    }
+
+    /// Transfers a pending reward to its user, before it expires
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the pending reward
+    ///
+    /// # Returns
+    /// * `i128` - The amount transferred
+    ///
+    /// # Panics
+    /// * When the reward does not exist (`Error::RewardNotFound`)
+    /// * When the user of the reward fails authentication
+    /// * When the reward expired (`Error::RewardExpired`)
+    pub fn claim_pending(env: Env, id: u32) -> i128 {
+        let reward = Self::take_pending(&env, id);
+        reward.user.require_auth();
+
+        if env.ledger().sequence() >= reward.expires_at {
+            panic_with_error!(&env, Error::RewardExpired);
+        }
+
+        token::Client::new(&env, &reward.token).transfer(&env.current_contract_address(), &reward.user, &reward.amount);
+
+        env.events().publish(
+            (TOPIC_REWARD, symbol_short!("collect")),
+            events::PendingRewardClaimed { id, user: reward.user, amount: reward.amount },
+        );
+        reward.amount
+    }
+
+    /// Transfers an expired pending reward back to its sponsor
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the pending reward
+    ///
+    /// # Returns
+    /// * `i128` - The amount transferred
+    ///
+    /// # Panics
+    /// * When the reward does not exist (`Error::RewardNotFound`)
+    /// * When the sponsor of the reward fails authentication
+    /// * When the reward has not expired yet (`Error::RewardNotExpired`)
+    pub fn reclaim(env: Env, id: u32) -> i128 {
+        let reward = Self::take_pending(&env, id);
+        reward.sponsor.require_auth();
+
+        if env.ledger().sequence() < reward.expires_at {
+            panic_with_error!(&env, Error::RewardNotExpired);
+        }
+
+        token::Client::new(&env, &reward.token).transfer(&env.current_contract_address(), &reward.sponsor, &reward.amount);
+
+        env.events().publish(
+            (TOPIC_REWARD, symbol_short!("reclaim")),
+            events::RewardReclaimed { id, sponsor: reward.sponsor, amount: reward.amount },
+        );
+        reward.amount
+    }
+
+    /// Returns a pending reward, if it was neither claimed nor reclaimed
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the pending reward
+    pub fn get_pending_reward(env: Env, id: u32) -> Option<PendingReward> {
+        env.storage().persistent().get(&DataKey::Pending(id))
+    }
+
+    /// Returns the ids of the pending rewards of a user, expired ones included, in allocation order
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The user to check
+    pub fn get_pending_rewards(env: Env, user: Address) -> Vec<u32> {
+        env.storage().persistent().get(&DataKey::UserPending(user)).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns an epoch, if it exists
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer of the epoch
+    /// * `epoch` - The epoch id
+    pub fn get_epoch(env: Env, scorer: Address, epoch: u32) -> Option<Epoch> {
+        env.storage().persistent().get(&DataKey::Epoch(scorer, epoch))
+    }
+
+    /// Returns the number of epochs of a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer
+    pub fn get_epoch_count(env: Env, scorer: Address) -> u32 {
+        env.storage().persistent().get(&DataKey::EpochCount(scorer)).unwrap_or(0)
+    }
+
+    /// Returns the snapshot score of a user in an epoch, 0 when none was recorded
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer of the epoch
+    /// * `epoch` - The epoch id
+    /// * `user` - The user to check
+    pub fn get_snapshot_score(env: Env, scorer: Address, epoch: u32, user: Address) -> u32 {
+        env.storage().persistent().get(&DataKey::Score(scorer, epoch, user)).unwrap_or(0)
+    }
+
+    fn require_manager(env: &Env, manager: &Address, scorer: &Address) {
+        manager.require_auth();
+
+        if !ScorerClient::new(env, scorer).get_managers().contains(manager) {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    // Removes a pending reward, which is then settled by the caller
+    fn take_pending(env: &Env, id: u32) -> PendingReward {
+        let reward: PendingReward = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pending(id))
+            .unwrap_or_else(|| panic_with_error!(env, Error::RewardNotFound));
+        env.storage().persistent().remove(&DataKey::Pending(id));
+
+        let mut pending = Self::get_pending_rewards(env.clone(), reward.user.clone());
+        if let Some(index) = pending.first_index_of(id) {
+            pending.remove(index);
+        }
+        let key = DataKey::UserPending(reward.user.clone());
+        if pending.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &pending);
+        }
+        reward
+    }
+
+    // Loads an epoch that still takes scores and funds
+    fn open_epoch(env: &Env, scorer: &Address, epoch: u32) -> Epoch {
+        let state = Self::get_epoch(env.clone(), scorer.clone(), epoch)
+            .unwrap_or_else(|| panic_with_error!(env, Error::EpochNotFound));
+        if state.finalized {
+            panic_with_error!(env, Error::EpochFinalized);
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::String;
+    use test_utils::{EventRecord, EventSnapshot};
+
+    struct Setup {
+        env: Env,
+        manager: Address,
+        funder: Address,
+        scorer: Address,
+        token: Address,
+        client: RewardDistributorContractClient<'static>,
+    }
+
+    // A scorer, a token and a funder holding 1000 of it
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let manager = Address::generate(&env);
+        let scorer = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        scorer.initialize(
+            &manager,
+            &Map::new(&env),
+            &String::from_str(&env, "Community"),
+            &String::from_str(&env, "A community"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let funder = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&funder, &1000);
+
+        let client = RewardDistributorContractClient::new(&env, &env.register(RewardDistributorContract, ()));
+
+        Setup { env, manager, funder, scorer: scorer.address, token, client }
+    }
+
+    #[test]
+    fn test_claim_rewards() {
+        let Setup { env, manager, funder, scorer, token, client } = setup();
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        let epoch = client.create_epoch(&manager, &scorer, &token);
+        assert_eq!(epoch, 0);
+        let mut scores = Map::new(&env);
+        scores.set(alice.clone(), 30);
+        scores.set(bob.clone(), 10);
+        client.record_scores(&manager, &scorer, &epoch, &scores);
+        client.fund(&funder, &scorer, &epoch, &1000);
+        assert_eq!(client.get_claimable(&scorer, &epoch, &alice), 0);
+        client.finalize(&manager, &scorer, &epoch);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_EPOCH, symbol_short!("finalize")),
+                events::EpochFinalized { scorer: scorer.clone(), epoch, pool: 1000, total_score: 40 },
+            ),
+        ]);
+
+        assert_eq!(client.get_claimable(&scorer, &epoch, &alice), 750);
+        assert_eq!(client.claim(&alice, &scorer, &epoch), 750);
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(
+                &env,
+                &client.address,
+                (TOPIC_REWARD, symbol_short!("claim")),
+                events::RewardClaimed { scorer: scorer.clone(), epoch, user: alice.clone(), amount: 750 },
+            ),
+        ]);
+        assert_eq!(client.claim(&bob, &scorer, &epoch), 250);
+        let token_client = token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&alice), 750);
+        assert_eq!(token_client.balance(&bob), 250);
+        assert_eq!(token_client.balance(&client.address), 0);
+        assert_eq!(client.get_claimable(&scorer, &epoch, &alice), 0);
+
+        assert_eq!(client.try_claim(&alice, &scorer, &epoch), Err(Ok(Error::AlreadyClaimed.into())));
+        assert_eq!(client.try_claim(&Address::generate(&env), &scorer, &epoch), Err(Ok(Error::NoScore.into())));
+    }
+
+    #[test]
+    fn test_record_scores_replaces_previous_scores() {
+        let Setup { env, manager, scorer, token, client, .. } = setup();
+        let alice = Address::generate(&env);
+
+        let epoch = client.create_epoch(&manager, &scorer, &token);
+        let mut scores = Map::new(&env);
+        scores.set(alice.clone(), 30);
+        client.record_scores(&manager, &scorer, &epoch, &scores);
+        scores.set(alice.clone(), 10);
+        scores.set(Address::generate(&env), 5);
+        client.record_scores(&manager, &scorer, &epoch, &scores);
+
+        assert_eq!(client.get_snapshot_score(&scorer, &epoch, &alice), 10);
+        assert_eq!(client.get_epoch(&scorer, &epoch).unwrap().total_score, 15);
+    }
+
+    #[test]
+    fn test_epoch_lifecycle() {
+        let Setup { env, manager, funder, scorer, token, client } = setup();
+        let alice = Address::generate(&env);
+
+        let epoch = client.create_epoch(&manager, &scorer, &token);
+        assert_eq!(client.try_finalize(&manager, &scorer, &epoch), Err(Ok(Error::NothingToDistribute.into())));
+        assert_eq!(client.try_fund(&funder, &scorer, &epoch, &0), Err(Ok(Error::InvalidAmount.into())));
+        assert_eq!(client.try_fund(&funder, &scorer, &1, &100), Err(Ok(Error::EpochNotFound.into())));
+
+        let mut scores = Map::new(&env);
+        scores.set(alice.clone(), 1);
+        client.record_scores(&manager, &scorer, &epoch, &scores);
+        client.fund(&funder, &scorer, &epoch, &100);
+        assert_eq!(client.try_claim(&alice, &scorer, &epoch), Err(Ok(Error::EpochNotFinalized.into())));
+
+        client.finalize(&manager, &scorer, &epoch);
+        assert_eq!(client.try_fund(&funder, &scorer, &epoch, &100), Err(Ok(Error::EpochFinalized.into())));
+        assert_eq!(client.try_record_scores(&manager, &scorer, &epoch, &scores), Err(Ok(Error::EpochFinalized.into())));
+        assert_eq!(client.try_finalize(&manager, &scorer, &epoch), Err(Ok(Error::EpochFinalized.into())));
+
+        // Epochs are numbered per scorer
+        assert_eq!(client.create_epoch(&manager, &scorer, &token), 1);
+        assert_eq!(client.get_epoch_count(&scorer), 2);
+    }
+
+    #[test]
+    fn test_pending_rewards() {
+        let Setup { env, manager, funder, scorer, token, client } = setup();
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&funder, &manager, &500);
+        let expires_at = env.ledger().sequence() + 100;
+
+        assert_eq!(client.try_allocate(&manager, &scorer, &alice, &token, &0, &expires_at), Err(Ok(Error::InvalidAmount.into())));
+        assert_eq!(
+            client.try_allocate(&manager, &scorer, &alice, &token, &100, &env.ledger().sequence()),
+            Err(Ok(Error::InvalidExpiration.into()))
+        );
+        assert_eq!(client.try_allocate(&alice, &scorer, &bob, &token, &100, &expires_at), Err(Ok(Error::Unauthorized.into())));
+
+        let first = client.allocate(&manager, &scorer, &alice, &token, &100, &expires_at);
+        let second = client.allocate(&manager, &scorer, &alice, &token, &150, &expires_at);
+        let third = client.allocate(&manager, &scorer, &bob, &token, &200, &expires_at);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_REWARD, symbol_short!("allocate")),
+            events::RewardAllocated {
+                id: third,
+                scorer: scorer.clone(),
+                user: bob.clone(),
+                sponsor: manager.clone(),
+                token: token.clone(),
+                amount: 200,
+                expires_at,
+            },
+        )]);
+        assert_eq!((first, second, third), (0, 1, 2));
+        assert_eq!(token_client.balance(&client.address), 450);
+        assert_eq!(client.get_pending_rewards(&alice), Vec::from_array(&env, [first, second]));
+        assert_eq!(
+            client.get_pending_reward(&first),
+            Some(PendingReward {
+                scorer: scorer.clone(),
+                user: alice.clone(),
+                sponsor: manager.clone(),
+                token: token.clone(),
+                amount: 100,
+                expires_at,
+            })
+        );
+
+        // Users claim while the reward has not expired, and the sponsor reclaims it after
+        assert_eq!(client.try_reclaim(&first), Err(Ok(Error::RewardNotExpired.into())));
+        assert_eq!(client.claim_pending(&first), 100);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_REWARD, symbol_short!("collect")),
+            events::PendingRewardClaimed { id: first, user: alice.clone(), amount: 100 },
+        )]);
+        assert_eq!(token_client.balance(&alice), 100);
+        assert_eq!(client.get_pending_rewards(&alice), Vec::from_array(&env, [second]));
+        assert_eq!(client.try_claim_pending(&first), Err(Ok(Error::RewardNotFound.into())));
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number = expires_at);
+        assert_eq!(client.try_claim_pending(&second), Err(Ok(Error::RewardExpired.into())));
+        assert_eq!(client.reclaim(&second), 150);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_REWARD, symbol_short!("reclaim")),
+            events::RewardReclaimed { id: second, sponsor: manager.clone(), amount: 150 },
+        )]);
+        assert_eq!(token_client.balance(&manager), 200);
+        assert!(client.get_pending_rewards(&alice).is_empty());
+        assert_eq!(client.get_pending_reward(&second), None);
+        assert_eq!(client.get_pending_rewards(&bob), Vec::from_array(&env, [third]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_create_epoch_unauthorized() {
+        let Setup { env, scorer, token, client, .. } = setup();
+
+        client.create_epoch(&Address::generate(&env), &scorer, &token);
+    }
+}
@@ -0,0 +1,692 @@
+#![no_std]
+use scorer_interface::{events, BadgeId, BadgeLogTrait, BadgeTokenClient, Extension, ScorerClient, UserStatus, MAX_URI_LENGTH};
+use scorer_interface::events::{EVENT_SCHEMA_VERSION, TOPIC_BADGE, TOPIC_SCORE};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address, BytesN, Env, Map, String, Vec};
+
+/// Number of badges in a page of `get_all_badges_info`.
+pub const BADGE_INFO_PAGE_SIZE: u32 = 20;
+
+/// Maximum number of entries in a page of `get_managers_page`.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of users in a page of `get_scoreboard`. Each active user
+/// costs a call to the scorer and one to its badge token.
+pub const SCOREBOARD_PAGE_SIZE: u32 = 5;
+
+/// Maximum number of changes kept in the history of a badge.
+pub const MAX_BADGE_HISTORY: u32 = 20;
+
+/// A change of the score of a badge: 0 as `old_score` when the badge was added,
+/// and as `new_score` when it was removed. The badges a scorer has when its
+/// scoreboard is initialized start with an entry from 0 to their score, with
+/// the scorer as `actor`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeScoreChange {
+    pub old_score: u32,
+    pub new_score: u32,
+    pub actor: Address,
+    pub ledger: u32,
+}
+
+/// A content-addressed document describing a badge, such as its artwork and
+/// criteria: `hash` is the SHA-256 of the document found at `uri`, so clients
+/// can verify what they fetched.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeMetadata {
+    pub uri: String,
+    pub hash: BytesN<32>,
+}
+
+/// What a client needs to render a badge of a scorer. Badges have no icon of
+/// their own, so `icon` is the scorer's; `uri` is the one of the badge
+/// metadata, if set, and `holders_count` is 0 without a badge token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeInfo {
+    pub id: BadgeId,
+    pub score: u32,
+    pub icon: String,
+    pub uri: Option<String>,
+    pub holders_count: u32,
+    pub enabled: bool,
+}
+
+/// A user's membership at a glance, for a profile page. Badges are read from
+/// the badge token, so `badges_held` and `score` only count staking boosts
+/// without one; `pending_badges` are the enabled badges the user does not hold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserSummary {
+    pub joined_at: u32,
+    pub status: UserStatus,
+    pub score: u32,
+    pub tier: u32,
+    pub badges_held: u32,
+    pub pending_badges: u32,
+}
+
+#[contracttype]
+enum DataKey {
+    Scorer,
+    Tiers,
+    BadgeMetadata(BadgeId),
+    /// The names and scores of the badges of an issuer.
+    IssuerBadges(Address),
+    /// The latest score changes of a badge, oldest first.
+    BadgeHistory(BadgeId),
+}
+
+// Errors raised by the scoreboard contract. They keep the codes the scorer
+// raised them with before these views moved to this contract.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    // The sender is not a manager, or not the scorer creator.
+    Unauthorized = 2,
+    // The badge is not part of the scorer.
+    BadgeNotFound = 10,
+    // The metadata URI is empty.
+    EmptyArg = 12,
+    // The tier thresholds are not strictly increasing.
+    InvalidTiers = 43,
+    // The metadata URI is longer than `MAX_URI_LENGTH` bytes.
+    UriTooLong = 49,
+    // The scorer is missing from storage.
+    ScorerNotFound = 51,
+}
+
+/// Badge metadata, member tiers and the paged views clients render a scorer with.
+///
+/// The contract reads the scorer, and its badge token when it has one. The
+/// scorer registers it as its `Extension::Scoreboard` to report badge changes,
+/// which keep the index of badges by issuer and their history. Managers attach a metadata document
+/// to each badge, and the scorer creator sets the score thresholds of the member
+/// tiers reported by `get_scoreboard` and `get_user_summary`.
+#[contract]
+pub struct ScoreboardContract;
+
+#[contractimpl]
+impl ScoreboardContract {
+    // Initializes the scoreboard of a scorer, indexing its badges by issuer
+    // and starting their history with their current score
+    pub fn initialize(env: Env, scorer: Address) {
+        if env.storage().persistent().has(&DataKey::Scorer) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        // Only the scorer owner can attach a scoreboard to its scorer
+        ScorerClient::new(&env, &scorer).get_contract_owner().require_auth();
+
+        env.storage().persistent().set(&DataKey::Scorer, &scorer);
+
+        let mut issuers: Map<Address, Map<String, u32>> = Map::new(&env);
+        for (badge_id, score) in ScorerClient::new(&env, &scorer).get_badges().iter() {
+            // Earlier changes are only in the scorer's badge events
+            let registered = BadgeScoreChange { old_score: 0, new_score: score, actor: scorer.clone(), ledger: env.ledger().sequence() };
+            env.storage().persistent().set(&DataKey::BadgeHistory(badge_id.clone()), &Vec::from_array(&env, [registered]));
+
+            let mut names = issuers.get(badge_id.issuer.clone()).unwrap_or_else(|| Map::new(&env));
+            names.set(badge_id.name, score);
+            issuers.set(badge_id.issuer, names);
+        }
+        for (issuer, names) in issuers.iter() {
+            env.storage().persistent().set(&DataKey::IssuerBadges(issuer), &names);
+        }
+    }
+
+    // Returns the scorer shown
+    pub fn get_scorer(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scorer)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    // Sets or clears the metadata document of a badge, referenced by a
+    // content-addressed URI such as `ipfs://<cid>` and the document's SHA-256 hash
+    pub fn set_badge_metadata(env: Env, sender: Address, badge_id: BadgeId, metadata: Option<BadgeMetadata>) {
+        sender.require_auth();
+
+        let scorer = Self::scorer(&env);
+        if !scorer.get_managers().contains(&sender) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if !scorer.get_badges().contains_key(badge_id.clone()) {
+            panic_with_error!(&env, Error::BadgeNotFound);
+        }
+
+        let key = DataKey::BadgeMetadata(badge_id.clone());
+        match metadata {
+            Some(metadata) => {
+                if metadata.uri.is_empty() {
+                    panic_with_error!(&env, Error::EmptyArg);
+                }
+                if metadata.uri.len() > MAX_URI_LENGTH {
+                    panic_with_error!(&env, Error::UriTooLong);
+                }
+                env.storage().persistent().set(&key, &metadata);
+                env.events().publish(
+                    (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("metadata")),
+                    events::BadgeMetadataSet { sender, badge_id, uri: metadata.uri, hash: metadata.hash },
+                );
+            }
+            None => {
+                env.storage().persistent().remove(&key);
+                env.events().publish(
+                    (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("metaclear")),
+                    events::BadgeMetadataCleared { sender, badge_id },
+                );
+            }
+        }
+    }
+
+    // Returns the metadata document of a badge, or None if not set
+    pub fn get_badge_metadata(env: Env, badge_id: BadgeId) -> Option<BadgeMetadata> {
+        env.storage().persistent().get(&DataKey::BadgeMetadata(badge_id))
+    }
+
+    // Returns a page of the badges with what clients need to render them,
+    // ordered by identifier and empty past the last one. Holders are read from
+    // the badge token of the scorer
+    pub fn get_all_badges_info(env: Env, page: u32) -> Vec<BadgeInfo> {
+        let scorer = Self::scorer(&env);
+        let badges = scorer.get_badges();
+        let token = Self::badge_token(&env, &scorer);
+        let (_, _, icon) = scorer.get_metadata();
+
+        let start = page.saturating_mul(BADGE_INFO_PAGE_SIZE).min(badges.len());
+        let end = start.saturating_add(BADGE_INFO_PAGE_SIZE).min(badges.len());
+        let mut infos = Vec::new(&env);
+        for (id, score) in badges.iter().skip(start as usize).take((end - start) as usize) {
+            let uri = Self::get_badge_metadata(env.clone(), id.clone()).map(|metadata| metadata.uri);
+            let holders_count = token.as_ref().map_or(0, |token| token.get_holders_count(&id));
+            let enabled = scorer.is_badge_enabled(&id);
+            infos.push_back(BadgeInfo { id, score, icon: icon.clone(), uri, holders_count, enabled });
+        }
+        infos
+    }
+
+    // Returns the badges of an issuer with their scores, from the index kept
+    // without scanning the scorer's badges
+    pub fn get_badges_by_issuer(env: Env, issuer: Address) -> Map<BadgeId, u32> {
+        let mut badges = Map::new(&env);
+        for (name, score) in Self::issuer_badges(&env, &issuer).iter() {
+            badges.set(BadgeId { name, issuer: issuer.clone() }, score);
+        }
+        badges
+    }
+
+    // Returns the latest `MAX_BADGE_HISTORY` score changes of a badge,
+    // including its addition and removal. The history starts when the
+    // scoreboard is initialized, with the score the badge had then
+    pub fn get_badge_history(env: Env, badge_id: BadgeId) -> Vec<BadgeScoreChange> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BadgeHistory(badge_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // Returns up to `limit` managers from index `offset`, in the order of the
+    // scorer's indexes, and empty past the last one. Only the managers of the
+    // page are read from the scorer
+    pub fn get_managers_page(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        Self::scorer(&env).get_managers_page(&offset, &limit.min(MAX_PAGE_SIZE))
+    }
+
+    // Returns the number of managers
+    pub fn get_manager_count(env: Env) -> u32 {
+        Self::scorer(&env).get_manager_count()
+    }
+
+    // Sets the score thresholds of the member tiers: a member is in tier N
+    // once its score reaches the Nth threshold
+    pub fn set_tiers(env: Env, sender: Address, thresholds: Vec<u32>) {
+        sender.require_auth();
+
+        if Self::scorer(&env).get_contract_owner() != sender {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        for index in 1..thresholds.len() {
+            if thresholds.get_unchecked(index) <= thresholds.get_unchecked(index - 1) {
+                panic_with_error!(&env, Error::InvalidTiers);
+            }
+        }
+
+        if thresholds.is_empty() {
+            env.storage().persistent().remove(&DataKey::Tiers);
+        } else {
+            env.storage().persistent().set(&DataKey::Tiers, &thresholds);
+        }
+
+        env.events().publish(
+            (EVENT_SCHEMA_VERSION, TOPIC_SCORE, symbol_short!("tiers")),
+            events::TiersSet { sender, thresholds },
+        );
+    }
+
+    // Returns the increasing score thresholds of the member tiers, empty if
+    // there are no tiers
+    pub fn get_tiers(env: Env) -> Vec<u32> {
+        env.storage().persistent().get(&DataKey::Tiers).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // Returns the active members among up to `limit` users from index
+    // `offset` of the scorer, with their score and tier. Only the users of the
+    // page are read, so a page holds fewer entries when some of its users are
+    // not active; page through the scorer's `get_user_count` users. Badges are
+    // read from the badge token of the scorer
+    pub fn get_scoreboard(env: Env, offset: u32, limit: u32) -> Vec<(Address, u32, u32)> {
+        let scorer = Self::scorer(&env);
+        let token = Self::badge_token(&env, &scorer);
+        let tiers = Self::get_tiers(env.clone());
+
+        let mut scoreboard = Vec::new(&env);
+        for (user, status) in scorer.get_users_page(&offset, &limit.min(SCOREBOARD_PAGE_SIZE)).iter() {
+            if status != UserStatus::Active {
+                continue;
+            }
+
+            let badges = match &token {
+                Some(token) => token.get_badges(&user),
+                None => Vec::new(&env),
+            };
+            let score = scorer.get_score(&user, &badges);
+            scoreboard.push_back((user, score, Self::tier_of(&tiers, score)));
+        }
+        scoreboard
+    }
+
+    // Returns everything a profile page shows of a member in a single read:
+    // when they joined, their status, score and tier, and how many of the
+    // scorer's badges they hold or can still earn. None if the user never joined
+    pub fn get_user_summary(env: Env, user: Address) -> Option<UserSummary> {
+        let scorer = Self::scorer(&env);
+        let joined_at = scorer.get_joined_at(&user)?;
+        let status = scorer.get_user_status(&user)?;
+        let held = match Self::badge_token(&env, &scorer) {
+            Some(token) => token.get_badges(&user),
+            None => Vec::new(&env),
+        };
+        let mut badges_held = 0;
+        let mut pending_badges = 0;
+        for badge in scorer.get_badges().keys().iter() {
+            if held.contains(&badge) {
+                badges_held += 1;
+            } else if scorer.is_badge_enabled(&badge) {
+                pending_badges += 1;
+            }
+        }
+
+        let score = scorer.get_score(&user, &held);
+        Some(UserSummary {
+            joined_at,
+            status,
+            score,
+            tier: Self::tier_of(&Self::get_tiers(env.clone()), score),
+            badges_held,
+            pending_badges,
+        })
+    }
+}
+
+#[contractimpl]
+impl BadgeLogTrait for ScoreboardContract {
+    // Updates the index of badges by issuer after a badge change in the scorer,
+    // and records the change in the history of the badge, which keeps the
+    // latest `MAX_BADGE_HISTORY` changes
+    fn record_badge_change(env: Env, badge_id: BadgeId, previous_score: Option<u32>, score: Option<u32>, sender: Address) {
+        Self::get_scorer(env.clone()).require_auth();
+
+        let history_key = DataKey::BadgeHistory(badge_id.clone());
+        let mut history: Vec<BadgeScoreChange> = env.storage().persistent().get(&history_key).unwrap_or_else(|| Vec::new(&env));
+        if history.len() >= MAX_BADGE_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(BadgeScoreChange {
+            old_score: previous_score.unwrap_or(0),
+            new_score: score.unwrap_or(0),
+            actor: sender,
+            ledger: env.ledger().sequence(),
+        });
+        env.storage().persistent().set(&history_key, &history);
+
+        let mut names = Self::issuer_badges(&env, &badge_id.issuer);
+        match score {
+            Some(score) => names.set(badge_id.name, score),
+            None => {
+                names.remove(badge_id.name);
+            }
+        }
+        let key = DataKey::IssuerBadges(badge_id.issuer);
+        if names.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &names);
+        }
+    }
+}
+
+impl ScoreboardContract {
+    /// Returns the names and scores of the indexed badges of an issuer
+    fn issuer_badges(env: &Env, issuer: &Address) -> Map<String, u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::IssuerBadges(issuer.clone()))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Returns a client of the scorer shown
+    fn scorer(env: &Env) -> ScorerClient<'_> {
+        ScorerClient::new(env, &Self::get_scorer(env.clone()))
+    }
+
+    /// Returns a client of the badge token of the scorer, if it has one
+    fn badge_token<'a>(env: &'a Env, scorer: &ScorerClient) -> Option<BadgeTokenClient<'a>> {
+        scorer.get_extension(&Extension::BadgeToken).map(|token| BadgeTokenClient::new(env, &token))
+    }
+
+    /// Returns the tier of a score: the number of thresholds it reaches
+    fn tier_of(tiers: &Vec<u32>, score: u32) -> u32 {
+        tiers.iter().filter(|threshold| score >= *threshold).count() as u32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::{vec, Bytes};
+    use test_utils::{scorer_with_extension, EventRecord, EventSnapshot, ScorerWithExtension};
+
+    mod held_badges {
+        use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+        use scorer_interface::BadgeId;
+
+        // Badge token holding the badges set by the test
+        #[contract]
+        pub struct HeldBadges;
+
+        #[contractimpl]
+        impl HeldBadges {
+            pub fn set_badges(env: Env, user: Address, badges: Vec<BadgeId>) {
+                for badge_id in badges.iter() {
+                    let holders = Self::get_holders_count(env.clone(), badge_id.clone());
+                    env.storage().persistent().set(&badge_id, &(holders + 1));
+                }
+                env.storage().persistent().set(&user, &badges);
+            }
+
+            pub fn get_badges(env: Env, user: Address) -> Vec<BadgeId> {
+                env.storage().persistent().get(&user).unwrap_or_else(|| Vec::new(&env))
+            }
+
+            pub fn get_holders_count(env: Env, badge_id: BadgeId) -> u32 {
+                env.storage().persistent().get(&badge_id).unwrap_or(0)
+            }
+        }
+    }
+
+    struct Setup {
+        env: Env,
+        scorer_creator: Address,
+        user: Address,
+        badges: Vec<BadgeId>,
+        scorer: ScorerContractClient<'static>,
+        client: ScoreboardContractClient<'static>,
+    }
+
+    // The scorer of `scorer_with_extension`, shown by the scoreboard
+    fn setup() -> Setup {
+        let ScorerWithExtension { env, scorer_creator, user, badges, scorer, extension } =
+            scorer_with_extension(ScorerContract, Extension::Scoreboard, ScoreboardContract, |env, extension, scorer| {
+                ScoreboardContractClient::new(env, extension).initialize(scorer)
+            });
+        let scorer = ScorerContractClient::new(&env, &scorer);
+        let client = ScoreboardContractClient::new(&env, &extension);
+        Setup { env, scorer_creator, user, badges, scorer, client }
+    }
+
+    // Registers a badge token holding `held` for each of the users
+    fn set_held_badges(setup: &Setup, held: &[(&Address, Vec<BadgeId>)]) {
+        let Setup { env, scorer_creator, scorer, .. } = setup;
+        let token = held_badges::HeldBadgesClient::new(env, &env.register(held_badges::HeldBadges, ()));
+        for (user, badges) in held {
+            token.set_badges(user, badges);
+        }
+        scorer.set_extension(scorer_creator, &Extension::BadgeToken, &Some(token.address.clone()));
+    }
+
+    #[test]
+    fn test_initialize() {
+        let Setup { scorer, client, .. } = setup();
+        assert_eq!(client.get_scorer(), scorer.address);
+        assert_eq!(client.try_initialize(&scorer.address), Err(Ok(Error::ContractAlreadyInitialized.into())));
+    }
+
+    #[test]
+    fn test_get_scoreboard() {
+        let setup = setup();
+        let Setup { env, scorer_creator, user, badges, scorer, client } = &setup;
+        let others = [Address::generate(env), Address::generate(env), Address::generate(env)];
+        for other in others.iter() {
+            scorer.add_user(other);
+        }
+        scorer.remove_user(&others[1]);
+
+        // Without a badge token members hold no badges
+        assert_eq!(client.get_scoreboard(&0, &10), vec![env, (user.clone(), 0, 0), (others[0].clone(), 0, 0), (others[2].clone(), 0, 0)]);
+
+        set_held_badges(&setup, &[(user, badges.clone()), (&others[0], badges.slice(0..1))]);
+
+        assert_eq!(client.try_set_tiers(user, &vec![env, 50]), Err(Ok(Error::Unauthorized.into())));
+        assert_eq!(client.try_set_tiers(scorer_creator, &vec![env, 100, 100]), Err(Ok(Error::InvalidTiers.into())));
+        client.set_tiers(scorer_creator, &vec![env, 100, 120]);
+        EventSnapshot::capture(env).assert_contains(&[EventRecord::new(
+            env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_SCORE, symbol_short!("tiers")),
+            events::TiersSet { sender: scorer_creator.clone(), thresholds: vec![env, 100, 120] },
+        )]);
+        assert_eq!(client.get_tiers(), vec![env, 100, 120]);
+
+        // Pages leave out the users who are not active
+        assert_eq!(client.get_scoreboard(&0, &2), vec![env, (user.clone(), 140, 2), (others[0].clone(), 100, 1)]);
+        assert_eq!(client.get_scoreboard(&2, &2), vec![env, (others[2].clone(), 0, 0)]);
+        assert!(client.get_scoreboard(&4, &2).is_empty());
+
+        client.set_tiers(scorer_creator, &Vec::new(env));
+        assert!(client.get_tiers().is_empty());
+    }
+
+    #[test]
+    fn test_get_user_summary() {
+        let setup = setup();
+        let Setup { env, scorer_creator, user, badges, scorer, client } = &setup;
+        let joined_at = scorer.get_joined_at(user).unwrap();
+        assert_eq!(client.get_user_summary(&Address::generate(env)), None);
+
+        set_held_badges(&setup, &[(user, badges.slice(0..1))]);
+        client.set_tiers(scorer_creator, &vec![env, 100, 120]);
+        assert_eq!(
+            client.get_user_summary(user),
+            Some(UserSummary {
+                joined_at,
+                status: UserStatus::Active,
+                score: 100,
+                tier: 1,
+                badges_held: 1,
+                pending_badges: 1,
+            })
+        );
+
+        // Disabled badges are not pending, and left members keep their badges
+        scorer.set_badge_enabled(scorer_creator, &badges.get_unchecked(1), &false);
+        scorer.remove_user(user);
+        let summary = client.get_user_summary(user).unwrap();
+        assert_eq!((summary.status, summary.badges_held, summary.pending_badges), (UserStatus::Left, 1, 0));
+    }
+
+    #[test]
+    fn test_get_all_badges_info() {
+        let setup = setup();
+        let Setup { env, scorer_creator, user, badges, scorer, client } = &setup;
+        let icon = String::from_str(env, "icon.png");
+        let info = |id: BadgeId, uri: Option<String>, holders_count: u32| BadgeInfo {
+            score: scorer.get_badges().get(id.clone()).unwrap(),
+            id,
+            icon: icon.clone(),
+            uri,
+            holders_count,
+            enabled: true,
+        };
+
+        // Without a badge token or metadata
+        let mut expected = Vec::new(env);
+        for id in scorer.get_badges().keys().iter() {
+            expected.push_back(info(id, None, 0));
+        }
+        assert_eq!(client.get_all_badges_info(&0), expected);
+
+        set_held_badges(&setup, &[(user, badges.slice(0..2)), (&Address::generate(env), badges.slice(1..2))]);
+        let speaker = badges.get_unchecked(1);
+        let uri = String::from_str(env, "ipfs://bafkreibm6jg3ux5qumhcn2b3flc3tyu6dmlb4xa7u5bf44yegnrjhc4yeq");
+        client.set_badge_metadata(scorer_creator, &speaker, &Some(BadgeMetadata { uri: uri.clone(), hash: BytesN::from_array(env, &[1; 32]) }));
+
+        let infos = client.get_all_badges_info(&0);
+        assert_eq!(infos.len(), 2);
+        assert!(infos.contains(info(badges.get_unchecked(0), None, 1)));
+        assert!(infos.contains(info(speaker.clone(), Some(uri), 2)));
+
+        scorer.set_badge_enabled(scorer_creator, &speaker, &false);
+        assert!(client.get_all_badges_info(&0).iter().any(|info| info.id == speaker && !info.enabled));
+
+        // Pages hold BADGE_INFO_PAGE_SIZE badges
+        for i in 0..BADGE_INFO_PAGE_SIZE {
+            scorer.add_badge(scorer_creator, &String::from_str(env, "Badge"), &Address::generate(env), &(i + 1));
+        }
+        assert_eq!(client.get_all_badges_info(&0).len(), BADGE_INFO_PAGE_SIZE);
+        assert_eq!(client.get_all_badges_info(&1).len(), 2);
+        assert!(client.get_all_badges_info(&2).is_empty());
+        assert!(client.get_all_badges_info(&u32::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_get_badges_by_issuer() {
+        let Setup { env, scorer_creator, scorer, client, .. } = setup();
+        let issuer = Address::generate(&env);
+        let community = String::from_str(&env, "Community");
+        let speaker = String::from_str(&env, "Speaker");
+
+        scorer.add_badge(&scorer_creator, &community, &issuer, &50);
+        scorer.add_badge(&scorer_creator, &speaker, &issuer, &80);
+        let mut expected = Map::new(&env);
+        expected.set(BadgeId { name: community.clone(), issuer: issuer.clone() }, 50);
+        expected.set(BadgeId { name: speaker.clone(), issuer: issuer.clone() }, 80);
+        assert_eq!(client.get_badges_by_issuer(&issuer), expected);
+
+        // The badges of the scorer at initialization are indexed too
+        let initial = client.get_badges_by_issuer(&scorer_creator);
+        assert_eq!(initial.len(), 1);
+        assert_eq!(initial.values().get(0), Some(100));
+
+        let community_id = BadgeId { name: community.clone(), issuer: issuer.clone() };
+        scorer.set_badge_score(&scorer_creator, &community_id, &60);
+        assert_eq!(client.get_badges_by_issuer(&issuer).get(community_id), Some(60));
+
+        // Only the scorer reports its badge changes
+        let unknown = BadgeId { name: String::from_str(&env, "Unknown"), issuer: issuer.clone() };
+        env.mock_auths(&[]);
+        assert!(client.try_record_badge_change(&unknown, &None, &Some(10), &scorer_creator).is_err());
+        env.mock_all_auths();
+
+        scorer.remove_badge(&scorer_creator, &community, &issuer);
+        scorer.remove_badge(&scorer_creator, &speaker, &issuer);
+        assert!(client.get_badges_by_issuer(&issuer).is_empty());
+        assert!(client.get_badges_by_issuer(&Address::generate(&env)).is_empty());
+    }
+
+    #[test]
+    fn test_badge_history() {
+        let Setup { env, scorer_creator, badges, scorer, client, .. } = setup();
+        let badge_id = BadgeId { name: String::from_str(&env, "Community"), issuer: Address::generate(&env) };
+        let entry = |old_score, new_score| BadgeScoreChange { old_score, new_score, actor: scorer_creator.clone(), ledger: env.ledger().sequence() };
+
+        scorer.add_badge(&scorer_creator, &badge_id.name, &badge_id.issuer, &40);
+        scorer.set_badge_score(&scorer_creator, &badge_id, &60);
+        scorer.remove_badge(&scorer_creator, &badge_id.name, &badge_id.issuer);
+        assert_eq!(client.get_badge_history(&badge_id), vec![&env, entry(0, 40), entry(40, 60), entry(60, 0)]);
+
+        // The history keeps the latest changes only
+        for _ in 0..10 {
+            scorer.add_badge(&scorer_creator, &badge_id.name, &badge_id.issuer, &10);
+            scorer.remove_badge(&scorer_creator, &badge_id.name, &badge_id.issuer);
+        }
+        let history = client.get_badge_history(&badge_id);
+        assert_eq!(history.len(), MAX_BADGE_HISTORY);
+        assert_eq!(history.get(0), Some(entry(0, 10)));
+
+        // Badges the scorer had before registering the scoreboard start with their score then
+        let registered = BadgeScoreChange { old_score: 0, new_score: 100, actor: scorer.address.clone(), ledger: env.ledger().sequence() };
+        assert_eq!(client.get_badge_history(&badges.get_unchecked(0)), vec![&env, registered.clone()]);
+        scorer.set_badge_score(&scorer_creator, &badges.get_unchecked(0), &120);
+        assert_eq!(client.get_badge_history(&badges.get_unchecked(0)), vec![&env, registered, entry(100, 120)]);
+    }
+
+    #[test]
+    fn test_get_managers_page() {
+        let Setup { env, scorer_creator, scorer, client, .. } = setup();
+        let managers = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+        for manager in managers.iter() {
+            scorer.add_manager(&scorer_creator, manager);
+        }
+
+        assert_eq!(client.get_manager_count(), 4);
+        assert_eq!(client.get_managers_page(&0, &2), vec![&env, scorer_creator, managers[0].clone()]);
+        assert_eq!(client.get_managers_page(&2, &5), vec![&env, managers[1].clone(), managers[2].clone()]);
+        assert!(client.get_managers_page(&4, &5).is_empty());
+        assert!(client.get_managers_page(&u32::MAX, &u32::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_badge_metadata() {
+        let Setup { env, scorer_creator, badges, client, .. } = setup();
+        let badge_id = badges.get_unchecked(0);
+        let document = Bytes::from_slice(&env, b"{\"criteria\":\"Spoke at a meetup\"}");
+        let metadata = BadgeMetadata {
+            uri: String::from_str(&env, "ipfs://bafkreibm6jg3ux5qumhcn2b3flc3tyu6dmlb4xa7u5bf44yegnrjhc4yeq"),
+            hash: env.crypto().sha256(&document).into(),
+        };
+
+        assert_eq!(client.try_set_badge_metadata(&Address::generate(&env), &badge_id, &Some(metadata.clone())), Err(Ok(Error::Unauthorized.into())));
+        assert_eq!(client.try_set_badge_metadata(&scorer_creator, &badges.get_unchecked(2), &Some(metadata.clone())), Err(Ok(Error::BadgeNotFound.into())));
+        let empty_uri = BadgeMetadata { uri: String::from_str(&env, ""), hash: metadata.hash.clone() };
+        assert_eq!(client.try_set_badge_metadata(&scorer_creator, &badge_id, &Some(empty_uri)), Err(Ok(Error::EmptyArg.into())));
+        let long_uri = BadgeMetadata { uri: String::from_bytes(&env, &[b'a'; MAX_URI_LENGTH as usize + 1]), hash: metadata.hash.clone() };
+        assert_eq!(client.try_set_badge_metadata(&scorer_creator, &badge_id, &Some(long_uri)), Err(Ok(Error::UriTooLong.into())));
+
+        client.set_badge_metadata(&scorer_creator, &badge_id, &Some(metadata.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("metadata")),
+            events::BadgeMetadataSet { sender: scorer_creator.clone(), badge_id: badge_id.clone(), uri: metadata.uri.clone(), hash: metadata.hash.clone() },
+        )]);
+        assert_eq!(client.get_badge_metadata(&badge_id), Some(metadata));
+
+        client.set_badge_metadata(&scorer_creator, &badge_id, &None);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("metaclear")),
+            events::BadgeMetadataCleared { sender: scorer_creator.clone(), badge_id: badge_id.clone() },
+        )]);
+        assert_eq!(client.get_badge_metadata(&badge_id), None);
+    }
+}
@@ -0,0 +1,108 @@
+//! Resource-budget benchmarks for the scoreboard pages.
+//!
+//! A full page of `get_scoreboard` of the release WASM is measured with
+//! `env.cost_estimate().budget()` on a scorer holding 100 and 1k users. A page
+//! only reads the users it shows, so its cost must not grow with the number of
+//! users, and it must fit in the network's default budget.
+//! Run with `cargo test -p scoreboard --test budget -- --nocapture` to print the
+//! measurements.
+
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig},
+    Address, Env, Map, String, Symbol,
+};
+
+mod scorer_contract {
+    soroban_sdk::contractimport!(file = "../../wasm/scorer.wasm");
+}
+
+mod badge_token_contract {
+    soroban_sdk::contractimport!(file = "../../wasm/badge_token.wasm");
+}
+
+mod scoreboard_contract {
+    soroban_sdk::contractimport!(file = "../../wasm/scoreboard.wasm");
+}
+
+use badge_token_contract::Client as BadgeTokenClient;
+use scorer_contract::{Client as ScorerClient, Extension, UserStatus};
+use scoreboard_contract::Client as ScoreboardClient;
+
+// A full page: `SCOREBOARD_PAGE_SIZE` users
+const PAGE_SIZE: u32 = 5;
+
+// Upper bounds of a page, whatever the number of users: (cpu instructions, memory bytes).
+// They leave some headroom over the measured costs; update them deliberately when a change
+// is expected to move costs.
+const MAX_PAGE_COST: (u64, u64) = (85_000_000, 36_000_000);
+
+struct Bench {
+    env: Env,
+    client: ScoreboardClient<'static>,
+}
+
+impl Bench {
+    // Deploys a scorer with its badge token and scoreboard, and seeds `size` active users
+    // directly in the scorer's storage, which is much faster than calling
+    // `add_user` `size` times.
+    fn new(size: u32) -> Self {
+        // A snapshot of thousands of entries takes longer to write than the bench to run
+        let env = Env::new_with_config(EnvTestConfig { capture_snapshot_at_drop: false });
+        env.mock_all_auths();
+        env.cost_estimate().budget().reset_unlimited();
+
+        let owner = Address::generate(&env);
+        let scorer = ScorerClient::new(&env, &env.register(scorer_contract::WASM, ()));
+        scorer.initialize(
+            &owner,
+            &Map::new(&env),
+            &String::from_str(&env, "Scorer"),
+            &String::from_str(&env, "A benchmarked scorer"),
+            &String::from_str(&env, "icon.png"),
+        );
+        let token = BadgeTokenClient::new(&env, &env.register(badge_token_contract::WASM, ()));
+        token.initialize(&scorer.address, &String::from_str(&env, "Badges"), &String::from_str(&env, "BDG"));
+        scorer.set_extension(&owner, &Extension::BadgeToken, &Some(token.address.clone()));
+        env.as_contract(&scorer.address, || {
+            let storage = env.storage().persistent();
+            for index in 0..size {
+                let user = Address::generate(&env);
+                storage.set(&(Symbol::new(&env, "UserAt"), index), &user);
+                storage.set(&(Symbol::new(&env, "UserStatus"), user), &UserStatus::Active);
+            }
+            storage.set(&(Symbol::new(&env, "UserCount"),), &size);
+        });
+
+        let client = ScoreboardClient::new(&env, &env.register(scoreboard_contract::WASM, ()));
+        client.initialize(&scorer.address);
+
+        Bench { env, client }
+    }
+
+    // Returns the cpu instructions and memory bytes consumed by the last page,
+    // within the network's default budget.
+    fn measure_last_page(&self, size: u32) -> (u64, u64) {
+        self.env.cost_estimate().budget().reset_default();
+        let page = self.client.get_scoreboard(&(size - PAGE_SIZE), &PAGE_SIZE);
+        assert_eq!(page.len(), PAGE_SIZE);
+        (
+            self.env.cost_estimate().budget().cpu_instruction_cost(),
+            self.env.cost_estimate().budget().memory_bytes_cost(),
+        )
+    }
+}
+
+#[test]
+fn budget_scoreboard_page() {
+    let mut costs = std::vec::Vec::new();
+    for size in [100, 1_000] {
+        let (cpu, mem) = Bench::new(size).measure_last_page(size);
+        std::println!("get_scoreboard {:>6} users: {:>12} cpu {:>12} mem", size, cpu, mem);
+        assert!(cpu <= MAX_PAGE_COST.0, "a page of {} users used {} cpu instructions (max {})", size, cpu, MAX_PAGE_COST.0);
+        assert!(mem <= MAX_PAGE_COST.1, "a page of {} users used {} memory bytes (max {})", size, mem, MAX_PAGE_COST.1);
+        costs.push(cpu);
+    }
+
+    // The page of the large scorer costs about as much as the one of the small scorer
+    assert!(costs[1] < costs[0] + costs[0] / 10, "a page cost {} cpu instructions with 1k users, {} with 100", costs[1], costs[0]);
+}
@@ -1,521 +1,1252 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, String, Vec};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, unwrap::UnwrapOptimized, vec, Address, BytesN, Env, IntoVal, InvokeError, Map, String, Symbol, Val, Vec};
 
-// Event topics
-const TOPIC_USER: &str = "user";
-const TOPIC_MANAGER: &str = "manager";
-const TOPIC_UPGRADE: &str = "upgrade";
-const TOPIC_INIT: &str = "init";
-const TOPIC_BADGE: &str = "badge";
+pub use scorer_interface::{events, AnalyticsClient, ArchivedEntry, BadgeId, BadgeLogClient, DisputesClient, Extension, InitialMembers, MaintenanceTask, MaintenanceTrait, MembershipClient, ScoreResolverClient, ScorerClient, ScorerTrait, StakingClient, UserStatus, MAX_BADGE_NAME_LENGTH, MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH, MAX_URI_LENGTH, TTL_THRESHOLD};
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct BadgeId {
-    pub name: String,
-    pub issuer: Address,
-}
+use scorer_interface::events::{EVENT_SCHEMA_VERSION, TOPIC_USER, TOPIC_MANAGER, TOPIC_UPGRADE, TOPIC_INIT, TOPIC_BADGE, TOPIC_STATUS, TOPIC_OWNER};
+
+// Version of the storage layout written by this code. Scorers initialized by
+// releases without a stored version are at version 1. Version 2 stores each
+// user with its `UserStatus`, and each manager, under its own index instead of
+// in a single map or list.
+const STORAGE_VERSION: u32 = 2;
+
+// Version this code declares through `contract_version`, bumped by every release
+pub const CONTRACT_VERSION: u32 = 1;
 
 #[contracttype]
 enum DataKey {
     ScorerCreator,
     ScorerBadges,
+    // The users and whether they are active, stored by version 1 and replaced
+    // by `UserAt` and `UserStatus` by `migrate`
     Users,
+    // The list of managers, stored by version 1 and replaced by `ManagerAt` by `migrate`
     Managers,
-    Initialized,
     Name,
     Description,
-    Icon
+    Icon,
+    UpgradeGuard,
+    Archived,
+    StorageVersion,
+    JoinedAt(Address),
+    MembershipExpiry,
+    ImportedBadges,
+    DisabledBadges,
+    CodeVersion,
+    WasmHash,
+    UpgradedFrom,
+    Extension(Extension),
+    PokeCursor,
+    UserNonce(Address),
+    // Whether the scorer is initialized, stored by version 1 and removed by `migrate`
+    Initialized,
+    // The number of managers, stored at indexes 0 to the count
+    ManagerCount,
+    ManagerAt(u32),
+    // The index of a manager
+    ManagerIndex(Address),
+    // The number of users who ever joined, stored at indexes 0 to the count
+    UserCount,
+    UserAt(u32),
+    UserStatus(Address),
 }
 
 #[contract]
 pub struct ScorerContract;
 
-#[contracttype]
-#[derive(Debug)]
-enum Error {
-    ContractAlreadyInitialized,
-    Unauthorized,
-    ManagerAlreadyExists,
-    ManagerNotFound,
-    ManagersNotFound,
-    ScorerCreatorDoesNotExist,
-    UserAlreadyExist,
-    UserDoesNotExist,
-    BadgeAlreadyExists,
-    BadgeNotFound,
-    InvalidScoreRange,
-    EmptyArg,
-    ScorerCreatorNotFound,
+// Errors raised by the scorer. Entrypoints fail with `panic_with_error!`, so
+// the error code can be read from the transaction result. Codes of errors
+// now raised by the membership, disputes and governance contracts are not reused.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    // The sender is not allowed to perform the operation.
+    Unauthorized = 2,
+    // The address is already a manager.
+    ManagerAlreadyExists = 3,
+    // The address is not a manager.
+    ManagerNotFound = 4,
+    // The managers list is missing from storage.
+    ManagersNotFound = 5,
+    // The scorer creator is missing from storage.
+    ScorerCreatorDoesNotExist = 6,
+    // The user is already active.
+    UserAlreadyExist = 7,
+    // The user does not exist or is inactive.
+    UserDoesNotExist = 8,
+    // A badge with this name and issuer already exists.
+    BadgeAlreadyExists = 9,
+    // No badge with this name and issuer exists.
+    BadgeNotFound = 10,
+    // The badge score is outside the allowed range.
+    InvalidScoreRange = 11,
+    // A required string argument is empty.
+    EmptyArg = 12,
+    // The scorer creator is missing from storage.
+    ScorerCreatorNotFound = 13,
+    // The upgrade guard was already enabled.
+    UpgradeGuardAlreadySet = 14,
+    // The upgrade guard's factory has not approved the WASM hash.
+    UpgradeNotApproved = 15,
+    // The issuer registry has not verified the badge issuer.
+    IssuerNotVerified = 16,
+    // The scorer is archived.
+    ScorerArchived = 17,
+    // The manager is the last one.
+    CannotRemoveLastManager = 18,
+    // The storage is already at the current version.
+    AlreadyMigrated = 19,
+    // A governance contract is registered, so badge changes must be voted there.
+    GovernanceRequired = 24,
+    // A membership contract is registered, so users must join through it.
+    ApprovalRequired = 29,
+    // The import weight exceeds 100, or the scorer imports its own badges.
+    InvalidBadgeImport = 38,
+    // The user was banned by a manager.
+    UserBanned = 40,
+    // The scorer name is longer than `MAX_NAME_LENGTH` bytes.
+    NameTooLong = 44,
+    // The scorer description is longer than `MAX_DESCRIPTION_LENGTH` bytes.
+    DescriptionTooLong = 45,
+    // The scorer icon is longer than `MAX_URI_LENGTH` bytes.
+    IconTooLong = 46,
+    // A badge name is longer than `MAX_BADGE_NAME_LENGTH` bytes.
+    BadgeNameTooLong = 47,
 }
 
+// Plain comments rather than doc comments on entrypoints and errors, here and
+// in the contracts listed under Test in the workspace README, keep the WASM small.
 #[contractimpl]
-impl ScorerContract {
-    /// Contract constructor
-    /// 
-    /// # Arguments
-    /// * `env` - The Soroban environment
-    /// * `scorer_creator` - The address of the contract creator who will be the initial manager
-    /// * `scorer_badges` - The initial set of badges for the contract
-    /// * `name` - The name of the scorer
-    /// * `description` - The description of the scorer
-    /// * `icon` - The icon URL or identifier for the scorer
-    /// 
-    /// # Panics
-    /// * When the contract is already initialized
-    /// * When any of the required string arguments are empty
-    /// * When the scorer_creator fails authentication
-    pub fn initialize(env: Env, scorer_creator: Address, scorer_badges: Map<BadgeId, u32>, name: String, description: String, icon: String) {
-        if name.is_empty() || description.is_empty() || icon.is_empty() {
-            panic!("{:?}", Error::EmptyArg);
+impl ScorerTrait for ScorerContract {
+    // Contract constructor
+    fn initialize(env: Env, scorer_creator: Address, scorer_badges: Map<BadgeId, u32>, name: String, description: String, icon: String) {
+        let members = InitialMembers { managers: Vec::new(&env), users: Vec::new(&env) };
+        Self::init(&env, scorer_creator, members, scorer_badges, name, description, icon);
+    }
+
+    // Contract constructor recording initial managers and users, added on the
+    // creator's authority
+    fn initialize_with_members(env: Env, scorer_creator: Address, members: InitialMembers, scorer_badges: Map<BadgeId, u32>, name: String, description: String, icon: String) {
+        Self::init(&env, scorer_creator, members, scorer_badges, name, description, icon);
+    }
+
+    // Returns the version this code declares
+    fn contract_version() -> u32 {
+        CONTRACT_VERSION
+    }
+
+    // Upgrades the contract's WASM code to a new version
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let admin: Address = env.storage()
+            .persistent()
+            .get(&DataKey::ScorerCreator)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerCreatorNotFound));
+
+        admin.require_auth();
+
+        // Only allow audited hashes when the scorer opted into the factory allowlist.
+        // The factory adds the upgrade to the scorer's history as it approves it
+        if let Some(factory) = Self::get_upgrade_guard(env.clone()) {
+            let approved: bool = env.invoke_contract(
+                &factory,
+                &Symbol::new(&env, "record_upgrade"),
+                vec![&env, env.current_contract_address().into_val(&env), new_wasm_hash.clone().into_val(&env)],
+            );
+            if !approved {
+                panic_with_error!(&env, Error::UpgradeNotApproved);
+            }
         }
 
-        // Ensure that the contract is not initialized
-        if Self::is_initialized(&env) {
-            panic!("{:?}", Error::ContractAlreadyInitialized);
+        // Only the new code knows the version it declares, so `migrate` reports
+        // the upgrade once it runs
+        let storage = env.storage().persistent();
+        let old_wasm_hash: BytesN<32> = storage
+            .get(&DataKey::WasmHash)
+            .unwrap_or_else(|| BytesN::from_array(&env, &[0; 32]));
+        storage.set(&DataKey::CodeVersion, &Self::contract_version());
+        storage.set(&DataKey::UpgradedFrom, &old_wasm_hash);
+        storage.set(&DataKey::WasmHash, &new_wasm_hash);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    // Records the version the upgraded code declares and migrates the storage
+    // written by a previous release to the current layout, right after `upgrade`.
+    // The users are migrated from `cursor` in batches of `limit`
+    fn migrate(env: Env, sender: Address, cursor: u32, limit: u32) -> u32 {
+        sender.require_auth();
+
+        if !Self::is_owner(&env, &sender) {
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
-        // Ensure that the scorer creator is the sender
-        scorer_creator.require_auth();
+        let storage = env.storage().persistent();
+        let upgraded_from: Option<BytesN<32>> = storage.get(&DataKey::UpgradedFrom);
+        let version = Self::get_storage_version(env.clone());
+        if version >= STORAGE_VERSION && upgraded_from.is_none() {
+            panic_with_error!(&env, Error::AlreadyMigrated);
+        }
 
-        // Create initial managers list with scorer_creator
-        let mut initial_managers = Vec::<Address>::new(&env);
-        initial_managers.push_back(scorer_creator.clone());
+        if let Some(old_wasm_hash) = upgraded_from {
+            storage.remove(&DataKey::UpgradedFrom);
+            Self::publish(&env, TOPIC_UPGRADE, symbol_short!("wasm"), events::ScorerUpgraded {
+                old_version: storage.get(&DataKey::CodeVersion).unwrap_optimized(),
+                new_version: CONTRACT_VERSION,
+                old_wasm_hash,
+                new_wasm_hash: storage.get(&DataKey::WasmHash).unwrap_optimized(),
+            }.into_val(&env));
+        }
+        storage.set(&DataKey::CodeVersion, &CONTRACT_VERSION);
+        if version >= STORAGE_VERSION {
+            return 0;
+        }
 
-        // Store initial state
-        env.storage().persistent().set(&DataKey::ScorerCreator, &scorer_creator);
-        env.storage().persistent().set(&DataKey::ScorerBadges, &scorer_badges);
-        env.storage().persistent().set(&DataKey::Users, &Map::<Address, bool>::new(&env));
-        env.storage().persistent().set(&DataKey::Managers, &initial_managers);
-        env.storage().persistent().set(&DataKey::Initialized, &true);
-        env.storage().persistent().set(&DataKey::Name, &name);
-        env.storage().persistent().set(&DataKey::Description, &description);
-        env.storage().persistent().set(&DataKey::Icon, &icon);
+        // Version 2 stores each user and its status, and each manager, under
+        // its own index, and no longer stores whether the scorer is
+        // initialized. The managers are moved by the first batch, and the
+        // users by all of them, at their index in the map of version 1
+        let users = Self::legacy_users(&env);
+        let cursor = if cursor < users.len() { cursor } else { 0 };
+        let end = cursor.saturating_add(limit).min(users.len());
+        if cursor == 0 {
+            storage.remove(&DataKey::Initialized);
+            if let Some(managers) = storage.get::<DataKey, Vec<Address>>(&DataKey::Managers) {
+                for manager in managers.iter() {
+                    Self::push_manager(&env, &manager);
+                }
+                storage.remove(&DataKey::Managers);
+            }
+            storage.set(&DataKey::UserCount, &Self::user_count(&env));
+        }
+        let keys = users.keys();
+        for index in cursor..end {
+            let user = keys.get_unchecked(index);
+            // Users who joined or left since the upgrade already have a status
+            let key = DataKey::UserStatus(user.clone());
+            if !storage.has(&key) {
+                storage.set(&key, &Self::legacy_status(users.get_unchecked(user.clone())));
+            }
+            storage.set(&DataKey::UserAt(index), &user);
+        }
+        if end < users.len() {
+            return end;
+        }
+        storage.remove(&DataKey::Users);
+        storage.set(&DataKey::StorageVersion, &STORAGE_VERSION);
 
-        // Emit a initialization event
-        env.events().publish(
-            (TOPIC_INIT, symbol_short!("contract")),
-            (scorer_creator, initial_managers, scorer_badges, name, description, icon),
-        );
+        Self::publish(&env, TOPIC_UPGRADE, symbol_short!("migrate"), events::ScorerMigrated { sender, from_version: version, to_version: STORAGE_VERSION }.into_val(&env));
+        0
     }
 
-    
-    /// Returns the current version of the contract
-    /// 
-    /// # Returns
-    /// * `u32` - The version number (currently 1)
-    pub fn contract_version() -> u32 {
-        1
+    // Retrieves the version of the storage layout
+    fn get_storage_version(env: Env) -> u32 {
+        env.storage().persistent().get(&DataKey::StorageVersion).unwrap_or(1)
     }
 
-    /// Upgrades the contract's WASM code to a new version
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// * `new_wasm_hash` - The hash of the new WASM code to upgrade to (32 bytes)
-    /// 
-    /// # Authorization
-    /// * Only the contract admin (scorer_creator) can perform the upgrade
-    /// 
-    /// # Panics
-    /// * If the caller is not the admin
-    /// * If the admin address cannot be found in storage
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = env.storage()
-            .persistent()
-            .get(&DataKey::ScorerCreator)
-            .unwrap_or_else(|| panic!("{:?}", Error::ScorerCreatorNotFound));
-        
-        admin.require_auth();
-        
-        // Emit event before upgrade
-        env.events().publish(
-            (TOPIC_UPGRADE, symbol_short!("wasm")),
-            new_wasm_hash.clone(),
-        );
-        
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    // Retrieves the version of the event payloads, published as the first
+    // topic of every event so indexers can branch their decoding across upgrades
+    fn get_event_schema_version(_env: Env) -> u32 {
+        EVENT_SCHEMA_VERSION
     }
 
-    /// Checks if a contract has been initialized
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// 
-    /// # Returns
-    /// * `bool` - True if the contract is initialized, false otherwise
-    fn is_initialized(env: &Env) -> bool {
-        env.storage().persistent().get(&DataKey::Initialized).unwrap_or(false)
+    // Opts the contract into the factory's allowlist of audited upgrade hashes.
+    // The guard cannot be disabled, so a compromised key cannot opt back out
+    fn enable_upgrade_guard(env: Env, sender: Address, factory: Address) {
+        sender.require_auth();
+
+        if !Self::is_owner(&env, &sender) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        if env.storage().persistent().has(&DataKey::UpgradeGuard) {
+            panic_with_error!(&env, Error::UpgradeGuardAlreadySet);
+        }
+
+        env.storage().persistent().set(&DataKey::UpgradeGuard, &factory);
+
+        Self::publish(&env, TOPIC_UPGRADE, symbol_short!("guard"), events::UpgradeGuardEnabled { sender, factory }.into_val(&env));
     }
 
-    /// Helper function to check if an address is the contract owner
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object
-    /// * `address` - The address to check
-    /// 
-    /// # Returns
-    /// * `bool` - True if the address is the contract owner
-    fn is_owner(env: &Env, address: &Address) -> bool {
-        let owner = env.storage()
-            .persistent()
-            .get::<DataKey, Address>(&DataKey::ScorerCreator)
-            .unwrap_or_else(|| panic!("{:?}", Error::ScorerCreatorNotFound));
-        
-        &owner == address
+    // Retrieves the factory whose allowlist guards upgrades, if any
+    fn get_upgrade_guard(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::UpgradeGuard)
     }
 
-    /// Retrieves the list of managers and checks if a specific manager exists
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// * `manager` - The address to check for existence in the managers list
-    /// 
-    /// # Returns
-    /// * `(bool, Vec<Address>)` - A tuple containing:
-    ///   - bool: Whether the manager exists in the list
-    ///   - Vec<Address>: The complete list of managers
-    fn manager_exists(env: &Env, manager: &Address) -> (bool, Vec<Address>) {
-        let managers = env.storage()
-            .persistent()
-            .get::<DataKey, Vec<Address>>(&DataKey::Managers)
-            .unwrap_or_else(|| Vec::new(env));
-        
-        let exists = managers.iter().any(|m| m == *manager);
-        (exists, managers)
+    // Registers or clears the contract extending the scorer with a feature,
+    // see `Extension`
+    fn set_extension(env: Env, sender: Address, extension: Extension, address: Option<Address>) {
+        sender.require_auth();
+
+        if !Self::is_owner(&env, &sender) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        let key = DataKey::Extension(extension);
+        match address.clone() {
+            Some(address) => env.storage().persistent().set(&key, &address),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        Self::publish(&env, TOPIC_STATUS, symbol_short!("extension"), events::ExtensionSet { sender, extension, address }.into_val(&env));
     }
 
-    /// Adds a new manager to the contract
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// * `sender` - The address of the account attempting to add the manager
-    /// * `new_manager` - The address of the new manager to be added
-    /// 
-    /// # Panics
-    /// * If the sender is not the scorer creator (`Error::Unauthorized`)
-    /// * If the manager already exists (`Error::ManagerAlreadyExists`)
-    pub fn add_manager(env: Env, sender: Address, new_manager: Address) {
+    // Retrieves the contract registered for an extension, if any
+    fn get_extension(env: Env, extension: Extension) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Extension(extension))
+    }
+
+    // Marks the contract as archived (retired) or active again. Archived
+    // contracts keep their state but reject new users
+    fn set_archived(env: Env, sender: Address, archived: bool) {
         sender.require_auth();
-        
+
+        if !Self::is_owner(&env, &sender) && Self::get_extension(env.clone(), Extension::Factory) != Some(sender.clone()) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        env.storage().persistent().set(&DataKey::Archived, &archived);
+
+        Self::publish(&env, TOPIC_STATUS, symbol_short!("archived"), events::ArchivedSet { sender, archived }.into_val(&env));
+    }
+
+    // Checks if the contract has been archived
+    fn is_archived(env: Env) -> bool {
+        env.storage().persistent().get(&DataKey::Archived).unwrap_or(false)
+    }
+
+    // Adds a new manager to the contract
+    fn add_manager(env: Env, sender: Address, new_manager: Address) {
+        sender.require_auth();
+
         if !Self::is_owner(&env, &sender) {
-            panic!("{:?}", Error::Unauthorized);
+            panic_with_error!(&env, Error::Unauthorized);
         }
 
-        let (exists, mut managers) = Self::manager_exists(&env, &new_manager);
-        if exists {
-            panic!("{:?}", Error::ManagerAlreadyExists);
+        if Self::is_manager(&env, &new_manager) {
+            panic_with_error!(&env, Error::ManagerAlreadyExists);
         }
-        
-        managers.push_back(new_manager.clone());
-        env.storage().persistent().set(&DataKey::Managers, &managers);
+
+        Self::push_manager(&env, &new_manager);
 
         // Emit event for manager addition
-        env.events().publish(
-            (TOPIC_MANAGER, symbol_short!("add")),
-            (sender, new_manager),
-        );
+        Self::publish(&env, TOPIC_MANAGER, symbol_short!("add"), events::ManagerAdded { sender, manager: new_manager }.into_val(&env));
     }
 
-    /// Removes a manager from the contract
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// * `sender` - The address of the account attempting to remove the manager
-    /// * `manager_to_remove` - The address of the manager to be removed
-    /// 
-    /// # Panics
-    /// * If the sender is not the scorer creator (`Error::Unauthorized`)
-    /// * If the manager does not exist (`Error::ManagerNotFound`)
-    pub fn remove_manager(env: Env, sender: Address, manager_to_remove: Address) {
+    // Removes a manager from the contract
+    fn remove_manager(env: Env, sender: Address, manager_to_remove: Address) {
         sender.require_auth();
-        
+
         if !Self::is_owner(&env, &sender) {
-            panic!("{:?}", Error::Unauthorized);
+            panic_with_error!(&env, Error::Unauthorized);
         }
-        
-        let (exists, mut managers) = Self::manager_exists(&env, &manager_to_remove);
-        if !exists {
-            panic!("{:?}", Error::ManagerNotFound);
+
+        if !Self::is_manager(&env, &manager_to_remove) {
+            panic_with_error!(&env, Error::ManagerNotFound);
         }
-        
-        if let Some(index) = managers.iter().position(|m| m == manager_to_remove) {
-            managers.remove(index as u32);
-            env.storage().persistent().set(&DataKey::Managers, &managers);
+
+        if Self::manager_count(&env) == 1 {
+            panic_with_error!(&env, Error::CannotRemoveLastManager);
         }
 
+        Self::pop_manager(&env, &manager_to_remove);
+
         // Emit event for manager removal
-        env.events().publish(
-            (TOPIC_MANAGER, symbol_short!("remove")),
-            (sender, manager_to_remove),
-        );
+        Self::publish(&env, TOPIC_MANAGER, symbol_short!("remove"), events::ManagerRemoved { sender, manager: manager_to_remove }.into_val(&env));
     }
 
-    /// Adds a new user to the contract's user registry
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// * `user` - The address of the user to be added
-    /// 
-    /// # Authorization
-    /// * Requires authorization from the user being added
-    /// 
-    /// # Panics
-    /// * If the user already exists and is active (`Error::UserAlreadyExist`)
-    pub fn add_user(env: Env, user: Address) {
-        user.require_auth();
+    // Transfers ownership of the contract (the scorer creator role) to another
+    // address, which replaces the previous owner among the managers
+    fn transfer_ownership(env: Env, sender: Address, new_owner: Address) {
+        sender.require_auth();
 
-        let mut users = env.storage()
-            .persistent()
-            .get::<DataKey, Map<Address, bool>>(&DataKey::Users)
-            .unwrap_or_else(|| Map::new(&env));
+        if !Self::is_owner(&env, &sender) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
 
-        // Check if user already exists and is active
-        if users.contains_key(user.clone()) && users.get(user.clone()).unwrap() {
-            panic!("{:?}", Error::UserAlreadyExist);
+        if Self::is_manager(&env, &sender) {
+            Self::pop_manager(&env, &sender);
+        }
+        if !Self::is_manager(&env, &new_owner) {
+            Self::push_manager(&env, &new_owner);
         }
 
-        users.set(user.clone(), true);
-        env.storage().persistent().set(&DataKey::Users, &users);
+        env.storage().persistent().set(&DataKey::ScorerCreator, &new_owner);
 
-        // Emit event for user addition
-        env.events().publish(
-            (TOPIC_USER, symbol_short!("add")),
-            user,
-        );
+        Self::publish(&env, TOPIC_OWNER, symbol_short!("transfer"), events::OwnershipTransferred { sender, new_owner }.into_val(&env));
     }
 
-    /// Removes a user from the contract's user registry
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// * `user` - The address of the user to be removed
-    /// 
-    /// # Authorization
-    /// * Requires authorization from the user
-    /// 
-    /// # Panics
-    /// * If the user does not exist or is already inactive (`Error::UserDoesNotExist`)
-    pub fn remove_user(env: Env, user: Address) {
-        user.require_auth();
-        
-        let mut users = env.storage()
-            .persistent()
-            .get::<DataKey, Map<Address, bool>>(&DataKey::Users)
-            .unwrap_or_else(|| panic!("{:?}", Error::UserDoesNotExist));
+    // Adds a new user to the contract's user registry
+    fn add_user(env: Env, user: Address) {
+        Self::require_user_auth(&env, &user);
 
-        // Check if user doesn't exist or is already inactive
-        if !users.contains_key(user.clone()) || !users.get(user.clone()).unwrap() {
-            panic!("{:?}", Error::UserDoesNotExist);
+        if Self::is_archived(env.clone()) {
+            panic_with_error!(&env, Error::ScorerArchived);
         }
-        
-        users.set(user.clone(), false);
-        env.storage().persistent().set(&DataKey::Users, &users);
 
-        // Emit event for user removal
-        env.events().publish(
-            (TOPIC_USER, symbol_short!("remove")),
-            user,
-        );
+        if Self::get_extension(env.clone(), Extension::Membership).is_some() {
+            panic_with_error!(&env, Error::ApprovalRequired);
+        }
+
+        Self::activate_user(&env, user);
     }
 
-    /// Retrieves the complete map of users and their status
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// 
-    /// # Returns
-    /// * `Map<Address, bool>` - A map where:
-    ///   - Key: User's address
-    ///   - Value: User's status (true = active, false = inactive)
-    pub fn get_users(env: Env) -> Map<Address, bool> {
+    // Activates a user admitted by the membership contract, unless it is an
+    // active member, and sets the last ledger of its membership
+    fn admit_user(env: Env, sender: Address, user: Address, expires_at: Option<u32>) {
+        Self::require_membership(&env, &sender);
+
+        if Self::is_archived(env.clone()) {
+            panic_with_error!(&env, Error::ScorerArchived);
+        }
+
+        if !Self::is_active_user(&env, &user) {
+            Self::activate_user(&env, user.clone());
+        }
+
+        let mut expiries = Self::membership_expiries(&env);
+        match expires_at {
+            Some(expires_at) => expiries.set(user, expires_at),
+            None => expiries.remove(user).unwrap_or_default(),
+        }
+        env.storage().persistent().set(&DataKey::MembershipExpiry, &expiries);
+    }
+
+    // Bans a user, or lifts its ban, for the membership contract. Banned
+    // members leave the scorer
+    fn dismiss_user(env: Env, sender: Address, user: Address, ban: bool) {
+        Self::require_membership(&env, &sender);
+
+        let status = Self::status_of(&env, &user);
+        let next = match (status, ban) {
+            (Some(UserStatus::Banned), true) => panic_with_error!(&env, Error::UserBanned),
+            (_, true) => UserStatus::Banned,
+            (Some(UserStatus::Banned | UserStatus::Active), false) => UserStatus::Left,
+            _ => panic_with_error!(&env, Error::UserDoesNotExist),
+        };
+        Self::set_status(&env, &user, status, next);
+
+        if status == Some(UserStatus::Active) {
+            Self::leave(&env, user);
+        }
+    }
+
+    // Removes a user from the contract's user registry. The membership contract,
+    // if any, is told about the departure
+    fn remove_user(env: Env, user: Address) {
+        Self::require_user_auth(&env, &user);
+
+        // Check if user doesn't exist or is not active
+        let status = Self::status_of(&env, &user);
+        if status != Some(UserStatus::Active) {
+            panic_with_error!(&env, Error::UserDoesNotExist);
+        }
+
+        Self::set_status(&env, &user, status, UserStatus::Left);
+
+        if let Some(membership) = Self::get_extension(env.clone(), Extension::Membership) {
+            MembershipClient::new(&env, &membership).record_departure(&user);
+        }
+        Self::leave(&env, user);
+    }
+
+    // Retrieves the last ledger at which a membership is active
+    fn get_membership_expiry(env: Env, user: Address) -> Option<u32> {
+        Self::membership_expiries(&env).get(user)
+    }
+
+    // Sweeps the expired members among the next `limit` users, resuming from
+    // the cursor the previous call stored and wrapping around after the last user
+    fn poke(env: Env, limit: u32) -> u32 {
+        let cursor = env.storage().persistent().get(&DataKey::PokeCursor).unwrap_or(0);
+        let (expired, next) = Self::run_task(&env, MaintenanceTask::SweepExpired, cursor, limit);
+        if next != cursor {
+            env.storage().persistent().set(&DataKey::PokeCursor, &next);
+        }
+        expired
+    }
+
+    // Extends the storage entries of a user or a badge to the network maximum,
+    // whatever their TTL, once a `RestoreFootprint` operation brought them back.
+    // Anyone can call it
+    fn restore_entry(env: Env, entry: ArchivedEntry) -> u32 {
+        let storage = env.storage().persistent();
+        let max_ttl = env.storage().max_ttl();
+        let mut restored = 0;
+        let mut extend = |key: DataKey| {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, max_ttl, max_ttl);
+                restored += 1;
+            }
+        };
+
+        match entry {
+            ArchivedEntry::User(user) => {
+                if Self::status_of(&env, &user).is_none() {
+                    panic_with_error!(&env, Error::UserDoesNotExist);
+                }
+                extend(DataKey::UserStatus(user.clone()));
+                extend(DataKey::MembershipExpiry);
+                extend(DataKey::JoinedAt(user.clone()));
+                extend(DataKey::UserNonce(user));
+            }
+            ArchivedEntry::Badge(badge_id) => {
+                if !Self::get_badges(env.clone()).contains_key(badge_id) {
+                    panic_with_error!(&env, Error::BadgeNotFound);
+                }
+                extend(DataKey::ScorerBadges);
+                extend(DataKey::DisabledBadges);
+            }
+        }
+        restored
+    }
+
+    // Retrieves the ledger at which a user last joined
+    fn get_joined_at(env: Env, user: Address) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::JoinedAt(user))
+    }
+
+    // Retrieves the nonce the next `add_user` or `remove_user` authorization
+    // of a user commits to
+    fn get_user_nonce(env: Env, user: Address) -> u32 {
+        env.storage().persistent().get(&DataKey::UserNonce(user)).unwrap_or(0)
+    }
+
+    // Retrieves the complete map of users and their status, reading the entries
+    // of every user. Members whose membership expired are reported as `Left`
+    // until they renew
+    fn get_users(env: Env) -> Map<Address, UserStatus> {
+        let expiries = Self::membership_expiries(&env);
+        let mut users = Map::new(&env);
+        for user in Self::users_page(&env, 0, u32::MAX).iter() {
+            users.set(user.clone(), Self::reported_status(&env, &expiries, &user));
+        }
+        users
+    }
+
+    // Retrieves the number of users who ever joined
+    fn get_user_count(env: Env) -> u32 {
+        Self::user_count(&env)
+    }
+
+    // Retrieves up to `limit` users from index `offset` and their status, reading
+    // only their entries. Members whose membership expired are reported as `Left`
+    fn get_users_page(env: Env, offset: u32, limit: u32) -> Vec<(Address, UserStatus)> {
+        let expiries = Self::membership_expiries(&env);
+        let mut users = Vec::new(&env);
+        for user in Self::users_page(&env, offset, limit).iter() {
+            let status = Self::reported_status(&env, &expiries, &user);
+            users.push_back((user, status));
+        }
+        users
+    }
+
+    // Retrieves the status of a user. A member whose membership expired is
+    // reported as `Left` until it renews
+    fn get_user_status(env: Env, user: Address) -> Option<UserStatus> {
+        match Self::status_of(&env, &user) {
+            Some(UserStatus::Active) if Self::membership_expired(&env, &user) => Some(UserStatus::Left),
+            status => status,
+        }
+    }
+
+    // Retrieves all scorer badges from the contract's storage
+    fn get_badges(env: Env) -> Map<BadgeId, u32> {
         env.storage()
             .persistent()
-            .get::<DataKey, Map<Address, bool>>(&DataKey::Users)
+            .get::<DataKey, Map<BadgeId, u32>>(&DataKey::ScorerBadges)
             .unwrap_or_else(|| Map::new(&env))
     }
 
-    /// Retrieves all scorer badges from the contract's storage
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// 
-    /// # Returns
-    /// * `Map<BadgeId, u32>` - A map where:
-    ///   - Key: Badge ID (BadgeId struct)
-    ///   - Value: Badge score value
-    pub fn get_badges(env: Env) -> Map<BadgeId, u32> {
+    // Enables or disables a badge. A disabled badge keeps its score and
+    // attestations but counts for nothing in `get_score`, and its token cannot
+    // be minted, until it is enabled again
+    fn set_badge_enabled(env: Env, sender: Address, badge_id: BadgeId, enabled: bool) {
+        Self::require_manager(&env, &sender);
+
+        if !Self::get_badges(env.clone()).contains_key(badge_id.clone()) {
+            panic_with_error!(&env, Error::BadgeNotFound);
+        }
+
+        let mut disabled = Self::disabled_badges(&env);
+        match (disabled.first_index_of(&badge_id), enabled) {
+            (Some(index), true) => {
+                disabled.remove(index);
+            }
+            (None, false) => disabled.push_back(badge_id.clone()),
+            _ => {}
+        }
+        env.storage().persistent().set(&DataKey::DisabledBadges, &disabled);
+
+        Self::publish(&env, TOPIC_BADGE, symbol_short!("enabled"), events::BadgeEnabledSet { sender, badge_id, enabled }.into_val(&env));
+    }
+
+    // Checks whether a badge was not disabled
+    fn is_badge_enabled(env: Env, badge_id: BadgeId) -> bool {
+        !Self::disabled_badges(&env).contains(&badge_id)
+    }
+
+    // Retrieves all the managers from the contract
+    fn get_managers(env: Env) -> Vec<Address> {
+        let count = Self::manager_count(&env);
+        if count == 0 {
+            panic_with_error!(&env, Error::ManagersNotFound);
+        }
+        Self::get_managers_page(env, 0, count)
+    }
+
+    // Retrieves the number of managers
+    fn get_manager_count(env: Env) -> u32 {
+        Self::manager_count(&env)
+    }
+
+    // Retrieves up to `limit` managers from index `offset`, reading only their entries
+    fn get_managers_page(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        let count = Self::manager_count(&env);
+        let start = offset.min(count);
+        let end = start.saturating_add(limit).min(count);
+        let mut managers = Vec::new(&env);
+        for index in start..end {
+            managers.push_back(env.storage().persistent().get(&DataKey::ManagerAt(index)).unwrap_optimized());
+        }
+        managers
+    }
+
+    // Retrieves the address of the contract creator
+    fn get_contract_owner(env: Env) -> Address {
         env.storage()
             .persistent()
-            .get::<DataKey, Map<BadgeId, u32>>(&DataKey::ScorerBadges)
+            .get::<DataKey, Address>(&DataKey::ScorerCreator)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerCreatorDoesNotExist))
+    }
+
+    // Adds a new badge to the contract
+    fn add_badge(env: Env, sender: Address, name: String, issuer: Address, score: u32) {
+        Self::authorize_badge_change(&env, &sender);
+
+        let badge_id = BadgeId { name, issuer };
+        if badge_id.name.is_empty() {
+            panic_with_error!(&env, Error::EmptyArg);
+        }
+        Self::check_length(&env, &badge_id.name, MAX_BADGE_NAME_LENGTH, Error::BadgeNameTooLong);
+
+        if score > 10000 {
+            panic_with_error!(&env, Error::InvalidScoreRange);
+        }
+
+        // Validate the issuer against the shared registry when one is configured
+        if let Some(registry) = Self::get_extension(env.clone(), Extension::IssuerRegistry) {
+            let verified: bool = env.invoke_contract(
+                &registry,
+                &Symbol::new(&env, "is_verified_issuer"),
+                vec![&env, badge_id.issuer.clone().into_val(&env)],
+            );
+            if !verified {
+                panic_with_error!(&env, Error::IssuerNotVerified);
+            }
+        }
+
+        let mut badges = Self::get_badges(env.clone());
+        if badges.contains_key(badge_id.clone()) {
+            panic_with_error!(&env, Error::BadgeAlreadyExists);
+        }
+
+        badges.set(badge_id.clone(), score);
+        env.storage().persistent().set(&DataKey::ScorerBadges, &badges);
+        Self::log_badge_change(&env, &badge_id, None, Some(score), &sender);
+
+        Self::publish(&env, TOPIC_BADGE, symbol_short!("add"), events::BadgeAdded { badge_id, score, sender }.into_val(&env));
+    }
+
+    // Removes a badge from the contract
+    fn remove_badge(env: Env, sender: Address, name: String, issuer: Address) {
+        Self::authorize_badge_change(&env, &sender);
+
+        let badge_id = BadgeId { name, issuer };
+        let mut badges = Self::get_badges(env.clone());
+        let score = badges
+            .get(badge_id.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::BadgeNotFound));
+
+        badges.remove(badge_id.clone());
+        env.storage().persistent().set(&DataKey::ScorerBadges, &badges);
+
+        // A badge added again under the same id starts enabled
+        let mut disabled = Self::disabled_badges(&env);
+        if let Some(index) = disabled.first_index_of(&badge_id) {
+            disabled.remove(index);
+            env.storage().persistent().set(&DataKey::DisabledBadges, &disabled);
+        }
+        Self::log_badge_change(&env, &badge_id, Some(score), None, &sender);
+
+        Self::publish(&env, TOPIC_BADGE, symbol_short!("remove"), events::BadgeRemoved { badge_id, score, sender }.into_val(&env));
+    }
+
+    // Changes the score of a badge
+    fn set_badge_score(env: Env, sender: Address, badge_id: BadgeId, score: u32) {
+        Self::authorize_badge_change(&env, &sender);
+
+        if score > 10000 {
+            panic_with_error!(&env, Error::InvalidScoreRange);
+        }
+
+        let mut badges = Self::get_badges(env.clone());
+        let previous_score = badges
+            .get(badge_id.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::BadgeNotFound));
+
+        badges.set(badge_id.clone(), score);
+        env.storage().persistent().set(&DataKey::ScorerBadges, &badges);
+        Self::log_badge_change(&env, &badge_id, Some(previous_score), Some(score), &sender);
+
+        Self::publish(&env, TOPIC_BADGE, symbol_short!("score"), events::BadgeScoreSet { badge_id, previous_score, score, sender }.into_val(&env));
+    }
+
+    // Recognizes badges of another scorer, which count in `get_score` for a
+    // percentage of their score there, or stops recognizing them with a zero weight
+    fn import_badges_from(env: Env, sender: Address, scorer: Address, badges: Vec<BadgeId>, weight: u32) {
+        Self::require_manager(&env, &sender);
+
+        if Self::get_extension(env.clone(), Extension::Governance).is_some() {
+            panic_with_error!(&env, Error::GovernanceRequired);
+        }
+
+        if weight > 100 || scorer == env.current_contract_address() {
+            panic_with_error!(&env, Error::InvalidBadgeImport);
+        }
+
+        let mut imported = Self::get_imported_badges(env.clone());
+        if weight == 0 {
+            for badge in badges.iter() {
+                imported.remove(badge);
+            }
+        } else {
+            let own_badges = Self::get_badges(env.clone());
+            let other_badges = ScorerClient::new(&env, &scorer).get_badges();
+            for badge in badges.iter() {
+                if !other_badges.contains_key(badge.clone()) {
+                    panic_with_error!(&env, Error::BadgeNotFound);
+                }
+                if own_badges.contains_key(badge.clone()) {
+                    panic_with_error!(&env, Error::BadgeAlreadyExists);
+                }
+                imported.set(badge, (scorer.clone(), weight));
+            }
+        }
+        env.storage().persistent().set(&DataKey::ImportedBadges, &imported);
+
+        Self::publish(&env, TOPIC_BADGE, symbol_short!("import"), events::BadgesImported { sender, scorer, badges, weight }.into_val(&env));
+    }
+
+    // Retrieves the badges recognized from other scorers
+    fn get_imported_badges(env: Env) -> Map<BadgeId, (Address, u32)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ImportedBadges)
             .unwrap_or_else(|| Map::new(&env))
     }
 
-    /// Retrieves all the managers from the contract.
-    ///
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
+    // Retrieves contract metadata (name, description, icon)
+    fn get_metadata(env: Env) -> (String, String, String) {
+        let storage = env.storage().persistent();
+        let read = |key: DataKey| storage.get::<DataKey, String>(&key).unwrap_or_else(|| String::from_str(&env, ""));
+        (read(DataKey::Name), read(DataKey::Description), read(DataKey::Icon))
+    }
+
+    // Computes the score of a user from the badges it holds
+    fn get_score(env: Env, user: Address, badges: Vec<BadgeId>) -> u32 {
+        if !Self::is_active_user(&env, &user) {
+            return 0;
+        }
+
+        // Disabled and revoked badges count for nothing, badges imported from
+        // other scorers for their weighted score there, and other badges that
+        // are not part of the scorer are ignored
+        let scorer_badges = Self::get_badges(env.clone());
+        let disabled = Self::disabled_badges(&env);
+        let disputes = Self::get_extension(env.clone(), Extension::Disputes);
+        // Read only when a badge is not part of the scorer
+        let mut imported_badges = None;
+        let mut attestations = Map::new(&env);
+        for badge in badges.iter() {
+            if let Some(score) = scorer_badges.get(badge.clone()) {
+                if !disabled.contains(&badge) && !Self::is_revoked(&env, &disputes, &badge, &user) {
+                    attestations.set(badge, score);
+                }
+            } else if let Some((scorer, weight)) = imported_badges
+                .get_or_insert_with(|| Self::get_imported_badges(env.clone()))
+                .get(badge.clone())
+            {
+                let scorer = ScorerClient::new(&env, &scorer);
+                if scorer.get_user_status(&user) == Some(UserStatus::Active) && !scorer.is_attestation_revoked(&badge, &user) {
+                    if let Some(score) = scorer.get_badges().get(badge.clone()) {
+                        attestations.set(badge, score * weight / 100);
+                    }
+                }
+            }
+        }
+
+        let score = match Self::get_extension(env.clone(), Extension::ScoreResolver) {
+            Some(resolver) => ScoreResolverClient::new(&env, &resolver).resolve(&user, &attestations),
+            None => attestations.values().iter().fold(0u32, |total, score| total.saturating_add(score)),
+        };
+
+        match Self::get_extension(env.clone(), Extension::Staking) {
+            Some(staking) => score.saturating_add(StakingClient::new(&env, &staking).get_boost(&user)),
+            None => score,
+        }
+    }
+
+    // Checks whether the disputes contract revoked an attestation after a challenge
+    fn is_attestation_revoked(env: Env, badge_id: BadgeId, user: Address) -> bool {
+        Self::is_revoked(&env, &Self::get_extension(env.clone(), Extension::Disputes), &badge_id, &user)
+    }
+}
+
+#[contractimpl]
+impl MaintenanceTrait for ScorerContract {
+    // Runs a batch of a maintenance task over the users from `cursor`
+    fn maintenance(env: Env, task: MaintenanceTask, cursor: u32, limit: u32) -> u32 {
+        Self::run_task(&env, task, cursor, limit).1
+    }
+}
+
+impl ScorerContract {
+    /// Runs a batch of a maintenance task over the users from `cursor`
     ///
     /// # Returns
-    /// * `Vec<Address>` - A vector of all manager addresses
+    /// * `(u32, u32)` - The number of membership expiries dropped, and the cursor of the next batch, 0 after the last user
+    fn run_task(env: &Env, task: MaintenanceTask, cursor: u32, limit: u32) -> (u32, u32) {
+        let storage = env.storage().persistent();
+        let count = Self::user_count(env);
+        let cursor = if cursor < count { cursor } else { 0 };
+        let users = Self::users_page(env, cursor, limit);
+        let end = cursor + users.len();
+        let mut expiries = Self::membership_expiries(env);
+        let before = expiries.len();
+
+        for (index, user) in (cursor..end).zip(users.iter()) {
+            let active = Self::status_of(env, &user) == Some(UserStatus::Active);
+            match task {
+                MaintenanceTask::ExtendTtl => {
+                    for key in [DataKey::UserAt(index), DataKey::UserStatus(user.clone()), DataKey::JoinedAt(user.clone()), DataKey::UserNonce(user)] {
+                        if storage.has(&key) {
+                            storage.extend_ttl(&key, TTL_THRESHOLD, env.storage().max_ttl());
+                        }
+                    }
+                }
+                MaintenanceTask::Compact => {
+                    if !active {
+                        expiries.remove(user);
+                    }
+                }
+                MaintenanceTask::SweepExpired => {
+                    if active && expiries.get(user.clone()).is_some_and(|expiry| env.ledger().sequence() > expiry) {
+                        storage.set(&DataKey::UserStatus(user.clone()), &UserStatus::Left);
+                        expiries.remove(user.clone());
+                        Self::report(env, "record_leave");
+                        Self::publish(env, TOPIC_USER, symbol_short!("expire"), events::MembershipExpired { user }.into_val(env));
+                    }
+                }
+            }
+        }
+
+        if task == MaintenanceTask::ExtendTtl && cursor == 0 {
+            let max_ttl = env.storage().max_ttl();
+            env.storage().instance().extend_ttl(TTL_THRESHOLD, max_ttl);
+            for key in Self::scorer_keys() {
+                if storage.has(&key) {
+                    storage.extend_ttl(&key, TTL_THRESHOLD, max_ttl);
+                }
+            }
+            for (index, manager) in Self::get_managers_page(env.clone(), 0, u32::MAX).iter().enumerate() {
+                storage.extend_ttl(&DataKey::ManagerAt(index as u32), TTL_THRESHOLD, max_ttl);
+                storage.extend_ttl(&DataKey::ManagerIndex(manager), TTL_THRESHOLD, max_ttl);
+            }
+        }
+        if expiries.len() != before {
+            storage.set(&DataKey::MembershipExpiry, &expiries);
+        }
+
+        (before - expiries.len(), if end == count { 0 } else { end })
+    }
+
+    /// Publishes a scorer event under the event schema version
+    fn publish(env: &Env, topic: Symbol, action: Symbol, event: Val) {
+        env.events().publish((EVENT_SCHEMA_VERSION, topic, action), event);
+    }
+
+    /// Checks if a contract has been initialized
     ///
-    /// # Panics
-    /// * When the managers vector cannot be found in storage (`Error::ManagersNotFound`)
-    pub fn get_managers(env: Env) -> Vec<Address> {
-        env.storage()
-            .persistent()
-            .get::<DataKey, Vec<Address>>(&DataKey::Managers)
-            .unwrap_or_else(|| panic!("{:?}", Error::ManagersNotFound))
+    /// # Returns
+    /// * `bool` - True if the contract is initialized, false otherwise
+    fn is_initialized(env: &Env) -> bool {
+        env.storage().persistent().has(&DataKey::ScorerCreator)
     }
 
-    /// Retrieves the address of the contract creator.
+    /// Helper function to check if an address is the contract owner
     ///
     /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
+    /// * `address` - The address to check
     ///
     /// # Returns
-    /// * `Address` - The address of the scorer creator
-    ///
-    /// # Panics
-    /// * When the creator's address is not found in storage (`Error::ScorerCreatorDoesNotExist`)
-    pub fn get_contract_owner(env: Env) -> Address {
-        env.storage()
+    /// * `bool` - True if the address is the contract owner
+    fn is_owner(env: &Env, address: &Address) -> bool {
+        let owner = env.storage()
             .persistent()
             .get::<DataKey, Address>(&DataKey::ScorerCreator)
-            .unwrap_or_else(|| panic!("{:?}", Error::ScorerCreatorDoesNotExist))
+            .unwrap_or_else(|| panic_with_error!(env, Error::ScorerCreatorNotFound));
+
+        &owner == address
     }
 
-    /// Adds a new badge to the contract
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// * `sender` - The address of the account attempting to add the badge
-    /// * `name` - The name of the badge
-    /// * `issuer` - The issuer of the badge
-    /// * `score` - The score value of the badge
-    /// 
+    /// Checks if an address is a manager
+    fn is_manager(env: &Env, address: &Address) -> bool {
+        env.storage().persistent().has(&DataKey::ManagerIndex(address.clone()))
+    }
+
+    /// Returns the number of managers
+    fn manager_count(env: &Env) -> u32 {
+        env.storage().persistent().get(&DataKey::ManagerCount).unwrap_or(0)
+    }
+
+    /// Stores a manager at the index after the last one
+    fn push_manager(env: &Env, manager: &Address) {
+        let storage = env.storage().persistent();
+        let count = Self::manager_count(env);
+        storage.set(&DataKey::ManagerAt(count), manager);
+        storage.set(&DataKey::ManagerIndex(manager.clone()), &count);
+        storage.set(&DataKey::ManagerCount, &(count + 1));
+    }
+
+    /// Removes a manager, moving the last manager to its index
+    fn pop_manager(env: &Env, manager: &Address) {
+        let storage = env.storage().persistent();
+        let index_key = DataKey::ManagerIndex(manager.clone());
+        let index: u32 = storage.get(&index_key).unwrap_optimized();
+        let last = Self::manager_count(env) - 1;
+        if index != last {
+            let moved: Address = storage.get(&DataKey::ManagerAt(last)).unwrap_optimized();
+            storage.set(&DataKey::ManagerAt(index), &moved);
+            storage.set(&DataKey::ManagerIndex(moved), &index);
+        }
+        storage.remove(&DataKey::ManagerAt(last));
+        storage.remove(&index_key);
+        storage.set(&DataKey::ManagerCount, &last);
+    }
+
+    /// Requires the authorization of the sender and that it is a manager
+    ///
     /// # Panics
     /// * If the sender is not a manager (`Error::Unauthorized`)
-    /// * If a badge with the given name and issuer already exists (`Error::BadgeAlreadyExists`)
-    /// * If the badge name is empty (`Error::EmptyArg`)
-    /// * If the badge score is invalid (greater than 10000) (`Error::InvalidScoreRange`)
-    pub fn add_badge(env: Env, sender: Address, name: String, issuer: Address, score: u32) {
+    fn require_manager(env: &Env, sender: &Address) {
         sender.require_auth();
-        
-        // Check if sender is a manager
-        let (is_manager, _) = Self::manager_exists(&env, &sender);
-        if !is_manager {
-            panic!("{:?}", Error::Unauthorized);
-        }
-        
-        // Validate badge name
-        if name.is_empty() {
-            panic!("{:?}", Error::EmptyArg);
-        }
-        
-        // Validate badge score
-        if score > 10000 {
-            panic!("{:?}", Error::InvalidScoreRange);
+
+        if !Self::is_manager(env, sender) {
+            panic_with_error!(env, Error::Unauthorized);
         }
-        
-        let mut badges = env.storage()
-            .persistent()
-            .get::<DataKey, Map<BadgeId, u32>>(&DataKey::ScorerBadges)
-            .unwrap_or_else(|| Map::new(&env));
-        
-        // Create the badge ID and details
-        let badge_id = BadgeId {
-            name: name.clone(),
-            issuer: issuer.clone(),
-        };
-        
-        // Check if badge with this ID already exists
-        if badges.contains_key(badge_id.clone()) {
-            panic!("{:?}", Error::BadgeAlreadyExists);
+    }
+
+    /// Requires the authorization of a user over this scorer's address and its
+    /// current nonce, so that it can be used once and only on this scorer
+    fn require_user_auth(env: &Env, user: &Address) {
+        let key = DataKey::UserNonce(user.clone());
+        let nonce: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        user.require_auth_for_args((env.current_contract_address(), nonce).into_val(env));
+        env.storage().persistent().set(&key, &(nonce + 1));
+    }
+
+    /// Requires the authorization of the sender and that it is the membership contract
+    ///
+    /// # Panics
+    /// * If the sender is not the contract registered as `Extension::Membership` (`Error::Unauthorized`)
+    fn require_membership(env: &Env, sender: &Address) {
+        sender.require_auth();
+
+        if Self::get_extension(env.clone(), Extension::Membership).as_ref() != Some(sender) {
+            panic_with_error!(env, Error::Unauthorized);
         }
-        
-        badges.set(badge_id.clone(), score.clone());
-        env.storage().persistent().set(&DataKey::ScorerBadges, &badges);
-        
-        env.events().publish(
-            (TOPIC_BADGE, symbol_short!("add")),
-            (badge_id, score, sender),
-        );
     }
 
-    /// Removes a badge from the contract
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// * `sender` - The address of the account attempting to remove the badge
-    /// * `name` - The name of the badge to remove
-    /// * `issuer` - The issuer of the badge to remove
-    /// 
+    /// Checks that the sender may change badges: the governance contract when
+    /// one is registered, a manager otherwise
+    ///
     /// # Panics
     /// * If the sender is not a manager (`Error::Unauthorized`)
-    /// * If the badge with the given name and issuer doesn't exist (`Error::BadgeNotFound`)
-    pub fn remove_badge(env: Env, sender: Address, name: String, issuer: Address) {
+    /// * If a governance contract is registered and is not the sender (`Error::GovernanceRequired`)
+    fn authorize_badge_change(env: &Env, sender: &Address) {
         sender.require_auth();
-        
-        // Check if sender is a manager
-        let (is_manager, _) = Self::manager_exists(&env, &sender);
-        if !is_manager {
-            panic!("{:?}", Error::Unauthorized);
+
+        let governance = Self::get_extension(env.clone(), Extension::Governance);
+        if governance.as_ref() == Some(sender) {
+            return;
         }
-        
-        let mut badges = env.storage()
-            .persistent()
-            .get::<DataKey, Map<BadgeId, u32>>(&DataKey::ScorerBadges)
-            .unwrap_or_else(|| panic!("{:?}", Error::BadgeNotFound));
-        
-        // Create the badge key
-        let badge_id = BadgeId {
+
+        if !Self::is_manager(env, sender) {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+        if governance.is_some() {
+            panic_with_error!(env, Error::GovernanceRequired);
+        }
+    }
+
+    /// Records that an active member left: its membership no longer expires,
+    /// the departure is reported to the analytics contract and `UserLeft` is published
+    fn leave(env: &Env, user: Address) {
+        let mut expiries = Self::membership_expiries(env);
+        if expiries.remove(user.clone()).is_some() {
+            env.storage().persistent().set(&DataKey::MembershipExpiry, &expiries);
+        }
+        Self::report(env, "record_leave");
+
+        Self::publish(env, TOPIC_USER, symbol_short!("remove"), events::UserLeft { user }.into_val(env));
+    }
+
+    /// Reports a join or a departure to the analytics contract, if any, calling
+    /// `record_join` or `record_leave` and ignoring its failures
+    fn report(env: &Env, function: &str) {
+        if let Some(analytics) = Self::get_extension(env.clone(), Extension::Analytics) {
+            let _ = env.try_invoke_contract::<(), InvokeError>(
+                &analytics,
+                &Symbol::new(env, function),
+                vec![env, env.current_contract_address().into_val(env)],
+            );
+        }
+    }
+
+    /// Reports a badge change to the scoreboard, if any
+    fn log_badge_change(env: &Env, badge_id: &BadgeId, previous_score: Option<u32>, score: Option<u32>, sender: &Address) {
+        if let Some(scoreboard) = Self::get_extension(env.clone(), Extension::Scoreboard) {
+            BadgeLogClient::new(env, &scoreboard).record_badge_change(badge_id, &previous_score, &score, sender);
+        }
+    }
+
+    /// Lists the storage entries holding scorer-wide state, extended by `MaintenanceTask::ExtendTtl`
+    fn scorer_keys() -> [DataKey; 27] {
+        [
+            DataKey::ScorerCreator, DataKey::ScorerBadges, DataKey::UserCount, DataKey::ManagerCount,
+            DataKey::Name, DataKey::Description, DataKey::Icon,
+            DataKey::UpgradeGuard, DataKey::Archived, DataKey::StorageVersion,
+            DataKey::MembershipExpiry, DataKey::ImportedBadges, DataKey::DisabledBadges,
+            DataKey::CodeVersion, DataKey::PokeCursor, DataKey::WasmHash, DataKey::UpgradedFrom,
+            DataKey::Extension(Extension::Factory), DataKey::Extension(Extension::IssuerRegistry),
+            DataKey::Extension(Extension::ScoreResolver), DataKey::Extension(Extension::Staking),
+            DataKey::Extension(Extension::Analytics), DataKey::Extension(Extension::BadgeToken),
+            DataKey::Extension(Extension::Membership), DataKey::Extension(Extension::Governance),
+            DataKey::Extension(Extension::Disputes), DataKey::Extension(Extension::Scoreboard),
+        ]
+    }
+
+    /// Initializes the scorer, recording the initial managers and users
+    ///
+    /// # Panics
+    /// * See `initialize`
+    fn init(env: &Env, scorer_creator: Address, members: InitialMembers, scorer_badges: Map<BadgeId, u32>, name: String, description: String, icon: String) {
+        if name.is_empty() || description.is_empty() || icon.is_empty() {
+            panic_with_error!(env, Error::EmptyArg);
+        }
+        Self::check_length(env, &name, MAX_NAME_LENGTH, Error::NameTooLong);
+        Self::check_length(env, &description, MAX_DESCRIPTION_LENGTH, Error::DescriptionTooLong);
+        Self::check_length(env, &icon, MAX_URI_LENGTH, Error::IconTooLong);
+        for badge_id in scorer_badges.keys().iter() {
+            Self::check_length(env, &badge_id.name, MAX_BADGE_NAME_LENGTH, Error::BadgeNameTooLong);
+        }
+
+        // Ensure that the contract is not initialized
+        if Self::is_initialized(env) {
+            panic_with_error!(env, Error::ContractAlreadyInitialized);
+        }
+
+        // Ensure that the scorer creator is the sender
+        scorer_creator.require_auth();
+
+        // Create initial managers list with scorer_creator
+        let mut initial_managers = Vec::<Address>::new(env);
+        initial_managers.push_back(scorer_creator.clone());
+        for manager in members.managers.iter() {
+            if !initial_managers.contains(&manager) {
+                initial_managers.push_back(manager);
+            }
+        }
+
+        // Store initial state
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::ScorerCreator, &scorer_creator);
+        storage.set(&DataKey::ScorerBadges, &scorer_badges);
+        storage.set(&DataKey::UserCount, &0u32);
+        let mut users = Vec::<Address>::new(env);
+        for user in members.users.iter() {
+            if !users.contains(&user) {
+                Self::set_status(env, &user, None, UserStatus::Active);
+                storage.set(&DataKey::JoinedAt(user.clone()), &env.ledger().sequence());
+                users.push_back(user);
+            }
+        }
+        for manager in initial_managers.iter() {
+            Self::push_manager(env, &manager);
+        }
+        storage.set(&DataKey::Name, &name);
+        storage.set(&DataKey::Description, &description);
+        storage.set(&DataKey::Icon, &icon);
+        storage.set(&DataKey::StorageVersion, &STORAGE_VERSION);
+
+        // Emit a initialization event
+        Self::publish(env, TOPIC_INIT, symbol_short!("contract"), events::ScorerInitialized {
+            creator: scorer_creator,
+            managers: initial_managers,
+            badges: scorer_badges,
             name,
-            issuer,
-        };
-        
-        // Check if badge exists
-        if !badges.contains_key(badge_id.clone()) {
-            panic!("{:?}", Error::BadgeNotFound);
+            description,
+            icon,
+        }.into_val(env));
+
+        for user in users.iter() {
+            Self::publish(env, TOPIC_USER, symbol_short!("add"), events::UserJoined { user }.into_val(env));
         }
-        
-        let badge_details = badges.get(badge_id.clone()).unwrap();
-        
-        badges.remove(badge_id.clone());
-        env.storage().persistent().set(&DataKey::ScorerBadges, &badges);
-        
-        env.events().publish(
-            (TOPIC_BADGE, symbol_short!("remove")),
-            (badge_id, badge_details, sender),
-        );
     }
 
-    /// Retrieves contract metadata (name, description, icon)
-    /// 
-    /// # Arguments
-    /// * `env` - The environment object providing access to the contract's storage
-    /// 
-    /// # Returns
-    /// * `(String, String, String)` - A tuple containing:
-    ///   - name: Contract name
-    ///   - description: Contract description
-    ///   - icon: Contract icon
-    pub fn get_metadata(env: Env) -> (String, String, String) {
-        let name = env.storage()
+    /// Marks a user active and records the ledger it joined at. Members whose
+    /// membership expired are activated again
+    ///
+    /// # Panics
+    /// * If the user is already active and its membership has not expired (`Error::UserAlreadyExist`)
+    /// * If the user was banned (`Error::UserBanned`)
+    fn activate_user(env: &Env, user: Address) {
+        // Check if user already exists and is active, or was banned
+        let status = Self::status_of(env, &user);
+        match status {
+            Some(UserStatus::Active) if !Self::membership_expired(env, &user) => {
+                panic_with_error!(env, Error::UserAlreadyExist)
+            }
+            Some(UserStatus::Banned) => panic_with_error!(env, Error::UserBanned),
+            _ => {}
+        }
+
+        Self::set_status(env, &user, status, UserStatus::Active);
+        env.storage().persistent().set(&DataKey::JoinedAt(user.clone()), &env.ledger().sequence());
+
+        // Expired members were never counted as leaving
+        if status != Some(UserStatus::Active) {
+            Self::report(env, "record_join");
+        }
+
+        // Emit event for user addition
+        Self::publish(env, TOPIC_USER, symbol_short!("add"), events::UserJoined { user }.into_val(env));
+    }
+
+    /// Checks if a user is active and its membership has not expired
+    fn is_active_user(env: &Env, user: &Address) -> bool {
+        Self::status_of(env, user) == Some(UserStatus::Active) && !Self::membership_expired(env, user)
+    }
+
+    /// Retrieves the stored status of a user. Users of scorers not yet migrated
+    /// to storage version 2 who have not changed since are read from the map
+    /// of version 1
+    fn status_of(env: &Env, user: &Address) -> Option<UserStatus> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserStatus(user.clone()))
+            .or_else(|| Self::legacy_users(env).get(user.clone()).map(Self::legacy_status))
+    }
+
+    /// Stores the status of a user, appending users who never joined to the
+    /// indexed users
+    fn set_status(env: &Env, user: &Address, previous: Option<UserStatus>, status: UserStatus) {
+        let storage = env.storage().persistent();
+        if previous.is_none() {
+            let count = Self::user_count(env);
+            storage.set(&DataKey::UserAt(count), user);
+            storage.set(&DataKey::UserCount, &(count + 1));
+        }
+        storage.set(&DataKey::UserStatus(user.clone()), &status);
+    }
+
+    /// Returns the number of users who ever joined. Scorers not yet migrated
+    /// to storage version 2 keep the first indexes for the users of version 1
+    fn user_count(env: &Env) -> u32 {
+        env.storage()
             .persistent()
-            .get::<DataKey, String>(&DataKey::Name)
-            .unwrap_or_else(|| String::from_str(&env, ""));
-            
-        let description = env.storage()
+            .get(&DataKey::UserCount)
+            .unwrap_or_else(|| Self::legacy_users(env).len())
+    }
+
+    /// Retrieves up to `limit` users from index `offset`
+    fn users_page(env: &Env, offset: u32, limit: u32) -> Vec<Address> {
+        let count = Self::user_count(env);
+        let start = offset.min(count);
+        let end = start.saturating_add(limit).min(count);
+        let mut legacy_keys = None;
+        let mut users = Vec::new(env);
+        for index in start..end {
+            // Users of version 1 not migrated yet are at their index in its map
+            users.push_back(env.storage().persistent().get(&DataKey::UserAt(index)).unwrap_or_else(|| {
+                legacy_keys.get_or_insert_with(|| Self::legacy_users(env).keys()).get_unchecked(index)
+            }));
+        }
+        users
+    }
+
+    /// Returns the status of a user as reported to callers: a member whose
+    /// membership expired is reported as `Left` until it renews
+    fn reported_status(env: &Env, expiries: &Map<Address, u32>, user: &Address) -> UserStatus {
+        match Self::status_of(env, user).unwrap_optimized() {
+            UserStatus::Active if expiries.get(user.clone()).is_some_and(|expiry| env.ledger().sequence() > expiry) => UserStatus::Left,
+            status => status,
+        }
+    }
+
+    /// Retrieves the users of storage version 1 and whether they are active,
+    /// kept until `migrate` moves them to their own entries
+    fn legacy_users(env: &Env) -> Map<Address, bool> {
+        env.storage().persistent().get(&DataKey::Users).unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Converts whether a user of storage version 1 is active to its status
+    fn legacy_status(active: bool) -> UserStatus {
+        if active {
+            UserStatus::Active
+        } else {
+            UserStatus::Left
+        }
+    }
+
+    /// Retrieves the last ledger of the memberships that expire
+    fn membership_expiries(env: &Env) -> Map<Address, u32> {
+        env.storage()
             .persistent()
-            .get::<DataKey, String>(&DataKey::Description)
-            .unwrap_or_else(|| String::from_str(&env, ""));
-            
-        let icon = env.storage()
+            .get(&DataKey::MembershipExpiry)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Checks if the membership of a user expired
+    fn membership_expired(env: &Env, user: &Address) -> bool {
+        Self::get_membership_expiry(env.clone(), user.clone())
+            .is_some_and(|expiry| env.ledger().sequence() > expiry)
+    }
+
+    /// Retrieves the badges disabled by managers
+    fn disabled_badges(env: &Env) -> Vec<BadgeId> {
+        env.storage()
             .persistent()
-            .get::<DataKey, String>(&DataKey::Icon)
-            .unwrap_or_else(|| String::from_str(&env, ""));
-            
-        (name, description, icon)
+            .get(&DataKey::DisabledBadges)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Checks whether the disputes contract, if any, revoked an attestation
+    fn is_revoked(env: &Env, disputes: &Option<Address>, badge_id: &BadgeId, user: &Address) -> bool {
+        disputes
+            .as_ref()
+            .is_some_and(|disputes| DisputesClient::new(env, disputes).is_attestation_revoked(badge_id, user))
+    }
+
+    /// Panics with `error` when `value` is longer than `max` bytes
+    fn check_length(env: &Env, value: &String, max: u32, error: Error) {
+        if value.len() > max {
+            panic_with_error!(env, error);
+        }
     }
 }
 
@@ -526,7 +1257,6 @@ mod test {
             file = "../../wasm/trustful_stellar_v1_test_upgradable.wasm"
         );
     }
-    
     pub mod new_contract {
         soroban_sdk::contractimport!(
             file = "../../wasm/trustful_stellar_v1.wasm"
@@ -534,8 +1264,9 @@ mod test {
     } 
 
     use super::*;
-    use soroban_sdk::testutils::{Address as _, Events};
-    use soroban_sdk::IntoVal;
+    use soroban_sdk::testutils::{storage::Persistent as _, Address as _, Deployer as _, Events as _, Ledger, MockAuth, MockAuthInvoke};
+    use soroban_sdk::xdr::{ScErrorCode, ScErrorType};
+    use test_utils::{EventRecord, EventSnapshot};
 
     fn setup_contract() -> (Env, Address, ScorerContractClient<'static>) {
         let env = Env::default();
@@ -553,7 +1284,7 @@ mod test {
         scorer_badges.set(badge_id, 100);
 
         // Register the contract
-        let scorer_contract_id = env.register_contract(None, ScorerContract);
+        let scorer_contract_id = env.register(ScorerContract, ());
         let scorer_client = ScorerContractClient::new(&env, &scorer_contract_id);
 
         // Initialize contract
@@ -561,44 +1292,73 @@ mod test {
 
         (env, scorer_creator, scorer_client)
     }
-
     #[test]
     fn test_initialize() {
         setup_contract();
     }
-
     #[test]
-    #[should_panic(expected = "ContractAlreadyInitialized")]
+    #[should_panic(expected = "Error(Contract, #1)")]
     fn test_double_initialization() {
         let (env, scorer_creator, client) = setup_contract();
         let scorer_badges = Map::new(&env);
         
         client.initialize(&scorer_creator, &scorer_badges, &String::from_str(&env, "New_contract"), &String::from_str(&env,"Contract's description."),&String::from_str(&env,"icon.png"));
     }
+    #[test]
+    fn test_initialize_with_members() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        let scorer_creator = Address::generate(&env);
+        let (member, other, manager) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
 
+        client.initialize_with_members(
+            &scorer_creator,
+            &InitialMembers {
+                managers: vec![&env, manager.clone(), scorer_creator.clone(), manager.clone()],
+                users: vec![&env, member.clone(), other.clone(), member.clone()],
+            },
+            &Map::new(&env),
+            &String::from_str(&env, "Migrated"),
+            &String::from_str(&env, "A migrated community"),
+            &String::from_str(&env, "icon.png"),
+        );
+        EventSnapshot::capture(&env).assert_contains(&[
+            EventRecord::new(&env, &client.address, (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("add")), events::UserJoined { user: member.clone() }),
+            EventRecord::new(&env, &client.address, (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("add")), events::UserJoined { user: other.clone() }),
+        ]);
+
+        // The managers and users are recorded once, without authorizing the call
+        assert_eq!(env.auths().len(), 1);
+        assert_eq!(client.get_managers(), vec![&env, scorer_creator.clone(), manager.clone()]);
+        assert_eq!(client.get_users(), Map::from_array(&env, [(member.clone(), UserStatus::Active), (other.clone(), UserStatus::Active)]));
+
+        // Seeded managers and members are regular ones
+        client.add_badge(&manager, &String::from_str(&env, "Founder"), &manager, &50);
+        client.remove_user(&member);
+        assert_eq!(client.get_user_status(&member), Some(UserStatus::Left));
+    }
     #[test]
     fn test_add_manager() {
         let (env, scorer_creator, client) = setup_contract();
         let new_manager = Address::generate(&env);
         client.add_manager(&scorer_creator, &new_manager);
+        let snapshot = EventSnapshot::capture(&env);
 
         // Verify storage update
-        let managers = env.as_contract(&client.address, || {
-            env.storage().persistent().get::<DataKey, Vec<Address>>(&DataKey::Managers).unwrap()
-        });
+        let managers = client.get_managers();
         assert_eq!(managers, Vec::from_slice(&env, &[scorer_creator.clone(), new_manager.clone()]));
 
         // Verify event emission - check if the expected event is in the events list
-        let expected_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_MANAGER), symbol_short!("add")).into_val(&env),
-            (scorer_creator, new_manager).into_val(&env)
+        let expected_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_MANAGER, symbol_short!("add")),
+            events::ManagerAdded { sender: scorer_creator, manager: new_manager },
         );
         
-        assert!(env.events().all().contains(&expected_event), 
-            "Expected event not found in events list");
+        snapshot.assert_contains(&[expected_event]);
     }
-
     #[test]
     fn test_remove_manager() {
         let (env, scorer_creator, client) = setup_contract();
@@ -606,26 +1366,24 @@ mod test {
 
         client.add_manager(&scorer_creator, &new_manager);
         client.remove_manager(&scorer_creator, &new_manager);
+        let snapshot = EventSnapshot::capture(&env);
 
         // Verify storage update
-        let managers = env.as_contract(&client.address, || {
-            env.storage().persistent().get::<DataKey, Vec<Address>>(&DataKey::Managers).unwrap()
-        });
-        assert_eq!(managers, Vec::from_slice(&env, &[scorer_creator.clone()]));
+        let managers = client.get_managers();
+        assert_eq!(managers, Vec::from_array(&env, [scorer_creator.clone()]));
 
         // Verify event emission - check if the expected event is in the events list
-        let expected_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_MANAGER), symbol_short!("remove")).into_val(&env),
-            (scorer_creator, new_manager).into_val(&env)
+        let expected_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_MANAGER, symbol_short!("remove")),
+            events::ManagerRemoved { sender: scorer_creator, manager: new_manager },
         );
         
-        assert!(env.events().all().contains(&expected_event), 
-            "Remove manager event not found in events list");
+        snapshot.assert_contains(&[expected_event]);
     }
-
     #[test]
-    #[should_panic(expected = "Unauthorized")]
+    #[should_panic(expected = "Error(Contract, #2)")]
     fn test_add_manager_unauthorized() {
         let (env, _scorer_creator, client) = setup_contract();
         let unauthorized_user = Address::generate(&env);
@@ -633,16 +1391,21 @@ mod test {
         
         client.add_manager(&unauthorized_user, &new_manager);
     }
-
     #[test]
-    #[should_panic(expected = "Unauthorized")]
+    #[should_panic(expected = "Error(Contract, #2)")]
     fn test_remove_manager_unauthorized() {
         let (env, _scorer_creator, client) = setup_contract();
         let unauthorized_user = Address::generate(&env);
         
         client.remove_manager(&unauthorized_user, &unauthorized_user);
     }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #18)")]
+    fn test_remove_last_manager() {
+        let (_env, scorer_creator, client) = setup_contract();
 
+        client.remove_manager(&scorer_creator, &scorer_creator);
+    }
     #[test]
     fn test_multiple_managers() {
         let (env, scorer_creator, client) = setup_contract();
@@ -654,73 +1417,274 @@ mod test {
         client.add_manager(&scorer_creator, &manager2);
         client.add_manager(&scorer_creator, &manager3);
 
-        let managers = env.as_contract(&client.address, || {
-            env.storage().persistent().get::<DataKey, Vec<Address>>(&DataKey::Managers).unwrap()
-        });
+        let managers = client.get_managers();
         
         assert_eq!(managers, Vec::from_slice(&env, &[scorer_creator.clone(), manager1.clone(), manager2.clone(), manager3.clone()]));
 
         client.remove_manager(&scorer_creator, &manager2);
 
-        let managers_after_remove = env.as_contract(&client.address, || {
-            env.storage().persistent().get::<DataKey, Vec<Address>>(&DataKey::Managers).unwrap()
-        });
+        let managers_after_remove = client.get_managers();
         
-        assert_eq!(managers_after_remove, Vec::from_slice(&env, &[scorer_creator, manager1, manager3]));
-    }
+        assert_eq!(managers_after_remove, Vec::from_slice(&env, &[scorer_creator.clone(), manager1.clone(), manager3.clone()]));
 
+        // Removing a manager moves the last one to its index
+        client.remove_manager(&scorer_creator, &scorer_creator);
+        assert_eq!(client.get_manager_count(), 2);
+        assert_eq!(client.get_managers_page(&0, &1), vec![&env, manager3.clone()]);
+        assert_eq!(client.get_managers_page(&1, &5), vec![&env, manager1.clone()]);
+        assert!(client.get_managers_page(&2, &5).is_empty());
+        assert!(client.try_add_manager(&scorer_creator, &manager3).is_err());
+        client.remove_manager(&scorer_creator, &manager3);
+        assert_eq!(client.get_managers(), Vec::from_slice(&env, &[manager1]));
+    }
     #[test]
     fn test_upgrade() {
         let (env, _scorer_creator, client) = setup_contract();
-        assert_eq!(1, client.contract_version());
+        assert_eq!(CONTRACT_VERSION, client.contract_version());
         let new_wasm_hash = env.deployer().upload_contract_wasm(old_contract::WASM);
         client.upgrade(&new_wasm_hash);
 
-        // Verify contract version
+        // The new code declares its own version
         assert_eq!(0, client.contract_version());
 
-        // Verify event emission
-        let expected_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_UPGRADE), symbol_short!("wasm")).into_val(&env),
-            new_wasm_hash.into_val(&env)
-        );
-        
-        assert!(env.events().all().contains(&expected_event), 
-            "Upgrade event not found in events list");
+        // The replaced version and hash wait for the new code's `migrate`
+        let (code_version, upgraded_from, wasm_hash) = env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            (
+                storage.get::<DataKey, u32>(&DataKey::CodeVersion).unwrap(),
+                storage.get::<DataKey, BytesN<32>>(&DataKey::UpgradedFrom).unwrap(),
+                storage.get::<DataKey, BytesN<32>>(&DataKey::WasmHash).unwrap(),
+            )
+        });
+        assert_eq!(code_version, CONTRACT_VERSION);
+        assert_eq!(upgraded_from, BytesN::from_array(&env, &[0; 32]));
+        assert_eq!(wasm_hash, new_wasm_hash);
     }
+    #[test]
+    fn test_migrate_reports_upgrade() {
+        let (env, scorer_creator, client) = setup_contract();
+        let old_wasm_hash = BytesN::from_array(&env, &[1; 32]);
+        let new_wasm_hash = BytesN::from_array(&env, &[2; 32]);
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            storage.set(&DataKey::CodeVersion, &0u32);
+            storage.set(&DataKey::UpgradedFrom, &old_wasm_hash);
+            storage.set(&DataKey::WasmHash, &new_wasm_hash);
+        });
+
+        // The storage is current, but the upgrade is still reported
+        assert_eq!(client.migrate(&scorer_creator, &0, &10), 0);
+        let expected_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_UPGRADE, symbol_short!("wasm")),
+            events::ScorerUpgraded { old_version: 0, new_version: CONTRACT_VERSION, old_wasm_hash, new_wasm_hash },
+        );
+        EventSnapshot::capture(&env).assert_contains(&[expected_event]);
 
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            assert_eq!(storage.get::<DataKey, u32>(&DataKey::CodeVersion), Some(CONTRACT_VERSION));
+            assert!(!storage.has(&DataKey::UpgradedFrom));
+        });
+        assert_eq!(client.try_migrate(&scorer_creator, &0, &10), Err(Ok(Error::AlreadyMigrated.into())));
+    }
     #[test]
-    #[should_panic(expected = "Unauthorized")]
+    #[should_panic(expected = "Error(Auth, InvalidAction)")]
     fn test_upgrade_unauthorized() {
         let (env, _scorer_creator, client) = setup_contract();
         let new_wasm_hash = env.deployer().upload_contract_wasm(new_contract::WASM);
         env.mock_auths(&[]);
         client.upgrade(&new_wasm_hash);
     }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #19)")]
+    fn test_migrate_current_storage() {
+        let (_env, scorer_creator, client) = setup_contract();
+        assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+        client.migrate(&scorer_creator, &0, &10);
+    }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_migrate_unauthorized() {
+        let (env, _scorer_creator, client) = setup_contract();
+        client.migrate(&Address::generate(&env), &0, &10);
+    }
+    mod mock_factory {
+        use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
+
+        #[contract]
+        pub struct MockFactory;
+
+        #[contractimpl]
+        impl MockFactory {
+            pub fn approve(env: Env, wasm_hash: BytesN<32>) {
+                env.storage().persistent().set(&wasm_hash, &true);
+            }
+
+            pub fn record_upgrade(env: Env, scorer: Address, wasm_hash: BytesN<32>) -> bool {
+                scorer.require_auth();
+                env.storage().persistent().get(&wasm_hash).unwrap_or(false)
+            }
+
+            pub fn verify_issuer(env: Env, issuer: Address) {
+                env.storage().persistent().set(&issuer, &true);
+            }
+
+            pub fn is_verified_issuer(env: Env, issuer: Address) -> bool {
+                env.storage().persistent().get(&issuer).unwrap_or(false)
+            }
+        }
+    }
+    #[test]
+    fn test_upgrade_with_approved_hash() {
+        let (env, scorer_creator, client) = setup_contract();
+        let factory_id = env.register(mock_factory::MockFactory, ());
+        let factory = mock_factory::MockFactoryClient::new(&env, &factory_id);
+
+        client.enable_upgrade_guard(&scorer_creator, &factory_id);
+        assert_eq!(client.get_upgrade_guard(), Some(factory_id));
+
+        let new_wasm_hash = env.deployer().upload_contract_wasm(old_contract::WASM);
+        factory.approve(&new_wasm_hash);
+        client.upgrade(&new_wasm_hash);
+
+        assert_eq!(0, client.contract_version());
+    }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #15)")]
+    fn test_upgrade_with_unapproved_hash() {
+        let (env, scorer_creator, client) = setup_contract();
+        let factory_id = env.register(mock_factory::MockFactory, ());
+
+        client.enable_upgrade_guard(&scorer_creator, &factory_id);
+
+        let new_wasm_hash = env.deployer().upload_contract_wasm(old_contract::WASM);
+        client.upgrade(&new_wasm_hash);
+    }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #14)")]
+    fn test_enable_upgrade_guard_twice() {
+        let (env, scorer_creator, client) = setup_contract();
+        let factory_id = env.register(mock_factory::MockFactory, ());
+
+        client.enable_upgrade_guard(&scorer_creator, &factory_id);
+        client.enable_upgrade_guard(&scorer_creator, &Address::generate(&env));
+    }
+    #[test]
+    fn test_add_badge_with_verified_issuer() {
+        let (env, scorer_creator, client) = setup_contract();
+        let registry_id = env.register(mock_factory::MockFactory, ());
+        let registry = mock_factory::MockFactoryClient::new(&env, &registry_id);
+        let issuer = Address::generate(&env);
+
+        client.set_extension(&scorer_creator, &Extension::IssuerRegistry, &Some(registry_id.clone()));
+        assert_eq!(client.get_extension(&Extension::IssuerRegistry), Some(registry_id));
+
+        registry.verify_issuer(&issuer);
+        let name = String::from_str(&env, "Verified Badge");
+        client.add_badge(&scorer_creator, &name, &issuer, &100);
+
+        assert!(client.get_badges().contains_key(BadgeId { name, issuer }));
+    }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #16)")]
+    fn test_add_badge_with_unverified_issuer() {
+        let (env, scorer_creator, client) = setup_contract();
+        let registry_id = env.register(mock_factory::MockFactory, ());
+
+        client.set_extension(&scorer_creator, &Extension::IssuerRegistry, &Some(registry_id));
+        client.add_badge(&scorer_creator, &String::from_str(&env, "Badge"), &Address::generate(&env), &100);
+    }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #17)")]
+    fn test_add_user_after_archived_by_factory() {
+        let (env, scorer_creator, client) = setup_contract();
+        let factory = Address::generate(&env);
+
+        client.set_extension(&scorer_creator, &Extension::Factory, &Some(factory.clone()));
+        client.set_archived(&factory, &true);
+        assert!(client.is_archived());
+
+        client.add_user(&Address::generate(&env));
+    }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_set_archived_unauthorized() {
+        let (env, _scorer_creator, client) = setup_contract();
+        client.set_archived(&Address::generate(&env), &true);
+    }
+    #[test]
+    fn test_transfer_ownership() {
+        let (env, scorer_creator, client) = setup_contract();
+        let new_owner = Address::generate(&env);
+
+        client.transfer_ownership(&scorer_creator, &new_owner);
+        let snapshot = EventSnapshot::capture(&env);
 
+        assert_eq!(client.get_contract_owner(), new_owner);
+        assert_eq!(client.get_managers(), Vec::from_array(&env, [new_owner.clone()]));
+
+        let expected_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_OWNER, symbol_short!("transfer")),
+            events::OwnershipTransferred { sender: scorer_creator, new_owner },
+        );
+        snapshot.assert_contains(&[expected_event]);
+    }
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_transfer_ownership_unauthorized() {
+        let (env, _scorer_creator, client) = setup_contract();
+        let attacker = Address::generate(&env);
+        client.transfer_ownership(&attacker, &attacker);
+    }
     #[test]
     fn test_add_user() {
         let (env, _scorer_creator, client) = setup_contract();
         let user = Address::generate(&env);
 
         client.add_user(&user);
+        let snapshot = EventSnapshot::capture(&env);
         
         // Verify storage update
         let users = client.get_users();
-        assert!(users.get(user.clone()).unwrap());
+        assert_eq!(users.get(user.clone()), Some(UserStatus::Active));
+        assert_eq!(client.get_joined_at(&user), Some(env.ledger().sequence()));
+        assert_eq!(client.get_joined_at(&Address::generate(&env)), None);
 
         // Verify event emission
-        let expected_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_USER), symbol_short!("add")).into_val(&env),
-            user.into_val(&env)
+        let expected_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("add")),
+            events::UserJoined { user },
         );
         
-        assert!(env.events().all().contains(&expected_event), 
-            "Add user event not found in events list");
+        snapshot.assert_contains(&[expected_event]);
+        assert_eq!(client.get_event_schema_version(), 2);
     }
+    #[test]
+    fn test_events_decode_with_catalog() {
+        let (env, scorer_creator, client) = setup_contract();
+        let user = Address::generate(&env);
+        let last_event = || {
+            let (contract, topics, data) = env.events().all().last().unwrap();
+            assert_eq!(contract, client.address);
+            events::Event::decode(&env, &topics, data)
+        };
 
+        client.add_user(&user);
+        assert_eq!(last_event(), Some(events::Event::UserJoined(events::UserJoined { user: user.clone() })));
+
+        let badge_id = BadgeId { name: String::from_str(&env, "Catalog"), issuer: user };
+        client.add_badge(&scorer_creator, &badge_id.name, &badge_id.issuer, &7);
+        assert_eq!(
+            last_event(),
+            Some(events::Event::BadgeAdded(events::BadgeAdded { badge_id, score: 7, sender: scorer_creator }))
+        );
+    }
     #[test]
     fn test_manager_can_add_user() {
         let (env, scorer_creator, client) = setup_contract();
@@ -734,11 +1698,10 @@ mod test {
         client.add_user(&user);
         
         let users = client.get_users();
-        assert!(users.get(user.clone()).unwrap());
+        assert_eq!(users.get(user.clone()), Some(UserStatus::Active));
     }
-
     #[test]
-    #[should_panic(expected = "UserAlreadyExist")]
+    #[should_panic(expected = "Error(Contract, #7)")]
     fn test_unauthorized_add_user() {
         let (env, _scorer_creator, client) = setup_contract();
         let user = Address::generate(&env);
@@ -749,42 +1712,40 @@ mod test {
         // Try to add the same user again - should panic with UserAlreadyExist
         client.add_user(&user);
     }
-
     #[test]
     fn test_remove_user() {
         let (env, _scorer_creator, client) = setup_contract();
         let user = Address::generate(&env);
         
         client.add_user(&user);
+        let add_events = EventSnapshot::capture(&env);
         client.remove_user(&user);
+        let remove_events = EventSnapshot::capture(&env);
         
         // Verify storage update
         let users = client.get_users();
-        assert!(!users.get(user.clone()).unwrap());
+        assert_eq!(users.get(user.clone()), Some(UserStatus::Left));
+        assert_eq!(client.get_user_status(&user), Some(UserStatus::Left));
 
         // Verify event emission
-        let events = env.events().all();
-        
-        // Check for add event
-        let expected_add_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_USER), symbol_short!("add")).into_val(&env),
-            user.clone().into_val(&env)
+        let expected_add_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("add")),
+            events::UserJoined { user: user.clone() },
         );
         
         // Check for remove event
-        let expected_remove_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_USER), symbol_short!("remove")).into_val(&env),
-            user.into_val(&env)
+        let expected_remove_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("remove")),
+            events::UserLeft { user },
         );
         
-        assert!(events.contains(&expected_add_event), 
-            "Add user event not found in events list");
-        assert!(events.contains(&expected_remove_event), 
-            "Remove user event not found in events list");
+        add_events.assert_contains(&[expected_add_event]);
+        remove_events.assert_contains(&[expected_remove_event]);
     }
-
     #[test]
     fn test_manager_can_remove_user() {
         let (env, scorer_creator, client) = setup_contract();
@@ -799,11 +1760,10 @@ mod test {
         client.remove_user(&user);
         
         let users = client.get_users();
-        assert!(!users.get(user.clone()).unwrap());
+        assert_eq!(users.get(user.clone()), Some(UserStatus::Left));
     }
-
     #[test]
-    #[should_panic(expected = "UserDoesNotExist")]
+    #[should_panic(expected = "Error(Contract, #8)")]
     fn test_unauthorized_remove_user() {
         let (env, _scorer_creator, client) = setup_contract();
         let user = Address::generate(&env);
@@ -815,7 +1775,6 @@ mod test {
         // Unauthorized address cannot remove users
         client.remove_user(&user);
     }
-
     #[test]
     fn test_get_users() {
         let (env, _scorer_creator, client) = setup_contract();
@@ -827,10 +1786,9 @@ mod test {
         client.add_user(&user2);
         
         let users = client.get_users();
-        assert!(users.get(user1.clone()).unwrap());
-        assert!(users.get(user2.clone()).unwrap());
+        assert_eq!(users.get(user1.clone()), Some(UserStatus::Active));
+        assert_eq!(users.get(user2.clone()), Some(UserStatus::Active));
     }
-
     #[test]
     fn test_get_managers() {
         let (env, scorer_creator, client) = setup_contract();
@@ -844,7 +1802,6 @@ mod test {
         let managers = client.get_managers();
         assert_eq!(managers, Vec::from_slice(&env, &[scorer_creator.clone(), new_manager_1, new_manager_2]));
     }
-
     #[test]
     fn test_get_scorer_creator() {
         let (_, scorer_creator, client) = setup_contract();
@@ -853,7 +1810,6 @@ mod test {
         let owner = client.get_contract_owner();
         assert_eq!(owner, scorer_creator);
     }
-
     #[test]
     fn test_add_badge() {
         let (env, scorer_creator, client) = setup_contract();
@@ -863,6 +1819,7 @@ mod test {
         let score = 200;
         
         client.add_badge(&scorer_creator, &name, &issuer, &score);
+        let snapshot = EventSnapshot::capture(&env);
         
         // Verify the badge was added
         let badges = client.get_badges();
@@ -877,16 +1834,15 @@ mod test {
         assert_eq!(stored_details, score);
         
         // Verify event emission
-        let expected_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_BADGE), symbol_short!("add")).into_val(&env),
-            (badge_id, stored_details, scorer_creator).into_val(&env)
+        let expected_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("add")),
+            events::BadgeAdded { badge_id, score: stored_details, sender: scorer_creator },
         );
         
-        assert!(env.events().all().contains(&expected_event), 
-            "Add badge event not found in events list");
+        snapshot.assert_contains(&[expected_event]);
     }
-
     #[test]
     fn test_remove_badge() {
         let (env, scorer_creator, client) = setup_contract();
@@ -898,6 +1854,7 @@ mod test {
         
         // Add the badge with the new method
         client.add_badge(&scorer_creator, &name, &issuer, &score);
+        let add_events = EventSnapshot::capture(&env);
         
         // Create badge ID for verification
         let badge_id = BadgeId {
@@ -907,35 +1864,34 @@ mod test {
 
         // Remove the badge
         client.remove_badge(&scorer_creator, &name, &issuer);
+        let remove_events = EventSnapshot::capture(&env);
         
         // Verify the badge was removed
         let badges_after = client.get_badges();
         assert!(!badges_after.contains_key(badge_id.clone()));
         
         // Verify event emission (should have both add and remove events)
-        let events = env.events().all();
-        
-        // Check for add event
-        let expected_add_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_BADGE), symbol_short!("add")).into_val(&env),
-            (badge_id.clone(), score.clone(), scorer_creator.clone()).into_val(&env)
+        let expected_add_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("add")),
+            events::BadgeAdded { badge_id: badge_id.clone(), score, sender: scorer_creator.clone() },
         );
         
         // Check for remove event
-        let expected_remove_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_BADGE), symbol_short!("remove")).into_val(&env),
-            (badge_id, score, scorer_creator).into_val(&env)
+        let expected_remove_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("remove")),
+            events::BadgeRemoved { badge_id, score, sender: scorer_creator },
         );
         
         // Check if both events exist in the events list
-        assert!(events.contains(&expected_add_event), "Add event not found in events list");
-        assert!(events.contains(&expected_remove_event), "Remove event not found in events list");
+        add_events.assert_contains(&[expected_add_event]);
+        remove_events.assert_contains(&[expected_remove_event]);
     }
-
     #[test]
-    #[should_panic(expected = "Unauthorized")]
+    #[should_panic(expected = "Error(Contract, #2)")]
     fn test_add_badge_unauthorized() {
         let (env, _scorer_creator, client) = setup_contract();
         
@@ -950,9 +1906,8 @@ mod test {
         // This should panic because unauthorized_user is not a manager
         client.add_badge(&unauthorized_user, &name, &issuer, &score);
     }
-
     #[test]
-    #[should_panic(expected = "BadgeNotFound")]
+    #[should_panic(expected = "Error(Contract, #10)")]
     fn test_remove_nonexistent_badge() {
         let (env, scorer_creator, client) = setup_contract();
         
@@ -962,9 +1917,8 @@ mod test {
         
         client.remove_badge(&scorer_creator, &nonexistent_name, &issuer);
     }
-
     #[test]
-    #[should_panic(expected = "BadgeAlreadyExists")]
+    #[should_panic(expected = "Error(Contract, #9)")]
     fn test_add_duplicate_badge() {
         let (env, scorer_creator, client) = setup_contract();
         
@@ -979,9 +1933,8 @@ mod test {
         // Try to add the same badge again (same name and issuer)
         client.add_badge(&scorer_creator, &name, &issuer, &300);
     }
-
     #[test]
-    #[should_panic(expected = "Unauthorized")]
+    #[should_panic(expected = "Error(Contract, #2)")]
     fn test_remove_badge_unauthorized() {
         let (env, scorer_creator, client) = setup_contract();
         
@@ -999,9 +1952,8 @@ mod test {
         // This should panic because unauthorized_user is not a manager
         client.remove_badge(&unauthorized_user, &name, &issuer);
     }
-
     #[test]
-    #[should_panic(expected = "EmptyArg")]
+    #[should_panic(expected = "Error(Contract, #12)")]
     fn test_add_badge_empty_name() {
         let (env, scorer_creator, client) = setup_contract();
         
@@ -1011,7 +1963,30 @@ mod test {
         
         client.add_badge(&scorer_creator, &name, &issuer, &score);
     }
-
+    #[test]
+    fn test_string_length_caps() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        let scorer_creator = Address::generate(&env);
+        let text = |len: u32| String::from_bytes(&env, &[b'a'; 2_048][..len as usize]);
+        let badges = |len: u32| Map::from_array(&env, [(BadgeId { name: text(len), issuer: scorer_creator.clone() }, 100)]);
+
+        let (name, description, icon) = (text(MAX_NAME_LENGTH), text(MAX_DESCRIPTION_LENGTH), text(MAX_URI_LENGTH));
+        assert_eq!(client.try_initialize(&scorer_creator, &badges(1), &text(MAX_NAME_LENGTH + 1), &description, &icon), Err(Ok(Error::NameTooLong.into())));
+        assert_eq!(client.try_initialize(&scorer_creator, &badges(1), &name, &text(MAX_DESCRIPTION_LENGTH + 1), &icon), Err(Ok(Error::DescriptionTooLong.into())));
+        assert_eq!(client.try_initialize(&scorer_creator, &badges(1), &name, &description, &text(MAX_URI_LENGTH + 1)), Err(Ok(Error::IconTooLong.into())));
+        assert_eq!(client.try_initialize(&scorer_creator, &badges(MAX_BADGE_NAME_LENGTH + 1), &name, &description, &icon), Err(Ok(Error::BadgeNameTooLong.into())));
+
+        // Values at the limits are accepted
+        client.initialize(&scorer_creator, &badges(MAX_BADGE_NAME_LENGTH), &name, &description, &icon);
+        assert_eq!(client.get_metadata(), (name, description, icon));
+
+        assert_eq!(
+            client.try_add_badge(&scorer_creator, &text(MAX_BADGE_NAME_LENGTH + 1), &scorer_creator, &100),
+            Err(Ok(Error::BadgeNameTooLong.into()))
+        );
+    }
     #[test]
     fn test_manager_can_add_and_remove_badge() {
         let (env, scorer_creator, client) = setup_contract();
@@ -1044,7 +2019,6 @@ mod test {
         let badges_after = client.get_badges();
         assert!(!badges_after.contains_key(badge_id));
     }
-
     #[test]
     fn test_get_contract_version() {
         let (_, _, client) = setup_contract();
@@ -1052,9 +2026,8 @@ mod test {
         // Verify initial contract version
         assert_eq!(1, client.contract_version());
     }
-
     #[test]
-    #[should_panic(expected = "EmptyArg")]
+    #[should_panic(expected = "Error(Contract, #12)")]
     fn test_initialize_empty_args() {
         let (env, scorer_creator, scorer_client) = setup_contract();
         let scorer_badges = Map::new(&env);
@@ -1068,7 +2041,6 @@ mod test {
             &String::from_str(&env, "icon.png")
         );
     }
-
     #[test]
     fn test_add_badge_max_score() {
         let (env, scorer_creator, client) = setup_contract();
@@ -1087,7 +2059,6 @@ mod test {
         
         assert_eq!(badges.get(badge_id).unwrap(), score);
     }
-
     #[test]
     fn test_initialize_storage_state() {
         let env = Env::default();
@@ -1099,7 +2070,7 @@ mod test {
         let description = String::from_str(&env, "Test Description");
         let icon = String::from_str(&env, "test_icon.png");
         
-        let scorer_contract_id = env.register_contract(None, ScorerContract);
+        let scorer_contract_id = env.register(ScorerContract, ());
         let client = ScorerContractClient::new(&env, &scorer_contract_id);
 
         client.initialize(
@@ -1109,11 +2080,9 @@ mod test {
             &description,
             &icon
         );
+        let snapshot = EventSnapshot::capture(&env);
 
-        let is_initialized: bool = env.as_contract(&client.address, || {
-            env.storage().persistent().get(&DataKey::Initialized).unwrap()
-        });
-        assert!(is_initialized);
+        assert!(env.as_contract(&client.address, || ScorerContract::is_initialized(&env)));
 
         let stored_creator = client.get_contract_owner();
         assert_eq!(stored_creator, scorer_creator);
@@ -1122,23 +2091,22 @@ mod test {
         assert_eq!(managers.len(), 1);
         assert_eq!(managers.get(0).unwrap(), scorer_creator);
 
-        let expected_init_event = (
-            client.address.clone(),
-            (String::from_str(&env, TOPIC_INIT), symbol_short!("contract")).into_val(&env),
-            (
-                scorer_creator,
+        let expected_init_event = EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_INIT, symbol_short!("contract")),
+            events::ScorerInitialized {
+                creator: scorer_creator,
                 managers,
-                scorer_badges,
+                badges: scorer_badges,
                 name,
                 description,
-                icon
-            ).into_val(&env)
+                icon,
+            },
         );
         
-        assert!(env.events().all().contains(&expected_init_event), 
-            "Initialization event not found in events list");
+        snapshot.assert_contains(&[expected_init_event]);
     }
-
     #[test]
     fn test_get_contract_metadata() {
         let env = Env::default();
@@ -1150,7 +2118,7 @@ mod test {
         let description = String::from_str(&env, "Test Description");
         let icon = String::from_str(&env, "test_icon.png");
         
-        let scorer_contract_id = env.register_contract(None, ScorerContract);
+        let scorer_contract_id = env.register(ScorerContract, ());
         let client = ScorerContractClient::new(&env, &scorer_contract_id);
 
         client.initialize(
@@ -1176,7 +2144,6 @@ mod test {
         });
         assert_eq!(stored_icon, icon);
     }
-
     #[test]
     fn test_user_read_after_remove() {
         let env = Env::default();
@@ -1186,7 +2153,7 @@ mod test {
         let scorer_badges = Map::new(&env);
         let user = Address::generate(&env);
         
-        let scorer_contract_id = env.register_contract(None, ScorerContract);
+        let scorer_contract_id = env.register(ScorerContract, ());
         let client = ScorerContractClient::new(&env, &scorer_contract_id);
 
         client.initialize(
@@ -1204,11 +2171,10 @@ mod test {
         client.add_user(&user);
         
         let users = client.get_users();
-        assert!(users.get(user).unwrap());
+        assert_eq!(users.get(user), Some(UserStatus::Active));
     }
-
     #[test]
-    #[should_panic(expected = "InvalidScoreRange")]
+    #[should_panic(expected = "Error(Contract, #11)")]
     fn test_add_badge_score_above_max() {
         let env = Env::default();
         env.mock_all_auths();
@@ -1216,7 +2182,7 @@ mod test {
         let scorer_creator = Address::generate(&env);
         let scorer_badges = Map::new(&env);
         
-        let scorer_contract_id = env.register_contract(None, ScorerContract);
+        let scorer_contract_id = env.register(ScorerContract, ());
         let client = ScorerContractClient::new(&env, &scorer_contract_id);
 
         client.initialize(
@@ -1234,4 +2200,543 @@ mod test {
             &10001
         );
     }
-}   
\ No newline at end of file
+    mod capped_resolver {
+        use soroban_sdk::{contract, contractimpl, Address, Env, Map};
+        use scorer_interface::BadgeId;
+
+        // Counts each badge for at most 50 points
+        #[contract]
+        pub struct CappedResolver;
+
+        #[contractimpl]
+        impl CappedResolver {
+            pub fn resolve(_env: Env, _user: Address, attestations: Map<BadgeId, u32>) -> u32 {
+                attestations.values().iter().map(|score| score.min(50)).sum()
+            }
+        }
+    }
+    fn setup_scoring() -> (Env, Address, ScorerContractClient<'static>, Address, Vec<BadgeId>) {
+        let (env, scorer_creator, client) = setup_contract();
+        let issuer = Address::generate(&env);
+        client.add_badge(&scorer_creator, &String::from_str(&env, "Speaker"), &issuer, &40);
+
+        let user = Address::generate(&env);
+        client.add_user(&user);
+
+        let badges = Vec::from_array(&env, [
+            BadgeId { name: String::from_str(&env, "Test Badge"), issuer: scorer_creator.clone() },
+            BadgeId { name: String::from_str(&env, "Speaker"), issuer },
+            BadgeId { name: String::from_str(&env, "Unknown"), issuer: Address::generate(&env) },
+        ]);
+        (env, scorer_creator, client, user, badges)
+    }
+    #[test]
+    fn test_get_score_sums_badge_scores() {
+        let (env, _, client, user, badges) = setup_scoring();
+
+        assert_eq!(client.get_extension(&Extension::ScoreResolver), None);
+        assert_eq!(client.get_score(&user, &badges), 140);
+        assert_eq!(client.get_score(&user, &Vec::new(&env)), 0);
+
+        client.remove_user(&user);
+        assert_eq!(client.get_score(&user, &badges), 0);
+    }
+    #[test]
+    fn test_get_score_with_resolver() {
+        let (env, scorer_creator, client, user, badges) = setup_scoring();
+        let resolver = env.register(capped_resolver::CappedResolver, ());
+
+        client.set_extension(&scorer_creator, &Extension::ScoreResolver, &Some(resolver.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_STATUS, symbol_short!("extension")),
+            events::ExtensionSet { sender: scorer_creator.clone(), extension: Extension::ScoreResolver, address: Some(resolver.clone()) },
+        )]);
+        assert_eq!(client.get_extension(&Extension::ScoreResolver), Some(resolver.clone()));
+        assert_eq!(client.get_score(&user, &badges), 90);
+
+        client.set_extension(&scorer_creator, &Extension::ScoreResolver, &None);
+        assert_eq!(client.get_score(&user, &badges), 140);
+    }
+    mod fixed_staking {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        // Boosts every user by 25 points
+        #[contract]
+        pub struct FixedStaking;
+
+        #[contractimpl]
+        impl FixedStaking {
+            pub fn get_boost(_env: Env, _user: Address) -> u32 {
+                25
+            }
+        }
+    }
+    #[test]
+    fn test_get_score_with_staking() {
+        let (env, scorer_creator, client, user, badges) = setup_scoring();
+        let staking = env.register(fixed_staking::FixedStaking, ());
+
+        client.set_extension(&scorer_creator, &Extension::Staking, &Some(staking.clone()));
+        assert_eq!(client.get_score(&user, &badges), 165);
+
+        // The boost is added to the resolved score, and inactive users still score 0
+        client.set_extension(&scorer_creator, &Extension::ScoreResolver, &Some(env.register(capped_resolver::CappedResolver, ())));
+        assert_eq!(client.get_score(&user, &badges), 115);
+        assert_eq!(client.get_score(&Address::generate(&env), &badges), 0);
+
+        client.set_extension(&scorer_creator, &Extension::Staking, &None);
+        assert_eq!(client.get_score(&user, &badges), 90);
+    }
+    #[test]
+    fn test_set_badge_enabled() {
+        let (env, scorer_creator, client, user, badges) = setup_scoring();
+        let speaker = badges.get(1).unwrap();
+        assert!(client.is_badge_enabled(&speaker));
+
+        assert_eq!(client.try_set_badge_enabled(&user, &speaker, &false), Err(Ok(Error::Unauthorized.into())));
+        assert_eq!(client.try_set_badge_enabled(&scorer_creator, &badges.get(2).unwrap(), &false), Err(Ok(Error::BadgeNotFound.into())));
+
+        // A disabled badge keeps its score but counts for nothing
+        client.set_badge_enabled(&scorer_creator, &speaker, &false);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("enabled")),
+            events::BadgeEnabledSet { sender: scorer_creator.clone(), badge_id: speaker.clone(), enabled: false },
+        )]);
+        assert!(!client.is_badge_enabled(&speaker));
+        assert_eq!(client.get_badges().get(speaker.clone()), Some(40));
+        assert_eq!(client.get_score(&user, &badges), 100);
+
+        client.set_badge_enabled(&scorer_creator, &speaker, &true);
+        assert!(client.is_badge_enabled(&speaker));
+        assert_eq!(client.get_score(&user, &badges), 140);
+
+        // Removing a disabled badge forgets it was disabled
+        client.set_badge_enabled(&scorer_creator, &speaker, &false);
+        client.remove_badge(&scorer_creator, &speaker.name, &speaker.issuer);
+        client.add_badge(&scorer_creator, &speaker.name, &speaker.issuer, &40);
+        assert!(client.is_badge_enabled(&speaker));
+    }
+
+    mod revoked_attestations {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+        use scorer_interface::BadgeId;
+
+        // Disputes contract revoking the attestations set by the test
+        #[contract]
+        pub struct RevokedAttestations;
+
+        #[contractimpl]
+        impl RevokedAttestations {
+            pub fn revoke(env: Env, badge_id: BadgeId, user: Address) {
+                env.storage().persistent().set(&(badge_id, user), &true);
+            }
+
+            pub fn is_attestation_revoked(env: Env, badge_id: BadgeId, user: Address) -> bool {
+                env.storage().persistent().has(&(badge_id, user))
+            }
+        }
+    }
+
+    #[test]
+    fn test_revoked_attestation_does_not_count() {
+        let (env, scorer_creator, client, user, badges) = setup_scoring();
+        let disputes_id = env.register(revoked_attestations::RevokedAttestations, ());
+        let disputes = revoked_attestations::RevokedAttestationsClient::new(&env, &disputes_id);
+        let badge_id = badges.get(0).unwrap();
+
+        client.set_extension(&scorer_creator, &Extension::Disputes, &Some(disputes_id));
+        assert!(!client.is_attestation_revoked(&badge_id, &user));
+        assert_eq!(client.get_score(&user, &badges), 140);
+
+        disputes.revoke(&badge_id, &user);
+        assert!(client.is_attestation_revoked(&badge_id, &user));
+        assert_eq!(client.get_score(&user, &badges), 40);
+    }
+
+    #[test]
+    fn test_import_badges_from() {
+        let (env, scorer_creator, client, user, mut badges) = setup_scoring();
+
+        // Another community attesting a Mentor badge worth 80 to the user
+        let other_creator = Address::generate(&env);
+        let mentor = BadgeId { name: String::from_str(&env, "Mentor"), issuer: Address::generate(&env) };
+        let other = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        other.initialize(&other_creator, &Map::from_array(&env, [(mentor.clone(), 80)]), &String::from_str(&env, "Other"), &String::from_str(&env, "Other community"), &String::from_str(&env, "icon.png"));
+        other.add_user(&user);
+
+        let imported = Vec::from_array(&env, [mentor.clone()]);
+        client.import_badges_from(&scorer_creator, &other.address, &imported, &50);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("import")),
+            events::BadgesImported { sender: scorer_creator.clone(), scorer: other.address.clone(), badges: imported.clone(), weight: 50 },
+        )]);
+        assert_eq!(client.get_imported_badges().get(mentor.clone()), Some((other.address.clone(), 50)));
+
+        badges.push_back(mentor.clone());
+        assert_eq!(client.get_score(&user, &badges), 180);
+
+        // Revoked in the other community, the badge no longer counts
+        let disputes_id = env.register(revoked_attestations::RevokedAttestations, ());
+        other.set_extension(&other_creator, &Extension::Disputes, &Some(disputes_id.clone()));
+        revoked_attestations::RevokedAttestationsClient::new(&env, &disputes_id).revoke(&mentor, &user);
+        assert_eq!(client.get_score(&user, &badges), 140);
+
+        client.import_badges_from(&scorer_creator, &other.address, &imported, &0);
+        assert_eq!(client.get_imported_badges().len(), 0);
+
+        assert_eq!(
+            client.try_import_badges_from(&scorer_creator, &other.address, &imported, &101),
+            Err(Ok(Error::InvalidBadgeImport.into()))
+        );
+        assert_eq!(
+            client.try_import_badges_from(&scorer_creator, &client.address, &imported, &50),
+            Err(Ok(Error::InvalidBadgeImport.into()))
+        );
+        assert_eq!(
+            client.try_import_badges_from(&scorer_creator, &other.address, &badges, &50),
+            Err(Ok(Error::BadgeNotFound.into()))
+        );
+        assert_eq!(
+            client.try_import_badges_from(&Address::generate(&env), &other.address, &imported, &50),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_set_extension_unauthorized() {
+        let (env, _, client) = setup_contract();
+        let manager = Address::generate(&env);
+
+        client.add_manager(&client.get_contract_owner(), &manager);
+        client.set_extension(&manager, &Extension::ScoreResolver, &Some(Address::generate(&env)));
+    }
+
+    #[test]
+    fn test_analytics_failure_does_not_block_users() {
+        let (env, scorer_creator, client) = setup_contract();
+        // An address without a contract, so every report fails
+        let analytics = Address::generate(&env);
+
+        client.set_extension(&scorer_creator, &Extension::Analytics, &Some(analytics.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_STATUS, symbol_short!("extension")),
+            events::ExtensionSet { sender: scorer_creator, extension: Extension::Analytics, address: Some(analytics.clone()) },
+        )]);
+        assert_eq!(client.get_extension(&Extension::Analytics), Some(analytics.clone()));
+
+        let user = Address::generate(&env);
+        client.add_user(&user);
+        client.remove_user(&user);
+        assert_eq!(client.get_users().get(user), Some(UserStatus::Left));
+    }
+
+    #[test]
+    fn test_membership_admits_and_dismisses_users() {
+        let (env, scorer_creator, client) = setup_contract();
+        let membership = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        assert_eq!(client.try_admit_user(&membership, &user, &None), Err(Ok(Error::Unauthorized.into())));
+        client.set_extension(&scorer_creator, &Extension::Membership, &Some(membership.clone()));
+
+        // Users can only join through the membership contract
+        assert_eq!(client.try_add_user(&user), Err(Ok(Error::ApprovalRequired.into())));
+        assert_eq!(client.try_admit_user(&scorer_creator, &user, &None), Err(Ok(Error::Unauthorized.into())));
+        client.admit_user(&membership, &user, &Some(100));
+        assert_eq!(client.get_user_status(&user), Some(UserStatus::Active));
+        assert_eq!(client.get_membership_expiry(&user), Some(100));
+
+        // Renewing an active membership only moves its expiry
+        client.admit_user(&membership, &user, &Some(200));
+        assert_eq!(client.get_membership_expiry(&user), Some(200));
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 201);
+        assert_eq!(client.get_user_status(&user), Some(UserStatus::Left));
+        client.admit_user(&membership, &user, &None);
+        assert_eq!(client.get_user_status(&user), Some(UserStatus::Active));
+        assert_eq!(client.get_membership_expiry(&user), None);
+
+        client.dismiss_user(&membership, &user, &true);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("remove")),
+            events::UserLeft { user: user.clone() },
+        )]);
+        assert_eq!(client.get_user_status(&user), Some(UserStatus::Banned));
+        assert_eq!(client.try_dismiss_user(&membership, &user, &true), Err(Ok(Error::UserBanned.into())));
+        assert_eq!(client.try_admit_user(&membership, &user, &None), Err(Ok(Error::UserBanned.into())));
+
+        client.dismiss_user(&membership, &user, &false);
+        assert_eq!(client.get_user_status(&user), Some(UserStatus::Left));
+        assert_eq!(
+            client.try_dismiss_user(&membership, &Address::generate(&env), &false),
+            Err(Ok(Error::UserDoesNotExist.into()))
+        );
+    }
+
+    #[test]
+    fn test_governance_owns_badge_changes() {
+        let (env, scorer_creator, client) = setup_contract();
+        let governance = Address::generate(&env);
+        let badge_id = client.get_badges().keys().get_unchecked(0);
+        let name = String::from_str(&env, "Voted");
+
+        client.set_extension(&scorer_creator, &Extension::Governance, &Some(governance.clone()));
+        assert_eq!(client.try_add_badge(&scorer_creator, &name, &scorer_creator, &10), Err(Ok(Error::GovernanceRequired.into())));
+        assert_eq!(client.try_set_badge_score(&scorer_creator, &badge_id, &10), Err(Ok(Error::GovernanceRequired.into())));
+        assert_eq!(
+            client.try_import_badges_from(&scorer_creator, &Address::generate(&env), &Vec::new(&env), &50),
+            Err(Ok(Error::GovernanceRequired.into()))
+        );
+
+        client.add_badge(&governance, &name, &scorer_creator, &10);
+        client.set_badge_score(&governance, &badge_id, &60);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_BADGE, symbol_short!("score")),
+            events::BadgeScoreSet { badge_id: badge_id.clone(), previous_score: 100, score: 60, sender: governance.clone() },
+        )]);
+        client.remove_badge(&governance, &name, &scorer_creator);
+        assert_eq!(client.get_badges(), Map::from_array(&env, [(badge_id, 60)]));
+    }
+
+    #[test]
+    fn test_user_auth_is_bound_to_the_scorer() {
+        let (env, scorer_creator, client) = setup_contract();
+        let other = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        other.initialize(&scorer_creator, &Map::new(&env), &String::from_str(&env, "Other"), &String::from_str(&env, "Other scorer"), &String::from_str(&env, "icon.png"));
+        let user = Address::generate(&env);
+
+        let invoke = |fn_name, nonce: u32| MockAuthInvoke { contract: &client.address, fn_name, args: (&client.address, nonce).into_val(&env), sub_invokes: &[] };
+        let join = invoke("add_user", 0);
+        let auth = [MockAuth { address: &user, invoke: &join }];
+        client.mock_auths(&auth).add_user(&user);
+        assert_eq!(client.get_user_nonce(&user), 1);
+
+        // The same authorization neither joins another scorer, nor leaves this one,
+        // nor joins again once the user left
+        let unauthorized = Err(Ok(soroban_sdk::Error::from_type_and_code(ScErrorType::Context, ScErrorCode::InvalidAction)));
+        assert_eq!(other.mock_auths(&auth).try_add_user(&user), unauthorized);
+        assert_eq!(client.mock_auths(&auth).try_remove_user(&user), unauthorized);
+        assert_eq!(client.get_user_status(&user), Some(UserStatus::Active));
+        assert_eq!(other.get_user_status(&user), None);
+
+        let leave = invoke("remove_user", 1);
+        client.mock_auths(&[MockAuth { address: &user, invoke: &leave }]).remove_user(&user);
+        assert_eq!(client.mock_auths(&auth).try_add_user(&user), unauthorized);
+        assert_eq!(client.get_user_status(&user), Some(UserStatus::Left));
+        assert_eq!(client.get_user_nonce(&user), 2);
+    }
+
+    #[test]
+    fn test_maintenance() {
+        let (env, scorer_creator, client) = setup_contract();
+        let membership = Address::generate(&env);
+        client.set_extension(&scorer_creator, &Extension::Membership, &Some(membership.clone()));
+        let users = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+        for user in users.iter() {
+            client.admit_user(&membership, user, &Some(100));
+        }
+
+        // Two batches of two users cover the three users
+        assert_eq!(client.maintenance(&MaintenanceTask::ExtendTtl, &0, &2), 2);
+        assert_eq!(client.maintenance(&MaintenanceTask::ExtendTtl, &2, &2), 0);
+        let max_ttl = env.as_contract(&client.address, || env.storage().max_ttl());
+        assert_eq!(env.deployer().get_contract_instance_ttl(&client.address), max_ttl);
+        env.as_contract(&client.address, || {
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::UserCount), max_ttl);
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::Extension(Extension::Membership)), max_ttl);
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::UserAt(2)), max_ttl);
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::UserStatus(users[2].clone())), max_ttl);
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::JoinedAt(users[2].clone())), max_ttl);
+        });
+
+        // The expiry left behind for a user who is no longer active is dropped
+        env.as_contract(&client.address, || {
+            env.storage().persistent().set(&DataKey::UserStatus(users[0].clone()), &UserStatus::Left);
+        });
+        assert_eq!(client.maintenance(&MaintenanceTask::Compact, &0, &10), 0);
+        assert_eq!(client.get_membership_expiry(&users[0]), None);
+        assert_eq!(client.get_membership_expiry(&users[1]), Some(100));
+
+        // Expired members are recorded as leaving
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 101);
+        assert_eq!(client.maintenance(&MaintenanceTask::SweepExpired, &0, &10), 0);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("expire")),
+            events::MembershipExpired { user: users[1].clone() },
+        )]);
+        assert_eq!(client.get_membership_expiry(&users[1]), None);
+        env.as_contract(&client.address, || {
+            assert_eq!(ScorerContract::status_of(&env, &users[1]), Some(UserStatus::Left));
+        });
+    }
+
+    #[test]
+    fn test_maintenance_keeps_user_nonce_live() {
+        let (env, _, client) = setup_contract();
+        let user = Address::generate(&env);
+        client.add_user(&user);
+
+        // The nonce outlives the TTL it was written with once maintenance extends it
+        let ttl = env.as_contract(&client.address, || env.storage().persistent().get_ttl(&DataKey::UserNonce(user.clone())));
+        assert!(ttl < TTL_THRESHOLD);
+        assert_eq!(client.maintenance(&MaintenanceTask::ExtendTtl, &0, &10), 0);
+        env.ledger().with_mut(|ledger| ledger.sequence_number += ttl + 1);
+
+        client.remove_user(&user);
+        assert_eq!(client.get_user_nonce(&user), 2);
+    }
+
+    #[test]
+    fn test_maintenance_keeps_managers_live() {
+        let (env, scorer_creator, client) = setup_contract();
+        let manager = Address::generate(&env);
+        client.add_manager(&scorer_creator, &manager);
+
+        let ttl = env.as_contract(&client.address, || env.storage().persistent().get_ttl(&DataKey::ManagerAt(1)));
+        assert!(ttl < TTL_THRESHOLD);
+        assert_eq!(client.maintenance(&MaintenanceTask::ExtendTtl, &0, &10), 0);
+        env.ledger().with_mut(|ledger| ledger.sequence_number += ttl + 1);
+
+        assert_eq!(client.get_managers(), vec![&env, scorer_creator, manager.clone()]);
+        client.add_badge(&manager, &String::from_str(&env, "Live"), &manager, &10);
+    }
+
+    #[test]
+    fn test_poke_deactivates_expired_members() {
+        let (env, scorer_creator, client) = setup_contract();
+        assert_eq!(client.poke(&10), 0);
+
+        let membership = Address::generate(&env);
+        client.set_extension(&scorer_creator, &Extension::Membership, &Some(membership.clone()));
+        let users = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+        for user in users.iter() {
+            client.admit_user(&membership, user, &Some(100));
+        }
+        client.admit_user(&membership, &users[1], &Some(150));
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 101);
+        assert_eq!(client.poke(&0), 0);
+
+        // Two batches of two users cover the three users, then wrap around
+        assert_eq!(client.poke(&2), 1);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_USER, symbol_short!("expire")),
+            events::MembershipExpired { user: users[0].clone() },
+        )]);
+        assert_eq!(client.poke(&2), 1);
+        assert_eq!(client.poke(&3), 0);
+        assert_eq!(client.get_user_status(&users[0]), Some(UserStatus::Left));
+        assert_eq!(client.get_user_status(&users[1]), Some(UserStatus::Active));
+        assert_eq!(client.get_membership_expiry(&users[2]), None);
+    }
+
+    #[test]
+    fn test_restore_entry() {
+        let (env, scorer_creator, client) = setup_contract();
+        let badge_id = client.get_badges().keys().get_unchecked(0);
+        let user = Address::generate(&env);
+        client.add_user(&user);
+
+        // The status, join ledger and nonce of the user
+        assert_eq!(client.restore_entry(&ArchivedEntry::User(user.clone())), 3);
+        let max_ttl = env.as_contract(&client.address, || env.storage().max_ttl());
+        env.as_contract(&client.address, || {
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::UserStatus(user.clone())), max_ttl);
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::JoinedAt(user.clone())), max_ttl);
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::UserNonce(user.clone())), max_ttl);
+        });
+
+        // The badges map and the disabled badges
+        assert_eq!(client.restore_entry(&ArchivedEntry::Badge(badge_id.clone())), 1);
+        client.set_badge_enabled(&scorer_creator, &badge_id, &false);
+        assert_eq!(client.restore_entry(&ArchivedEntry::Badge(badge_id.clone())), 2);
+        env.as_contract(&client.address, || {
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::DisabledBadges), max_ttl);
+        });
+
+        assert_eq!(
+            client.try_restore_entry(&ArchivedEntry::User(Address::generate(&env))),
+            Err(Ok(Error::UserDoesNotExist.into()))
+        );
+        let unknown = BadgeId { name: String::from_str(&env, "Unknown"), issuer: badge_id.issuer };
+        assert_eq!(client.try_restore_entry(&ArchivedEntry::Badge(unknown)), Err(Ok(Error::BadgeNotFound.into())));
+    }
+
+    #[test]
+    fn test_migrate_from_version_1() {
+        let (env, scorer_creator, client) = setup_contract();
+        let (member, former, manager) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+
+        // Version 1 stored whether each user is active in a map, the managers
+        // in a list, and whether the scorer is initialized
+        let mut users = Map::<Address, bool>::new(&env);
+        users.set(member.clone(), true);
+        users.set(former.clone(), false);
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            storage.set(&DataKey::Users, &users);
+            storage.remove(&DataKey::UserCount);
+            storage.set(&DataKey::Managers, &Vec::from_slice(&env, &[scorer_creator.clone(), manager.clone()]));
+            storage.remove(&DataKey::ManagerCount);
+            storage.remove(&DataKey::ManagerAt(0));
+            storage.remove(&DataKey::ManagerIndex(scorer_creator.clone()));
+            storage.set(&DataKey::Initialized, &true);
+            storage.remove(&DataKey::StorageVersion);
+        });
+        // Until they are migrated, users are read from the map, at their index in it
+        let (first, second) = (users.keys().get_unchecked(0), users.keys().get_unchecked(1));
+        assert_eq!(client.get_user_status(&member), Some(UserStatus::Active));
+        assert_eq!(client.get_user_status(&former), Some(UserStatus::Left));
+        assert_eq!(client.get_users_page(&1, &5).get_unchecked(0).0, second);
+
+        // Users joining or leaving before their batch keep their index and new status
+        let newcomer = Address::generate(&env);
+        client.add_user(&newcomer);
+        client.add_user(&former);
+        assert_eq!(client.get_user_count(), 3);
+
+        // Each batch migrates one user, and the storage version only moves after the last one
+        assert_eq!(client.migrate(&scorer_creator, &0, &1), 1);
+        assert_eq!(client.get_storage_version(), 1);
+        // The managers are moved by the first batch
+        assert_eq!(client.get_managers(), Vec::from_slice(&env, &[scorer_creator.clone(), manager.clone()]));
+        assert_eq!(client.migrate(&scorer_creator, &1, &1), 0);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (EVENT_SCHEMA_VERSION, TOPIC_UPGRADE, symbol_short!("migrate")),
+            events::ScorerMigrated { sender: scorer_creator.clone(), from_version: 1, to_version: STORAGE_VERSION },
+        )]);
+        assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+
+        assert_eq!(
+            client.get_users_page(&0, &5),
+            vec![&env, (first, UserStatus::Active), (second, UserStatus::Active), (newcomer, UserStatus::Active)]
+        );
+        client.remove_user(&member);
+        assert_eq!(client.get_user_status(&member), Some(UserStatus::Left));
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            assert!(!storage.has(&DataKey::Users));
+            assert!(!storage.has(&DataKey::Initialized));
+            assert!(!storage.has(&DataKey::Managers));
+        });
+        client.remove_manager(&scorer_creator, &manager);
+        assert_eq!(client.get_manager_count(), 1);
+    }
+}
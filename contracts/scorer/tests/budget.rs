@@ -0,0 +1,241 @@
+//! Resource-budget benchmarks for the scorer entrypoints.
+//!
+//! Each entrypoint of the release WASM is measured with
+//! `env.cost_estimate().budget()` on a scorer holding 100, 1k and 10k users
+//! and badges, and the measured CPU instructions and memory bytes are checked
+//! against regression thresholds. `get_users` reads every user, so users are
+//! measured a page at a time with `get_users_page`.
+//! Run with `cargo test -p scorer --test budget -- --nocapture` to print the
+//! measurements.
+
+use soroban_sdk::{
+    testutils::Address as _,
+    vec,
+    xdr::{ScMap, ScVal},
+    Address, Env, IntoVal, Map, String, Symbol, TryFromVal, Val,
+};
+
+mod scorer_contract {
+    soroban_sdk::contractimport!(file = "../../wasm/scorer.wasm");
+}
+
+use scorer_contract::{BadgeId, Client as ScorerClient, UserStatus};
+
+const SIZES: [u32; 3] = [100, 1_000, 10_000];
+
+// Number of users in a measured page of `get_users_page`.
+const PAGE_SIZE: u32 = 10;
+
+// Upper bounds per entrypoint and collection size: (entrypoint, size, cpu instructions, memory bytes).
+// They leave some headroom over the measured costs; update them deliberately when a change is
+// expected to move costs.
+const THRESHOLDS: &[(&str, u32, u64, u64)] = &[
+    ("add_user", 100, 8_700_000, 2_900_000),
+    ("remove_user", 100, 8_400_000, 2_900_000),
+    ("get_users_page", 100, 9_400_000, 2_900_000),
+    ("add_badge", 100, 15_800_000, 4_400_000),
+    ("remove_badge", 100, 15_800_000, 4_300_000),
+    ("get_badges", 100, 14_600_000, 4_200_000),
+    ("add_manager", 100, 14_500_000, 4_200_000),
+    ("remove_manager", 100, 14_500_000, 4_200_000),
+    ("get_metadata", 100, 14_400_000, 4_200_000),
+    ("add_user", 1_000, 8_700_000, 2_900_000),
+    ("remove_user", 1_000, 8_400_000, 2_900_000),
+    ("get_users_page", 1_000, 9_400_000, 2_900_000),
+    ("add_badge", 1_000, 22_700_000, 5_500_000),
+    ("remove_badge", 1_000, 22_800_000, 5_500_000),
+    ("get_badges", 1_000, 17_700_000, 4_500_000),
+    ("add_manager", 1_000, 14_500_000, 4_200_000),
+    ("remove_manager", 1_000, 14_500_000, 4_200_000),
+    ("get_metadata", 1_000, 14_400_000, 4_200_000),
+    ("add_user", 10_000, 8_700_000, 2_900_000),
+    ("remove_user", 10_000, 8_400_000, 2_900_000),
+    ("get_users_page", 10_000, 9_400_000, 2_900_000),
+    ("add_badge", 10_000, 100_600_000, 17_700_000),
+    ("remove_badge", 10_000, 100_600_000, 17_700_000),
+    ("get_badges", 10_000, 48_200_000, 7_300_000),
+    ("add_manager", 10_000, 14_500_000, 4_200_000),
+    ("remove_manager", 10_000, 14_500_000, 4_200_000),
+    ("get_metadata", 10_000, 14_400_000, 4_200_000),
+];
+
+struct Bench {
+    env: Env,
+    client: ScorerClient<'static>,
+    owner: Address,
+}
+
+impl Bench {
+    // Deploys the scorer and seeds `size` users and badges directly in storage,
+    // which is much faster than calling the entrypoints `size` times. Each user
+    // has its own entries, so only the last page of users is written, at the
+    // end of a count of `size`: no measured call reads the others.
+    fn new(size: u32) -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.cost_estimate().budget().reset_unlimited();
+
+        let owner = Address::generate(&env);
+        let contract_id = env.register(scorer_contract::WASM, ());
+        let client = ScorerClient::new(&env, &contract_id);
+        client.initialize(
+            &owner,
+            &Map::new(&env),
+            &String::from_str(&env, "Scorer"),
+            &String::from_str(&env, "A benchmarked scorer"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        let badges = (0..size).map(|index| {
+            let badge_id: Val = Self::badge_id(&env, &owner, index).into_val(&env);
+            (
+                ScVal::try_from_val(&env, &badge_id).unwrap(),
+                ScVal::U32(100),
+            )
+        });
+        let badges = Self::map_val(&env, badges);
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().persistent();
+            for index in size - PAGE_SIZE..size {
+                let user = Address::generate(&env);
+                storage.set(&(Symbol::new(&env, "UserAt"), index), &user);
+                storage.set(&(Symbol::new(&env, "UserStatus"), user), &UserStatus::Active);
+            }
+            storage.set(&vec![&env, Symbol::new(&env, "UserCount")], &size);
+            storage.set(&vec![&env, Symbol::new(&env, "ScorerBadges")], &badges);
+        });
+
+        Bench { env, client, owner }
+    }
+
+    // Builds a map in a single host call, as growing it entry by entry is quadratic.
+    fn map_val(env: &Env, entries: impl Iterator<Item = (ScVal, ScVal)>) -> Val {
+        let map = ScMap::sorted_from_pairs(entries).unwrap();
+        Val::try_from_val(env, &ScVal::Map(Some(map))).unwrap()
+    }
+
+    fn badge_id(env: &Env, issuer: &Address, index: u32) -> BadgeId {
+        BadgeId {
+            name: String::from_str(env, &format!("Badge {}", index)),
+            issuer: issuer.clone(),
+        }
+    }
+
+    // Returns the cpu instructions and memory bytes consumed by `call`.
+    fn measure(&self, call: impl FnOnce(&Self)) -> (u64, u64) {
+        self.env.cost_estimate().budget().reset_unlimited();
+        call(self);
+        (
+            self.env.cost_estimate().budget().cpu_instruction_cost(),
+            self.env.cost_estimate().budget().memory_bytes_cost(),
+        )
+    }
+}
+
+fn measure_entrypoints(size: u32) -> std::vec::Vec<(&'static str, u64, u64)> {
+    let bench = Bench::new(size);
+    let user = Address::generate(&bench.env);
+    let manager = Address::generate(&bench.env);
+    let badge = Bench::badge_id(&bench.env, &bench.owner, size);
+
+    let mut costs = std::vec::Vec::new();
+    let mut record = |name: &'static str, (cpu, mem): (u64, u64)| costs.push((name, cpu, mem));
+
+    record("add_user", bench.measure(|b| b.client.add_user(&user)));
+    record(
+        "remove_user",
+        bench.measure(|b| b.client.remove_user(&user)),
+    );
+    record(
+        "get_users_page",
+        bench.measure(|b| {
+            b.client.get_users_page(&(size - PAGE_SIZE), &PAGE_SIZE);
+        }),
+    );
+    record(
+        "add_badge",
+        bench.measure(|b| {
+            b.client
+                .add_badge(&b.owner, &badge.name, &badge.issuer, &100)
+        }),
+    );
+    record(
+        "remove_badge",
+        bench.measure(|b| b.client.remove_badge(&b.owner, &badge.name, &badge.issuer)),
+    );
+    record(
+        "get_badges",
+        bench.measure(|b| {
+            b.client.get_badges();
+        }),
+    );
+    record(
+        "add_manager",
+        bench.measure(|b| b.client.add_manager(&b.owner, &manager)),
+    );
+    record(
+        "remove_manager",
+        bench.measure(|b| b.client.remove_manager(&b.owner, &manager)),
+    );
+    record(
+        "get_metadata",
+        bench.measure(|b| {
+            b.client.get_metadata();
+        }),
+    );
+
+    costs
+}
+
+fn check_size(size: u32) {
+    let costs = measure_entrypoints(size);
+    for (name, cpu, mem) in costs.iter() {
+        std::println!(
+            "{:>14} {:>6} users/badges: {:>12} cpu {:>12} mem",
+            name,
+            size,
+            cpu,
+            mem
+        );
+    }
+
+    for (name, cpu, mem) in costs {
+        let (_, _, max_cpu, max_mem) = THRESHOLDS
+            .iter()
+            .find(|(entrypoint, threshold_size, _, _)| {
+                *entrypoint == name && *threshold_size == size
+            })
+            .unwrap_or_else(|| panic!("no threshold for {} at {}", name, size));
+        assert!(
+            cpu <= *max_cpu,
+            "{} at {} used {} cpu instructions (max {})",
+            name,
+            size,
+            cpu,
+            max_cpu
+        );
+        assert!(
+            mem <= *max_mem,
+            "{} at {} used {} memory bytes (max {})",
+            name,
+            size,
+            mem,
+            max_mem
+        );
+    }
+}
+
+#[test]
+fn budget_100() {
+    check_size(SIZES[0]);
+}
+
+#[test]
+fn budget_1k() {
+    check_size(SIZES[1]);
+}
+
+#[test]
+fn budget_10k() {
+    check_size(SIZES[2]);
+}
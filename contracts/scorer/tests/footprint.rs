@@ -0,0 +1,248 @@
+//! Ledger-footprint regression guard for the scorer entrypoints.
+//!
+//! Each entrypoint of the release WASM is run on a scorer holding 100 users and
+//! badges while the host records its footprint: the scorer storage entries it
+//! reads and the ones it writes, with the XDR size of the written entries. The
+//! entries must match `FOOTPRINTS` exactly and the written bytes must stay under
+//! its limits, so a change that reads, writes or rewrites more state than
+//! before fails `cargo test`. Run with
+//! `cargo test -p scorer --test footprint -- --nocapture` to print the footprints.
+
+use std::rc::Rc;
+
+use soroban_env_host::storage::AccessType;
+use soroban_sdk::{
+    testutils::Address as _,
+    vec,
+    xdr::{Limits, LedgerEntry, LedgerKey, ScMap, ScVal, WriteXdr},
+    Address, Env, IntoVal, Map, String, Symbol, TryFromVal, Val,
+};
+
+mod scorer_contract {
+    soroban_sdk::contractimport!(file = "../../wasm/scorer.wasm");
+}
+
+use scorer_contract::{BadgeId, Client as ScorerClient, Extension, UserStatus};
+
+const SIZE: u32 = 100;
+
+// `Extension` variants by discriminant, to name the `Extension(..)` entries.
+const EXTENSIONS: &[&str] = &["Factory", "IssuerRegistry", "ScoreResolver", "Staking", "Analytics", "BadgeToken", "Membership", "Governance", "Disputes", "Scoreboard"];
+
+// Expected footprint per entrypoint: (entrypoint, entries read, entries written, max bytes written).
+// Entries are named after their `DataKey` variant, `Instance` being the contract instance and
+// `Extension::X` the contract registered for an extension. The byte limits leave some headroom
+// over the measured sizes; update the table deliberately when a change is expected to move the
+// footprint.
+const FOOTPRINTS: &[(&str, &[&str], &[&str], usize)] = &[
+    ("add_user", &["Archived", "Extension::Analytics", "Extension::Membership", "Instance", "Users"], &["JoinedAt", "UserAt", "UserCount", "UserNonce", "UserStatus"], 700),
+    ("remove_user", &["Extension::Analytics", "Extension::Membership", "Instance", "MembershipExpiry"], &["UserNonce", "UserStatus"], 300),
+    ("get_users_page", &["Instance", "MembershipExpiry", "UserAt", "UserAt", "UserCount", "UserStatus", "UserStatus"], &[], 0),
+    ("add_badge", &["Extension::Governance", "Extension::IssuerRegistry", "Extension::Scoreboard", "Instance", "ManagerIndex"], &["ScorerBadges"], 11_200),
+    ("remove_badge", &["DisabledBadges", "Extension::Governance", "Extension::Scoreboard", "Instance", "ManagerIndex"], &["ScorerBadges"], 11_100),
+    ("get_badges", &["Instance", "ScorerBadges"], &[], 0),
+    ("add_manager", &["Instance", "ScorerCreator"], &["ManagerAt", "ManagerCount", "ManagerIndex"], 400),
+    ("remove_manager", &["Instance", "ScorerCreator"], &["ManagerAt", "ManagerCount", "ManagerIndex"], 200),
+    ("get_managers", &["Instance", "ManagerAt", "ManagerCount"], &[], 0),
+    ("get_managers_page", &["Instance", "ManagerAt", "ManagerCount"], &[], 0),
+    ("get_metadata", &["Description", "Icon", "Instance", "Name"], &[], 0),
+    ("set_extension", &["Instance", "ScorerCreator"], &["Extension::Factory"], 200),
+    ("set_archived", &["Instance", "ScorerCreator"], &["Archived"], 100),
+    ("enable_upgrade_guard", &["Instance", "ScorerCreator"], &["UpgradeGuard"], 200),
+    ("get_storage_version", &["Instance", "StorageVersion"], &[], 0),
+    ("get_score", &["DisabledBadges", "Extension::Disputes", "Extension::ScoreResolver", "Extension::Staking", "ImportedBadges", "Instance", "MembershipExpiry", "ScorerBadges", "UserStatus"], &[], 0),
+    ("transfer_ownership", &["Instance"], &["ManagerAt", "ManagerCount", "ManagerIndex", "ManagerIndex", "ScorerCreator"], 600),
+];
+
+struct Footprint {
+    reads: std::vec::Vec<std::string::String>,
+    writes: std::vec::Vec<std::string::String>,
+    write_bytes: usize,
+}
+
+struct Bench {
+    env: Env,
+    client: ScorerClient<'static>,
+    owner: Address,
+}
+
+impl Bench {
+    // Deploys the scorer and seeds `SIZE` users and badges directly in storage.
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.cost_estimate().budget().reset_unlimited();
+
+        let owner = Address::generate(&env);
+        let contract_id = env.register(scorer_contract::WASM, ());
+        let client = ScorerClient::new(&env, &contract_id);
+        client.initialize(
+            &owner,
+            &Map::new(&env),
+            &String::from_str(&env, "Scorer"),
+            &String::from_str(&env, "A measured scorer"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        let badges = (0..SIZE).map(|index| {
+            let badge_id: Val = Self::badge_id(&env, &owner, index).into_val(&env);
+            (ScVal::try_from_val(&env, &badge_id).unwrap(), ScVal::U32(100))
+        });
+        let badges = Self::map_val(&env, badges);
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().persistent();
+            for index in 0..SIZE {
+                let user = Address::generate(&env);
+                storage.set(&(Symbol::new(&env, "UserAt"), index), &user);
+                storage.set(&(Symbol::new(&env, "UserStatus"), user), &UserStatus::Active);
+            }
+            storage.set(&vec![&env, Symbol::new(&env, "UserCount")], &SIZE);
+            storage.set(&vec![&env, Symbol::new(&env, "ScorerBadges")], &badges);
+        });
+
+        Bench { env, client, owner }
+    }
+
+    fn map_val(env: &Env, entries: impl Iterator<Item = (ScVal, ScVal)>) -> Val {
+        let map = ScMap::sorted_from_pairs(entries).unwrap();
+        Val::try_from_val(env, &ScVal::Map(Some(map))).unwrap()
+    }
+
+    fn badge_id(env: &Env, issuer: &Address, index: u32) -> BadgeId {
+        BadgeId {
+            name: String::from_str(env, &format!("Badge {}", index)),
+            issuer: issuer.clone(),
+        }
+    }
+
+    // Records the scorer storage entries accessed by `call`, named after their
+    // `DataKey` variant.
+    fn measure(&self, call: impl FnOnce(&Self)) -> Footprint {
+        let host = self.env.host();
+        host.with_mut_storage(|storage| {
+            storage.footprint = Default::default();
+            Ok(())
+        })
+        .unwrap();
+
+        call(self);
+
+        let scorer = ScVal::from(&self.client.address);
+        let budget = host.budget_cloned();
+        host.with_mut_storage(|storage| {
+            let mut footprint = Footprint { reads: std::vec::Vec::new(), writes: std::vec::Vec::new(), write_bytes: 0 };
+            for (key, access) in storage.footprint.0.iter(&budget)? {
+                let LedgerKey::ContractData(data) = key.as_ref() else {
+                    continue;
+                };
+                if ScVal::Address(data.contract.clone()) != scorer {
+                    continue;
+                }
+                let name = Self::key_name(&data.key);
+                match access {
+                    AccessType::ReadOnly => footprint.reads.push(name),
+                    AccessType::ReadWrite => {
+                        footprint.writes.push(name);
+                        if let Some(Some((entry, _))) = storage.map.get::<Rc<LedgerKey>>(key, &budget)? {
+                            footprint.write_bytes += Self::entry_size(entry);
+                        }
+                    }
+                }
+            }
+            footprint.reads.sort();
+            footprint.writes.sort();
+            Ok(footprint)
+        })
+        .unwrap()
+    }
+
+    fn key_name(key: &ScVal) -> std::string::String {
+        match key {
+            ScVal::LedgerKeyContractInstance => "Instance".to_string(),
+            ScVal::Vec(Some(parts)) => match (parts.first(), parts.get(1)) {
+                (Some(ScVal::Symbol(symbol)), Some(ScVal::U32(extension))) if symbol.to_utf8_string_lossy() == "Extension" => {
+                    format!("Extension::{}", EXTENSIONS[*extension as usize])
+                }
+                (Some(ScVal::Symbol(symbol)), _) => symbol.to_utf8_string_lossy(),
+                _ => format!("{:?}", key),
+            },
+            _ => format!("{:?}", key),
+        }
+    }
+
+    fn entry_size(entry: &LedgerEntry) -> usize {
+        entry.to_xdr(Limits::none()).unwrap().len()
+    }
+}
+
+fn measure_entrypoints() -> std::vec::Vec<(&'static str, Footprint)> {
+    let bench = Bench::new();
+    let user = Address::generate(&bench.env);
+    let manager = Address::generate(&bench.env);
+    let badge = Bench::badge_id(&bench.env, &bench.owner, SIZE);
+    let factory = Address::generate(&bench.env);
+
+    std::vec![
+        ("add_user", bench.measure(|b| b.client.add_user(&user))),
+        ("get_score", bench.measure(|b| {
+            b.client.get_score(&user, &vec![&b.env, badge.clone()]);
+        })),
+        ("remove_user", bench.measure(|b| b.client.remove_user(&user))),
+        ("get_users_page", bench.measure(|b| {
+            b.client.get_users_page(&0, &2);
+        })),
+        ("add_badge", bench.measure(|b| b.client.add_badge(&b.owner, &badge.name, &badge.issuer, &100))),
+        ("remove_badge", bench.measure(|b| b.client.remove_badge(&b.owner, &badge.name, &badge.issuer))),
+        ("get_badges", bench.measure(|b| {
+            b.client.get_badges();
+        })),
+        ("add_manager", bench.measure(|b| b.client.add_manager(&b.owner, &manager))),
+        ("remove_manager", bench.measure(|b| b.client.remove_manager(&b.owner, &manager))),
+        ("get_managers", bench.measure(|b| {
+            b.client.get_managers();
+        })),
+        ("get_managers_page", bench.measure(|b| {
+            b.client.get_managers_page(&0, &1);
+        })),
+        ("get_metadata", bench.measure(|b| {
+            b.client.get_metadata();
+        })),
+        ("set_extension", bench.measure(|b| b.client.set_extension(&b.owner, &Extension::Factory, &Some(factory.clone())))),
+        ("set_archived", bench.measure(|b| b.client.set_archived(&b.owner, &true))),
+        ("enable_upgrade_guard", bench.measure(|b| b.client.enable_upgrade_guard(&b.owner, &factory))),
+        ("get_storage_version", bench.measure(|b| {
+            b.client.get_storage_version();
+        })),
+        ("transfer_ownership", bench.measure(|b| b.client.transfer_ownership(&b.owner, &manager))),
+    ]
+}
+
+#[test]
+fn footprint() {
+    let footprints = measure_entrypoints();
+    for (name, footprint) in footprints.iter() {
+        std::println!(
+            "{:>20}: reads {:?} writes {:?} ({} bytes)",
+            name,
+            footprint.reads,
+            footprint.writes,
+            footprint.write_bytes
+        );
+    }
+
+    for (name, footprint) in footprints {
+        let (_, reads, writes, max_write_bytes) = FOOTPRINTS
+            .iter()
+            .find(|(entrypoint, _, _, _)| *entrypoint == name)
+            .unwrap_or_else(|| panic!("no footprint for {}", name));
+        assert_eq!(footprint.reads, *reads, "{} read unexpected entries", name);
+        assert_eq!(footprint.writes, *writes, "{} wrote unexpected entries", name);
+        assert!(
+            footprint.write_bytes <= *max_write_bytes,
+            "{} wrote {} bytes (max {})",
+            name,
+            footprint.write_bytes,
+            max_write_bytes
+        );
+    }
+}
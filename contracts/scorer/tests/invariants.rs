@@ -0,0 +1,188 @@
+//! Property-based tests running random sequences of scorer operations against
+//! a simple model of the expected state.
+
+use proptest::prelude::*;
+use scorer::{BadgeId, ScorerContract, ScorerContractClient, UserStatus};
+use soroban_sdk::{testutils::Address as _, Address, Env, Map, String};
+use std::collections::{BTreeMap, BTreeSet};
+
+const BADGE_NAMES: usize = 4;
+const USERS: usize = 4;
+const MANAGERS: usize = 3;
+
+#[derive(Clone, Debug)]
+enum Op {
+    AddBadge { badge: usize, score: u32 },
+    RemoveBadge { badge: usize },
+    AddUser { user: usize },
+    RemoveUser { user: usize },
+    // Manager 0 is the scorer creator
+    AddManager { manager: usize },
+    RemoveManager { manager: usize },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (0..BADGE_NAMES, 0u32..12_000).prop_map(|(badge, score)| Op::AddBadge { badge, score }),
+        (0..BADGE_NAMES).prop_map(|badge| Op::RemoveBadge { badge }),
+        (0..USERS).prop_map(|user| Op::AddUser { user }),
+        (0..USERS).prop_map(|user| Op::RemoveUser { user }),
+        (0..MANAGERS).prop_map(|manager| Op::AddManager { manager }),
+        (0..MANAGERS).prop_map(|manager| Op::RemoveManager { manager }),
+    ]
+}
+
+#[derive(Default)]
+struct Model {
+    badges: BTreeMap<usize, u32>,
+    users: BTreeMap<usize, bool>,
+    managers: BTreeSet<usize>,
+}
+
+impl Model {
+    // Applies `op` and returns whether the contract call should succeed.
+    // Every call is sent by the creator, who only manages badges while being
+    // a manager.
+    fn apply(&mut self, op: &Op) -> bool {
+        let creator_is_manager = self.managers.contains(&0);
+        match *op {
+            Op::AddBadge { badge, score } => {
+                if !creator_is_manager || score > 10_000 || self.badges.contains_key(&badge) {
+                    return false;
+                }
+                self.badges.insert(badge, score);
+            }
+            Op::RemoveBadge { badge } => {
+                if !creator_is_manager || self.badges.remove(&badge).is_none() {
+                    return false;
+                }
+            }
+            Op::AddUser { user } => {
+                if self.users.get(&user) == Some(&true) {
+                    return false;
+                }
+                self.users.insert(user, true);
+            }
+            Op::RemoveUser { user } => {
+                if self.users.get(&user) != Some(&true) {
+                    return false;
+                }
+                self.users.insert(user, false);
+            }
+            Op::AddManager { manager } => {
+                if !self.managers.insert(manager) {
+                    return false;
+                }
+            }
+            Op::RemoveManager { manager } => {
+                if self.managers.len() == 1 || !self.managers.remove(&manager) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+struct World {
+    env: Env,
+    client: ScorerContractClient<'static>,
+    users: std::vec::Vec<Address>,
+    managers: std::vec::Vec<Address>,
+}
+
+impl World {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.cost_estimate().budget().reset_unlimited();
+
+        let users = (0..USERS).map(|_| Address::generate(&env)).collect();
+        let managers: std::vec::Vec<Address> = (0..MANAGERS).map(|_| Address::generate(&env)).collect();
+
+        let client = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        client.initialize(
+            &managers[0],
+            &Map::new(&env),
+            &String::from_str(&env, "Scorer"),
+            &String::from_str(&env, "A scorer under test"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        World { env, client, users, managers }
+    }
+
+    fn badge_name(&self, badge: usize) -> String {
+        String::from_str(&self.env, &format!("Badge {}", badge))
+    }
+
+    // Returns whether the contract call succeeded.
+    fn apply(&self, op: &Op) -> bool {
+        let owner = &self.managers[0];
+        match *op {
+            Op::AddBadge { badge, score } => self
+                .client
+                .try_add_badge(owner, &self.badge_name(badge), owner, &score)
+                .is_ok(),
+            Op::RemoveBadge { badge } => self
+                .client
+                .try_remove_badge(owner, &self.badge_name(badge), owner)
+                .is_ok(),
+            Op::AddUser { user } => self.client.try_add_user(&self.users[user]).is_ok(),
+            Op::RemoveUser { user } => self.client.try_remove_user(&self.users[user]).is_ok(),
+            Op::AddManager { manager } => self
+                .client
+                .try_add_manager(owner, &self.managers[manager])
+                .is_ok(),
+            Op::RemoveManager { manager } => self
+                .client
+                .try_remove_manager(owner, &self.managers[manager])
+                .is_ok(),
+        }
+    }
+
+    fn assert_matches(&self, model: &Model) {
+        let managers = self.client.get_managers();
+        assert!(!managers.is_empty(), "scorer has no managers");
+        let expected_managers: std::vec::Vec<Address> =
+            model.managers.iter().map(|manager| self.managers[*manager].clone()).collect();
+        assert_eq!(managers.len() as usize, expected_managers.len());
+        for manager in expected_managers.iter() {
+            assert!(managers.contains(manager));
+        }
+
+        let mut expected_badges = Map::new(&self.env);
+        for (badge, score) in model.badges.iter() {
+            let badge_id = BadgeId {
+                name: self.badge_name(*badge),
+                issuer: self.managers[0].clone(),
+            };
+            expected_badges.set(badge_id, *score);
+        }
+        assert_eq!(self.client.get_badges(), expected_badges);
+
+        let mut expected_users = Map::new(&self.env);
+        for (user, active) in model.users.iter() {
+            let status = if *active { UserStatus::Active } else { UserStatus::Left };
+            expected_users.set(self.users[*user].clone(), status);
+        }
+        assert_eq!(self.client.get_users(), expected_users);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn scorer_state_matches_model(ops in proptest::collection::vec(op(), 1..40)) {
+        let world = World::new();
+        let mut model = Model::default();
+        model.managers.insert(0);
+
+        for op in ops.iter() {
+            let expected = model.apply(op);
+            prop_assert_eq!(world.apply(op), expected, "unexpected result for {:?}", op);
+            world.assert_matches(&model);
+        }
+    }
+}
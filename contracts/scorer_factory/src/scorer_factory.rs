@@ -1,33 +1,130 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, String, Symbol, Val, Vec, FromVal};
+use soroban_sdk::EnvBase as _;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, unwrap::UnwrapOptimized, Address, BytesN, Env, Map, String, Symbol, Val, Vec, FromVal, IntoVal, TryFromVal};
+use scorer_interface::{FactoryOperation, IssuerRegistryClient, IssuerRegistryTrait, MaintenanceTask, MaintenanceTrait, ScorerClient, TTL_THRESHOLD};
+use scorer_interface::events::{TOPIC_INIT, TOPIC_SCORER, TOPIC_MANAGER, TOPIC_WASM_HASH, TOPIC_TEMPLATE, TOPIC_PAUSE, TOPIC_ISSUER};
 
-// Event topics
-const TOPIC_SCORER: &str = "scorer";
-const TOPIC_MANAGER: &str = "manager"; 
+pub use scorer_interface::{events, BadgeId};
+
+// Number of scorers returned per page by paginated queries
+const PAGE_SIZE: u32 = 10;
+
+// Maximum length in bytes of a scorer name indexed for lookups, the one scorers accept
+const MAX_NAME_LENGTH: usize = scorer_interface::MAX_NAME_LENGTH as usize;
+
+// Version of the storage layout written by this code. Factories initialized by
+// releases without a stored version are at version 1
+const STORAGE_VERSION: u32 = 2;
 
 #[contracttype]
 enum DataKey {
+    /// Map of every registered scorer to its metadata, replaced by `Scorer` and the creation slots in storage version 2.
     CreatedScorers,
     Initialized,
     ScorerFactoryCreator,
     Managers,
     ScorerWasmHash,
+    ApprovedWasmHashes,
+    BadgeTemplates,
+    DefaultTemplate,
+    PausedOperations,
+    TemplateHashes,
+    Scorer(Address),
+    StorageVersion,
+    ScorerCount,
+    ScorerAt(u32),
+    ScorerSlot(Address),
+    ScorerByName(String),
+    ScorerCreator(Address),
+    CreatorScorers(Address),
+    IssuerRegistry,
+    UpgradeHistory(Address),
+    VerifiedIssuers,
+    WasmHashGovernance,
+}
+
+/// A registered scorer as listed by `list_scorers`, and moved between factory
+/// deployments with `export_registration` and `import_registration`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerInfo {
+    pub address: Address,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    // The creator recorded for the scorer, if any
+    pub creator: Option<Address>,
+    // Ledger at which the scorer was created, 0 for scorers created before it was recorded
+    pub created_at: u32,
 }
 
+/// An upgrade of a registered scorer, as recorded by `record_upgrade`.
 #[contracttype]
-#[derive(Debug)]
-enum Error {
-    ContractAlreadyInitialized,
-    Unauthorized,
-    ManagerAlreadyExists,
-    ManagerNotFound,
-    ManagersNotFound,
-    ContractCreatorNotFound,
-    ScorersWereNotFound,
-    ScorerNotFound,
-    InvalidInitArgs,
-    ScorerFactoryCreatorNotFound,
-    CannotRemoveLastManager,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerUpgrade {
+    pub wasm_hash: BytesN<32>,
+    // Ledger at which the scorer installed the code
+    pub ledger: u32,
+}
+
+// Errors raised by the factory. Entrypoints fail with `panic_with_error!`, so
+// the error code can be read from the transaction result.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    // The caller is not allowed to perform the operation.
+    Unauthorized = 2,
+    // The address is already a manager.
+    ManagerAlreadyExists = 3,
+    // The address is not a manager.
+    ManagerNotFound = 4,
+    // The managers list is missing from storage.
+    ManagersNotFound = 5,
+    // The scorer WASM hash is missing from storage.
+    ContractCreatorNotFound = 6,
+    // The registry of created scorers is missing from storage.
+    ScorersWereNotFound = 7,
+    // The scorer is not registered in the factory.
+    ScorerNotFound = 8,
+    // The scorer init arguments are missing required values.
+    InvalidInitArgs = 9,
+    // The factory creator is missing from storage.
+    ScorerFactoryCreatorNotFound = 10,
+    // The manager is the last one.
+    CannotRemoveLastManager = 11,
+    // The WASM hash is already approved.
+    WasmHashAlreadyApproved = 12,
+    // The WASM hash is not approved.
+    WasmHashNotApproved = 13,
+    // No badge template with this id exists.
+    BadgeTemplateNotFound = 14,
+    // The issuer is already in the factory's list of verified issuers.
+    IssuerAlreadyVerified = 15,
+    // The issuer is not in the factory's list of verified issuers.
+    IssuerNotVerified = 16,
+    // The scorer has no recorded creator.
+    ScorerCreatorNotFound = 17,
+    // The scorer name exceeds the maximum length.
+    NameTooLong = 18,
+    // The scorer is already registered in the factory.
+    ScorerAlreadyRegistered = 19,
+    // The contract does not implement the scorer interface.
+    NotAScorer = 20,
+    // The operation is paused.
+    OperationPaused = 21,
+    // Scorer WASM hash changes go through the governance contract.
+    ProposalRequired = 23,
+    // The storage is already at the current layout.
+    AlreadyMigrated = 28,
+    // Another scorer already uses the name.
+    ScorerNameTaken = 29,
+    // The WASM hash governance contract does not govern this factory.
+    InvalidWasmHashGovernance = 30,
+    // The storage is at a previous layout and must be migrated first.
+    MigrationRequired = 31,
 }
 
 #[contract]
@@ -36,73 +133,178 @@ pub struct ScorerFactoryContract;
 #[contractimpl]
 impl ScorerFactoryContract {
     
-    /// Initializes the ScorerFactory contract with the initial manager (scorer_creator)
-    /// 
-    /// # Arguments
-    /// * `env` - The Soroban environment
-    /// * `scorer_creator` - The address that will be set as both the factory creator and initial manager
-    /// * `scorer_wasm_hash` - The hash of the Wasm binary for the scorer contract
-    /// 
-    /// # Panics
-    /// * When the contract is already initialized
-    /// * When the scorer_creator fails authentication
+    // Initializes the ScorerFactory contract with the initial manager (scorer_creator)
     pub fn initialize(env: Env, scorer_creator: Address, scorer_wasm_hash: BytesN<32>) {
-        if Self::is_initialized(env.clone()) {
-            panic!("{:?}", Error::ContractAlreadyInitialized);
+        if Self::initialized(&env) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
         }
         scorer_creator.require_auth();
 
         let mut managers = Vec::<Address>::new(&env);
         managers.push_back(scorer_creator.clone());
         
-        env.storage().persistent().set(&DataKey::Initialized, &true);
-        env.storage().persistent().set(&DataKey::ScorerFactoryCreator, &scorer_creator);
-        env.storage().persistent().set(&DataKey::Managers, &managers);
-        env.storage().persistent().set(&DataKey::ScorerWasmHash, &scorer_wasm_hash);
-        env.storage().persistent().set(&DataKey::CreatedScorers, &Map::<Address, (String, String, String)>::new(&env));
+        env.storage().instance().set(&DataKey::Initialized, &true);
+        env.storage().instance().set(&DataKey::ScorerFactoryCreator, &scorer_creator);
+        env.storage().instance().set(&DataKey::Managers, &managers);
+        env.storage().instance().set(&DataKey::ScorerWasmHash, &scorer_wasm_hash);
+        env.storage().instance().set(&DataKey::StorageVersion, &STORAGE_VERSION);
+        env.storage().persistent().set(&DataKey::TemplateHashes, &Vec::from_array(&env, [scorer_wasm_hash.clone()]));
+        env.storage().instance().set(&DataKey::ScorerCount, &0u32);
+
+        // Emit an initialization event
+        env.events().publish(
+            (TOPIC_INIT, symbol_short!("contract")),
+            events::FactoryInitialized { creator: scorer_creator, managers, scorer_wasm_hash },
+        );
     }
 
-    /// Checks if the contract has been initialized
-    /// 
-    /// # Arguments
-    /// * `env` - The Soroban environment
-    /// 
-    /// # Returns
-    /// * `bool` - True if the contract is initialized, false otherwise
+    // Checks if the contract has been initialized
     pub fn is_initialized(env: Env) -> bool {
-        env.storage().persistent().get::<DataKey, bool>(&DataKey::Initialized).unwrap_or(false)
+        Self::require_migrated(env.clone());
+
+        Self::initialized(&env)
+    }
+
+    /// Checks if the contract has been initialized, by this release or by one
+    /// that kept the flag in persistent storage, until `migrate` moves it
+    fn initialized(env: &Env) -> bool {
+        env.storage().instance().has(&DataKey::Initialized) || env.storage().persistent().has(&DataKey::Initialized)
+    }
+
+    /// Rejects the calls to a factory upgraded from a previous storage layout
+    /// until `migrate` brings it to the current one, as this code would misread
+    /// it. Entrypoints run it first, directly or through the creator, manager,
+    /// pause and instance TTL checks they start with, and `initialize` fails
+    /// on the flag the previous layout left. `migrate` alone skips it
+    ///
+    /// # Panics
+    /// * When the factory was initialized at a previous layout and not migrated (`Error::MigrationRequired`)
+    fn require_migrated(env: Env) {
+        if Self::storage_version(&env) < STORAGE_VERSION && Self::initialized(&env) {
+            panic_with_error!(&env, Error::MigrationRequired);
+        }
+    }
+
+    // Migrates the storage written by a previous release to the current layout,
+    // in batches of `limit` scorers from `cursor`
+    pub fn migrate(env: Env, caller: Address, cursor: u32, limit: u32) -> u32 {
+        // Meant to be called by the factory creator once the factory runs the current
+        // code, from cursor 0 and then with the returned cursor until it returns 0.
+        // Factories initialized by the current code are already at the current layout.
+        // Version 1 kept the configuration in persistent storage and the whole registry
+        // in a single map: the migration moves the configuration to instance storage,
+        // and gives each scorer of the map a creation slot in the map's order, with a
+        // creation ledger of 0 and no creator. A scorer is indexed under its name when
+        // the name is free, so of the scorers sharing a normalized name the first one
+        // keeps it and the others are only reachable by address.
+
+        caller.require_auth();
+
+        let version = Self::storage_version(&env);
+        if version >= STORAGE_VERSION {
+            panic_with_error!(&env, Error::AlreadyMigrated);
+        }
+
+        for key in [DataKey::Initialized, DataKey::ScorerFactoryCreator, DataKey::Managers, DataKey::ScorerWasmHash] {
+            Self::move_to_instance(&env, key);
+        }
+        let instance = env.storage().instance();
+        let creator = instance
+            .get::<DataKey, Address>(&DataKey::ScorerFactoryCreator)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerFactoryCreatorNotFound));
+        if creator != caller {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        let storage = env.storage().persistent();
+        let created_scorers = storage
+            .get::<DataKey, Map<Address, (String, String, String)>>(&DataKey::CreatedScorers)
+            .unwrap_or_else(|| Map::new(&env));
+        // The slots of the map are reserved from the first batch, so scorers created
+        // while the migration runs are recorded after them
+        if !instance.has(&DataKey::ScorerCount) {
+            instance.set(&DataKey::ScorerCount, &created_scorers.len());
+        }
+
+        let cursor = if cursor < created_scorers.len() { cursor } else { 0 };
+        let end = cursor.saturating_add(limit).min(created_scorers.len());
+        let scorer_addresses = created_scorers.keys();
+        for slot in cursor..end {
+            let scorer_address = scorer_addresses.get_unchecked(slot);
+            let metadata = created_scorers.get_unchecked(scorer_address.clone());
+            // Names too long to normalize are not indexed
+            if let Some(name) = Self::normalize_name(&env, &metadata.0) {
+                let name_key = DataKey::ScorerByName(name);
+                if !storage.has(&name_key) {
+                    storage.set(&name_key, &scorer_address);
+                }
+            }
+            storage.set(&DataKey::Scorer(scorer_address.clone()), &metadata);
+            storage.set(&DataKey::ScorerAt(slot), &scorer_address);
+            storage.set(&DataKey::ScorerSlot(scorer_address), &(slot, 0u32));
+        }
+        if end < created_scorers.len() {
+            return end;
+        }
+
+        storage.remove(&DataKey::CreatedScorers);
+        instance.set(&DataKey::StorageVersion, &STORAGE_VERSION);
+
+        env.events().publish(
+            (TOPIC_INIT, symbol_short!("migrate")),
+            events::FactoryMigrated { caller, from_version: version, to_version: STORAGE_VERSION },
+        );
+        0
+    }
+
+    // Retrieves the version of the storage layout
+    pub fn get_storage_version(env: Env) -> u32 {
+        Self::require_migrated(env.clone());
+
+        Self::storage_version(&env)
+    }
+
+    /// Reads the version of the storage layout, 1 when none is stored
+    fn storage_version(env: &Env) -> u32 {
+        env.storage().instance().get(&DataKey::StorageVersion).unwrap_or(1)
+    }
+
+    // Upgrades the factory's WASM code, authorized by the factory creator. When
+    // the new code has a new storage layout, `migrate` must run before any other call
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        Self::get_contract_creator(env.clone()).require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
-    /// Verifies if the provided address is the scorer factory creator
+    /// Moves a configuration entry written by version 1 from persistent to instance storage
     /// 
     /// # Arguments
     /// * `env` - The Soroban environment
-    /// * `address` - The address to check
-    /// 
-    /// # Returns
-    /// * `bool` - True if the address is the scorer factory creator, false otherwise
-    /// 
-    /// # Panics
-    /// * When the factory creator address is not found in storage
+    /// * `key` - The key of the entry, left alone when it does not exist
+    fn move_to_instance(env: &Env, key: DataKey) {
+        // The value is moved as is, whatever its type
+        if let Some(value) = env.storage().persistent().get::<DataKey, Val>(&key) {
+            env.storage().instance().set(&key, &value);
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    // Verifies if the provided address is the scorer factory creator
     pub fn is_scorer_factory_creator(env: Env, address: Address) -> bool {
+        Self::extend_instance_on_access(&env);
         let creator = env.storage()
-                        .persistent()
+                        .instance()
                         .get::<DataKey, Address>(&DataKey::ScorerFactoryCreator)
-                        .unwrap_or_else(|| panic!("{:?}", Error::ScorerFactoryCreatorNotFound));
+                        .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerFactoryCreatorNotFound));
         creator == address
     }
 
-    /// Checks if the provided address is a manager
-    /// 
-    /// # Arguments
-    /// * `env` - The Soroban environment
-    /// * `address` - The address to check
-    /// 
-    /// # Returns
-    /// * `bool` - True if the address is a manager, false otherwise
+    // Checks if the provided address is a manager
     pub fn is_manager(env: Env, address: Address) -> bool {
+        Self::extend_instance_on_access(&env);
         env.storage()
-           .persistent()
+           .instance()
            .get::<DataKey, Vec<Address>>(&DataKey::Managers)
            .unwrap_or_else(|| Vec::new(&env))
            .contains(address)
@@ -121,25 +323,52 @@ impl ScorerFactoryContract {
         Self::is_manager(env.clone(), caller.clone())
     }
 
-    /// Deploy a new scorer contract
+    /// Finds the first position of an item in a vector
+    /// 
+    /// Compares the items one by one instead of calling `Vec::first_index_of`,
+    /// to keep the factory WASM small.
     /// 
     /// # Arguments
-    /// * `env` - The Soroban environment
-    /// * `deployer` - The address that will deploy the scorer contract
-    /// * `salt` - A unique value to ensure unique contract addresses
-    /// * `init_fn` - The initialization function name to call on the deployed contract
-    /// * `init_args` - Arguments to pass to the initialization function, must include:
-    ///    - Argument at index (len-3): scorer_name (String)
-    ///    - Argument at index (len-2): scorer_description (String)
-    ///    - Argument at index (len-1): scorer_icon (String)
+    /// * `items` - The vector to search
+    /// * `item` - The item to find
     /// 
     /// # Returns
-    /// * `Address` - The address of the newly deployed scorer contract
+    /// * `Option<u32>` - The index of the item, or None if it is missing
+    fn index_of<T: PartialEq + IntoVal<Env, Val> + TryFromVal<Env, Val>>(items: &Vec<T>, item: impl core::borrow::Borrow<T>) -> Option<u32> {
+        (0..items.len()).find(|&index| items.get_unchecked(index) == *item.borrow())
+    }
+
+    /// Requires the caller's authorization, and that it is the factory creator or a manager
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The address to check
+    /// 
+    /// # Panics
+    /// * When the caller is neither the factory creator nor a manager (`Error::Unauthorized`)
+    fn require_authorized(env: &Env, caller: &Address) {
+        caller.require_auth();
+        if !Self::is_authorized(env, caller) {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    /// Requires the caller's authorization, and that it is a manager
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The address to check
     /// 
     /// # Panics
-    /// * When the deployer is not the current contract and fails authentication
-    /// * When the deployer is not a registered manager (`Error::Unauthorized`)
-    /// * When init_args has fewer than 3 arguments (`Error::InvalidInitArgs`)
+    /// * When the caller is not a manager (`Error::Unauthorized`)
+    fn require_manager(env: &Env, caller: &Address) {
+        caller.require_auth();
+        if !Self::is_manager(env.clone(), caller.clone()) {
+            panic_with_error!(env, Error::Unauthorized);
+        }
+    }
+
+    // Deploy a new scorer contract
     pub fn create_scorer(
         env: Env,
         deployer: Address,
@@ -153,245 +382,1370 @@ impl ScorerFactoryContract {
         }
 
         if init_args.len() < 3 {
-            panic!("{:?}", Error::InvalidInitArgs);
+            panic_with_error!(&env, Error::InvalidInitArgs);
+        }
+
+        Self::deploy_scorer(&env, deployer.clone(), deployer, salt, init_fn, init_args, Vec::new(&env))
+    }
+
+    // Deploy a new scorer contract whose initial badges are merged with curated badge templates
+    pub fn create_scorer_from_templates(
+        env: Env,
+        deployer: Address,
+        salt: BytesN<32>,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+        template_ids: Vec<String>,
+    ) -> Address {
+        // Both the scorer's `initialize` and `initialize_with_members`, whose initial members precede the badges, fit this layout.
+
+        // Skip authorization if deployer is the current contract
+        if deployer != env.current_contract_address() {
+            deployer.require_auth();
+        }
+
+        if init_args.len() < 4 {
+            panic_with_error!(&env, Error::InvalidInitArgs);
+        }
+
+        Self::deploy_scorer(&env, deployer.clone(), deployer, salt, init_fn, init_args, template_ids)
+    }
+
+    // Deploy a new scorer contract on behalf of a creator, letting a relayer submit and pay for the transaction
+    pub fn create_scorer_for(
+        env: Env,
+        relayer: Address,
+        creator: Address,
+        salt: BytesN<32>,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+    ) -> Address {
+        // The creator's consent is the signed authorization entry covering `(salt, init_fn, init_args)`,
+        // which the relayer attaches to its transaction, so the creator never needs to hold fees.
+
+        relayer.require_auth();
+        creator.require_auth_for_args((salt.clone(), init_fn.clone(), init_args.clone()).into_val(&env));
+
+        if init_args.len() < 3 {
+            panic_with_error!(&env, Error::InvalidInitArgs);
+        }
+
+        Self::deploy_scorer(&env, relayer, creator, salt, init_fn, init_args, Vec::new(&env))
+    }
+
+    /// Adds the badges of a template missing from a scorer's initial badges
+    /// 
+    /// # Arguments
+    /// * `scorer_badges` - The initial badges, whose scores are kept
+    /// * `template` - The badges to merge and their scores
+    fn merge_badges(scorer_badges: &mut Map<BadgeId, u32>, template: &Map<BadgeId, u32>) {
+        for (badge_id, score) in template.try_iter().map(UnwrapOptimized::unwrap_optimized) {
+            if !scorer_badges.contains_key(badge_id.clone()) {
+                scorer_badges.set(badge_id, score);
+            }
+        }
+    }
+
+    /// Deploys, initializes and records a scorer contract
+    /// 
+    /// The badges of the chosen templates, then of the default template if one is set,
+    /// are merged into the scorer badges fourth from the end of `init_args`.
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `deployer` - The address that will deploy the scorer contract
+    /// * `creator` - The address recorded as the scorer's creator
+    /// * `salt` - A unique value to ensure unique contract addresses
+    /// * `init_fn` - The initialization function name to call on the deployed contract
+    /// * `init_args` - Arguments to pass to the initialization function, ending with name, description and icon
+    /// * `template_ids` - The badge templates chosen by the creator
+    /// 
+    /// # Returns
+    /// * `Address` - The address of the newly deployed scorer contract
+    /// 
+    /// # Panics
+    /// * When a chosen template does not exist (`Error::BadgeTemplateNotFound`)
+    /// * When templates are merged and init_args has fewer than 4 arguments (`Error::InvalidInitArgs`)
+    fn deploy_scorer(
+        env: &Env,
+        deployer: Address,
+        creator: Address,
+        salt: BytesN<32>,
+        init_fn: Symbol,
+        init_args: Vec<Val>,
+        template_ids: Vec<String>,
+    ) -> Address {
+        Self::check_not_paused(env, FactoryOperation::Creation);
+
+        // A default template removed since it was set is skipped
+        let templates = Self::get_badge_templates(env.clone());
+        let default = Self::get_default_template(env.clone()).and_then(|id| templates.get(id));
+
+        let mut init_args = init_args;
+        if !template_ids.is_empty() || default.is_some() {
+            if init_args.len() < 4 {
+                panic_with_error!(env, Error::InvalidInitArgs);
+            }
+            let badges_index = init_args.len() - 4;
+            let mut scorer_badges = Map::<BadgeId, u32>::from_val(env, &init_args.get(badges_index).unwrap());
+            for template_id in template_ids.try_iter().map(UnwrapOptimized::unwrap_optimized) {
+                let template = templates
+                    .get(template_id)
+                    .unwrap_or_else(|| panic_with_error!(env, Error::BadgeTemplateNotFound));
+                Self::merge_badges(&mut scorer_badges, &template);
+            }
+            if let Some(defaults) = default {
+                Self::merge_badges(&mut scorer_badges, &defaults);
+            }
+            init_args.set(badges_index, scorer_badges.into_val(env));
         }
 
         // Get the stored WASM hash
         let wasm_hash = env.storage()
-            .persistent()
+            .instance()
             .get::<DataKey, BytesN<32>>(&DataKey::ScorerWasmHash)
-            .unwrap_or_else(|| panic!("{:?}", Error::ContractCreatorNotFound));
+            .unwrap_or_else(|| panic_with_error!(env, Error::ContractCreatorNotFound));
+        Self::extend_instance_on_access(env);
+
+        // Extract name, description and icon from init_args 
+        let args_len = init_args.len();
+        let scorer_icon = String::from_val(env, &init_args.get(args_len - 1).unwrap());
+        let scorer_description = String::from_val(env, &init_args.get(args_len - 2).unwrap());
+        let scorer_name = String::from_val(env, &init_args.get(args_len - 3).unwrap());
+
+        // Record the scorer under its future address first, so a taken name fails before deploying
+        let deployer = env.deployer().with_address(deployer, salt);
+        let scorer_address = deployer.deployed_address();
+        Self::record_scorer(env, &scorer_address, &creator, (scorer_name.clone(), scorer_description.clone(), scorer_icon.clone()), env.ledger().sequence());
 
         // Deploy the contract using the stored Wasm hash
-        let scorer_address = env
-            .deployer()
-            .with_address(deployer.clone(), salt)
-            .deploy(wasm_hash);
+        deployer.deploy_v2(wasm_hash, ());
 
         // Initialize the contract
-        let _: () = env.invoke_contract(&scorer_address, &init_fn, init_args.clone());
-        
-        // Record the created scorer
-        let mut created_scorers = env.storage()
-            .persistent()
-            .get::<DataKey, Map<Address, (String, String, String)>>(&DataKey::CreatedScorers)
-            .unwrap_or_else(|| Map::new(&env));
+        let _: Val = env.invoke_contract(&scorer_address, &init_fn, init_args);
 
-        // Extract name, description and icon from init_args 
-        let args_len = init_args.len();
-        let scorer_icon = String::from_val(&env, &init_args.get(args_len - 1).unwrap());
-        let scorer_description = String::from_val(&env, &init_args.get(args_len - 2).unwrap());
-        let scorer_name = String::from_val(&env, &init_args.get(args_len - 3).unwrap());
-            
-        created_scorers.set(scorer_address.clone(), (scorer_name.clone(), scorer_description.clone(), scorer_icon.clone()));
-        env.storage().persistent().set(&DataKey::CreatedScorers, &created_scorers);
-        env.events().publish((TOPIC_SCORER, symbol_short!("create")), (deployer, scorer_address.clone(), scorer_name, scorer_description, scorer_icon));
+        env.events().publish(
+            (TOPIC_SCORER, symbol_short!("create")),
+            events::ScorerCreated {
+                creator,
+                scorer: scorer_address.clone(),
+                name: scorer_name,
+                description: scorer_description,
+                icon: scorer_icon,
+            },
+        );
 
         scorer_address
     }
 
-    /// Returns a map of all scorer contracts created by this factory
+    /// Adds a scorer to the registry with its metadata, name, creator and creation order
     /// 
     /// # Arguments
     /// * `env` - The Soroban environment
-    /// 
-    /// # Returns
-    /// * `Map<Address, (String, String, String)>` - A map where keys are scorer contract addresses and values are tuples containing
-    ///   (scorer_name, scorer_description, scorer_icon)
+    /// * `scorer_address` - The address of the scorer contract
+    /// * `creator` - The address recorded as the scorer's creator
+    /// * `metadata` - The scorer's name, description and icon
+    /// * `created_at` - The ledger at which the scorer was created
     /// 
     /// # Panics
-    /// * When the scorers map cannot be found in storage (`Error::ScorersWereNotFound`)
-    pub fn get_scorers(env: Env) -> Map<Address, (String, String, String)> {
-        env.storage()
-           .persistent()
-           .get::<DataKey, Map<Address, (String, String, String)>>(&DataKey::CreatedScorers)
-           .unwrap_or_else(|| panic!("{:?}", Error::ScorersWereNotFound))
+    /// * When another scorer already uses the normalized name (`Error::ScorerNameTaken`)
+    fn record_scorer(env: &Env, scorer_address: &Address, creator: &Address, metadata: (String, String, String), created_at: u32) {
+        let storage = env.storage().persistent();
+
+        // Index the scorer under its normalized name, which must be free
+        let name = Self::normalize_name(env, &metadata.0).unwrap_or_else(|| panic_with_error!(env, Error::NameTooLong));
+        let name_key = DataKey::ScorerByName(name);
+        if storage.has(&name_key) {
+            panic_with_error!(env, Error::ScorerNameTaken);
+        }
+        storage.set(&name_key, scorer_address);
+
+        storage.set(&DataKey::Scorer(scorer_address.clone()), &metadata);
+        Self::set_creator(env, scorer_address, creator);
+
+        // Record the creation order and ledger in the next slot
+        let slot = Self::get_scorer_count(env);
+        storage.set(&DataKey::ScorerAt(slot), scorer_address);
+        storage.set(&DataKey::ScorerSlot(scorer_address.clone()), &(slot, created_at));
+        env.storage().instance().set(&DataKey::ScorerCount, &(slot + 1));
     }
 
-    /// Adds a new manager to the contract
+    /// Records the creator of a scorer and adds the scorer to the creator's index
     /// 
     /// # Arguments
     /// * `env` - The Soroban environment
-    /// * `caller` - The address that will authenticate the addition of the new manager
-    /// * `manager` - The address to be added as a manager
-    /// 
-    /// # Panics
-    /// * When the caller is not the scorer factory creator or a manager (`Error::Unauthorized`)
-    /// * When the manager already exists (`Error::ManagerAlreadyExists`)
-    pub fn add_manager(env: Env, caller: Address, manager: Address) {
-        // Require authentication from the caller
-        caller.require_auth();
+    /// * `scorer_address` - The address of the scorer contract
+    /// * `creator` - The address recorded as the scorer's creator
+    fn set_creator(env: &Env, scorer_address: &Address, creator: &Address) {
+        let mut scorers = Self::get_scorers_by_creator(env.clone(), creator.clone());
+        scorers.push_back(scorer_address.clone());
+        env.storage().persistent().set(&DataKey::CreatorScorers(creator.clone()), &scorers);
+        env.storage().persistent().set(&DataKey::ScorerCreator(scorer_address.clone()), creator);
+    }
 
-        // Verify caller is authorized
-        if !Self::is_authorized(&env, &caller) {
-            panic!("{:?}", Error::Unauthorized);
+    /// Removes a scorer from its creator's index
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer_address` - The address of the scorer contract
+    /// * `creator` - The creator recorded for the scorer
+    fn remove_from_creator(env: &Env, scorer_address: &Address, creator: &Address) {
+        let key = DataKey::CreatorScorers(creator.clone());
+        let mut scorers = Self::get_scorers_by_creator(env.clone(), creator.clone());
+        if let Some(index) = Self::index_of(&scorers, scorer_address) {
+            scorers.remove(index);
         }
-
-        let mut managers = env.storage()
-            .persistent()
-            .get::<DataKey, Vec<Address>>(&DataKey::Managers)
-            .unwrap_or_else(|| Vec::new(&env));
-        
-        // Check if manager already exists to avoid duplication
-        if managers.contains(manager.clone()) {
-            panic!("{:?}", Error::ManagerAlreadyExists);
+        if scorers.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &scorers);
         }
-        
-        managers.push_back(manager.clone());
-        env.storage().persistent().set(&DataKey::Managers, &managers);
+    }
+
+    // Adds a scorer deployed outside the factory to the registry, so pre-existing
+    // or manually deployed communities can join the directory
+    pub fn register_external_scorer(env: Env, admin: Address, scorer_address: Address) {
+        // The scorer must answer the scorer interface's `contract_version`, `get_metadata`
+        // and `get_contract_owner` calls. Its owner is recorded as its creator, and its
+        // registration ledger as its creation ledger.
+
+        Self::require_manager(&env, &admin);
+
+        Self::check_not_paused(&env, FactoryOperation::Creation);
+
+        Self::check_unregistered(&env, &scorer_address);
+
+        let scorer = ScorerClient::new(&env, &scorer_address);
+        let (Ok(Ok(_)), Ok(Ok((name, description, icon))), Ok(Ok(creator))) =
+            (scorer.try_contract_version(), scorer.try_get_metadata(), scorer.try_get_contract_owner())
+        else {
+            panic_with_error!(&env, Error::NotAScorer);
+        };
 
-        env.events().publish((TOPIC_MANAGER, symbol_short!("add")), (caller, manager));
+        Self::record_scorer(&env, &scorer_address, &creator, (name.clone(), description.clone(), icon.clone()), env.ledger().sequence());
+
+        env.events().publish(
+            (TOPIC_SCORER, symbol_short!("register")),
+            events::ScorerRegistered { caller: admin, creator, scorer: scorer_address, name, description, icon },
+        );
     }
-    
-    /// Removes a manager from the contract
+
+    /// Checks that a scorer is not in the registry yet
     /// 
     /// # Arguments
     /// * `env` - The Soroban environment
-    /// * `caller` - The address that will authenticate the removal of the manager
-    /// * `manager` - The address to be removed as a manager
+    /// * `scorer_address` - The address of the scorer contract
     /// 
     /// # Panics
-    /// * When the caller is not the scorer factory creator or a manager (`Error::Unauthorized`)
-    /// * When the manager to be removed is not found (`Error::ManagerNotFound`)
-    /// * When attempting to remove the last manager (`Error::CannotRemoveLastManager`)
-    pub fn remove_manager(env: Env, caller: Address, manager: Address) {
-        // Require authentication from the caller
-        caller.require_auth();
-
-        // Verify caller is authorized
-        if !Self::is_authorized(&env, &caller) {
-            panic!("{:?}", Error::Unauthorized);
+    /// * When the scorer is already registered (`Error::ScorerAlreadyRegistered`)
+    fn check_unregistered(env: &Env, scorer_address: &Address) {
+        if env.storage().persistent().has(&DataKey::Scorer(scorer_address.clone())) {
+            panic_with_error!(env, Error::ScorerAlreadyRegistered);
         }
+    }
 
-        let mut managers = env.storage()
-            .persistent()
-            .get::<DataKey, Vec<Address>>(&DataKey::Managers)
-            .unwrap_or_else(|| panic!("{:?}", Error::ManagersNotFound));
+    // Returns a map of all scorer contracts created by this factory
+    pub fn get_scorers(env: Env) -> Map<Address, (String, String, String)> {
+        let scorer_count = Self::get_registry_len(&env);
+        Self::extend_instance_on_access(&env);
 
-        let position = managers.iter().position(|addr| addr == manager);
-        
-        if let Some(idx) = position {
-            managers.remove(idx as u32);
-            env.storage().persistent().set(&DataKey::Managers, &managers);
-            env.events().publish((TOPIC_MANAGER, symbol_short!("remove")), (caller, manager));
-        } else {
-            panic!("{:?}", Error::ManagerNotFound);
+        let mut scorers = Map::new(&env);
+        for scorer_address in Self::get_scorer_slots(&env, 0, scorer_count).try_iter().map(UnwrapOptimized::unwrap_optimized) {
+            scorers.set(scorer_address.clone(), Self::get_scorer_metadata(&env, &scorer_address));
         }
+        scorers
     }
 
-    /// Retrieves all the managers from the contract.
-    ///
+    /// Retrieves the metadata recorded for a registered scorer
+    /// 
     /// # Arguments
     /// * `env` - The Soroban environment
-    ///
+    /// * `scorer_address` - The address of the scorer contract
+    /// 
     /// # Returns
-    /// * `Vec<Address>` - A vector of all manager addresses
-    ///
+    /// * `(String, String, String)` - The scorer's name, description and icon
+    /// 
     /// # Panics
-    /// * When the managers vector cannot be found in storage (`Error::ManagersNotFound`)
-    pub fn get_managers(env: Env) -> Vec<Address> {
+    /// * When the scorer is not registered (`Error::ScorerNotFound`)
+    fn get_scorer_metadata(env: &Env, scorer_address: &Address) -> (String, String, String) {
         env.storage()
            .persistent()
-           .get::<DataKey, Vec<Address>>(&DataKey::Managers)
-           .unwrap_or_else(|| panic!("{:?}", Error::ManagersNotFound))
+           .get::<DataKey, (String, String, String)>(&DataKey::Scorer(scorer_address.clone()))
+           .unwrap_or_else(|| panic_with_error!(env, Error::ScorerNotFound))
     }
 
-    /// Retrieves the address of the contract creator.
-    ///
-    /// # Arguments
-    /// * `env` - The Soroban environment
-    ///
-    /// # Returns
-    /// * `Address` - The address of the scorer factory creator
-    ///
-    /// # Panics
-    /// * When the creator's address is not found in storage (`Error::ContractCreatorNotFound`)
-    pub fn get_contract_creator(env: Env) -> Address {
-        env.storage()
-           .persistent()
-           .get::<DataKey, Address>(&DataKey::ScorerFactoryCreator)
-           .unwrap_or_else(|| panic!("{:?}", Error::ContractCreatorNotFound))
+    // Returns the contract version reported by each registered scorer, one page at a time
+    pub fn get_scorer_versions(env: Env, page: u32) -> Map<Address, u32> {
+        // Pages cover `PAGE_SIZE` creation slots, so they hold fewer scorers once
+        // scorers of the page were removed.
+
+        Self::get_registry_len(&env);
+
+        let mut versions = Map::new(&env);
+        for scorer_address in Self::get_scorer_slots(&env, page.saturating_mul(PAGE_SIZE), PAGE_SIZE).try_iter().map(UnwrapOptimized::unwrap_optimized) {
+            let version = match ScorerClient::new(&env, &scorer_address).try_contract_version() {
+                Ok(Ok(version)) => version,
+                _ => 0,
+            };
+            versions.set(scorer_address, version);
+        }
+
+        versions
     }
 
-    /// Removes a scorer contract from the factory's registry
-    /// 
-    /// # Arguments
-    /// * `env` - The Soroban environment
-    /// * `caller` - The address that will authenticate the removal of the scorer
-    /// * `scorer_address` - The address of the scorer contract to be removed
-    /// 
-    /// # Returns
-    /// * `()` - Returns unit type on success
-    ///
-    /// # Panics
-    /// * When the caller is not a registered manager (`Error::Unauthorized`)
-    /// * When the scorer address is not found in the registry (`Error::ScorerNotFound`)
-    pub fn remove_scorer(env: Env, caller: Address, scorer_address: Address) {
-        // Require authentication from the caller
-        caller.require_auth();
+    // Lists the registered scorers one page at a time, oldest first
+    pub fn list_scorers(env: Env, page: u32) -> Vec<ScorerInfo> {
+        // Scorers created before creation ledgers were recorded come first, with a
+        // `created_at` of 0. Pages cover `PAGE_SIZE` creation slots, so they hold
+        // fewer scorers once scorers of the page were removed.
 
-        // Verify caller is a manager
-        if !Self::is_manager(env.clone(), caller.clone()) {
-            panic!("{:?}", Error::Unauthorized);
+        Self::get_registry_len(&env);
+
+        let mut scorers = Vec::new(&env);
+        for address in Self::get_scorer_slots(&env, page.saturating_mul(PAGE_SIZE), PAGE_SIZE).try_iter().map(UnwrapOptimized::unwrap_optimized) {
+            scorers.push_back(Self::export_registration(env.clone(), address));
         }
 
-        let mut created_scorers = env.storage()
+        scorers
+    }
+
+    // Returns the registry entry of a scorer, so another factory deployment can
+    // take it over with `import_registration`
+    pub fn export_registration(env: Env, scorer_address: Address) -> ScorerInfo {
+        Self::require_migrated(env.clone());
+
+        let (name, description, icon) = Self::get_scorer_metadata(&env, &scorer_address);
+        let (_, created_at) = env.storage()
             .persistent()
-            .get::<DataKey, Map<Address, (String, String, String)>>(&DataKey::CreatedScorers)
-            .unwrap_or_else(|| panic!("{:?}", Error::ScorersWereNotFound));
+            .get::<DataKey, (u32, u32)>(&DataKey::ScorerSlot(scorer_address.clone()))
+            .unwrap_or((0, 0));
 
-        // Check if the scorer exists
-        if !created_scorers.contains_key(scorer_address.clone()) {
-            panic!("{:?}", Error::ScorerNotFound);
+        ScorerInfo {
+            creator: env.storage().persistent().get(&DataKey::ScorerCreator(scorer_address.clone())),
+            address: scorer_address,
+            name,
+            description,
+            icon,
+            created_at,
         }
+    }
 
-        let (scorer_name, scorer_description, icon) = created_scorers.get(scorer_address.clone()).unwrap();
-        
-        // Remove the scorer from the map
-        created_scorers.remove(scorer_address.clone());
+    // Copies the registry entry of a scorer from another factory deployment,
+    // keeping its creator, metadata and creation ledger
+    pub fn import_registration(env: Env, admin: Address, source_factory: Address, scorer_address: Address) {
+        // The entry is whatever the source factory's `export_registration` reports,
+        // and the source factory is not checked, so managers should only import from
+        // factory deployments they trust. The source factory keeps its entry until
+        // its managers remove it.
+
+        Self::require_manager(&env, &admin);
+
+        Self::check_not_paused(&env, FactoryOperation::Creation);
+
+        Self::check_unregistered(&env, &scorer_address);
+
+        let Ok(Ok(registration)) = ScorerFactoryContractClient::new(&env, &source_factory).try_export_registration(&scorer_address) else {
+            panic_with_error!(&env, Error::ScorerNotFound);
+        };
+        let creator = registration.creator
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerCreatorNotFound));
+
+        Self::record_scorer(
+            &env,
+            &scorer_address,
+            &creator,
+            (registration.name, registration.description, registration.icon),
+            registration.created_at,
+        );
+
+        env.events().publish(
+            (TOPIC_SCORER, symbol_short!("import")),
+            events::ScorerImported { caller: admin, source_factory, scorer: scorer_address, creator, created_at: registration.created_at },
+        );
+    }
+
+    /// Retrieves the number of creation slots of the registry
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// 
+    /// # Returns
+    /// * `u32` - The number of scorers ever recorded, including the removed ones
+    /// 
+    /// # Panics
+    /// * When the registry cannot be found in storage (`Error::ScorersWereNotFound`)
+    fn get_registry_len(env: &Env) -> u32 {
+        Self::require_migrated(env.clone());
+        env.storage()
+           .instance()
+           .get::<DataKey, u32>(&DataKey::ScorerCount)
+           .unwrap_or_else(|| panic_with_error!(env, Error::ScorersWereNotFound))
+    }
+
+    /// Retrieves the number of creation slots of the registry, 0 when it is missing
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    fn get_scorer_count(env: &Env) -> u32 {
+        env.storage().instance().get(&DataKey::ScorerCount).unwrap_or(0)
+    }
+
+    /// Retrieves the scorers of a range of creation slots, skipping the removed ones
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `start` - The first slot of the range
+    /// * `limit` - The number of slots in the range
+    /// 
+    /// # Returns
+    /// * `Vec<Address>` - The addresses of the scorers, oldest first
+    fn get_scorer_slots(env: &Env, start: u32, limit: u32) -> Vec<Address> {
+        let storage = env.storage().persistent();
+        let end = start.saturating_add(limit).min(Self::get_scorer_count(env));
+
+        let mut scorers = Vec::new(env);
+        for slot in start..end {
+            if let Some(scorer_address) = storage.get::<DataKey, Address>(&DataKey::ScorerAt(slot)) {
+                scorers.push_back(scorer_address);
+            }
+        }
+        scorers
+    }
+
+    // Retrieves the creator recorded for a scorer in the registry
+    pub fn get_scorer_creator(env: Env, scorer_address: Address) -> Address {
+        Self::require_migrated(env.clone());
+
+        env.storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::ScorerCreator(scorer_address))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerCreatorNotFound))
+    }
+
+    // Retrieves all scorers recorded under a creator in the registry
+    pub fn get_scorers_by_creator(env: Env, creator: Address) -> Vec<Address> {
+        Self::require_migrated(env.clone());
+
+        env.storage()
+            .persistent()
+            .get::<DataKey, Vec<Address>>(&DataKey::CreatorScorers(creator))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // Transfers a scorer's registration to another creator
+    pub fn transfer_scorer_registration(env: Env, current_creator: Address, scorer_address: Address, new_creator: Address) {
+        current_creator.require_auth();
+
+        Self::check_not_paused(&env, FactoryOperation::Metadata);
+
+        let recorded_creator = Self::get_scorer_creator(env.clone(), scorer_address.clone());
+        if recorded_creator != current_creator {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        Self::remove_from_creator(&env, &scorer_address, &current_creator);
+        Self::set_creator(&env, &scorer_address, &new_creator);
+
+        env.events().publish(
+            (TOPIC_SCORER, symbol_short!("transfer")),
+            events::ScorerTransferred { creator: current_creator, scorer: scorer_address, new_creator },
+        );
+    }
+
+    // Resolves a scorer contract from its name
+    pub fn get_scorer_by_name(env: Env, name: String) -> Address {
+        Self::require_migrated(env.clone());
+
+        // Names are compared after normalization (surrounding whitespace trimmed, ASCII lowercased)
+        // and are unique within the registry.
+
+        let name = Self::normalize_name(&env, &name).unwrap_or_else(|| panic_with_error!(&env, Error::NameTooLong));
+        env.storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::ScorerByName(name))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    // Checks if a scorer name is free, so a creation UI can validate it before
+    // the user signs the creation transaction
+    pub fn is_name_available(env: Env, name: String) -> bool {
+        Self::require_migrated(env.clone());
+
+        // Names are compared after normalization, like in `get_scorer_by_name`.
+
+        Self::normalize_name(&env, &name)
+            .is_some_and(|name| !env.storage().persistent().has(&DataKey::ScorerByName(name)))
+    }
+
+    /// Normalizes a scorer name for the name index
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `name` - The name to normalize
+    /// 
+    /// # Returns
+    /// * `Option<String>` - The name with surrounding whitespace trimmed and ASCII letters
+    ///   lowercased, or None when it is longer than `MAX_NAME_LENGTH` bytes and can't be indexed
+    fn normalize_name(env: &Env, name: &String) -> Option<String> {
+        let len = name.len() as usize;
+        if len > MAX_NAME_LENGTH {
+            return None;
+        }
+
+        // Copied through the host function, as `String::copy_into_slice` checks the
+        // length again with a panic that pulls string formatting into the WASM
+        let mut buffer = [0u8; MAX_NAME_LENGTH];
+        env.string_copy_to_slice(name.to_object(), Val::U32_ZERO, &mut buffer[..len]).unwrap_optimized();
+        buffer[..len].make_ascii_lowercase();
+
+        Some(String::from_bytes(env, buffer[..len].trim_ascii()))
+    }
+
+    // Pauses or resumes a group of operations, so the platform can freeze risky
+    // operations while keeping the directory maintainable
+    pub fn set_paused(env: Env, caller: Address, operation: FactoryOperation, paused: bool) {
+        Self::require_authorized(&env, &caller);
+
+        let mut paused_operations = Self::get_paused_operations(env.clone());
+        match (Self::index_of(&paused_operations, operation), paused) {
+            (None, true) => paused_operations.push_back(operation),
+            (Some(index), false) => {
+                paused_operations.remove(index);
+            }
+            _ => {}
+        }
+        if paused_operations.is_empty() {
+            env.storage().persistent().remove(&DataKey::PausedOperations);
+        } else {
+            env.storage().persistent().set(&DataKey::PausedOperations, &paused_operations);
+        }
+
+        env.events().publish((TOPIC_PAUSE, symbol_short!("set")), events::FactoryPauseSet { caller, operation, paused });
+    }
+
+    // Checks if a group of operations is paused
+    pub fn is_paused(env: Env, operation: FactoryOperation) -> bool {
+        Self::get_paused_operations(env).contains(operation)
+    }
+
+    // Retrieves the paused groups of operations
+    pub fn get_paused_operations(env: Env) -> Vec<FactoryOperation> {
+        Self::require_migrated(env.clone());
+
+        env.storage()
+           .persistent()
+           .get::<DataKey, Vec<FactoryOperation>>(&DataKey::PausedOperations)
+           .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Checks that a group of operations is not paused
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `operation` - The group of operations
+    /// 
+    /// # Panics
+    /// * When the operations are paused (`Error::OperationPaused`)
+    fn check_not_paused(env: &Env, operation: FactoryOperation) {
+        if Self::is_paused(env.clone(), operation) {
+            panic_with_error!(env, Error::OperationPaused);
+        }
+    }
+
+    // Adds a new manager to the contract
+    pub fn add_manager(env: Env, caller: Address, manager: Address) {
+        Self::require_authorized(&env, &caller);
+
+        let mut managers = env.storage()
+            .instance()
+            .get::<DataKey, Vec<Address>>(&DataKey::Managers)
+            .unwrap_or_else(|| Vec::new(&env));
         
-        // Update storage
-        env.storage().persistent().set(&DataKey::CreatedScorers, &created_scorers);
+        // Check if manager already exists to avoid duplication
+        if managers.contains(manager.clone()) {
+            panic_with_error!(&env, Error::ManagerAlreadyExists);
+        }
         
+        managers.push_back(manager.clone());
+        env.storage().instance().set(&DataKey::Managers, &managers);
+
+        env.events().publish((TOPIC_MANAGER, symbol_short!("add")), events::ManagerAdded { sender: caller, manager });
+    }
+    
+    // Removes a manager from the contract
+    pub fn remove_manager(env: Env, caller: Address, manager: Address) {
+        Self::require_authorized(&env, &caller);
+
+        let mut managers = env.storage()
+            .instance()
+            .get::<DataKey, Vec<Address>>(&DataKey::Managers)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ManagersNotFound));
+
+        if let Some(idx) = Self::index_of(&managers, &manager) {
+            managers.remove(idx);
+            env.storage().instance().set(&DataKey::Managers, &managers);
+            env.events().publish((TOPIC_MANAGER, symbol_short!("remove")), events::ManagerRemoved { sender: caller, manager });
+        } else {
+            panic_with_error!(&env, Error::ManagerNotFound);
+        }
+    }
+
+    // Retrieves all the managers from the contract.
+    pub fn get_managers(env: Env) -> Vec<Address> {
+        Self::extend_instance_on_access(&env);
+        env.storage()
+           .instance()
+           .get::<DataKey, Vec<Address>>(&DataKey::Managers)
+           .unwrap_or_else(|| panic_with_error!(&env, Error::ManagersNotFound))
+    }
+
+    // Retrieves the address of the contract creator.
+    pub fn get_contract_creator(env: Env) -> Address {
+        Self::require_migrated(env.clone());
+
+        env.storage()
+           .instance()
+           .get::<DataKey, Address>(&DataKey::ScorerFactoryCreator)
+           .unwrap_or_else(|| panic_with_error!(&env, Error::ContractCreatorNotFound))
+    }
+
+    // Replaces the scorer WASM hash used by `create_scorer` for new deployments.
+    // Once a governance contract is set, only it can call this
+    pub fn set_scorer_wasm_hash(env: Env, caller: Address, scorer_wasm_hash: BytesN<32>) {
+        Self::require_wasm_hash_governance(&env, &caller);
+
+        Self::store_scorer_wasm_hash(&env, caller, scorer_wasm_hash);
+    }
+
+    // Hands scorer WASM hash changes to a governance contract of this factory,
+    // where managers vote on them, or back to the factory creator with None.
+    // Once a governance contract is set, only its proposals can replace it
+    pub fn set_wasm_hash_governance(env: Env, caller: Address, governance: Option<Address>) {
+        Self::require_wasm_hash_governance(&env, &caller);
+
+        match &governance {
+            Some(governance) => {
+                // A contract governing another factory, or none, would let its
+                // owner change the hash without the managers. Called untyped,
+                // as the typed client grows the WASM by over 2 KiB
+                let factory: Val = env.invoke_contract(governance, &Symbol::new(&env, "get_factory"), Vec::new(&env));
+                if Address::try_from_val(&env, &factory).ok() != Some(env.current_contract_address()) {
+                    panic_with_error!(&env, Error::InvalidWasmHashGovernance);
+                }
+                env.storage().instance().set(&DataKey::WasmHashGovernance, governance)
+            }
+            None => env.storage().instance().remove(&DataKey::WasmHashGovernance),
+        }
+
+        env.events().publish((TOPIC_WASM_HASH, symbol_short!("gov")), events::WasmHashGovernanceSet { caller, governance });
+    }
+
+    // Retrieves the governance contract scorer WASM hash changes go through, if any
+    pub fn get_wasm_hash_governance(env: Env) -> Option<Address> {
+        Self::require_migrated(env.clone());
+
+        env.storage().instance().get(&DataKey::WasmHashGovernance)
+    }
+
+    /// Requires the authorization of the caller and that it is the WASM hash
+    /// governance contract, or the factory creator when there is none
+    ///
+    /// # Panics
+    /// * When a governance contract is set and the caller is not it (`Error::ProposalRequired`)
+    /// * When no governance contract is set and the caller is not the creator (`Error::Unauthorized`)
+    fn require_wasm_hash_governance(env: &Env, caller: &Address) {
+        caller.require_auth();
+
+        match Self::get_wasm_hash_governance(env.clone()) {
+            Some(governance) if governance != *caller => panic_with_error!(env, Error::ProposalRequired),
+            Some(_) => {}
+            None if !Self::is_scorer_factory_creator(env.clone(), caller.clone()) => panic_with_error!(env, Error::Unauthorized),
+            None => {}
+        }
+    }
+
+    /// Replaces the scorer WASM hash, once the change is known to be allowed
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The address recorded as making the change
+    /// * `scorer_wasm_hash` - The hash of the new scorer Wasm binary
+    fn store_scorer_wasm_hash(env: &Env, caller: Address, scorer_wasm_hash: BytesN<32>) {
+        let previous_wasm_hash = env.storage()
+            .instance()
+            .get::<DataKey, BytesN<32>>(&DataKey::ScorerWasmHash)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ContractCreatorNotFound));
+
+        let mut template_hashes = Self::template_hashes(env);
+        template_hashes.push_back(scorer_wasm_hash.clone());
+        env.storage().persistent().set(&DataKey::TemplateHashes, &template_hashes);
+        env.storage().instance().set(&DataKey::ScorerWasmHash, &scorer_wasm_hash);
+
+        env.events().publish(
+            (TOPIC_WASM_HASH, symbol_short!("set")),
+            events::ScorerWasmHashSet { caller, previous_wasm_hash, wasm_hash: scorer_wasm_hash },
+        );
+    }
+
+    // Extends the TTL of the factory instance and its configuration entries to
+    // the network maximum once less than `TTL_THRESHOLD` ledgers are left. Anyone
+    // can call it.
+    pub fn extend_ttl(env: Env) {
+        // The registry entries of each scorer are not extended here, as there is no
+        // bound on their number: `maintenance` with `MaintenanceTask::ExtendTtl` does
+        // the same and also extends them, a batch of scorers at a time.
+
+        Self::extend_instance_on_access(&env);
+        let storage = env.storage().persistent();
+        let max_ttl = env.storage().max_ttl();
+        for key in [
+            DataKey::ApprovedWasmHashes, DataKey::BadgeTemplates, DataKey::DefaultTemplate, DataKey::PausedOperations,
+            DataKey::TemplateHashes, DataKey::IssuerRegistry, DataKey::VerifiedIssuers,
+        ] {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, TTL_THRESHOLD, max_ttl);
+            }
+        }
+    }
+
+    /// Extends the TTL of the factory instance, which holds its configuration,
+    /// once less than `TTL_THRESHOLD` ledgers are left
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    fn extend_instance_on_access(env: &Env) {
+        Self::require_migrated(env.clone());
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, env.storage().max_ttl());
+    }
+
+    // Retrieves the scorer WASM hash used for new deployments
+    pub fn get_scorer_wasm_hash(env: Env) -> BytesN<32> {
+        Self::require_migrated(env.clone());
+
+        env.storage()
+            .instance()
+            .get::<DataKey, BytesN<32>>(&DataKey::ScorerWasmHash)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ContractCreatorNotFound))
+    }
+
+    // Retrieves the version of the scorer template, which starts at 1 and
+    // increases each time the scorer WASM hash is replaced
+    pub fn get_template_version(env: Env) -> u32 {
+        Self::template_hashes(&env).len()
+    }
+
+    // Retrieves the scorer WASM hash of a template version
+    pub fn get_template_hash(env: Env, version: u32) -> Option<BytesN<32>> {
+        Self::template_hashes(&env).get(version.checked_sub(1)?)
+    }
+
+    /// Retrieves the scorer WASM hashes of every template version, oldest first
+    /// 
+    /// Factories initialized before versions were recorded start their history
+    /// at the hash they currently deploy.
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    fn template_hashes(env: &Env) -> Vec<BytesN<32>> {
+        Self::require_migrated(env.clone());
+        let storage = env.storage().persistent();
+        storage.get(&DataKey::TemplateHashes).unwrap_or_else(|| {
+            let mut hashes = Vec::new(env);
+            if let Some(hash) = env.storage().instance().get::<DataKey, BytesN<32>>(&DataKey::ScorerWasmHash) {
+                hashes.push_back(hash);
+            }
+            hashes
+        })
+    }
+
+    // Adds a scorer WASM hash to the allowlist of audited upgrade targets
+    pub fn approve_wasm_hash(env: Env, caller: Address, wasm_hash: BytesN<32>) {
+        Self::require_authorized(&env, &caller);
+
+        let mut approved_hashes = Self::get_approved_wasm_hashes(env.clone());
+        if approved_hashes.contains(wasm_hash.clone()) {
+            panic_with_error!(&env, Error::WasmHashAlreadyApproved);
+        }
+
+        approved_hashes.push_back(wasm_hash.clone());
+        env.storage().persistent().set(&DataKey::ApprovedWasmHashes, &approved_hashes);
+
+        env.events().publish((TOPIC_WASM_HASH, symbol_short!("approve")), events::WasmHashApproved { caller, wasm_hash });
+    }
+
+    // Removes a scorer WASM hash from the allowlist of audited upgrade targets
+    pub fn revoke_wasm_hash(env: Env, caller: Address, wasm_hash: BytesN<32>) {
+        Self::require_authorized(&env, &caller);
+
+        let mut approved_hashes = Self::get_approved_wasm_hashes(env.clone());
+        let position = Self::index_of(&approved_hashes, wasm_hash.clone());
+
+        if let Some(idx) = position {
+            approved_hashes.remove(idx);
+            env.storage().persistent().set(&DataKey::ApprovedWasmHashes, &approved_hashes);
+            env.events().publish((TOPIC_WASM_HASH, symbol_short!("revoke")), events::WasmHashRevoked { caller, wasm_hash });
+        } else {
+            panic_with_error!(&env, Error::WasmHashNotApproved);
+        }
+    }
+
+    // Checks if a scorer WASM hash is in the allowlist of audited upgrade targets
+    pub fn is_wasm_hash_approved(env: Env, wasm_hash: BytesN<32>) -> bool {
+        Self::get_approved_wasm_hashes(env).contains(wasm_hash)
+    }
+
+    // Records an upgrade reported by a scorer whose upgrade guard is this factory,
+    // and returns whether the new hash is approved, which the scorer requires
+    pub fn record_upgrade(env: Env, scorer_address: Address, wasm_hash: BytesN<32>) -> bool {
+        // Only upgrades to approved hashes go through, and only registered scorers
+        // have a history, so other reports are answered without being recorded.
+
+        scorer_address.require_auth();
+
+        let approved = Self::is_wasm_hash_approved(env.clone(), wasm_hash.clone());
+        if approved && env.storage().persistent().has(&DataKey::Scorer(scorer_address.clone())) {
+            let ledger = env.ledger().sequence();
+            let mut history = Self::get_scorer_upgrade_history(env.clone(), scorer_address.clone());
+            history.push_back(ScorerUpgrade { wasm_hash: wasm_hash.clone(), ledger });
+            env.storage().persistent().set(&DataKey::UpgradeHistory(scorer_address.clone()), &history);
+
+            env.events().publish(
+                (TOPIC_SCORER, symbol_short!("upgrade")),
+                events::ScorerUpgradeRecorded { scorer: scorer_address, wasm_hash, ledger },
+            );
+        }
+        approved
+    }
+
+    // Retrieves the recorded upgrades of a scorer, oldest first, so auditors can
+    // verify which code a community ran at any time
+    pub fn get_scorer_upgrade_history(env: Env, scorer_address: Address) -> Vec<ScorerUpgrade> {
+        Self::require_migrated(env.clone());
+
+        env.storage()
+           .persistent()
+           .get::<DataKey, Vec<ScorerUpgrade>>(&DataKey::UpgradeHistory(scorer_address))
+           .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // Retrieves the allowlist of audited scorer WASM hashes
+    pub fn get_approved_wasm_hashes(env: Env) -> Vec<BytesN<32>> {
+        Self::require_migrated(env.clone());
+
+        env.storage()
+           .persistent()
+           .get::<DataKey, Vec<BytesN<32>>>(&DataKey::ApprovedWasmHashes)
+           .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // Adds or replaces a badge template in the factory's shared catalog
+    pub fn set_badge_template(env: Env, caller: Address, template_id: String, badges: Map<BadgeId, u32>) {
+        Self::require_authorized(&env, &caller);
+
+        Self::check_not_paused(&env, FactoryOperation::Metadata);
+
+        let mut templates = Self::get_badge_templates(env.clone());
+        templates.set(template_id.clone(), badges.clone());
+        env.storage().persistent().set(&DataKey::BadgeTemplates, &templates);
+
+        env.events().publish((TOPIC_TEMPLATE, symbol_short!("set")), events::BadgeTemplateSet { caller, template_id, badges });
+    }
+
+    // Removes a badge template from the factory's shared catalog
+    pub fn remove_badge_template(env: Env, caller: Address, template_id: String) {
+        Self::require_authorized(&env, &caller);
+
+        Self::check_not_paused(&env, FactoryOperation::Metadata);
+
+        let mut templates = Self::get_badge_templates(env.clone());
+        if !templates.contains_key(template_id.clone()) {
+            panic_with_error!(&env, Error::BadgeTemplateNotFound);
+        }
+
+        templates.remove(template_id.clone());
+        env.storage().persistent().set(&DataKey::BadgeTemplates, &templates);
+
+        env.events().publish((TOPIC_TEMPLATE, symbol_short!("remove")), events::BadgeTemplateRemoved { caller, template_id });
+    }
+
+    // Sets the badge template merged into the badges of every new scorer, for
+    // platform-wide badges, or stops merging one
+    pub fn set_default_template(env: Env, caller: Address, template_id: Option<String>) {
+        // Badges a creator sets, directly or through templates, keep their score.
+
+        Self::require_authorized(&env, &caller);
+
+        Self::check_not_paused(&env, FactoryOperation::Metadata);
+
+        match template_id.clone() {
+            Some(id) => {
+                if !Self::get_badge_templates(env.clone()).contains_key(id.clone()) {
+                    panic_with_error!(&env, Error::BadgeTemplateNotFound);
+                }
+                env.storage().persistent().set(&DataKey::DefaultTemplate, &id);
+            }
+            None => env.storage().persistent().remove(&DataKey::DefaultTemplate),
+        }
+
+        env.events().publish((TOPIC_TEMPLATE, symbol_short!("default")), events::DefaultTemplateSet { caller, template_id });
+    }
+
+    // Retrieves the id of the badge template merged into every new scorer
+    pub fn get_default_template(env: Env) -> Option<String> {
+        Self::require_migrated(env.clone());
+
+        env.storage().persistent().get(&DataKey::DefaultTemplate)
+    }
+
+    // Retrieves the factory's shared catalog of badge templates
+    pub fn get_badge_templates(env: Env) -> Map<String, Map<BadgeId, u32>> {
+        Self::require_migrated(env.clone());
+
+        env.storage()
+           .persistent()
+           .get::<DataKey, Map<String, Map<BadgeId, u32>>>(&DataKey::BadgeTemplates)
+           .unwrap_or_else(|| Map::new(&env))
+    }
+
+    // Sets or clears the issuer registry the factory answers `is_verified_issuer` from,
+    // besides its own list, so scorers can use the factory as their shared trust anchor
+    // for badge issuers
+    pub fn set_issuer_registry(env: Env, caller: Address, registry: Option<Address>) {
+        Self::require_authorized(&env, &caller);
+
+        match registry.clone() {
+            Some(registry) => env.storage().persistent().set(&DataKey::IssuerRegistry, &registry),
+            None => env.storage().persistent().remove(&DataKey::IssuerRegistry),
+        }
+
+        env.events().publish((TOPIC_ISSUER, symbol_short!("registry")), events::IssuerRegistrySet { sender: caller, registry });
+    }
+
+    // Retrieves the issuer registry the factory answers `is_verified_issuer` from, if any
+    pub fn get_issuer_registry(env: Env) -> Option<Address> {
+        Self::require_migrated(env.clone());
+
+        env.storage().persistent().get(&DataKey::IssuerRegistry)
+    }
+
+    // Adds a badge issuer to the factory's own list of verified issuers
+    pub fn add_verified_issuer(env: Env, caller: Address, issuer: Address) {
+        Self::require_authorized(&env, &caller);
+
+        let mut issuers = Self::get_verified_issuers(env.clone());
+        if issuers.contains(issuer.clone()) {
+            panic_with_error!(&env, Error::IssuerAlreadyVerified);
+        }
+
+        issuers.push_back(issuer.clone());
+        env.storage().persistent().set(&DataKey::VerifiedIssuers, &issuers);
+
+        env.events().publish((TOPIC_ISSUER, symbol_short!("add")), events::VerifiedIssuerAdded { caller, issuer });
+    }
+
+    // Removes a badge issuer from the factory's own list of verified issuers
+    pub fn remove_verified_issuer(env: Env, caller: Address, issuer: Address) {
+        Self::require_authorized(&env, &caller);
+
+        let mut issuers = Self::get_verified_issuers(env.clone());
+        if let Some(idx) = Self::index_of(&issuers, issuer.clone()) {
+            issuers.remove(idx);
+            env.storage().persistent().set(&DataKey::VerifiedIssuers, &issuers);
+            env.events().publish((TOPIC_ISSUER, symbol_short!("remove")), events::VerifiedIssuerRemoved { caller, issuer });
+        } else {
+            panic_with_error!(&env, Error::IssuerNotVerified);
+        }
+    }
+
+    // Retrieves the factory's own list of verified issuers, in the order they were added
+    pub fn get_verified_issuers(env: Env) -> Vec<Address> {
+        Self::require_migrated(env.clone());
+
+        env.storage().persistent().get(&DataKey::VerifiedIssuers).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // Removes a scorer contract from the factory's registry
+    pub fn remove_scorer(env: Env, caller: Address, scorer_address: Address) {
+        // The scorer is also notified through its `set_archived` entrypoint. The notification
+        // only succeeds when the scorer lists this factory as its `Extension::Factory`, and a failure
+        // does not prevent the removal.
+
+        Self::require_manager(&env, &caller);
+
+        Self::check_not_paused(&env, FactoryOperation::Removal);
+
+        // Check that the registry holds the scorer
+        Self::get_registry_len(&env);
+        let (scorer_name, scorer_description, icon) = Self::unregister_scorer(&env, &scorer_address)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound));
+
         // Emit an event for the removal
         env.events().publish(
             (TOPIC_SCORER, symbol_short!("remove")), 
-            (caller, scorer_address, scorer_name, scorer_description, icon)
+            events::ScorerRemoved {
+                caller,
+                scorer: scorer_address,
+                name: scorer_name,
+                description: scorer_description,
+                icon,
+            },
         );
     }
+
+    // Removes several scorers from the registry at once, archiving those that
+    // list this factory, to clean up abandoned test communities in one transaction
+    pub fn archive_scorers(env: Env, admin: Address, scorers: Vec<Address>) {
+        // Addresses missing from the registry are skipped. A single summary event
+        // lists the removed scorers instead of one removal event per scorer.
+
+        Self::require_manager(&env, &admin);
+
+        Self::check_not_paused(&env, FactoryOperation::Removal);
+
+        let mut archived = Vec::new(&env);
+        for scorer_address in scorers.try_iter().map(UnwrapOptimized::unwrap_optimized) {
+            if Self::unregister_scorer(&env, &scorer_address).is_some() {
+                archived.push_back(scorer_address);
+            }
+        }
+
+        env.events().publish(
+            (TOPIC_SCORER, symbol_short!("archive")),
+            events::ScorersArchived { caller: admin, scorers: archived },
+        );
+    }
+
+    /// Removes a scorer from the registry and its indexes, then asks it to archive itself
+    /// 
+    /// The archival only succeeds when the scorer lists this factory as its `Extension::Factory`,
+    /// and a failure does not prevent the removal.
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer_address` - The address of the scorer contract to remove
+    /// 
+    /// # Returns
+    /// * `Option<(String, String, String)>` - The name, description and icon of the removed
+    ///   scorer, or None if it was not registered
+    fn unregister_scorer(env: &Env, scorer_address: &Address) -> Option<(String, String, String)> {
+        let scorer_key = DataKey::Scorer(scorer_address.clone());
+        let (scorer_name, scorer_description, icon) = env.storage()
+            .persistent()
+            .get::<DataKey, (String, String, String)>(&scorer_key)?;
+
+        // Remove the scorer's entry
+        let storage = env.storage().persistent();
+        storage.remove(&scorer_key);
+
+        // Release the scorer's name if it holds it. Names too long to normalize,
+        // recorded before their length was checked, were never indexed
+        if let Some(name) = Self::normalize_name(env, &scorer_name) {
+            let name_key = DataKey::ScorerByName(name);
+            if storage.get::<DataKey, Address>(&name_key).as_ref() == Some(scorer_address) {
+                storage.remove(&name_key);
+            }
+        }
+
+        let creator_key = DataKey::ScorerCreator(scorer_address.clone());
+        if let Some(creator) = storage.get::<DataKey, Address>(&creator_key) {
+            storage.remove(&creator_key);
+            Self::remove_from_creator(env, scorer_address, &creator);
+        }
+
+        // Leave the scorer's creation slot empty
+        let slot_key = DataKey::ScorerSlot(scorer_address.clone());
+        if let Some((slot, _)) = storage.get::<DataKey, (u32, u32)>(&slot_key) {
+            storage.remove(&slot_key);
+            storage.remove(&DataKey::ScorerAt(slot));
+        }
+
+        // Let the scorer reflect its retired status, if it accepts the factory
+        let _ = ScorerClient::new(env, scorer_address).try_set_archived(&env.current_contract_address(), &true);
+
+        Some((scorer_name, scorer_description, icon))
+    }
+}
+
+#[contractimpl]
+impl IssuerRegistryTrait for ScorerFactoryContract {
+    // Checks if an issuer is in the factory's own list of verified issuers, or is
+    // verified by the issuer registry set on the factory
+    fn is_verified_issuer(env: Env, issuer: Address) -> bool {
+        if Self::get_verified_issuers(env.clone()).contains(issuer.clone()) {
+            return true;
+        }
+        match Self::get_issuer_registry(env.clone()) {
+            Some(registry) => matches!(IssuerRegistryClient::new(&env, &registry).try_is_verified_issuer(&issuer), Ok(Ok(true))),
+            None => false,
+        }
+    }
+}
+
+#[contractimpl]
+impl MaintenanceTrait for ScorerFactoryContract {
+    // Runs a batch of a maintenance task over the registered scorers from `cursor`
+    fn maintenance(env: Env, task: MaintenanceTask, cursor: u32, limit: u32) -> u32 {
+        Self::require_migrated(env.clone());
+
+        // `ExtendTtl` extends the factory instance and its storage entries with the first
+        // batch, as `extend_ttl` does, then the registry and index entries, instance and
+        // code of the scorers in the batch; the scorers' own storage is extended by
+        // their `maintenance`. Nothing in the factory expires, so `Compact`
+        // and `SweepExpired` complete at once.
+
+        if task != MaintenanceTask::ExtendTtl {
+            return 0;
+        }
+
+        let storage = env.storage().persistent();
+        let max_ttl = env.storage().max_ttl();
+        if cursor == 0 {
+            Self::extend_ttl(env.clone());
+        }
+
+        let scorer_count = Self::get_scorer_count(&env);
+        let cursor = if cursor < scorer_count { cursor } else { 0 };
+        let end = cursor.saturating_add(limit).min(scorer_count);
+        for scorer_address in Self::get_scorer_slots(&env, cursor, end - cursor).try_iter().map(UnwrapOptimized::unwrap_optimized) {
+            let (name, _, _) = Self::get_scorer_metadata(&env, &scorer_address);
+            let creator = storage.get::<DataKey, Address>(&DataKey::ScorerCreator(scorer_address.clone()));
+            // Names too long to normalize were never indexed
+            let name_key = Self::normalize_name(&env, &name).map(DataKey::ScorerByName);
+            for key in [
+                Some(DataKey::Scorer(scorer_address.clone())),
+                Some(DataKey::ScorerSlot(scorer_address.clone())),
+                Some(DataKey::ScorerCreator(scorer_address.clone())),
+                Some(DataKey::UpgradeHistory(scorer_address.clone())),
+                name_key,
+            ].into_iter().flatten() {
+                if storage.has(&key) {
+                    storage.extend_ttl(&key, TTL_THRESHOLD, max_ttl);
+                }
+            }
+            if let Some(creator) = creator {
+                storage.extend_ttl(&DataKey::CreatorScorers(creator), TTL_THRESHOLD, max_ttl);
+            }
+            env.deployer().extend_ttl(scorer_address, TTL_THRESHOLD, max_ttl);
+        }
+        for slot in cursor..end {
+            let key = DataKey::ScorerAt(slot);
+            if storage.has(&key) {
+                storage.extend_ttl(&key, TTL_THRESHOLD, max_ttl);
+            }
+        }
+
+        if end == scorer_count { 0 } else { end }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::{storage::Persistent as _, Address as _, AuthorizedFunction, Deployer as _, Ledger};
+    use test_utils::{EventRecord, EventSnapshot};
+    use scorer_interface::{Extension, InitialMembers};
     
     fn install_scorer_wasm(e: &Env) -> BytesN<32> {
         soroban_sdk::contractimport!(
             file = "../../wasm/scorer.wasm"
         );
-        e.deployer().upload_contract_wasm(WASM)
+        // Validating the upload takes about the whole default budget, which on the
+        // network would be spent by a transaction of its own
+        e.cost_estimate().budget().reset_unlimited();
+        let wasm_hash = e.deployer().upload_contract_wasm(WASM);
+        e.cost_estimate().budget().reset_default();
+        wasm_hash
+    }
+
+    fn setup_contract() -> (Env, Address, ScorerFactoryContractClient<'static>) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let scorer_factory_creator = Address::generate(&env);
+        let scorer_factory_contract_id = env.register(ScorerFactoryContract, ());
+        let scorer_factory_client = ScorerFactoryContractClient::new(&env, &scorer_factory_contract_id);
+        
+        // Upload the real scorer WASM and get its hash
+        let wasm_hash = install_scorer_wasm(&env);
+        
+        scorer_factory_client.initialize(&scorer_factory_creator, &wasm_hash);
+        (env, scorer_factory_creator, scorer_factory_client)
+    }
+
+    fn create_test_scorer(env: &Env, creator: &Address, client: &ScorerFactoryContractClient, salt: u8) -> Address {
+        // Scorer names are unique, so salts past the first get a letter suffix ("Test Scorer B", ...)
+        let mut name = *b"Test Scorer  ";
+        name[12] = b'A' + salt - 1;
+        let name = String::from_bytes(env, if salt == 1 { &name[..11] } else { &name });
+
+        let mut init_args: Vec<Val> = Vec::new(env);
+        init_args.push_back(creator.clone().into_val(env));
+        init_args.push_back(Map::<BadgeId, u32>::new(env).into_val(env));
+        init_args.push_back(name.into_val(env));
+        init_args.push_back(String::from_str(env, "A test scorer").into_val(env));
+        init_args.push_back(String::from_str(env, "icon.png").into_val(env));
+
+        client.create_scorer(
+            creator,
+            &BytesN::from_array(env, &[salt; 32]),
+            &Symbol::new(env, "initialize"),
+            &init_args,
+        )
+    }
+
+    #[test]
+    fn test_initialize() {
+        let (_env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        assert!(scorer_factory_client.is_initialized());
+        assert!(scorer_factory_client.is_scorer_factory_creator(&scorer_factory_creator));
+        assert_eq!(scorer_factory_client.get_storage_version(), STORAGE_VERSION);
+    }
+
+    #[test]
+    fn test_initialize_event() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let scorer_factory_creator = Address::generate(&env);
+        let scorer_factory_contract_id = env.register(ScorerFactoryContract, ());
+        let scorer_factory_client = ScorerFactoryContractClient::new(&env, &scorer_factory_contract_id);
+        let wasm_hash = install_scorer_wasm(&env);
+
+        scorer_factory_client.initialize(&scorer_factory_creator, &wasm_hash);
+
+        let expected_event = EventRecord::new(
+            &env,
+            &scorer_factory_contract_id,
+            (TOPIC_INIT, symbol_short!("contract")),
+            events::FactoryInitialized {
+                creator: scorer_factory_creator.clone(),
+                managers: Vec::from_array(&env, [scorer_factory_creator]),
+                scorer_wasm_hash: wasm_hash,
+            },
+        );
+        EventSnapshot::capture(&env).assert_contains(&[expected_event]);
+    }
+
+    #[test]
+    fn test_set_scorer_wasm_hash() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let previous_wasm_hash = install_scorer_wasm(&env);
+        let new_wasm_hash = BytesN::from_array(&env, &[7; 32]);
+
+        scorer_factory_client.set_scorer_wasm_hash(&scorer_factory_creator, &new_wasm_hash);
+
+        let expected_event = EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_WASM_HASH, symbol_short!("set")),
+            events::ScorerWasmHashSet { caller: scorer_factory_creator, previous_wasm_hash, wasm_hash: new_wasm_hash },
+        );
+        EventSnapshot::capture(&env).assert_contains(&[expected_event]);
+    }
+
+    #[test]
+    fn test_template_versions() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let initial_wasm_hash = install_scorer_wasm(&env);
+        let new_wasm_hash = BytesN::from_array(&env, &[7; 32]);
+
+        assert_eq!(scorer_factory_client.get_scorer_wasm_hash(), initial_wasm_hash);
+        assert_eq!(scorer_factory_client.get_template_version(), 1);
+
+        scorer_factory_client.set_scorer_wasm_hash(&scorer_factory_creator, &new_wasm_hash);
+
+        assert_eq!(scorer_factory_client.get_scorer_wasm_hash(), new_wasm_hash);
+        assert_eq!(scorer_factory_client.get_template_version(), 2);
+        assert_eq!(scorer_factory_client.get_template_hash(&1), Some(initial_wasm_hash.clone()));
+        assert_eq!(scorer_factory_client.get_template_hash(&2), Some(new_wasm_hash.clone()));
+        assert_eq!(scorer_factory_client.get_template_hash(&0), None);
+        assert_eq!(scorer_factory_client.get_template_hash(&3), None);
+
+        // Factories initialized before versions were recorded start at their current hash
+        env.as_contract(&scorer_factory_client.address, || {
+            env.storage().persistent().remove(&DataKey::TemplateHashes);
+        });
+        assert_eq!(scorer_factory_client.get_template_version(), 1);
+        scorer_factory_client.set_scorer_wasm_hash(&scorer_factory_creator, &initial_wasm_hash);
+        assert_eq!(scorer_factory_client.get_template_hash(&1), Some(new_wasm_hash));
+        assert_eq!(scorer_factory_client.get_template_hash(&2), Some(initial_wasm_hash));
     }
 
-    fn setup_contract() -> (Env, Address, ScorerFactoryContractClient<'static>) {
-        let env = Env::default();
-        env.mock_all_auths();
-        let scorer_factory_creator = Address::generate(&env);
-        let scorer_factory_contract_id = env.register_contract(None, ScorerFactoryContract);
-        let scorer_factory_client = ScorerFactoryContractClient::new(&env, &scorer_factory_contract_id);
-        
-        // Upload the real scorer WASM and get its hash
-        let wasm_hash = install_scorer_wasm(&env);
-        
-        scorer_factory_client.initialize(&scorer_factory_creator, &wasm_hash);
-        (env, scorer_factory_creator, scorer_factory_client)
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_set_scorer_wasm_hash_by_manager() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let manager = Address::generate(&env);
+        scorer_factory_client.add_manager(&scorer_factory_creator, &manager);
+        scorer_factory_client.set_scorer_wasm_hash(&manager, &BytesN::from_array(&env, &[7; 32]));
+    }
+
+    mod wasm_hash_governance {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        // Governs the factory it was constructed with
+        #[contract]
+        pub struct WasmHashGovernance;
+
+        #[contractimpl]
+        impl WasmHashGovernance {
+            pub fn __constructor(env: Env, factory: Address) {
+                env.storage().instance().set(&(), &factory);
+            }
+
+            pub fn get_factory(env: Env) -> Address {
+                env.storage().instance().get(&()).unwrap()
+            }
+        }
     }
 
     #[test]
-    fn test_initialize() {
-        let (_env, scorer_factory_creator, scorer_factory_client) = setup_contract();
-        assert!(scorer_factory_client.is_initialized());
-        assert!(scorer_factory_client.is_scorer_factory_creator(&scorer_factory_creator));
+    fn test_wasm_hash_governance() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let governance = env.register(wasm_hash_governance::WasmHashGovernance, (scorer_factory_client.address.clone(),));
+        let new_wasm_hash = BytesN::from_array(&env, &[7; 32]);
+
+        let manager = Address::generate(&env);
+        scorer_factory_client.add_manager(&scorer_factory_creator, &manager);
+        assert_eq!(
+            scorer_factory_client.try_set_wasm_hash_governance(&manager, &Some(governance.clone())),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+
+        // A contract governing another factory would skip the managers' votes
+        let other_factory = env.register(wasm_hash_governance::WasmHashGovernance, (Address::generate(&env),));
+        assert_eq!(
+            scorer_factory_client.try_set_wasm_hash_governance(&scorer_factory_creator, &Some(other_factory)),
+            Err(Ok(Error::InvalidWasmHashGovernance.into()))
+        );
+
+        scorer_factory_client.set_wasm_hash_governance(&scorer_factory_creator, &Some(governance.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_WASM_HASH, symbol_short!("gov")),
+            events::WasmHashGovernanceSet { caller: scorer_factory_creator.clone(), governance: Some(governance.clone()) },
+        )]);
+        assert_eq!(scorer_factory_client.get_wasm_hash_governance(), Some(governance.clone()));
+
+        // The creator's changes, of the hash and of the governance, now go
+        // through the governance contract's votes
+        assert_eq!(
+            scorer_factory_client.try_set_scorer_wasm_hash(&scorer_factory_creator, &new_wasm_hash),
+            Err(Ok(Error::ProposalRequired.into()))
+        );
+        assert_eq!(
+            scorer_factory_client.try_set_wasm_hash_governance(&scorer_factory_creator, &None),
+            Err(Ok(Error::ProposalRequired.into()))
+        );
+        scorer_factory_client.set_scorer_wasm_hash(&governance, &new_wasm_hash);
+        assert_eq!(scorer_factory_client.get_scorer_wasm_hash(), new_wasm_hash);
+
+        scorer_factory_client.set_wasm_hash_governance(&governance, &None);
+        assert_eq!(scorer_factory_client.get_wasm_hash_governance(), None);
+        assert_eq!(
+            scorer_factory_client.try_set_scorer_wasm_hash(&governance, &new_wasm_hash),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+        scorer_factory_client.set_scorer_wasm_hash(&scorer_factory_creator, &new_wasm_hash);
     }
 
     #[test]
@@ -404,6 +1758,918 @@ mod test {
     fn test_get_scorers() {
         let (_env, _scorer_factory_creator, scorer_factory_client) = setup_contract();
         let scorers = scorer_factory_client.get_scorers();
-        assert!(scorers.len() == 0);
+        assert!(scorers.is_empty());
+    }
+
+    #[test]
+    fn test_get_scorer_versions() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+
+        let versions = scorer_factory_client.get_scorer_versions(&0);
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions.get(scorer_address).unwrap(), 1);
+
+        // Pages past the end of the registry are empty
+        assert!(scorer_factory_client.get_scorer_versions(&1).is_empty());
+    }
+
+    #[test]
+    fn test_list_scorers() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let mut created = Vec::new(&env);
+        for salt in 1..=12 {
+            env.ledger().with_mut(|ledger| ledger.sequence_number += 1);
+            env.cost_estimate().budget().reset_default();
+            created.push_back(create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, salt));
+        }
+
+        let first_page = scorer_factory_client.list_scorers(&0);
+        assert_eq!(first_page.len(), PAGE_SIZE);
+        assert_eq!(
+            first_page.get(0).unwrap(),
+            ScorerInfo {
+                address: created.get_unchecked(0),
+                name: String::from_str(&env, "Test Scorer"),
+                description: String::from_str(&env, "A test scorer"),
+                icon: String::from_str(&env, "icon.png"),
+                creator: Some(scorer_factory_creator.clone()),
+                created_at: 1,
+            }
+        );
+
+        // Scorers are listed in creation order across pages
+        let second_page = scorer_factory_client.list_scorers(&1);
+        let mut listed = Vec::new(&env);
+        for info in first_page.iter().chain(second_page.iter()) {
+            listed.push_back(info.address);
+        }
+        assert_eq!(listed, created);
+        assert_eq!(second_page.get(1).unwrap().created_at, 12);
+        assert!(scorer_factory_client.list_scorers(&2).is_empty());
+
+        // Removed scorers are no longer listed and leave their slot empty
+        scorer_factory_client.remove_scorer(&scorer_factory_creator, &created.get_unchecked(0));
+        let first_page = scorer_factory_client.list_scorers(&0);
+        assert_eq!(first_page.len(), PAGE_SIZE - 1);
+        assert_eq!(first_page.get(0).unwrap().address, created.get_unchecked(1));
+        assert_eq!(scorer_factory_client.list_scorers(&1).len(), 2);
+    }
+
+    #[test]
+    fn test_export_and_import_registration() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 42);
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+
+        let registration = scorer_factory_client.export_registration(&scorer_address);
+        assert_eq!(
+            registration,
+            ScorerInfo {
+                address: scorer_address.clone(),
+                name: String::from_str(&env, "Test Scorer"),
+                description: String::from_str(&env, "A test scorer"),
+                icon: String::from_str(&env, "icon.png"),
+                creator: Some(scorer_factory_creator.clone()),
+                created_at: 42,
+            }
+        );
+        assert_eq!(
+            scorer_factory_client.try_export_registration(&Address::generate(&env)),
+            Err(Ok(Error::ScorerNotFound.into()))
+        );
+
+        // A new factory deployment takes the entry over with its provenance
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 100);
+        let new_admin = Address::generate(&env);
+        let new_factory = ScorerFactoryContractClient::new(&env, &env.register(ScorerFactoryContract, ()));
+        new_factory.initialize(&new_admin, &BytesN::from_array(&env, &[7; 32]));
+
+        assert_eq!(
+            new_factory.try_import_registration(&Address::generate(&env), &scorer_factory_client.address, &scorer_address),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+        assert_eq!(
+            new_factory.try_import_registration(&new_admin, &scorer_factory_client.address, &Address::generate(&env)),
+            Err(Ok(Error::ScorerNotFound.into()))
+        );
+
+        new_factory.import_registration(&new_admin, &scorer_factory_client.address, &scorer_address);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &new_factory.address,
+            (TOPIC_SCORER, symbol_short!("import")),
+            events::ScorerImported {
+                caller: new_admin.clone(),
+                source_factory: scorer_factory_client.address.clone(),
+                scorer: scorer_address.clone(),
+                creator: scorer_factory_creator,
+                created_at: 42,
+            },
+        )]);
+        assert_eq!(new_factory.export_registration(&scorer_address), registration);
+        assert_eq!(new_factory.get_scorer_by_name(&String::from_str(&env, "Test Scorer")), scorer_address);
+
+        assert_eq!(
+            new_factory.try_import_registration(&new_admin, &scorer_factory_client.address, &scorer_address),
+            Err(Ok(Error::ScorerAlreadyRegistered.into()))
+        );
+    }
+
+    #[test]
+    fn test_is_name_available() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        assert!(scorer_factory_client.is_name_available(&String::from_str(&env, "Test Scorer")));
+
+        create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+        assert!(!scorer_factory_client.is_name_available(&String::from_str(&env, "Test Scorer")));
+        assert!(!scorer_factory_client.is_name_available(&String::from_str(&env, "  test SCORER ")));
+        assert!(scorer_factory_client.is_name_available(&String::from_str(&env, "Other Scorer")));
+
+        let too_long = String::from_bytes(&env, &[b'a'; MAX_NAME_LENGTH + 1]);
+        assert!(!scorer_factory_client.is_name_available(&too_long));
+    }
+
+    #[test]
+    fn test_register_external_scorer() {
+        mod scorer_contract {
+            soroban_sdk::contractimport!(
+                file = "../../wasm/scorer.wasm"
+            );
+        }
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let owner = Address::generate(&env);
+        let manager = Address::generate(&env);
+        scorer_factory_client.add_manager(&scorer_factory_creator, &manager);
+
+        // A scorer deployed without the factory
+        env.cost_estimate().budget().reset_unlimited();
+        let scorer_address = env.register(scorer_contract::WASM, ());
+        env.cost_estimate().budget().reset_default();
+        scorer_contract::Client::new(&env, &scorer_address).initialize(
+            &owner,
+            &Map::new(&env),
+            &String::from_str(&env, "External Scorer"),
+            &String::from_str(&env, "Deployed by hand"),
+            &String::from_str(&env, "external.png"),
+        );
+
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            scorer_factory_client.try_register_external_scorer(&outsider, &scorer_address),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+
+        scorer_factory_client.register_external_scorer(&manager, &scorer_address);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_SCORER, symbol_short!("register")),
+            events::ScorerRegistered {
+                caller: manager.clone(),
+                creator: owner.clone(),
+                scorer: scorer_address.clone(),
+                name: String::from_str(&env, "External Scorer"),
+                description: String::from_str(&env, "Deployed by hand"),
+                icon: String::from_str(&env, "external.png"),
+            },
+        )]);
+        assert_eq!(scorer_factory_client.get_scorer_creator(&scorer_address), owner);
+        assert_eq!(scorer_factory_client.get_scorer_by_name(&String::from_str(&env, "external scorer")), scorer_address);
+        assert_eq!(scorer_factory_client.list_scorers(&0).get(0).unwrap().address, scorer_address);
+
+        assert_eq!(
+            scorer_factory_client.try_register_external_scorer(&manager, &scorer_address),
+            Err(Ok(Error::ScorerAlreadyRegistered.into()))
+        );
+
+        // Contracts without the scorer interface are rejected
+        let not_a_scorer = env.register(ScorerFactoryContract, ());
+        assert_eq!(
+            scorer_factory_client.try_register_external_scorer(&manager, &not_a_scorer),
+            Err(Ok(Error::NotAScorer.into()))
+        );
+    }
+
+    #[test]
+    fn test_archive_scorers() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let linked = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+        let unlinked = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 2);
+        let kept = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 3);
+        ScorerClient::new(&env, &linked).set_extension(&scorer_factory_creator, &Extension::Factory, &Some(scorer_factory_client.address.clone()));
+
+        let unknown = Address::generate(&env);
+        let scorers = Vec::from_array(&env, [linked.clone(), unlinked.clone(), unknown]);
+        assert_eq!(
+            scorer_factory_client.try_archive_scorers(&Address::generate(&env), &scorers),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+
+        env.cost_estimate().budget().reset_default();
+        scorer_factory_client.archive_scorers(&scorer_factory_creator, &scorers);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_SCORER, symbol_short!("archive")),
+            events::ScorersArchived { caller: scorer_factory_creator.clone(), scorers: Vec::from_array(&env, [linked.clone(), unlinked.clone()]) },
+        )]);
+        assert_eq!(scorer_factory_client.get_scorers().keys(), Vec::from_array(&env, [kept]));
+        assert!(ScorerClient::new(&env, &linked).is_archived());
+        assert!(!ScorerClient::new(&env, &unlinked).is_archived());
+
+        // Only the scorers found in the registry are listed
+    }
+
+    #[test]
+    fn test_set_paused() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+
+        assert_eq!(
+            scorer_factory_client.try_set_paused(&Address::generate(&env), &FactoryOperation::Creation, &true),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+        scorer_factory_client.set_paused(&scorer_factory_creator, &FactoryOperation::Creation, &true);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_PAUSE, symbol_short!("set")),
+            events::FactoryPauseSet { caller: scorer_factory_creator.clone(), operation: FactoryOperation::Creation, paused: true },
+        )]);
+        assert!(scorer_factory_client.is_paused(&FactoryOperation::Creation));
+        assert!(!scorer_factory_client.is_paused(&FactoryOperation::Removal));
+
+        // Paused creation leaves the directory maintainable
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(scorer_factory_creator.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Other Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "Another scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+        assert_eq!(
+            scorer_factory_client.try_create_scorer(
+                &scorer_factory_creator,
+                &BytesN::from_array(&env, &[2; 32]),
+                &Symbol::new(&env, "initialize"),
+                &init_args,
+            ),
+            Err(Ok(Error::OperationPaused.into()))
+        );
+        let new_creator = Address::generate(&env);
+        scorer_factory_client.transfer_scorer_registration(&scorer_factory_creator, &scorer_address, &new_creator);
+
+        scorer_factory_client.set_paused(&scorer_factory_creator, &FactoryOperation::Metadata, &true);
+        scorer_factory_client.set_paused(&scorer_factory_creator, &FactoryOperation::Removal, &true);
+        assert_eq!(
+            scorer_factory_client.get_paused_operations(),
+            Vec::from_array(&env, [FactoryOperation::Creation, FactoryOperation::Metadata, FactoryOperation::Removal])
+        );
+        assert_eq!(
+            scorer_factory_client.try_transfer_scorer_registration(&new_creator, &scorer_address, &scorer_factory_creator),
+            Err(Ok(Error::OperationPaused.into()))
+        );
+        assert_eq!(
+            scorer_factory_client.try_set_badge_template(&scorer_factory_creator, &String::from_str(&env, "starter"), &Map::new(&env)),
+            Err(Ok(Error::OperationPaused.into()))
+        );
+        assert_eq!(
+            scorer_factory_client.try_remove_scorer(&scorer_factory_creator, &scorer_address),
+            Err(Ok(Error::OperationPaused.into()))
+        );
+
+        // Resumed operations go through again
+        scorer_factory_client.set_paused(&scorer_factory_creator, &FactoryOperation::Removal, &false);
+        assert!(!scorer_factory_client.is_paused(&FactoryOperation::Removal));
+        scorer_factory_client.remove_scorer(&scorer_factory_creator, &scorer_address);
+    }
+
+    #[test]
+    fn test_approve_and_revoke_wasm_hash() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let wasm_hash = install_scorer_wasm(&env);
+        assert!(!scorer_factory_client.is_wasm_hash_approved(&wasm_hash));
+
+        scorer_factory_client.approve_wasm_hash(&scorer_factory_creator, &wasm_hash);
+        assert!(scorer_factory_client.is_wasm_hash_approved(&wasm_hash));
+        assert_eq!(scorer_factory_client.get_approved_wasm_hashes(), Vec::from_array(&env, [wasm_hash.clone()]));
+
+        scorer_factory_client.revoke_wasm_hash(&scorer_factory_creator, &wasm_hash);
+        assert!(!scorer_factory_client.is_wasm_hash_approved(&wasm_hash));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_approve_wasm_hash_unauthorized() {
+        let (env, _scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let wasm_hash = install_scorer_wasm(&env);
+        scorer_factory_client.approve_wasm_hash(&Address::generate(&env), &wasm_hash);
+    }
+
+    #[test]
+    fn test_scorer_upgrade_history() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+        assert!(scorer_factory_client.get_scorer_upgrade_history(&scorer_address).is_empty());
+
+        // Scorers guarded by the factory report their upgrades
+        let scorer = ScorerClient::new(&env, &scorer_address);
+        scorer.enable_upgrade_guard(&scorer_factory_creator, &scorer_factory_client.address);
+        let wasm_hash = install_scorer_wasm(&env);
+        scorer_factory_client.approve_wasm_hash(&scorer_factory_creator, &wasm_hash);
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 42);
+        scorer.upgrade(&wasm_hash);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_SCORER, symbol_short!("upgrade")),
+            events::ScorerUpgradeRecorded { scorer: scorer_address.clone(), wasm_hash: wasm_hash.clone(), ledger: 42 },
+        )]);
+        assert_eq!(
+            scorer_factory_client.get_scorer_upgrade_history(&scorer_address),
+            Vec::from_array(&env, [ScorerUpgrade { wasm_hash: wasm_hash.clone(), ledger: 42 }])
+        );
+
+        // Unapproved hashes are rejected without being recorded
+        let unapproved = BytesN::from_array(&env, &[7; 32]);
+        assert!(!scorer_factory_client.record_upgrade(&scorer_address, &unapproved));
+        assert_eq!(scorer_factory_client.get_scorer_upgrade_history(&scorer_address).len(), 1);
+
+        // Only registered scorers have a history
+        let unknown = Address::generate(&env);
+        assert!(scorer_factory_client.record_upgrade(&unknown, &wasm_hash));
+        assert!(scorer_factory_client.get_scorer_upgrade_history(&unknown).is_empty());
+    }
+
+    #[test]
+    fn test_create_scorer_from_templates() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let issuer = Address::generate(&env);
+        let quest_badge = BadgeId { name: String::from_str(&env, "Stellar Quest"), issuer: issuer.clone() };
+        let own_badge = BadgeId { name: String::from_str(&env, "Own Badge"), issuer: issuer.clone() };
+
+        let mut template = Map::new(&env);
+        template.set(quest_badge.clone(), 100);
+        template.set(own_badge.clone(), 100);
+        let template_id = String::from_str(&env, "stellar_quest");
+        scorer_factory_client.set_badge_template(&scorer_factory_creator, &template_id, &template);
+
+        let mut scorer_badges = Map::<BadgeId, u32>::new(&env);
+        scorer_badges.set(own_badge.clone(), 500);
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(scorer_factory_creator.clone().into_val(&env));
+        init_args.push_back(scorer_badges.into_val(&env));
+        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        let scorer_address = scorer_factory_client.create_scorer_from_templates(
+            &scorer_factory_creator,
+            &BytesN::from_array(&env, &[1; 32]),
+            &Symbol::new(&env, "initialize"),
+            &init_args,
+            &Vec::from_array(&env, [template_id]),
+        );
+
+        let badges = ScorerClient::new(&env, &scorer_address).get_badges();
+        assert_eq!(badges.len(), 2);
+        assert_eq!(badges.get(quest_badge).unwrap(), 100);
+        assert_eq!(badges.get(own_badge).unwrap(), 500);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_default_template() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let issuer = Address::generate(&env);
+        let learner = BadgeId { name: String::from_str(&env, "Stellar Quest Learner"), issuer: issuer.clone() };
+        let template_id = String::from_str(&env, "platform");
+        scorer_factory_client.set_badge_template(&scorer_factory_creator, &template_id, &Map::from_array(&env, [(learner.clone(), 10)]));
+
+        assert_eq!(
+            scorer_factory_client.try_set_default_template(&Address::generate(&env), &Some(template_id.clone())),
+            Err(Ok(Error::Unauthorized.into()))
+        );
+        assert_eq!(
+            scorer_factory_client.try_set_default_template(&scorer_factory_creator, &Some(String::from_str(&env, "missing"))),
+            Err(Ok(Error::BadgeTemplateNotFound.into()))
+        );
+        scorer_factory_client.set_default_template(&scorer_factory_creator, &Some(template_id.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_TEMPLATE, symbol_short!("default")),
+            events::DefaultTemplateSet { caller: scorer_factory_creator.clone(), template_id: Some(template_id.clone()) },
+        )]);
+        assert_eq!(scorer_factory_client.get_default_template(), Some(template_id.clone()));
+
+        // Every new scorer gets the default badges
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+        assert_eq!(ScorerClient::new(&env, &scorer_address).get_badges(), Map::from_array(&env, [(learner.clone(), 10)]));
+
+        scorer_factory_client.set_default_template(&scorer_factory_creator, &None);
+        assert_eq!(scorer_factory_client.get_default_template(), None);
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 2);
+        assert!(ScorerClient::new(&env, &scorer_address).get_badges().is_empty());
+    }
+
+    #[test]
+    fn test_create_scorer_with_members() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let members = Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+        let template_id = String::from_str(&env, "starter");
+        let badge = BadgeId { name: String::from_str(&env, "Starter"), issuer: Address::generate(&env) };
+        scorer_factory_client.set_badge_template(&scorer_factory_creator, &template_id, &Map::from_array(&env, [(badge.clone(), 10)]));
+
+        // The initial members precede the badges, which stay fourth from the end for the templates
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(scorer_factory_creator.clone().into_val(&env));
+        init_args.push_back(InitialMembers { managers: Vec::new(&env), users: members.clone() }.into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Migrated Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A migrated community").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        let scorer_address = scorer_factory_client.create_scorer_from_templates(
+            &scorer_factory_creator,
+            &BytesN::from_array(&env, &[1; 32]),
+            &Symbol::new(&env, "initialize_with_members"),
+            &init_args,
+            &Vec::from_array(&env, [template_id]),
+        );
+
+        let scorer = ScorerClient::new(&env, &scorer_address);
+        assert_eq!(scorer.get_users().keys(), members);
+        assert_eq!(scorer.get_badges().get(badge), Some(10));
+        assert_eq!(scorer_factory_client.get_scorer_by_name(&String::from_str(&env, "Migrated Scorer")), scorer_address);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #14)")]
+    fn test_remove_unknown_badge_template() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        scorer_factory_client.remove_badge_template(&scorer_factory_creator, &String::from_str(&env, "missing"));
+    }
+
+    mod shared_registry {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        // Verifies the issuers it was told about
+        #[contract]
+        pub struct SharedRegistry;
+
+        #[contractimpl]
+        impl SharedRegistry {
+            pub fn list_issuer(env: Env, issuer: Address) {
+                env.storage().persistent().set(&issuer, &true);
+            }
+
+            pub fn is_verified_issuer(env: Env, issuer: Address) -> bool {
+                env.storage().persistent().has(&issuer)
+            }
+        }
+    }
+
+    #[test]
+    fn test_verified_issuer_from_shared_registry() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let registry = env.register(shared_registry::SharedRegistry, ());
+        let registry_client = shared_registry::SharedRegistryClient::new(&env, &registry);
+        let issuer = Address::generate(&env);
+        registry_client.list_issuer(&issuer);
+        assert!(!scorer_factory_client.is_verified_issuer(&issuer));
+
+        scorer_factory_client.set_issuer_registry(&scorer_factory_creator, &Some(registry.clone()));
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_ISSUER, symbol_short!("registry")),
+            events::IssuerRegistrySet { sender: scorer_factory_creator.clone(), registry: Some(registry.clone()) },
+        )]);
+        assert_eq!(scorer_factory_client.get_issuer_registry(), Some(registry.clone()));
+        assert!(scorer_factory_client.is_verified_issuer(&issuer));
+        assert!(!scorer_factory_client.is_verified_issuer(&Address::generate(&env)));
+
+        scorer_factory_client.set_issuer_registry(&scorer_factory_creator, &None);
+        assert!(!scorer_factory_client.is_verified_issuer(&issuer));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_set_issuer_registry_unauthorized() {
+        let (env, _scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let registry = env.register(shared_registry::SharedRegistry, ());
+        scorer_factory_client.set_issuer_registry(&Address::generate(&env), &Some(registry));
+    }
+
+    #[test]
+    fn test_add_and_remove_verified_issuer() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let issuer = Address::generate(&env);
+        assert!(!scorer_factory_client.is_verified_issuer(&issuer));
+
+        scorer_factory_client.add_verified_issuer(&scorer_factory_creator, &issuer);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_ISSUER, symbol_short!("add")),
+            events::VerifiedIssuerAdded { caller: scorer_factory_creator.clone(), issuer: issuer.clone() },
+        )]);
+        assert_eq!(scorer_factory_client.get_verified_issuers(), Vec::from_array(&env, [issuer.clone()]));
+        assert!(scorer_factory_client.is_verified_issuer(&issuer));
+        assert_eq!(scorer_factory_client.try_add_verified_issuer(&scorer_factory_creator, &issuer), Err(Ok(Error::IssuerAlreadyVerified.into())));
+
+        // The factory's own list and the shared registry both verify issuers
+        let registry = env.register(shared_registry::SharedRegistry, ());
+        let listed = Address::generate(&env);
+        shared_registry::SharedRegistryClient::new(&env, &registry).list_issuer(&listed);
+        scorer_factory_client.set_issuer_registry(&scorer_factory_creator, &Some(registry));
+        assert!(scorer_factory_client.is_verified_issuer(&issuer));
+        assert!(scorer_factory_client.is_verified_issuer(&listed));
+
+        scorer_factory_client.remove_verified_issuer(&scorer_factory_creator, &issuer);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &scorer_factory_client.address,
+            (TOPIC_ISSUER, symbol_short!("remove")),
+            events::VerifiedIssuerRemoved { caller: scorer_factory_creator.clone(), issuer: issuer.clone() },
+        )]);
+        assert!(scorer_factory_client.get_verified_issuers().is_empty());
+        assert!(!scorer_factory_client.is_verified_issuer(&issuer));
+        assert_eq!(scorer_factory_client.try_remove_verified_issuer(&scorer_factory_creator, &issuer), Err(Ok(Error::IssuerNotVerified.into())));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_add_verified_issuer_unauthorized() {
+        let (env, _scorer_factory_creator, scorer_factory_client) = setup_contract();
+        scorer_factory_client.add_verified_issuer(&Address::generate(&env), &Address::generate(&env));
+    }
+
+    #[test]
+    fn test_transfer_scorer_registration() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+        let new_creator = Address::generate(&env);
+
+        assert_eq!(scorer_factory_client.get_scorer_creator(&scorer_address), scorer_factory_creator);
+
+        scorer_factory_client.transfer_scorer_registration(&scorer_factory_creator, &scorer_address, &new_creator);
+
+        assert_eq!(scorer_factory_client.get_scorer_creator(&scorer_address), new_creator);
+        assert_eq!(scorer_factory_client.get_scorers_by_creator(&new_creator), Vec::from_array(&env, [scorer_address]));
+        assert!(scorer_factory_client.get_scorers_by_creator(&scorer_factory_creator).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_transfer_scorer_registration_not_creator() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+        let other = Address::generate(&env);
+
+        scorer_factory_client.transfer_scorer_registration(&other, &scorer_address, &other);
+    }
+
+    #[test]
+    fn test_get_scorer_by_name() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+
+        assert_eq!(scorer_factory_client.get_scorer_by_name(&String::from_str(&env, "Test Scorer")), scorer_address);
+        assert_eq!(scorer_factory_client.get_scorer_by_name(&String::from_str(&env, "  test SCORER ")), scorer_address);
+
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #29)")]
+    fn test_create_scorer_with_taken_name() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+
+        // Names collide after normalization too
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(scorer_factory_creator.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "  test SCORER ").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        scorer_factory_client.create_scorer(
+            &scorer_factory_creator,
+            &BytesN::from_array(&env, &[2; 32]),
+            &Symbol::new(&env, "initialize"),
+            &init_args,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #8)")]
+    fn test_get_scorer_by_name_after_removal() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let scorer_address = create_test_scorer(&env, &scorer_factory_creator, &scorer_factory_client, 1);
+
+        scorer_factory_client.remove_scorer(&scorer_factory_creator, &scorer_address);
+        scorer_factory_client.get_scorer_by_name(&String::from_str(&env, "Test Scorer"));
+    }
+
+    #[test]
+    fn test_create_scorer_for() {
+        let (env, _scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let relayer = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let salt = BytesN::from_array(&env, &[1; 32]);
+        let init_fn = Symbol::new(&env, "initialize");
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(creator.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        let scorer_address = scorer_factory_client.create_scorer_for(&relayer, &creator, &salt, &init_fn, &init_args);
+
+        // The creator only signed the creation parameters
+        let creator_auth = env.auths().into_iter().find(|(address, _)| *address == creator).unwrap();
+        assert_eq!(
+            creator_auth.1.function,
+            AuthorizedFunction::Contract((
+                scorer_factory_client.address.clone(),
+                Symbol::new(&env, "create_scorer_for"),
+                (salt, init_fn, init_args).into_val(&env),
+            ))
+        );
+
+        assert_eq!(scorer_factory_client.get_scorer_creator(&scorer_address), creator);
+        assert!(scorer_factory_client.get_scorers_by_creator(&relayer).is_empty());
+    }
+
+    #[test]
+    fn test_maintenance_extends_scorers() {
+        let (env, creator, client) = setup_contract();
+        let scorers = [
+            create_test_scorer(&env, &creator, &client, 1),
+            create_test_scorer(&env, &creator, &client, 2),
+            create_test_scorer(&env, &creator, &client, 3),
+        ];
+
+        assert_eq!(client.maintenance(&MaintenanceTask::ExtendTtl, &0, &2), 2);
+        assert_eq!(client.maintenance(&MaintenanceTask::ExtendTtl, &2, &2), 0);
+
+        let max_ttl = env.as_contract(&client.address, || env.storage().max_ttl());
+        assert_eq!(env.deployer().get_contract_instance_ttl(&client.address), max_ttl);
+        for scorer in scorers.iter() {
+            assert_eq!(env.deployer().get_contract_instance_ttl(scorer), max_ttl);
+        }
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            assert_eq!(storage.get_ttl(&DataKey::ScorerAt(2)), max_ttl);
+            assert_eq!(storage.get_ttl(&DataKey::ScorerByName(String::from_str(&env, "test scorer"))), max_ttl);
+            assert_eq!(storage.get_ttl(&DataKey::CreatorScorers(creator.clone())), max_ttl);
+        });
+        assert_eq!(client.maintenance(&MaintenanceTask::SweepExpired, &0, &2), 0);
+    }
+
+    #[test]
+    fn test_unindexed_long_name() {
+        let (env, creator, client) = setup_contract();
+        let scorer = create_test_scorer(&env, &creator, &client, 1);
+
+        // A name recorded before lengths were checked is too long to normalize
+        let long_name = String::from_bytes(&env, &[b'a'; MAX_NAME_LENGTH + 1]);
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            storage.remove(&DataKey::ScorerByName(String::from_str(&env, "test scorer")));
+            let metadata = (long_name.clone(), String::from_str(&env, "Description"), String::from_str(&env, "icon.png"));
+            storage.set(&DataKey::Scorer(scorer.clone()), &metadata);
+        });
+
+        assert_eq!(client.maintenance(&MaintenanceTask::ExtendTtl, &0, &10), 0);
+        assert!(!client.is_name_available(&long_name));
+        client.remove_scorer(&creator, &scorer);
+        assert!(!client.get_scorers().contains_key(scorer));
+    }
+
+    // A factory as written by version 1, which kept the configuration and the
+    // whole registry in persistent storage, with the creator, a manager and
+    // the scorers of `created_scorers`
+    fn setup_version_1(
+        env: &Env,
+        creator: &Address,
+        manager: &Address,
+        created_scorers: &Map<Address, (String, String, String)>,
+    ) -> ScorerFactoryContractClient<'static> {
+        let client = ScorerFactoryContractClient::new(env, &env.register(ScorerFactoryContract, ()));
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            storage.set(&DataKey::Initialized, &true);
+            storage.set(&DataKey::ScorerFactoryCreator, creator);
+            storage.set(&DataKey::Managers, &Vec::from_array(env, [creator.clone(), manager.clone()]));
+            storage.set(&DataKey::ScorerWasmHash, &BytesN::from_array(env, &[7; 32]));
+            storage.set(&DataKey::CreatedScorers, created_scorers);
+        });
+        client
+    }
+
+    #[test]
+    fn test_migrate_from_version_1() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let creator = Address::generate(&env);
+        let manager = Address::generate(&env);
+        let metadata = |name: &str| (String::from_str(&env, name), String::from_str(&env, "A test scorer"), String::from_str(&env, "icon.png"));
+        let mut created_scorers = Map::new(&env);
+        for name in ["First", "Second", "Third"] {
+            created_scorers.set(Address::generate(&env), metadata(name));
+        }
+        let client = setup_version_1(&env, &creator, &manager, &created_scorers);
+        let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+
+        // The factory is known to be initialized, so it can't be taken over before its migration
+        assert_eq!(
+            client.try_initialize(&manager, &wasm_hash),
+            Err(Ok(Error::ContractAlreadyInitialized.into()))
+        );
+        assert_eq!(client.try_get_storage_version(), Err(Ok(Error::MigrationRequired.into())));
+
+        assert_eq!(client.try_migrate(&manager, &0, &2), Err(Ok(Error::Unauthorized.into())));
+
+        // Batches of two scorers cover the three scorers, and calls are only
+        // accepted again after the last one
+        assert_eq!(client.migrate(&creator, &0, &2), 2);
+        assert_eq!(client.try_is_initialized(), Err(Ok(Error::MigrationRequired.into())));
+        assert_eq!(
+            client.try_initialize(&manager, &wasm_hash),
+            Err(Ok(Error::ContractAlreadyInitialized.into()))
+        );
+        assert_eq!(client.migrate(&creator, &2, &2), 0);
+
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_INIT, symbol_short!("migrate")),
+            events::FactoryMigrated { caller: creator.clone(), from_version: 1, to_version: STORAGE_VERSION },
+        )]);
+        assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+        assert!(client.is_initialized());
+        assert!(client.is_scorer_factory_creator(&creator));
+        assert!(client.is_manager(&manager));
+        assert_eq!(client.get_scorer_wasm_hash(), wasm_hash);
+
+        // The scorers keep the order of the map, with no creation ledger or creator
+        let scorers = client.list_scorers(&0);
+        assert_eq!(scorers.len(), 3);
+        for (index, (scorer_address, (name, _, _))) in created_scorers.iter().enumerate() {
+            let info = scorers.get_unchecked(index as u32);
+            assert_eq!((info.address, info.name.clone(), info.creator, info.created_at), (scorer_address.clone(), name.clone(), None, 0));
+            assert_eq!(client.get_scorer_by_name(&name), scorer_address);
+        }
+
+        env.as_contract(&client.address, || {
+            assert!(!env.storage().persistent().has(&DataKey::CreatedScorers));
+            assert!(!env.storage().persistent().has(&DataKey::Managers));
+        });
+        assert_eq!(client.try_migrate(&creator, &0, &2), Err(Ok(Error::AlreadyMigrated.into())));
+    }
+
+    #[test]
+    fn test_entrypoints_require_migration() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let creator = Address::generate(&env);
+        let manager = Address::generate(&env);
+        let scorer = Address::generate(&env);
+        let client = setup_version_1(&env, &creator, &manager, &Map::from_array(&env, [(scorer.clone(), (
+            String::from_str(&env, "Scorer"),
+            String::from_str(&env, "A test scorer"),
+            String::from_str(&env, "icon.png"),
+        ))]));
+        let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+        let name = String::from_str(&env, "Scorer");
+        let salt = BytesN::from_array(&env, &[1; 32]);
+        let init_fn = Symbol::new(&env, "initialize");
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(creator.clone().into_val(&env));
+        init_args.push_back(Map::<BadgeId, u32>::new(&env).into_val(&env));
+        init_args.push_back(name.clone().into_val(&env));
+        init_args.push_back(String::from_str(&env, "A test scorer").into_val(&env));
+        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+
+        // Every entrypoint but `migrate` misreads the storage of version 1, so all of them wait for it
+        let errors = [
+            client.try_is_initialized().err(),
+            client.try_get_storage_version().err(),
+            client.try_upgrade(&wasm_hash).err(),
+            client.try_is_scorer_factory_creator(&creator).err(),
+            client.try_is_manager(&manager).err(),
+            client.try_create_scorer(&creator, &salt, &init_fn, &init_args).err(),
+            client.try_create_scorer_from_templates(&creator, &salt, &init_fn, &init_args, &Vec::new(&env)).err(),
+            client.try_create_scorer_for(&manager, &creator, &salt, &init_fn, &init_args).err(),
+            client.try_register_external_scorer(&creator, &scorer).err(),
+            client.try_get_scorers().err(),
+            client.try_get_scorer_versions(&0).err(),
+            client.try_list_scorers(&0).err(),
+            client.try_export_registration(&scorer).err(),
+            client.try_import_registration(&creator, &manager, &scorer).err(),
+            client.try_get_scorer_creator(&scorer).err(),
+            client.try_get_scorers_by_creator(&creator).err(),
+            client.try_transfer_scorer_registration(&creator, &scorer, &manager).err(),
+            client.try_get_scorer_by_name(&name).err(),
+            client.try_is_name_available(&name).err(),
+            client.try_set_paused(&creator, &FactoryOperation::Creation, &true).err(),
+            client.try_is_paused(&FactoryOperation::Creation).err(),
+            client.try_get_paused_operations().err(),
+            client.try_add_manager(&creator, &scorer).err(),
+            client.try_remove_manager(&creator, &manager).err(),
+            client.try_get_managers().err(),
+            client.try_get_contract_creator().err(),
+            client.try_set_scorer_wasm_hash(&creator, &wasm_hash).err(),
+            client.try_set_wasm_hash_governance(&creator, &None).err(),
+            client.try_get_wasm_hash_governance().err(),
+            client.try_extend_ttl().err(),
+            client.try_get_scorer_wasm_hash().err(),
+            client.try_get_template_version().err(),
+            client.try_get_template_hash(&1).err(),
+            client.try_approve_wasm_hash(&creator, &wasm_hash).err(),
+            client.try_revoke_wasm_hash(&creator, &wasm_hash).err(),
+            client.try_is_wasm_hash_approved(&wasm_hash).err(),
+            client.try_record_upgrade(&scorer, &wasm_hash).err(),
+            client.try_get_scorer_upgrade_history(&scorer).err(),
+            client.try_get_approved_wasm_hashes().err(),
+            client.try_set_badge_template(&creator, &name, &Map::new(&env)).err(),
+            client.try_remove_badge_template(&creator, &name).err(),
+            client.try_set_default_template(&creator, &None).err(),
+            client.try_get_default_template().err(),
+            client.try_get_badge_templates().err(),
+            client.try_set_issuer_registry(&creator, &None).err(),
+            client.try_get_issuer_registry().err(),
+            client.try_add_verified_issuer(&creator, &manager).err(),
+            client.try_remove_verified_issuer(&creator, &manager).err(),
+            client.try_get_verified_issuers().err(),
+            client.try_is_verified_issuer(&manager).err(),
+            client.try_remove_scorer(&creator, &scorer).err(),
+            client.try_archive_scorers(&creator, &Vec::from_array(&env, [scorer.clone()])).err(),
+            client.try_maintenance(&MaintenanceTask::ExtendTtl, &0, &1).err(),
+        ];
+        for (index, error) in errors.into_iter().enumerate() {
+            assert_eq!(error, Some(Ok(Error::MigrationRequired.into())), "entrypoint {index}");
+        }
+
+        client.migrate(&creator, &0, &1);
+        assert_eq!(client.get_scorer_by_name(&name), scorer);
+    }
+
+    #[test]
+    fn test_upgrade() {
+        let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
+        let wasm_hash = install_scorer_wasm(&env);
+
+        scorer_factory_client.upgrade(&wasm_hash);
+        let (address, invocation) = env.auths().pop().unwrap();
+        assert_eq!(address, scorer_factory_creator);
+        assert_eq!(
+            invocation.function,
+            AuthorizedFunction::Contract((
+                scorer_factory_client.address.clone(),
+                Symbol::new(&env, "upgrade"),
+                (wasm_hash,).into_val(&env),
+            ))
+        );
+
+        // The factory now runs the uploaded code
+        assert_eq!(ScorerClient::new(&env, &scorer_factory_client.address).contract_version(), 1);
+    }
+
+    #[test]
+    fn test_extend_ttl() {
+        let (env, creator, client) = setup_contract();
+        create_test_scorer(&env, &creator, &client, 1);
+        let max_ttl = env.as_contract(&client.address, || env.storage().max_ttl());
+
+        // Entries aren't extended while more than the threshold is left
+        client.extend_ttl();
+        let ttl = env.as_contract(&client.address, || env.storage().persistent().get_ttl(&DataKey::TemplateHashes));
+        assert!(ttl > TTL_THRESHOLD);
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number += ttl.saturating_sub(TTL_THRESHOLD) + 1);
+        client.extend_ttl();
+        env.as_contract(&client.address, || {
+            assert_eq!(env.storage().persistent().get_ttl(&DataKey::TemplateHashes), max_ttl);
+        });
+        assert_eq!(env.deployer().get_contract_instance_ttl(&client.address), max_ttl);
+    }
+
+    #[test]
+    fn test_registry_extended_on_access() {
+        let (env, creator, client) = setup_contract();
+        let ttl = env.deployer().get_contract_instance_ttl(&client.address);
+        env.ledger().with_mut(|ledger| ledger.sequence_number += ttl.saturating_sub(TTL_THRESHOLD) + 1);
+
+        client.get_scorers();
+        client.is_manager(&creator);
+
+        let max_ttl = env.as_contract(&client.address, || env.storage().max_ttl());
+        assert_eq!(env.deployer().get_contract_instance_ttl(&client.address), max_ttl);
+    }
+}
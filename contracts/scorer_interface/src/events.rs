@@ -0,0 +1,1384 @@
+//! Catalog of the events published by the scorer and its membership,
+//! disputes, badge governance and scoreboard contracts, the factory and its
+//! governance contract, the deployer, issuers, the issuer registry, badge
+//! tokens, the quests contract, the reward distributor, the staking contract,
+//! the price adapter, the delegation registry, the policy account, the
+//! timelock, the multisig, the upgrade coordinator and the attestation bridge.
+//!
+//! Every event is published as `(topic, action)` topics, with one of the
+//! `TOPIC_*` constants below as topic, and one of these structs as data, so
+//! indexers can decode the XDR event data into the same types the contracts
+//! use. The types are not exported in the contract specs, which keeps each
+//! contract WASM free of the events of the others; decode them with this
+//! crate. Structs are grouped by the contract that emits them; manager events
+//! are shared by the scorer and the factory, vote events by both governance
+//! contracts, and the quests contract and the attestation bridge publish the
+//! issuer events when they grant a badge.
+//!
+//! The scorer prefixes its topics with `EVENT_SCHEMA_VERSION`, publishing
+//! `(version, topic, action)`, so indexers can branch their decoding when the
+//! payloads change across upgrades. The membership, disputes, badge governance
+//! and scoreboard contracts publish the events they took over from the scorer
+//! the same way.
+//!
+//! `Event` enumerates every event, and `Event::decode` maps published topics
+//! and data back to it. The contracts publish through the same constants and
+//! structs, so the catalog cannot drift from what they emit.
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, Map, String, Symbol, TryFromVal, Val, Vec};
+
+/// Version of the scorer event payloads, the first topic of every scorer event.
+/// Bumped whenever a payload changes in a way older decoders cannot read.
+pub const EVENT_SCHEMA_VERSION: u32 = 2;
+
+// Scorer topics
+pub const TOPIC_INIT: Symbol = symbol_short!("init");
+pub const TOPIC_UPGRADE: Symbol = symbol_short!("upgrade");
+pub const TOPIC_STATUS: Symbol = symbol_short!("status");
+pub const TOPIC_OWNER: Symbol = symbol_short!("owner");
+pub const TOPIC_USER: Symbol = symbol_short!("user");
+pub const TOPIC_ESCROW: Symbol = symbol_short!("escrow");
+pub const TOPIC_BADGE: Symbol = symbol_short!("badge");
+pub const TOPIC_MANAGER: Symbol = symbol_short!("manager");
+pub const TOPIC_SCORE: Symbol = symbol_short!("score");
+pub const TOPIC_DISPUTE: Symbol = symbol_short!("dispute");
+pub const TOPIC_GOVERNANCE: Symbol = symbol_short!("gov");
+
+// Factory topics, besides the shared init, manager, governance and issuer topics
+pub const TOPIC_SCORER: Symbol = symbol_short!("scorer");
+pub const TOPIC_WASM_HASH: Symbol = symbol_short!("wasm_hash");
+pub const TOPIC_TEMPLATE: Symbol = symbol_short!("template");
+pub const TOPIC_PAUSE: Symbol = symbol_short!("pause");
+
+// Topics of the other contracts
+pub const TOPIC_DEPLOY: Symbol = symbol_short!("deploy");
+pub const TOPIC_ALLOWLIST: Symbol = symbol_short!("allowlist");
+pub const TOPIC_ISSUER: Symbol = symbol_short!("issuer");
+pub const TOPIC_SESSION: Symbol = symbol_short!("session");
+pub const TOPIC_CLAIM: Symbol = symbol_short!("claim");
+pub const TOPIC_PRIVATE: Symbol = symbol_short!("private");
+pub const TOPIC_QUEST: Symbol = symbol_short!("quest");
+pub const TOPIC_EPOCH: Symbol = symbol_short!("epoch");
+pub const TOPIC_REWARD: Symbol = symbol_short!("reward");
+pub const TOPIC_STAKE: Symbol = symbol_short!("stake");
+pub const TOPIC_ORACLE: Symbol = symbol_short!("oracle");
+pub const TOPIC_FEE: Symbol = symbol_short!("fee");
+pub const TOPIC_DELEGATE: Symbol = symbol_short!("delegate");
+pub const TOPIC_SIGNERS: Symbol = symbol_short!("signers");
+pub const TOPIC_POLICY: Symbol = symbol_short!("policy");
+pub const TOPIC_TIMELOCK: Symbol = symbol_short!("timelock");
+pub const TOPIC_MULTISIG: Symbol = symbol_short!("multisig");
+pub const TOPIC_ROLLOUT: Symbol = symbol_short!("rollout");
+pub const TOPIC_BRIDGE: Symbol = symbol_short!("bridge");
+
+use crate::{BadgeChange, BadgeId, Extension, FactoryChange, FactoryOperation};
+
+// Scorer events
+
+/// `("init", "contract")`: a scorer was initialized.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerInitialized {
+    pub creator: Address,
+    pub managers: Vec<Address>,
+    pub badges: Map<BadgeId, u32>,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+}
+
+/// `("upgrade", "wasm")`: a scorer's code was upgraded, published by the new code's
+/// `migrate`. Versions are the ones the old and new code declare through
+/// `contract_version`; `old_wasm_hash` is zeroed when the replaced hash is unknown.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerUpgraded {
+    pub old_version: u32,
+    pub new_version: u32,
+    pub old_wasm_hash: BytesN<32>,
+    pub new_wasm_hash: BytesN<32>,
+}
+
+/// `("upgrade", "migrate")`: a scorer's storage was migrated.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerMigrated {
+    pub sender: Address,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+/// `("init", "migrate")`: a factory's storage was migrated.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FactoryMigrated {
+    pub caller: Address,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+/// `("upgrade", "guard")`: upgrades of a scorer now need the factory's approval.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeGuardEnabled {
+    pub sender: Address,
+    pub factory: Address,
+}
+
+/// `("status", "extension")`: the contract registered for an extension of a
+/// scorer changed, `address` being `None` once it was cleared.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionSet {
+    pub sender: Address,
+    pub extension: Extension,
+    pub address: Option<Address>,
+}
+
+/// `("status", "archived")`: a scorer was archived or restored.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedSet {
+    pub sender: Address,
+    pub archived: bool,
+}
+
+/// `("owner", "transfer")`: a scorer changed owner.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnershipTransferred {
+    pub sender: Address,
+    pub new_owner: Address,
+}
+
+/// `("user", "add")`: a user joined a scorer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserJoined {
+    pub user: Address,
+}
+
+/// `("user", "remove")`: a user left a scorer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserLeft {
+    pub user: Address,
+}
+
+/// `("user", "ban")`: a manager banned a user from a scorer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserBanned {
+    pub sender: Address,
+    pub user: Address,
+}
+
+/// `("user", "unban")`: a manager lifted the ban of a user.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserUnbanned {
+    pub sender: Address,
+    pub user: Address,
+}
+
+/// `("user", "cooldown")`: the number of ledgers after leaving during which a user cannot join
+/// a scorer again changed. `ledgers` is zero when users can rejoin at once.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RejoinCooldownSet {
+    pub sender: Address,
+    pub ledgers: u32,
+}
+
+/// `("user", "approval")`: joining a scorer started or stopped requiring approval.
+/// `token` is `None`, and `fee` and `ttl` are zero, when approval is no longer required.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JoinApprovalSet {
+    pub sender: Address,
+    pub token: Option<Address>,
+    pub fee: i128,
+    pub ttl: u32,
+}
+
+/// `("user", "feeswap")`: a scorer started or stopped accepting join fees in other assets.
+/// `router` is `None`, and `assets` empty, when fees must be paid in the fee token.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSwapSet {
+    pub sender: Address,
+    pub router: Option<Address>,
+    pub assets: Vec<Address>,
+}
+
+/// `("user", "deposit")`: a scorer started or stopped requiring a join deposit.
+/// `token` is `None`, and `amount` is zero, when no deposit is required anymore.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JoinDepositSet {
+    pub sender: Address,
+    pub token: Option<Address>,
+    pub amount: i128,
+}
+
+/// `("user", "terms")`: memberships of a scorer started or stopped expiring.
+/// `token` is `None`, and `period` and `fee` are zero, when memberships no longer expire.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MembershipTermsSet {
+    pub sender: Address,
+    pub period: u32,
+    pub token: Option<Address>,
+    pub fee: i128,
+}
+
+/// `("user", "renew")`: a member renewed its membership.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MembershipRenewed {
+    pub user: Address,
+    pub fee: i128,
+    pub expires_at: u32,
+}
+
+/// `("user", "expire")`: a keeper deactivated a member whose membership expired.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MembershipExpired {
+    pub user: Address,
+}
+
+/// `("user", "request")`: a user requested to join a scorer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JoinRequested {
+    pub user: Address,
+    pub fee: i128,
+    pub expires_at: u32,
+}
+
+/// `("user", "reject")`: a manager rejected a join request.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JoinRejected {
+    pub sender: Address,
+    pub user: Address,
+}
+
+/// `("escrow", "hold")`: a membership contract escrowed the fee of a join request,
+/// or locked a join deposit.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowHeld {
+    pub user: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// `("escrow", "swap")`: a user paid the fee of a join request in another asset,
+/// swapped to `amount` of the fee token before it was escrowed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowSwapped {
+    pub user: Address,
+    pub asset: Address,
+    pub amount_in: i128,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// `("escrow", "release")`: an escrowed fee was paid to the scorer creator on approval,
+/// or a join deposit was forfeited to it on a ban.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowReleased {
+    pub user: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub recipient: Address,
+}
+
+/// `("escrow", "refund")`: an escrowed fee or deposit was returned after a rejection,
+/// an expiry or a departure.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowRefunded {
+    pub user: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// `("badge", "add")`: a badge was added to a scorer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeAdded {
+    pub badge_id: BadgeId,
+    pub score: u32,
+    pub sender: Address,
+}
+
+/// `("badge", "remove")`: a badge was removed from a scorer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeRemoved {
+    pub badge_id: BadgeId,
+    pub score: u32,
+    pub sender: Address,
+}
+
+/// `("badge", "propose")`: an issuer proposed a badge to a scorer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeProposed {
+    pub badge_id: BadgeId,
+    pub score: u32,
+}
+
+/// `("badge", "reject")`: a manager rejected a proposed badge.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeProposalRejected {
+    pub sender: Address,
+    pub badge_id: BadgeId,
+}
+
+/// `("badge", "import")`: a scorer recognized badges of another scorer, or
+/// stopped recognizing them when `weight` is 0.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgesImported {
+    pub sender: Address,
+    pub scorer: Address,
+    pub badges: Vec<BadgeId>,
+    pub weight: u32,
+}
+
+/// `("score", "tiers")`: the score thresholds of a scorer's member tiers changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TiersSet {
+    pub sender: Address,
+    pub thresholds: Vec<u32>,
+}
+
+/// `("status", "person")`: the personhood contract a membership contract requires users to be verified by changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PersonhoodSet {
+    pub sender: Address,
+    pub personhood: Option<Address>,
+}
+
+/// `("dispute", "open")`: a member challenged the attestation of a badge to a user.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttestationChallenged {
+    pub challenger: Address,
+    pub badge_id: BadgeId,
+    pub user: Address,
+    pub reason: String,
+}
+
+/// `("dispute", "resolve")`: a manager upheld or revoked a challenged attestation.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengeResolved {
+    pub sender: Address,
+    pub badge_id: BadgeId,
+    pub user: Address,
+    pub revoked: bool,
+}
+
+/// `("badge", "score")`: the score of a scorer's badge changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeScoreSet {
+    pub badge_id: BadgeId,
+    pub previous_score: u32,
+    pub score: u32,
+    pub sender: Address,
+}
+
+/// `("gov", "config")`: the quorum or voting period of a badge or factory
+/// governance contract changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceSet {
+    pub sender: Address,
+    pub quorum: u32,
+    pub voting_period: u32,
+}
+
+/// `("gov", "propose")`: a manager proposed a badge change.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCreated {
+    pub proposal_id: u32,
+    pub proposer: Address,
+    pub change: BadgeChange,
+    pub deadline: u32,
+}
+
+/// `("gov", "vote")`: a manager voted for a proposal.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalVoted {
+    pub proposal_id: u32,
+    pub voter: Address,
+    pub votes: u32,
+}
+
+/// `("gov", "execute")`: a proposal reached its quorum and its change was applied.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalExecuted {
+    pub proposal_id: u32,
+}
+
+/// `("gov", "propose")`: a factory manager proposed a change to the factory
+/// governance contract.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FactoryChangeProposed {
+    pub proposal_id: u32,
+    pub proposer: Address,
+    pub change: FactoryChange,
+    pub deadline: u32,
+}
+
+/// `("badge", "metadata")`: the metadata document of a badge was set.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeMetadataSet {
+    pub sender: Address,
+    pub badge_id: BadgeId,
+    pub uri: String,
+    pub hash: BytesN<32>,
+}
+
+/// `("badge", "metaclear")`: the metadata document of a badge was cleared.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeMetadataCleared {
+    pub sender: Address,
+    pub badge_id: BadgeId,
+}
+
+/// `("badge", "enabled")`: a badge of a scorer was enabled or disabled.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeEnabledSet {
+    pub sender: Address,
+    pub badge_id: BadgeId,
+    pub enabled: bool,
+}
+
+// Scorer and factory events
+
+/// `("manager", "add")`: a manager was added to a scorer or the factory.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ManagerAdded {
+    pub sender: Address,
+    pub manager: Address,
+}
+
+/// `("manager", "remove")`: a manager was removed from a scorer or the factory.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ManagerRemoved {
+    pub sender: Address,
+    pub manager: Address,
+}
+
+// Factory events
+
+/// `("init", "contract")`: the factory was initialized.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FactoryInitialized {
+    pub creator: Address,
+    pub managers: Vec<Address>,
+    pub scorer_wasm_hash: BytesN<32>,
+}
+
+/// `("scorer", "create")`: the factory created a scorer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerCreated {
+    pub creator: Address,
+    pub scorer: Address,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+}
+
+/// `("scorer", "register")`: a manager added a scorer deployed outside the factory to its registry.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerRegistered {
+    pub caller: Address,
+    pub creator: Address,
+    pub scorer: Address,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+}
+
+/// `("scorer", "import")`: a manager copied a scorer's registry entry from another factory.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerImported {
+    pub caller: Address,
+    pub source_factory: Address,
+    pub scorer: Address,
+    pub creator: Address,
+    pub created_at: u32,
+}
+
+/// `("scorer", "upgrade")`: a registered scorer reported an upgrade to the factory.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerUpgradeRecorded {
+    pub scorer: Address,
+    pub wasm_hash: BytesN<32>,
+    pub ledger: u32,
+}
+
+/// `("scorer", "remove")`: a scorer was removed from the factory.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerRemoved {
+    pub caller: Address,
+    pub scorer: Address,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+}
+
+/// `("scorer", "archive")`: a manager removed several scorers from the factory at once.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorersArchived {
+    pub caller: Address,
+    pub scorers: Vec<Address>,
+}
+
+/// `("scorer", "transfer")`: a scorer's registration moved to another creator.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerTransferred {
+    pub creator: Address,
+    pub scorer: Address,
+    pub new_creator: Address,
+}
+
+/// `("wasm_hash", "set")`: the WASM hash of new scorers changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScorerWasmHashSet {
+    pub caller: Address,
+    pub previous_wasm_hash: BytesN<32>,
+    pub wasm_hash: BytesN<32>,
+}
+
+/// `("wasm_hash", "approve")`: a scorer WASM hash was approved for upgrades.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WasmHashApproved {
+    pub caller: Address,
+    pub wasm_hash: BytesN<32>,
+}
+
+/// `("wasm_hash", "revoke")`: a scorer WASM hash approval was revoked.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WasmHashRevoked {
+    pub caller: Address,
+    pub wasm_hash: BytesN<32>,
+}
+
+/// `("wasm_hash", "gov")`: scorer WASM hash changes were handed to a factory
+/// governance contract, or back to the factory creator.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WasmHashGovernanceSet {
+    pub caller: Address,
+    pub governance: Option<Address>,
+}
+
+/// `("template", "set")`: a badge template was added or replaced.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeTemplateSet {
+    pub caller: Address,
+    pub template_id: String,
+    pub badges: Map<BadgeId, u32>,
+}
+
+/// `("template", "remove")`: a badge template was removed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeTemplateRemoved {
+    pub caller: Address,
+    pub template_id: String,
+}
+
+/// `("template", "default")`: the badge template merged into every new scorer was set or cleared.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultTemplateSet {
+    pub caller: Address,
+    pub template_id: Option<String>,
+}
+
+/// `("pause", "set")`: a group of factory operations was paused or resumed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FactoryPauseSet {
+    pub caller: Address,
+    pub operation: FactoryOperation,
+    pub paused: bool,
+}
+
+/// `("issuer", "registry")`: the issuer registry a factory answers `is_verified_issuer` from was set or cleared.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerRegistrySet {
+    pub sender: Address,
+    pub registry: Option<Address>,
+}
+
+/// `("issuer", "add")`: an issuer was added to a factory's own list of verified issuers.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifiedIssuerAdded {
+    pub caller: Address,
+    pub issuer: Address,
+}
+
+/// `("issuer", "remove")`: an issuer was removed from a factory's own list of verified issuers.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifiedIssuerRemoved {
+    pub caller: Address,
+    pub issuer: Address,
+}
+
+// Deployer events
+
+/// `("deploy", "contract")`: the deployer deployed and initialized a contract.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractDeployed {
+    pub deployer: Address,
+    pub wasm_hash: BytesN<32>,
+    pub salt: BytesN<32>,
+    pub contract: Address,
+    pub init_fn: Symbol,
+}
+
+/// `("deploy", "sac")`: the deployer deployed a Stellar Asset Contract.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetContractDeployed {
+    pub deployer: Address,
+    pub asset: Bytes,
+    pub contract: Address,
+}
+
+/// `("deploy", "bootstrap")`: the deployer set up a factory and its scorer Wasm.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnvironmentBootstrapped {
+    pub admin: Address,
+    pub factory: Address,
+    pub factory_wasm_hash: BytesN<32>,
+    pub scorer_wasm_hash: BytesN<32>,
+}
+
+/// `("allowlist", "enable")`: the deployer switched to allowlist mode.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowlistEnabled {
+    pub admin: Address,
+}
+
+/// `("allowlist", "add")`: a deployer was allowed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeployerAllowed {
+    pub deployer: Address,
+}
+
+/// `("allowlist", "remove")`: a deployer was disallowed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeployerDisallowed {
+    pub deployer: Address,
+}
+
+// Issuer registry events
+
+/// `("issuer", "list")`: an issuer was listed in an issuer registry, by its
+/// admin or by staking. `stake` is zero for admin listings.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerListed {
+    pub caller: Address,
+    pub issuer: Address,
+    pub stake: i128,
+}
+
+/// `("issuer", "revoke")`: the admin of an issuer registry revoked an issuer,
+/// forfeiting its stake, if any.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerRevoked {
+    pub caller: Address,
+    pub issuer: Address,
+    pub slashed: i128,
+    pub reason: String,
+}
+
+/// `("issuer", "unstake")`: a staked issuer left an issuer registry and got
+/// its stake back.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerUnstaked {
+    pub issuer: Address,
+    pub stake: i128,
+}
+
+// Issuer events
+
+/// `("badge", "issue")`: an issuer issued a badge to a user.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeIssued {
+    pub user: Address,
+    pub badge: String,
+}
+
+/// `("badge", "revoke")`: an issuer revoked a badge from a user.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeRevoked {
+    pub user: Address,
+    pub badge: String,
+}
+
+/// `("session", "add")`: an issuer's admin allowed a session key to issue and
+/// revoke some badges until an expiry ledger.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionKeyAdded {
+    pub key: Address,
+    pub badges: Vec<String>,
+    pub expires_at: u32,
+}
+
+/// `("session", "remove")`: an issuer's admin removed a session key.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionKeyRemoved {
+    pub key: Address,
+}
+
+/// `("claim", "root")`: an issuer's admin published the Merkle root of the claims of a badge.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimRootSet {
+    pub badge: String,
+    pub merkle_root: BytesN<32>,
+}
+
+/// `("private", "verifier")`: an issuer's admin set or removed the proof verifier of a badge.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofVerifierSet {
+    pub badge: String,
+    pub verifier: Option<Address>,
+}
+
+/// `("private", "claim")`: a badge was attested to a commitment with a verified proof.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivateBadgeClaimed {
+    pub badge: String,
+    pub commitment: BytesN<32>,
+}
+
+// Badge token events
+
+/// `("badge", "mint")`: the token of a badge was minted to a user. `caller` is
+/// the badge token itself when the user claimed the badge from the legacy root.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeMinted {
+    pub caller: Address,
+    pub user: Address,
+    pub badge_id: BadgeId,
+}
+
+/// `("badge", "legacy")`: a manager published the Merkle root of the legacy
+/// badge holders of a badge token.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRootSet {
+    pub manager: Address,
+    pub root: BytesN<32>,
+}
+
+/// `("badge", "legclear")`: a manager removed the Merkle root of the legacy
+/// badge holders of a badge token.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRootCleared {
+    pub manager: Address,
+}
+
+/// `("badge", "burn")`: the token of a badge was burned from a user.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeBurned {
+    pub caller: Address,
+    pub user: Address,
+    pub badge_id: BadgeId,
+}
+
+// Quest events
+
+/// `("quest", "add")`: a manager added or replaced a quest.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuestSet {
+    pub manager: Address,
+    pub quest: String,
+}
+
+/// `("quest", "remove")`: a manager removed a quest.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuestRemoved {
+    pub manager: Address,
+    pub quest: String,
+}
+
+// Reward distributor events
+
+/// `("epoch", "create")`: a manager opened a reward epoch for a scorer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochCreated {
+    pub scorer: Address,
+    pub epoch: u32,
+    pub manager: Address,
+    pub token: Address,
+}
+
+/// `("epoch", "scores")`: a manager recorded snapshot scores of an epoch.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoresRecorded {
+    pub scorer: Address,
+    pub epoch: u32,
+    pub manager: Address,
+    pub users: u32,
+    pub total_score: u64,
+}
+
+/// `("epoch", "fund")`: a funder added tokens to the pool of an epoch.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochFunded {
+    pub scorer: Address,
+    pub epoch: u32,
+    pub funder: Address,
+    pub amount: i128,
+}
+
+/// `("epoch", "finalize")`: a manager closed an epoch, opening its claims.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochFinalized {
+    pub scorer: Address,
+    pub epoch: u32,
+    pub pool: i128,
+    pub total_score: u64,
+}
+
+/// `("reward", "claim")`: a user claimed the reward of an epoch.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardClaimed {
+    pub scorer: Address,
+    pub epoch: u32,
+    pub user: Address,
+    pub amount: i128,
+}
+
+/// `("reward", "allocate")`: a manager set aside a reward for a user to claim before it expires.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardAllocated {
+    pub id: u32,
+    pub scorer: Address,
+    pub user: Address,
+    pub sponsor: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub expires_at: u32,
+}
+
+/// `("reward", "collect")`: a user claimed a pending reward.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingRewardClaimed {
+    pub id: u32,
+    pub user: Address,
+    pub amount: i128,
+}
+
+/// `("reward", "reclaim")`: the sponsor of an expired pending reward took it back.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardReclaimed {
+    pub id: u32,
+    pub sponsor: Address,
+    pub amount: i128,
+}
+
+// Staking events
+
+/// `("stake", "lock")`: a member locked tokens, or added to its stake.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeLocked {
+    pub user: Address,
+    pub amount: i128,
+    pub total: i128,
+    pub unlock_ledger: u32,
+}
+
+/// `("stake", "withdraw")`: a member withdrew its unlocked stake.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeWithdrawn {
+    pub user: Address,
+    pub amount: i128,
+}
+
+/// `("stake", "slash")`: a manager slashed the stake of a member.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeSlashed {
+    pub manager: Address,
+    pub user: Address,
+    pub amount: i128,
+    pub reason: String,
+}
+
+// Price adapter events
+
+/// `("oracle", "set")`: the admin changed the oracle of a price adapter or its maximum price age.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceOracleSet {
+    pub admin: Address,
+    pub oracle: Address,
+    pub max_age: u64,
+}
+
+/// `("fee", "pay")`: a payer paid a fee denominated in USD.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeePaid {
+    pub payer: Address,
+    pub asset: Address,
+    pub usd_amount: i128,
+    pub amount: i128,
+    pub recipient: Address,
+}
+
+// Delegation registry events
+
+/// `("delegate", "set")`: a user delegated to another address, for one scorer
+/// or globally when `scorer` is `None`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegationSet {
+    pub delegator: Address,
+    pub scorer: Option<Address>,
+    pub delegatee: Address,
+}
+
+/// `("delegate", "remove")`: a user removed a delegation.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegationRemoved {
+    pub delegator: Address,
+    pub scorer: Option<Address>,
+}
+
+// Policy account events
+
+/// `("signers", "set")`: the signers of a policy account or its default threshold changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignersSet {
+    pub signers: Vec<BytesN<32>>,
+    pub threshold: u32,
+}
+
+/// `("policy", "set")`: a policy account added or replaced a policy.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicySet {
+    pub name: Symbol,
+    pub functions: Vec<Symbol>,
+    pub threshold: u32,
+    pub daily_limit: u32,
+}
+
+/// `("policy", "remove")`: a policy account removed a policy.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyRemoved {
+    pub name: Symbol,
+}
+
+// Timelock events
+
+/// `("timelock", "schedule")`: the admin of a timelock scheduled a call.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallScheduled {
+    pub id: u32,
+    pub contract: Address,
+    pub fn_name: Symbol,
+    pub ready_at: u32,
+}
+
+/// `("timelock", "execute")`: a scheduled call was executed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallExecuted {
+    pub id: u32,
+}
+
+/// `("timelock", "cancel")`: the admin of a timelock cancelled a scheduled call.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallCancelled {
+    pub id: u32,
+}
+
+/// `("timelock", "admin")`: the admin of a timelock changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimelockAdminSet {
+    pub previous_admin: Address,
+    pub admin: Address,
+}
+
+// Multisig events
+
+/// `("multisig", "propose")`: an owner of a multisig proposed an action.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActionProposed {
+    pub id: u32,
+    pub proposer: Address,
+}
+
+/// `("multisig", "approve")`: an owner of a multisig approved a proposed action.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActionApproved {
+    pub id: u32,
+    pub owner: Address,
+}
+
+/// `("multisig", "execute")`: a multisig executed an approved action.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActionExecuted {
+    pub id: u32,
+}
+
+/// `("multisig", "owners")`: the owners of a multisig or its threshold changed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnersSet {
+    pub owners: Vec<Address>,
+    pub threshold: u32,
+}
+
+// Upgrade coordinator events
+
+/// `("rollout", "create")`: the admin of an upgrade coordinator created a rollout.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolloutCreated {
+    pub id: u32,
+    pub wasm_hash: BytesN<32>,
+    pub previous_wasm_hash: BytesN<32>,
+    pub waves: u32,
+}
+
+/// `("rollout", "wave")`: a wave of a rollout was upgraded; the rollout halts
+/// when `failed` is not 0.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaveUpgraded {
+    pub id: u32,
+    pub wave: u32,
+    pub upgraded: u32,
+    pub failed: u32,
+}
+
+/// `("rollout", "halt")`: the admin of an upgrade coordinator halted a rollout.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolloutHalted {
+    pub id: u32,
+}
+
+/// `("rollout", "resume")`: the admin of an upgrade coordinator resumed a halted rollout.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolloutResumed {
+    pub id: u32,
+}
+
+/// `("rollout", "rollback")`: the scorers a rollout upgraded were upgraded back
+/// to the previous WASM; `failed` counts those that could not be.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolloutRolledBack {
+    pub id: u32,
+    pub rolled_back: u32,
+    pub failed: u32,
+}
+
+// Attestation bridge events
+
+/// `("bridge", "relayer")`: the admin registered or unregistered a relayer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayerSet {
+    pub admin: Address,
+    pub relayer: Address,
+    pub enabled: bool,
+}
+
+/// `("bridge", "schema")`: the admin mapped an EAS schema of an EVM chain to a
+/// badge, or unmapped it when `badge` is `None`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaMapped {
+    pub admin: Address,
+    pub chain_id: u64,
+    pub schema: BytesN<32>,
+    pub badge: Option<String>,
+}
+
+/// `("bridge", "import")`: a relayer imported an EVM attestation as a badge.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttestationImported {
+    pub relayer: Address,
+    pub chain_id: u64,
+    pub uid: BytesN<32>,
+    pub recipient: BytesN<20>,
+    pub user: Address,
+    pub badge: String,
+}
+
+/// `("bridge", "revoke")`: a relayer reported that an imported attestation was
+/// revoked on its EVM chain.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportRevoked {
+    pub relayer: Address,
+    pub chain_id: u64,
+    pub uid: BytesN<32>,
+    pub user: Address,
+    pub badge: String,
+}
+
+// Catalog
+
+// Scorer events carry the event schema version before their topic
+#[derive(Clone, Copy, PartialEq)]
+enum Origin {
+    Scorer,
+    Contract,
+}
+
+macro_rules! event_catalog {
+    ($($event:ident => $($origin:ident($topic:ident, $action:literal)),+;)*) => {
+        /// Every event published by the contracts, by payload.
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub enum Event {
+            $($event($event),)*
+        }
+
+        impl Event {
+            /// Decodes an event from the topics and data it was published with
+            ///
+            /// Returns `None` for topics outside the catalog, scorer events of another
+            /// schema version, and data that does not convert to the payload of the
+            /// topics. A map with other fields than the payload fails in the host.
+            pub fn decode(env: &Env, topics: &Vec<Val>, data: Val) -> Option<Event> {
+                let (origin, topic, action) = event_key(env, topics)?;
+                $(
+                    if $((origin == Origin::$origin && topic == $topic && action == symbol_short!($action)))||+ {
+                        return $event::try_from_val(env, &data).ok().map(Event::$event);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+event_catalog! {
+    ScorerInitialized => Scorer(TOPIC_INIT, "contract");
+    ScorerUpgraded => Scorer(TOPIC_UPGRADE, "wasm");
+    ScorerMigrated => Scorer(TOPIC_UPGRADE, "migrate");
+    FactoryMigrated => Contract(TOPIC_INIT, "migrate");
+    UpgradeGuardEnabled => Scorer(TOPIC_UPGRADE, "guard");
+    ExtensionSet => Scorer(TOPIC_STATUS, "extension");
+    ArchivedSet => Scorer(TOPIC_STATUS, "archived");
+    OwnershipTransferred => Scorer(TOPIC_OWNER, "transfer");
+    UserJoined => Scorer(TOPIC_USER, "add");
+    UserLeft => Scorer(TOPIC_USER, "remove");
+    UserBanned => Scorer(TOPIC_USER, "ban");
+    UserUnbanned => Scorer(TOPIC_USER, "unban");
+    RejoinCooldownSet => Scorer(TOPIC_USER, "cooldown");
+    JoinApprovalSet => Scorer(TOPIC_USER, "approval");
+    JoinDepositSet => Scorer(TOPIC_USER, "deposit");
+    FeeSwapSet => Scorer(TOPIC_USER, "feeswap");
+    MembershipTermsSet => Scorer(TOPIC_USER, "terms");
+    MembershipRenewed => Scorer(TOPIC_USER, "renew");
+    MembershipExpired => Scorer(TOPIC_USER, "expire");
+    JoinRequested => Scorer(TOPIC_USER, "request");
+    JoinRejected => Scorer(TOPIC_USER, "reject");
+    EscrowHeld => Scorer(TOPIC_ESCROW, "hold");
+    EscrowSwapped => Scorer(TOPIC_ESCROW, "swap");
+    EscrowReleased => Scorer(TOPIC_ESCROW, "release");
+    EscrowRefunded => Scorer(TOPIC_ESCROW, "refund");
+    BadgeAdded => Scorer(TOPIC_BADGE, "add");
+    BadgeRemoved => Scorer(TOPIC_BADGE, "remove");
+    BadgeProposed => Scorer(TOPIC_BADGE, "propose");
+    BadgeProposalRejected => Scorer(TOPIC_BADGE, "reject");
+    BadgesImported => Scorer(TOPIC_BADGE, "import");
+    TiersSet => Scorer(TOPIC_SCORE, "tiers");
+    PersonhoodSet => Scorer(TOPIC_STATUS, "person");
+    AttestationChallenged => Scorer(TOPIC_DISPUTE, "open");
+    ChallengeResolved => Scorer(TOPIC_DISPUTE, "resolve");
+    BadgeScoreSet => Scorer(TOPIC_BADGE, "score");
+    GovernanceSet => Scorer(TOPIC_GOVERNANCE, "config"), Contract(TOPIC_GOVERNANCE, "config");
+    ProposalCreated => Scorer(TOPIC_GOVERNANCE, "propose");
+    FactoryChangeProposed => Contract(TOPIC_GOVERNANCE, "propose");
+    ProposalVoted => Scorer(TOPIC_GOVERNANCE, "vote"), Contract(TOPIC_GOVERNANCE, "vote");
+    ProposalExecuted => Scorer(TOPIC_GOVERNANCE, "execute"), Contract(TOPIC_GOVERNANCE, "execute");
+    BadgeMetadataSet => Scorer(TOPIC_BADGE, "metadata");
+    BadgeMetadataCleared => Scorer(TOPIC_BADGE, "metaclear");
+    BadgeEnabledSet => Scorer(TOPIC_BADGE, "enabled");
+    ManagerAdded => Scorer(TOPIC_MANAGER, "add"), Contract(TOPIC_MANAGER, "add");
+    ManagerRemoved => Scorer(TOPIC_MANAGER, "remove"), Contract(TOPIC_MANAGER, "remove");
+    FactoryInitialized => Contract(TOPIC_INIT, "contract");
+    ScorerCreated => Contract(TOPIC_SCORER, "create");
+    ScorerRegistered => Contract(TOPIC_SCORER, "register");
+    ScorerImported => Contract(TOPIC_SCORER, "import");
+    ScorerUpgradeRecorded => Contract(TOPIC_SCORER, "upgrade");
+    ScorerRemoved => Contract(TOPIC_SCORER, "remove");
+    ScorersArchived => Contract(TOPIC_SCORER, "archive");
+    ScorerTransferred => Contract(TOPIC_SCORER, "transfer");
+    ScorerWasmHashSet => Contract(TOPIC_WASM_HASH, "set");
+    WasmHashApproved => Contract(TOPIC_WASM_HASH, "approve");
+    WasmHashRevoked => Contract(TOPIC_WASM_HASH, "revoke");
+    WasmHashGovernanceSet => Contract(TOPIC_WASM_HASH, "gov");
+    BadgeTemplateSet => Contract(TOPIC_TEMPLATE, "set");
+    BadgeTemplateRemoved => Contract(TOPIC_TEMPLATE, "remove");
+    DefaultTemplateSet => Contract(TOPIC_TEMPLATE, "default");
+    FactoryPauseSet => Contract(TOPIC_PAUSE, "set");
+    IssuerRegistrySet => Contract(TOPIC_ISSUER, "registry");
+    VerifiedIssuerAdded => Contract(TOPIC_ISSUER, "add");
+    VerifiedIssuerRemoved => Contract(TOPIC_ISSUER, "remove");
+    IssuerListed => Contract(TOPIC_ISSUER, "list");
+    IssuerRevoked => Contract(TOPIC_ISSUER, "revoke");
+    IssuerUnstaked => Contract(TOPIC_ISSUER, "unstake");
+    BadgeIssued => Contract(TOPIC_BADGE, "issue");
+    BadgeRevoked => Contract(TOPIC_BADGE, "revoke");
+    SessionKeyAdded => Contract(TOPIC_SESSION, "add");
+    SessionKeyRemoved => Contract(TOPIC_SESSION, "remove");
+    ClaimRootSet => Contract(TOPIC_CLAIM, "root");
+    ProofVerifierSet => Contract(TOPIC_PRIVATE, "verifier");
+    PrivateBadgeClaimed => Contract(TOPIC_PRIVATE, "claim");
+    BadgeMinted => Contract(TOPIC_BADGE, "mint");
+    LegacyRootSet => Contract(TOPIC_BADGE, "legacy");
+    LegacyRootCleared => Contract(TOPIC_BADGE, "legclear");
+    BadgeBurned => Contract(TOPIC_BADGE, "burn");
+    QuestSet => Contract(TOPIC_QUEST, "add");
+    QuestRemoved => Contract(TOPIC_QUEST, "remove");
+    EpochCreated => Contract(TOPIC_EPOCH, "create");
+    ScoresRecorded => Contract(TOPIC_EPOCH, "scores");
+    EpochFunded => Contract(TOPIC_EPOCH, "fund");
+    EpochFinalized => Contract(TOPIC_EPOCH, "finalize");
+    RewardClaimed => Contract(TOPIC_REWARD, "claim");
+    RewardAllocated => Contract(TOPIC_REWARD, "allocate");
+    PendingRewardClaimed => Contract(TOPIC_REWARD, "collect");
+    RewardReclaimed => Contract(TOPIC_REWARD, "reclaim");
+    StakeLocked => Contract(TOPIC_STAKE, "lock");
+    StakeWithdrawn => Contract(TOPIC_STAKE, "withdraw");
+    StakeSlashed => Contract(TOPIC_STAKE, "slash");
+    PriceOracleSet => Contract(TOPIC_ORACLE, "set");
+    FeePaid => Contract(TOPIC_FEE, "pay");
+    DelegationSet => Contract(TOPIC_DELEGATE, "set");
+    DelegationRemoved => Contract(TOPIC_DELEGATE, "remove");
+    SignersSet => Contract(TOPIC_SIGNERS, "set");
+    PolicySet => Contract(TOPIC_POLICY, "set");
+    PolicyRemoved => Contract(TOPIC_POLICY, "remove");
+    CallScheduled => Contract(TOPIC_TIMELOCK, "schedule");
+    CallExecuted => Contract(TOPIC_TIMELOCK, "execute");
+    CallCancelled => Contract(TOPIC_TIMELOCK, "cancel");
+    TimelockAdminSet => Contract(TOPIC_TIMELOCK, "admin");
+    ActionProposed => Contract(TOPIC_MULTISIG, "propose");
+    ActionApproved => Contract(TOPIC_MULTISIG, "approve");
+    ActionExecuted => Contract(TOPIC_MULTISIG, "execute");
+    OwnersSet => Contract(TOPIC_MULTISIG, "owners");
+    RolloutCreated => Contract(TOPIC_ROLLOUT, "create");
+    WaveUpgraded => Contract(TOPIC_ROLLOUT, "wave");
+    RolloutHalted => Contract(TOPIC_ROLLOUT, "halt");
+    RolloutResumed => Contract(TOPIC_ROLLOUT, "resume");
+    RolloutRolledBack => Contract(TOPIC_ROLLOUT, "rollback");
+    RelayerSet => Contract(TOPIC_BRIDGE, "relayer");
+    SchemaMapped => Contract(TOPIC_BRIDGE, "schema");
+    AttestationImported => Contract(TOPIC_BRIDGE, "import");
+    ImportRevoked => Contract(TOPIC_BRIDGE, "revoke");
+    ContractDeployed => Contract(TOPIC_DEPLOY, "contract");
+    AssetContractDeployed => Contract(TOPIC_DEPLOY, "sac");
+    EnvironmentBootstrapped => Contract(TOPIC_DEPLOY, "bootstrap");
+    AllowlistEnabled => Contract(TOPIC_ALLOWLIST, "enable");
+    DeployerAllowed => Contract(TOPIC_ALLOWLIST, "add");
+    DeployerDisallowed => Contract(TOPIC_ALLOWLIST, "remove");
+}
+
+// Splits the topics of an event into its origin, topic and action
+fn event_key(env: &Env, topics: &Vec<Val>) -> Option<(Origin, Symbol, Symbol)> {
+    let symbol = |index| topics.get(index).and_then(|topic| Symbol::try_from_val(env, &topic).ok());
+    match topics.len() {
+        2 => Some((Origin::Contract, symbol(0)?, symbol(1)?)),
+        3 => {
+            let version = u32::try_from_val(env, &topics.get(0)?).ok()?;
+            if version != EVENT_SCHEMA_VERSION {
+                return None;
+            }
+            Some((Origin::Scorer, symbol(1)?, symbol(2)?))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,25 @@
+//! Merkle trees of claims, so large cohorts can claim badges with a proof of
+//! inclusion instead of an admin submitting each of them.
+//!
+//! Leaves are the SHA-256 hashes of the XDR-encoded claims. Each node is the
+//! SHA-256 hash of its two children concatenated in ascending byte order, so a
+//! proof is just the list of sibling hashes from the leaf up to the root.
+use soroban_sdk::{xdr::ToXdr, Bytes, BytesN, Env, Vec};
+
+/// Hashes a claim into a leaf of a tree.
+pub fn leaf(env: &Env, claim: impl ToXdr) -> BytesN<32> {
+    env.crypto().sha256(&claim.to_xdr(env)).to_bytes()
+}
+
+/// Hashes two nodes of a tree in ascending order, so proofs need no positions.
+pub fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a < b { (a, b) } else { (b, a) };
+    let mut bytes = Bytes::from_array(env, &first.to_array());
+    bytes.append(&Bytes::from_array(env, &second.to_array()));
+    env.crypto().sha256(&bytes).to_bytes()
+}
+
+/// Returns whether `proof` leads from `leaf` to `root`.
+pub fn verify(env: &Env, leaf: BytesN<32>, proof: &Vec<BytesN<32>>, root: &BytesN<32>) -> bool {
+    proof.iter().fold(leaf, |node, sibling| hash_pair(env, &node, &sibling)) == *root
+}
@@ -0,0 +1,127 @@
+//! WebAuthn passkey signatures, so users of passkey-based smart wallets can
+//! join scorers and claim badges without a classic Stellar keypair.
+//!
+//! A passkey acts through a passkey wallet, an account contract whose
+//! `__check_auth` checks the passkey's signatures, so its address can authorize
+//! calls like any account. Contracts can also accept a `PasskeySignature` of a
+//! `challenge` committing to the contract, the action, its arguments and the
+//! wallet's nonce in that contract, so a relayer can submit the call, and check
+//! it with `verify_wallet`, which uses the host's secp256r1 verification.
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Val, Vec};
+
+use crate::PasskeyWalletClient;
+
+/// Maximum length in bytes of the client data JSON of a passkey signature.
+pub const MAX_CLIENT_DATA_LENGTH: u32 = 1024;
+
+/// Flag of the authenticator data set when the user was present (UP).
+pub const FLAG_USER_PRESENT: u8 = 0x01;
+
+/// Flag of the authenticator data set when the user was verified (UV).
+pub const FLAG_USER_VERIFIED: u8 = 0x04;
+
+// Length of the base64url encoding, without padding, of a 32-byte challenge
+const ENCODED_CHALLENGE_LENGTH: usize = 43;
+
+// Offset of the flags in the authenticator data, after the relying party id hash,
+// which the flags and the 4-byte signature counter follow
+const FLAGS_OFFSET: u32 = 32;
+const MIN_AUTHENTICATOR_DATA_LENGTH: u32 = 37;
+
+// Start of the client data JSON of an assertion, which browsers serialize with
+// the type first and the challenge second
+const CLIENT_DATA_PREFIX: &[u8] = b"{\"type\":\"webauthn.get\",\"challenge\":\"";
+const BASE64URL_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// A WebAuthn assertion, as returned by `navigator.credentials.get`.
+#[cfg_attr(feature = "passkey", contracttype)]
+#[cfg_attr(not(feature = "passkey"), contracttype(export = false))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PasskeySignature {
+    pub authenticator_data: Bytes,
+    /// The client data JSON, whose `challenge` must be the base64url encoding of the challenge.
+    pub client_data_json: Bytes,
+    /// The `r` and `s` scalars of the secp256r1 signature, with a low `s`.
+    pub signature: BytesN<64>,
+}
+
+/// Returns the challenge a passkey signs to perform `action` with `args` in
+/// the current contract, the SHA-256 hash of their XDR encoding with `nonce`.
+pub fn challenge(env: &Env, action: Symbol, args: Vec<Val>, nonce: u32) -> BytesN<32> {
+    let preimage = (env.current_contract_address(), action, args, nonce).to_xdr(env);
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Checks that a passkey signed `challenge`
+///
+/// The client data JSON must be an assertion holding the base64url-encoded
+/// challenge, serialized as browsers do with `"type":"webauthn.get"` first and
+/// `"challenge"` second, and be at most `MAX_CLIENT_DATA_LENGTH` bytes long.
+/// The authenticator data must have the user present flag, and the user
+/// verified flag when `user_verification` is set. The signed message is the
+/// authenticator data followed by the SHA-256 hash of the client data JSON.
+///
+/// Returns false when the assertion does not match the challenge or lacks a
+/// flag. An invalid signature aborts the invocation with a host error.
+pub fn verify(
+    env: &Env,
+    public_key: &BytesN<65>,
+    challenge: &BytesN<32>,
+    signature: &PasskeySignature,
+    user_verification: bool,
+) -> bool {
+    let authenticator_data = &signature.authenticator_data;
+    if authenticator_data.len() < MIN_AUTHENTICATOR_DATA_LENGTH {
+        return false;
+    }
+    let mut required = FLAG_USER_PRESENT;
+    if user_verification {
+        required |= FLAG_USER_VERIFIED;
+    }
+    if authenticator_data.get_unchecked(FLAGS_OFFSET) & required != required {
+        return false;
+    }
+
+    let client_data = &signature.client_data_json;
+    if client_data.len() > MAX_CLIENT_DATA_LENGTH {
+        return false;
+    }
+    let mut buffer = [0u8; MAX_CLIENT_DATA_LENGTH as usize];
+    let json = &mut buffer[..client_data.len() as usize];
+    client_data.copy_into_slice(json);
+
+    // The challenge is followed by its closing quote, then by another member or the end of the object
+    let mut expected = [0u8; CLIENT_DATA_PREFIX.len() + ENCODED_CHALLENGE_LENGTH + 1];
+    expected[..CLIENT_DATA_PREFIX.len()].copy_from_slice(CLIENT_DATA_PREFIX);
+    encode_challenge(&challenge.to_array(), &mut expected[CLIENT_DATA_PREFIX.len()..][..ENCODED_CHALLENGE_LENGTH]);
+    expected[CLIENT_DATA_PREFIX.len() + ENCODED_CHALLENGE_LENGTH] = b'"';
+    if !json.starts_with(&expected) || !matches!(json.get(expected.len()), Some(b',') | Some(b'}')) {
+        return false;
+    }
+
+    let mut message = authenticator_data.clone();
+    message.append(&env.crypto().sha256(client_data).to_bytes().into());
+    env.crypto().secp256r1_verify(public_key, &env.crypto().sha256(&message), &signature.signature);
+    true
+}
+
+/// Checks that the passkey of a passkey wallet signed `challenge`, with the
+/// public key and user verification setting the wallet reports
+pub fn verify_wallet(env: &Env, wallet: &Address, challenge: &BytesN<32>, signature: &PasskeySignature) -> bool {
+    let wallet = PasskeyWalletClient::new(env, wallet);
+    verify(env, &wallet.public_key(), challenge, signature, wallet.user_verification())
+}
+
+// Encodes 32 bytes in base64url without padding
+fn encode_challenge(bytes: &[u8; 32], out: &mut [u8]) {
+    let mut written = 0;
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let bits = (group[0] as u32) << 16 | (group[1] as u32) << 8 | group[2] as u32;
+        for index in 0..=chunk.len() {
+            out[written] = BASE64URL_ALPHABET[(bits >> (18 - 6 * index) & 0x3f) as usize];
+            written += 1;
+        }
+    }
+}
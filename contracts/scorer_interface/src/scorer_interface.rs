@@ -0,0 +1,530 @@
+#![no_std]
+//! Public interface of the scorer contract.
+//!
+//! `ScorerTrait` lists the entrypoints every scorer implementation exposes.
+//! The scorer contract implements it, so the compiler keeps the two in sync,
+//! and other contracts call scorers through the generated `ScorerClient`
+//! instead of invoking them with raw `Val`s. The `events` module holds the
+//! payload types of the events of scorers, the factory and issuers, and the
+//! `passkey` module the WebAuthn verification shared by the membership
+//! contract and issuers.
+//! This crate has no contract exports, so it can be linked into any contract WASM.
+//!
+//! Types only some contracts take as arguments are exported in the contract
+//! spec of the contracts enabling their feature: `scorer` for the entrypoints
+//! of the scorer itself, `governance` for badge changes, `factory_governance`
+//! for factory changes, `factory` for factory operations and `passkey` for
+//! passkey signatures. The others link them without growing their spec.
+use soroban_sdk::{contractclient, contracttype, Address, Bytes, BytesN, Env, Map, String, Vec};
+
+pub mod events;
+pub mod merkle;
+pub mod passkey;
+
+pub use passkey::PasskeySignature;
+
+/// Identifier of a badge: its name and the address of its issuer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeId {
+    pub name: String,
+    pub issuer: Address,
+}
+
+/// Kind of a badge change that managers vote on in governance mode.
+#[cfg_attr(feature = "governance", contracttype)]
+#[cfg_attr(not(feature = "governance"), contracttype(export = false))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BadgeChangeKind {
+    /// Adds the badge with the score.
+    Add = 0,
+    /// Removes the badge; the score is ignored.
+    Remove = 1,
+    /// Changes the score of the existing badge.
+    SetScore = 2,
+}
+
+/// A change to a scorer's badges that managers vote on in governance mode.
+#[cfg_attr(feature = "governance", contracttype)]
+#[cfg_attr(not(feature = "governance"), contracttype(export = false))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeChange {
+    pub kind: BadgeChangeKind,
+    pub badge_id: BadgeId,
+    pub score: u32,
+}
+
+/// A change to a factory that its managers vote on in a factory governance contract.
+#[cfg_attr(feature = "factory_governance", contracttype)]
+#[cfg_attr(not(feature = "factory_governance"), contracttype(export = false))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FactoryChange {
+    /// Replaces the scorer WASM hash new scorers are deployed with.
+    WasmHash(BytesN<32>),
+    /// Hands scorer WASM hash changes to another governance contract, or back
+    /// to the factory creator with None.
+    Governance(Option<Address>),
+}
+
+/// Rules of governance mode: how many manager votes pass a proposal, and for
+/// how many ledgers after its creation a proposal accepts votes.
+#[cfg_attr(feature = "governance", contracttype)]
+#[cfg_attr(not(feature = "governance"), contracttype(export = false))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfig {
+    pub quorum: u32,
+    pub voting_period: u32,
+}
+
+/// Status of a user in a scorer.
+// The variants have plain comments to keep them out of the scorer spec; its
+// README lists the statuses.
+#[cfg_attr(feature = "scorer", contracttype)]
+#[cfg_attr(not(feature = "scorer"), contracttype(export = false))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum UserStatus {
+    // A member, unless its membership expired.
+    Active = 0,
+    // Left the scorer, or its membership expired.
+    Left = 1,
+    // Removed by a manager, and cannot join again until unbanned.
+    Banned = 2,
+}
+
+/// A maintenance task keepers run on a contract through `MaintenanceTrait`.
+// The variants have plain comments to keep them out of the contract specs; the
+// READMEs describe the tasks.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MaintenanceTask {
+    // Extends the TTL of the contract and of its storage entries close to archival.
+    ExtendTtl = 0,
+    // Drops index entries that no longer serve a purpose.
+    Compact = 1,
+    // Processes the state that expired, such as lapsed memberships.
+    SweepExpired = 2,
+}
+
+/// A storage record of a scorer that operators restore with `restore_entry`
+/// once its entries were archived.
+#[cfg_attr(feature = "scorer", contracttype)]
+#[cfg_attr(not(feature = "scorer"), contracttype(export = false))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArchivedEntry {
+    /// The membership of a user: their status, join ledger and expiry.
+    User(Address),
+    /// A badge: its score and whether it is disabled.
+    Badge(BadgeId),
+}
+
+/// A contract a scorer delegates part of its behavior to, registered with
+/// `set_extension`.
+// The variants have plain comments to keep them out of the scorer spec; its
+// README lists the extensions.
+#[cfg_attr(feature = "scorer", contracttype)]
+#[cfg_attr(not(feature = "scorer"), contracttype(export = false))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Extension {
+    // The factory allowed to archive the scorer.
+    Factory = 0,
+    // The registry verifying the issuers of added badges.
+    IssuerRegistry = 1,
+    // The `ScoreResolverTrait` contract computing scores instead of summing them.
+    ScoreResolver = 2,
+    // The `StakingTrait` contract whose boosts are added to scores.
+    Staking = 3,
+    // The `AnalyticsTrait` contract joins and departures are reported to.
+    Analytics = 4,
+    // The badge token mirroring attestations, burned when one is revoked.
+    BadgeToken = 5,
+    // The `MembershipTrait` contract users join through, which gates and
+    // charges joins and holds join deposits.
+    Membership = 6,
+    // The contract badge changes must come from, such as a badge governance
+    // contract where managers vote on them.
+    Governance = 7,
+    // The `DisputesTrait` contract where members challenge attestations, whose
+    // revocations no longer count towards scores.
+    Disputes = 8,
+    // The `BadgeLogTrait` contract badge additions, score changes and removals
+    // are reported to, such as a scoreboard indexing badges by issuer.
+    Scoreboard = 9,
+}
+
+/// A group of factory operations that can be paused independently.
+// The variants have plain comments to keep them out of the factory spec; its
+// README lists the operations of each group.
+#[cfg_attr(feature = "factory", contracttype)]
+#[cfg_attr(not(feature = "factory"), contracttype(export = false))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FactoryOperation {
+    // Creating scorers and adding existing ones to the registry.
+    Creation = 0,
+    // Removing scorers from the registry.
+    Removal = 1,
+    // Transferring registrations and editing badge templates.
+    Metadata = 2,
+}
+
+/// Number of ledgers, about 30 days, under which `MaintenanceTask::ExtendTtl`
+/// extends the TTL of an entry to the network maximum.
+pub const TTL_THRESHOLD: u32 = 518_400;
+
+/// Maximum length in bytes of a scorer name.
+pub const MAX_NAME_LENGTH: u32 = 256;
+
+/// Maximum length in bytes of a scorer description.
+pub const MAX_DESCRIPTION_LENGTH: u32 = 1_024;
+
+/// Maximum length in bytes of a URI, such as a scorer icon.
+pub const MAX_URI_LENGTH: u32 = 512;
+
+/// Maximum length in bytes of a badge name.
+pub const MAX_BADGE_NAME_LENGTH: u32 = 128;
+
+/// Maximum length in bytes of the reason of a challenge.
+pub const MAX_REASON_LENGTH: u32 = 512;
+
+/// Managers and users recorded when a scorer is initialized, besides the
+/// creator, who is always the first manager.
+#[cfg_attr(feature = "scorer", contracttype)]
+#[cfg_attr(not(feature = "scorer"), contracttype(export = false))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitialMembers {
+    pub managers: Vec<Address>,
+    pub users: Vec<Address>,
+}
+
+#[contractclient(name = "ScorerClient")]
+pub trait ScorerTrait {
+    /// Initializes the scorer with its creator, initial badges and metadata
+    fn initialize(env: Env, scorer_creator: Address, scorer_badges: Map<BadgeId, u32>, name: String, description: String, icon: String);
+
+    /// Initializes the scorer like `initialize`, with managers and users recorded at creation
+    fn initialize_with_members(env: Env, scorer_creator: Address, members: InitialMembers, scorer_badges: Map<BadgeId, u32>, name: String, description: String, icon: String);
+
+    /// Returns the version the scorer code declares, bumped by every release
+    fn contract_version() -> u32;
+
+    /// Upgrades the scorer's WASM code, authorized by its creator
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
+
+    /// Migrates the storage written by a previous release, authorized by the creator.
+    /// The users are migrated in batches of `limit` from `cursor`; returns the
+    /// cursor of the next batch, 0 once the migration is complete
+    fn migrate(env: Env, sender: Address, cursor: u32, limit: u32) -> u32;
+
+    /// Returns the version of the storage layout
+    fn get_storage_version(env: Env) -> u32;
+
+    /// Returns the version of the event payloads, the first topic of every event
+    fn get_event_schema_version(env: Env) -> u32;
+
+    /// Requires upgrades to be approved by `factory`
+    fn enable_upgrade_guard(env: Env, sender: Address, factory: Address);
+
+    /// Returns the factory guarding upgrades, if any
+    fn get_upgrade_guard(env: Env) -> Option<Address>;
+
+    /// Registers or clears the contract of an extension, authorized by the scorer creator
+    fn set_extension(env: Env, sender: Address, extension: Extension, address: Option<Address>);
+
+    /// Returns the contract registered for an extension, if any
+    fn get_extension(env: Env, extension: Extension) -> Option<Address>;
+
+    /// Archives or restores the scorer
+    fn set_archived(env: Env, sender: Address, archived: bool);
+
+    /// Returns whether the scorer is archived
+    fn is_archived(env: Env) -> bool;
+
+    /// Adds a manager, authorized by the scorer creator
+    fn add_manager(env: Env, sender: Address, new_manager: Address);
+
+    /// Removes a manager, authorized by the scorer creator
+    fn remove_manager(env: Env, sender: Address, manager_to_remove: Address);
+
+    /// Transfers the scorer ownership to `new_owner`
+    fn transfer_ownership(env: Env, sender: Address, new_owner: Address);
+
+    /// Registers `user` as an active user, unless users join through a membership contract
+    fn add_user(env: Env, user: Address);
+
+    /// Activates `user` unless it is a member and sets the last ledger of its membership,
+    /// authorized by the membership contract
+    fn admit_user(env: Env, sender: Address, user: Address, expires_at: Option<u32>);
+
+    /// Bans `user`, or lifts its ban with `ban` false, authorized by the membership contract
+    fn dismiss_user(env: Env, sender: Address, user: Address, ban: bool);
+
+    /// Deactivates `user`
+    fn remove_user(env: Env, user: Address);
+
+    /// Returns the last ledger at which the membership of `user` is active, if it expires
+    fn get_membership_expiry(env: Env, user: Address) -> Option<u32>;
+
+    /// Deactivates the expired members among the next `limit` users, resuming where the last call stopped, and returns how many
+    fn poke(env: Env, limit: u32) -> u32;
+
+    /// Extends the storage entries of a user or a badge to the network maximum, and returns how many were extended
+    fn restore_entry(env: Env, entry: ArchivedEntry) -> u32;
+
+    /// Returns the ledger at which `user` last joined, if recorded
+    fn get_joined_at(env: Env, user: Address) -> Option<u32>;
+
+    /// Returns the nonce the next `add_user` or `remove_user` authorization of `user` commits to
+    fn get_user_nonce(env: Env, user: Address) -> u32;
+
+    /// Returns the users and their status, reading the entries of every user
+    fn get_users(env: Env) -> Map<Address, UserStatus>;
+
+    /// Returns the number of users who ever joined
+    fn get_user_count(env: Env) -> u32;
+
+    /// Returns up to `limit` users from index `offset` and their status, empty
+    /// past the last one. Users keep the index they first joined at
+    fn get_users_page(env: Env, offset: u32, limit: u32) -> Vec<(Address, UserStatus)>;
+
+    /// Returns the status of `user`, if it ever joined
+    fn get_user_status(env: Env, user: Address) -> Option<UserStatus>;
+
+    /// Returns the badges and their scores
+    fn get_badges(env: Env) -> Map<BadgeId, u32>;
+
+    /// Excludes `badge_id` from scoring and minting while disabled, for managers
+    fn set_badge_enabled(env: Env, sender: Address, badge_id: BadgeId, enabled: bool);
+
+    /// Returns whether `badge_id` was not disabled
+    fn is_badge_enabled(env: Env, badge_id: BadgeId) -> bool;
+
+    /// Returns the managers
+    fn get_managers(env: Env) -> Vec<Address>;
+
+    /// Returns the number of managers
+    fn get_manager_count(env: Env) -> u32;
+
+    /// Returns up to `limit` managers from index `offset`, empty past the last
+    /// one. Managers are at the index they were added at, except that removing
+    /// one moves the last manager to its index
+    fn get_managers_page(env: Env, offset: u32, limit: u32) -> Vec<Address>;
+
+    /// Returns the scorer creator
+    fn get_contract_owner(env: Env) -> Address;
+
+    /// Adds a badge with its score, authorized by a manager or the governance contract
+    fn add_badge(env: Env, sender: Address, name: String, issuer: Address, score: u32);
+
+    /// Removes a badge, authorized by a manager or the governance contract
+    fn remove_badge(env: Env, sender: Address, name: String, issuer: Address);
+
+    /// Changes the score of a badge, authorized by a manager or the governance contract
+    fn set_badge_score(env: Env, sender: Address, badge_id: BadgeId, score: u32);
+
+    /// Recognizes badges of another scorer at a percentage of their score there, authorized by a manager
+    fn import_badges_from(env: Env, sender: Address, scorer: Address, badges: Vec<BadgeId>, weight: u32);
+
+    /// Returns the recognized badges of other scorers, with their scorer and weight
+    fn get_imported_badges(env: Env) -> Map<BadgeId, (Address, u32)>;
+
+    /// Returns the scorer's name, description and icon
+    fn get_metadata(env: Env) -> (String, String, String);
+
+    /// Returns the score of `user` holding `badges`
+    fn get_score(env: Env, user: Address, badges: Vec<BadgeId>) -> u32;
+
+    /// Returns whether the attestation of `badge_id` to `user` was revoked
+    fn is_attestation_revoked(env: Env, badge_id: BadgeId, user: Address) -> bool;
+}
+
+/// Interface of membership contracts, which users join a scorer through when
+/// it registers one as its `Extension::Membership`. The contract checks its
+/// own conditions, such as approval, fees or deposits, before admitting users
+/// with `admit_user`, and the scorer reports departures to it.
+#[contractclient(name = "MembershipClient")]
+pub trait MembershipTrait {
+    /// Settles what `user` locked to join now that it left, authorized by the scorer
+    fn record_departure(env: Env, user: Address);
+}
+
+/// Interface of disputes contracts, where members of a scorer that registers
+/// one as its `Extension::Disputes` challenge the attestations of its badges.
+/// The scorer ignores revoked attestations when computing scores.
+#[contractclient(name = "DisputesClient")]
+pub trait DisputesTrait {
+    /// Returns whether the attestation of `badge_id` to `user` was revoked after a challenge
+    fn is_attestation_revoked(env: Env, badge_id: BadgeId, user: Address) -> bool;
+}
+
+/// Interface of contracts following the badges of a scorer that registers one
+/// as its `Extension::Scoreboard`. The scorer reports each badge change, with
+/// `None` as the previous score of an added badge and the score of a removed one.
+#[contractclient(name = "BadgeLogClient")]
+pub trait BadgeLogTrait {
+    /// Records that `sender` changed the score of `badge_id`, authorized by the scorer
+    fn record_badge_change(env: Env, badge_id: BadgeId, previous_score: Option<u32>, score: Option<u32>, sender: Address);
+}
+
+/// Interface of score resolvers, the contracts a scorer can delegate score
+/// computation to. `attestations` maps the scorer's badges held by the user to
+/// their scores, so a resolver can weigh them as it sees fit (quadratic, capped,
+/// time-weighted, ...) instead of summing them.
+#[contractclient(name = "ScoreResolverClient")]
+pub trait ScoreResolverTrait {
+    /// Returns the score of `user` for the badges it holds
+    fn resolve(env: Env, user: Address, attestations: Map<BadgeId, u32>) -> u32;
+}
+
+/// Interface of staking contracts, where members lock tokens to boost their
+/// score. The scorer adds the boost of a member to the score of its badges.
+#[contractclient(name = "StakingClient")]
+pub trait StakingTrait {
+    /// Returns the score boost of `user` from its locked stake
+    fn get_boost(env: Env, user: Address) -> u32;
+}
+
+/// Interface of analytics contracts, which count the activity scorers and
+/// issuers report so dashboards can read growth metrics on-chain. Each report
+/// is authorized by the scorer, or by the issuer of the attested badge.
+#[contractclient(name = "AnalyticsClient")]
+pub trait AnalyticsTrait {
+    /// Counts a user joining `scorer` in the current epoch
+    fn record_join(env: Env, scorer: Address);
+
+    /// Counts a member leaving `scorer` in the current epoch
+    fn record_leave(env: Env, scorer: Address);
+
+    /// Counts an attestation of `badge`, a badge of `scorer`
+    fn record_attestation(env: Env, scorer: Address, badge: BadgeId);
+}
+
+/// Interface of price adapters, which convert fees denominated in USD to an
+/// amount of the payment asset at the oracle price of the call.
+#[contractclient(name = "PriceAdapterClient")]
+pub trait PriceAdapterTrait {
+    /// Returns the amount of `asset` worth `usd_amount`, in units of 10^-7 USD
+    fn quote(env: Env, asset: Address, usd_amount: i128) -> i128;
+
+    /// Transfers the amount of `asset` worth `usd_amount` from `payer` to `recipient`, authorized by the payer
+    fn pay(env: Env, payer: Address, asset: Address, usd_amount: i128, recipient: Address) -> i128;
+}
+
+/// The entrypoints of the scorer factory its governance contract calls. Once
+/// the contract is registered with `set_wasm_hash_governance`, the factory only
+/// takes scorer WASM hash changes from it.
+#[contractclient(name = "ScorerFactoryClient")]
+pub trait ScorerFactoryTrait {
+    /// Returns the factory creator
+    fn get_contract_creator(env: Env) -> Address;
+
+    /// Returns the factory managers
+    fn get_managers(env: Env) -> Vec<Address>;
+
+    /// Replaces the scorer WASM hash new scorers are deployed with
+    fn set_scorer_wasm_hash(env: Env, caller: Address, scorer_wasm_hash: BytesN<32>);
+
+    /// Hands scorer WASM hash changes to another governance contract, or back to the factory creator with None
+    fn set_wasm_hash_governance(env: Env, caller: Address, governance: Option<Address>);
+}
+
+/// Interface of passkey wallets, the account contracts passkey users act through.
+#[contractclient(name = "PasskeyWalletClient")]
+pub trait PasskeyWalletTrait {
+    /// Returns the SEC-1 encoded uncompressed secp256r1 public key of the wallet's passkey
+    fn public_key(env: Env) -> BytesN<65>;
+
+    /// Returns whether the wallet only accepts assertions with the user verified flag
+    fn user_verification(env: Env) -> bool;
+}
+
+/// Interface of AMMs the join fee is swapped through, so users can pay it in
+/// an asset they hold.
+#[contractclient(name = "SwapClient")]
+pub trait SwapTrait {
+    /// Transfers exactly `amount_out` of `asset_out` to `to` for at most `max_amount_in` of `asset_in`
+    /// from `from`, authorized by `from`, and returns the amount of `asset_in` spent
+    fn swap_exact_out(
+        env: Env,
+        from: Address,
+        asset_in: Address,
+        asset_out: Address,
+        amount_out: i128,
+        max_amount_in: i128,
+        to: Address,
+    ) -> i128;
+}
+
+/// Interface of delegation registries, where users delegate their score and
+/// votes to another address, for one scorer or for every scorer at once.
+#[contractclient(name = "DelegationRegistryClient")]
+pub trait DelegationRegistryTrait {
+    /// Returns the address `user` delegated to for `scorer`, falling back to its global delegation
+    fn get_delegate(env: Env, user: Address, scorer: Address) -> Option<Address>;
+
+    /// Follows the delegations of `user` for `scorer` and returns the last address of the chain
+    fn resolve(env: Env, user: Address, scorer: Address) -> Address;
+}
+
+/// Interface of the periodic maintenance of the scorer and the factory, so a
+/// single keeper can service every Trustful contract the same way. Anyone can
+/// call it. A task walks the contract's records from `cursor` in batches of
+/// `limit`, each call returning the cursor of the next batch, or 0 once the
+/// task is complete; tasks with nothing to do on a contract complete at once.
+#[contractclient(name = "MaintenanceClient")]
+pub trait MaintenanceTrait {
+    /// Runs a batch of `task` from `cursor` and returns the cursor of the next batch, or 0 when done
+    fn maintenance(env: Env, task: MaintenanceTask, cursor: u32, limit: u32) -> u32;
+}
+
+/// Interface of personhood contracts, such as identity or proof-of-personhood
+/// registries, that membership contracts can require users to be verified by
+/// before they join, as a sybil-resistance option.
+#[contractclient(name = "PersonhoodClient")]
+pub trait PersonhoodTrait {
+    /// Returns whether `user` holds a valid personhood attestation
+    fn is_verified_person(env: Env, user: Address) -> bool;
+}
+
+/// Interface of issuer registries, the contracts a scorer can register as its
+/// `Extension::IssuerRegistry` to check that a badge issuer is verified.
+#[contractclient(name = "IssuerRegistryClient")]
+pub trait IssuerRegistryTrait {
+    /// Returns whether `issuer` is verified
+    fn is_verified_issuer(env: Env, issuer: Address) -> bool;
+}
+
+/// Interface of badge tokens, the non-transferable tokens mirroring the badges
+/// attested to the members of a scorer. The disputes contract burns the token
+/// of an attestation it revokes.
+#[contractclient(name = "BadgeTokenClient")]
+pub trait BadgeTokenTrait {
+    /// Mints the token of `badge_id` to `user`, authorized by the scorer or the badge issuer
+    fn mint_badge(env: Env, caller: Address, user: Address, badge_id: BadgeId);
+
+    /// Burns the token of `badge_id` from `user`, authorized by the scorer, its disputes contract or the badge issuer
+    fn burn_badge(env: Env, caller: Address, user: Address, badge_id: BadgeId);
+
+    /// Returns whether `user` holds the token of `badge_id`
+    fn holds_badge(env: Env, user: Address, badge_id: BadgeId) -> bool;
+
+    /// Returns the badges whose tokens `user` holds
+    fn get_badges(env: Env, user: Address) -> Vec<BadgeId>;
+
+    /// Returns the number of users holding the token of `badge_id`
+    fn get_holders_count(env: Env, badge_id: BadgeId) -> u32;
+}
+
+/// Interface of proof verifiers, the contracts that check zero-knowledge
+/// proofs that the holder of a secret qualifies for a badge, such as holding a
+/// balance above a threshold, without revealing the holder's account. The
+/// proof binds `commitment`, which the attestation is recorded under, and
+/// `nullifier`, which is derived from the secret so each holder claims once.
+#[contractclient(name = "ProofVerifierClient")]
+pub trait ProofVerifierTrait {
+    /// Returns whether `proof` shows the holder of `commitment` qualifies for `badge` of `issuer`
+    fn verify(env: Env, issuer: Address, badge: String, commitment: BytesN<32>, nullifier: BytesN<32>, proof: Bytes) -> bool;
+}
@@ -0,0 +1,379 @@
+#![no_std]
+use scorer_interface::{events, ScorerClient, StakingTrait, UserStatus};
+use scorer_interface::events::{TOPIC_STAKE};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token, Address, Env, String};
+
+/// How stakes boost scores.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakingConfig {
+    /// The token members lock.
+    pub token: Address,
+    /// The locked amount worth one point of boost.
+    pub amount_per_point: i128,
+    /// The highest boost a stake can give.
+    pub max_boost: u32,
+}
+
+/// The locked stake of a member.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stake {
+    /// The locked amount.
+    pub amount: i128,
+    /// The ledger from which the stake can be withdrawn and stops boosting the score.
+    pub unlock_ledger: u32,
+}
+
+#[contracttype]
+enum DataKey {
+    Scorer,
+    Config,
+    Stake(Address),
+}
+
+/// Errors raised by the staking contract.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    /// The scorer is missing from storage.
+    ScorerNotFound = 2,
+    /// The caller is neither the scorer nor one of its managers.
+    Unauthorized = 3,
+    /// The amount, duration or amount per point is not positive.
+    InvalidAmount = 4,
+    /// The user is not an active member of the scorer.
+    NotAMember = 5,
+    /// The user has no stake.
+    StakeNotFound = 6,
+    /// The stake is still locked.
+    StakeLocked = 7,
+}
+
+/// Stakes members of a scorer lock to boost their score.
+///
+/// Members lock the token for a number of ledgers. While the stake is locked,
+/// the scorer adds its boost, one point per `amount_per_point` up to
+/// `max_boost`, to the member's score through `StakingTrait`. Once unlocked,
+/// the stake stops counting and can be withdrawn. The managers of the scorer,
+/// or the scorer itself, can slash the stake of a member they ban, forfeiting
+/// it to the scorer creator.
+#[contract]
+pub struct StakingContract;
+
+#[contractimpl]
+impl StakingContract {
+    /// Initializes the staking of a scorer
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `scorer` - The scorer whose members stake
+    /// * `config` - The staked token and how stakes convert to boosts
+    ///
+    /// # Panics
+    /// * When the scorer owner fails authentication
+    /// * When the contract is already initialized (`Error::ContractAlreadyInitialized`)
+    /// * When the amount per point is not positive (`Error::InvalidAmount`)
+    pub fn initialize(env: Env, scorer: Address, config: StakingConfig) {
+        if env.storage().persistent().has(&DataKey::Scorer) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        // Only the scorer owner can attach staking boosts to its scores
+        ScorerClient::new(&env, &scorer).get_contract_owner().require_auth();
+
+        if config.amount_per_point <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+
+        env.storage().persistent().set(&DataKey::Scorer, &scorer);
+        env.storage().persistent().set(&DataKey::Config, &config);
+    }
+
+    /// Returns the scorer whose members stake
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::ScorerNotFound`)
+    pub fn get_scorer(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Scorer)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    /// Returns the staked token and how stakes convert to boosts
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::ScorerNotFound`)
+    pub fn get_config(env: Env) -> StakingConfig {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Config)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ScorerNotFound))
+    }
+
+    /// Locks tokens of a member, adding to its stake
+    ///
+    /// The stake stays locked until the later of its current unlock ledger and
+    /// `duration` ledgers from now.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The member staking
+    /// * `amount` - The amount of the token to lock
+    /// * `duration` - The number of ledgers to lock the stake for
+    ///
+    /// # Panics
+    /// * When the user fails authentication
+    /// * When the amount or duration is not positive (`Error::InvalidAmount`)
+    /// * When the user is not an active member of the scorer (`Error::NotAMember`)
+    pub fn stake(env: Env, user: Address, amount: i128, duration: u32) {
+        user.require_auth();
+
+        if amount <= 0 || duration == 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+
+        let scorer = ScorerClient::new(&env, &Self::get_scorer(env.clone()));
+        if scorer.get_user_status(&user) != Some(UserStatus::Active) {
+            panic_with_error!(&env, Error::NotAMember);
+        }
+
+        let config = Self::get_config(env.clone());
+        token::Client::new(&env, &config.token).transfer(&user, &env.current_contract_address(), &amount);
+
+        let unlock_ledger = env.ledger().sequence() + duration;
+        let stake = match Self::get_stake(env.clone(), user.clone()) {
+            Some(stake) => Stake { amount: stake.amount + amount, unlock_ledger: stake.unlock_ledger.max(unlock_ledger) },
+            None => Stake { amount, unlock_ledger },
+        };
+        env.storage().persistent().set(&DataKey::Stake(user.clone()), &stake);
+
+        env.events().publish(
+            (TOPIC_STAKE, symbol_short!("lock")),
+            events::StakeLocked { user, amount, total: stake.amount, unlock_ledger: stake.unlock_ledger },
+        );
+    }
+
+    /// Returns the stake of a member once it is unlocked
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The member withdrawing
+    ///
+    /// # Returns
+    /// * `i128` - The amount withdrawn
+    ///
+    /// # Panics
+    /// * When the user fails authentication
+    /// * When the user has no stake (`Error::StakeNotFound`)
+    /// * When the stake is still locked (`Error::StakeLocked`)
+    pub fn withdraw(env: Env, user: Address) -> i128 {
+        user.require_auth();
+
+        let stake = Self::get_stake(env.clone(), user.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::StakeNotFound));
+        if env.ledger().sequence() < stake.unlock_ledger {
+            panic_with_error!(&env, Error::StakeLocked);
+        }
+
+        env.storage().persistent().remove(&DataKey::Stake(user.clone()));
+        let config = Self::get_config(env.clone());
+        token::Client::new(&env, &config.token).transfer(&env.current_contract_address(), &user, &stake.amount);
+
+        env.events().publish(
+            (TOPIC_STAKE, symbol_short!("withdraw")),
+            events::StakeWithdrawn { user, amount: stake.amount },
+        );
+        stake.amount
+    }
+
+    /// Slashes the stake of a banned member, transferring it to the scorer creator
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - The scorer or one of its managers
+    /// * `user` - The member whose stake is slashed
+    /// * `reason` - Why the member is banned
+    ///
+    /// # Panics
+    /// * When the caller fails authentication or is neither the scorer nor a manager (`Error::Unauthorized`)
+    /// * When the user has no stake (`Error::StakeNotFound`)
+    pub fn slash(env: Env, caller: Address, user: Address, reason: String) {
+        caller.require_auth();
+
+        let scorer_address = Self::get_scorer(env.clone());
+        let scorer = ScorerClient::new(&env, &scorer_address);
+        if caller != scorer_address && !scorer.get_managers().contains(&caller) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        let stake = Self::get_stake(env.clone(), user.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::StakeNotFound));
+        env.storage().persistent().remove(&DataKey::Stake(user.clone()));
+        let config = Self::get_config(env.clone());
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &scorer.get_contract_owner(),
+            &stake.amount,
+        );
+
+        env.events().publish(
+            (TOPIC_STAKE, symbol_short!("slash")),
+            events::StakeSlashed { manager: caller, user, amount: stake.amount, reason },
+        );
+    }
+
+    /// Returns the stake of a member, if any
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The member to check
+    pub fn get_stake(env: Env, user: Address) -> Option<Stake> {
+        env.storage().persistent().get(&DataKey::Stake(user))
+    }
+}
+
+#[contractimpl]
+impl StakingTrait for StakingContract {
+    /// Returns the score boost of a member, 0 once its stake is unlocked
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `user` - The member to check
+    fn get_boost(env: Env, user: Address) -> u32 {
+        let stake = match Self::get_stake(env.clone(), user) {
+            Some(stake) if env.ledger().sequence() < stake.unlock_ledger => stake,
+            _ => return 0,
+        };
+
+        let config = Self::get_config(env);
+        let points = stake.amount / config.amount_per_point;
+        points.min(config.max_boost as i128) as u32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{BadgeId, Extension, ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{Map, Vec};
+    use test_utils::{EventRecord, EventSnapshot};
+
+    struct Setup {
+        env: Env,
+        manager: Address,
+        user: Address,
+        token: Address,
+        scorer: ScorerContractClient<'static>,
+        client: StakingContractClient<'static>,
+    }
+
+    // A scorer counting stakes, with an active user holding 1000 of the staked
+    // token; every 100 staked is worth a point, up to 5
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let manager = Address::generate(&env);
+        let scorer = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        scorer.initialize(
+            &manager,
+            &Map::new(&env),
+            &String::from_str(&env, "Community"),
+            &String::from_str(&env, "A community"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let client = StakingContractClient::new(&env, &env.register(StakingContract, ()));
+        client.initialize(&scorer.address, &StakingConfig { token: token.clone(), amount_per_point: 100, max_boost: 5 });
+        scorer.set_extension(&manager, &Extension::Staking, &Some(client.address.clone()));
+
+        let user = Address::generate(&env);
+        scorer.add_user(&user);
+        token::StellarAssetClient::new(&env, &token).mint(&user, &1000);
+
+        Setup { env, manager, user, token, scorer, client }
+    }
+
+    #[test]
+    fn test_stake_boosts_score() {
+        let Setup { env, user, token, scorer, client, .. } = setup();
+        let no_badges: Vec<BadgeId> = Vec::new(&env);
+
+        client.stake(&user, &250, &100);
+        assert_eq!(client.get_boost(&user), 2);
+        assert_eq!(scorer.get_score(&user, &no_badges), 2);
+
+        // Adding to the stake keeps the later unlock ledger, and the boost is capped
+        client.stake(&user, &750, &10);
+        let unlock_ledger = env.ledger().sequence() + 100;
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_STAKE, symbol_short!("lock")),
+            events::StakeLocked { user: user.clone(), amount: 750, total: 1000, unlock_ledger },
+        )]);
+        assert_eq!(client.get_stake(&user), Some(Stake { amount: 1000, unlock_ledger }));
+        assert_eq!(scorer.get_score(&user, &no_badges), 5);
+
+        assert_eq!(client.try_withdraw(&user), Err(Ok(Error::StakeLocked.into())));
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number = unlock_ledger);
+        assert_eq!(scorer.get_score(&user, &no_badges), 0);
+        assert_eq!(client.withdraw(&user), 1000);
+        assert_eq!(token::Client::new(&env, &token).balance(&user), 1000);
+        assert_eq!(client.get_stake(&user), None);
+        assert_eq!(client.try_withdraw(&user), Err(Ok(Error::StakeNotFound.into())));
+    }
+
+    #[test]
+    fn test_slash_stake() {
+        let Setup { env, manager, user, token, scorer, client } = setup();
+        let reason = String::from_str(&env, "Spam");
+
+        client.stake(&user, &500, &100);
+        client.slash(&manager, &user, &reason);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_STAKE, symbol_short!("slash")),
+            events::StakeSlashed { manager: manager.clone(), user: user.clone(), amount: 500, reason: reason.clone() },
+        )]);
+
+        assert_eq!(client.get_stake(&user), None);
+        assert_eq!(scorer.get_score(&user, &Vec::new(&env)), 0);
+        assert_eq!(token::Client::new(&env, &token).balance(&manager), 500);
+
+        assert_eq!(client.try_slash(&manager, &user, &reason), Err(Ok(Error::StakeNotFound.into())));
+        assert_eq!(client.try_slash(&user, &user, &reason), Err(Ok(Error::Unauthorized.into())));
+    }
+
+    #[test]
+    fn test_stake_rejections() {
+        let Setup { env, user, client, .. } = setup();
+
+        assert_eq!(client.try_stake(&user, &0, &100), Err(Ok(Error::InvalidAmount.into())));
+        assert_eq!(client.try_stake(&user, &100, &0), Err(Ok(Error::InvalidAmount.into())));
+        assert_eq!(client.try_stake(&Address::generate(&env), &100, &100), Err(Ok(Error::NotAMember.into())));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_initialize_twice() {
+        let Setup { scorer, token, client, .. } = setup();
+
+        client.initialize(&scorer.address, &StakingConfig { token, amount_per_point: 1, max_boost: 1 });
+    }
+}
@@ -0,0 +1,168 @@
+//! Snapshot assertions for contract events.
+//!
+//! Events are decoded into XDR values, so expected and captured events are
+//! compared structurally and mismatches are printed as a readable diff.
+
+use core::fmt;
+use soroban_sdk::{testutils::Events, xdr::ScVal, Address, Env, IntoVal, TryFromVal, Val, Vec};
+
+/// A contract event with decoded topics and data.
+#[derive(Clone, PartialEq)]
+pub struct EventRecord {
+    pub contract: Address,
+    pub topics: ScVal,
+    pub data: ScVal,
+}
+
+impl EventRecord {
+    /// Builds the expected record of an event published by `contract` as
+    /// `env.events().publish(topics, data)`.
+    pub fn new<T, D>(env: &Env, contract: &Address, topics: T, data: D) -> Self
+    where
+        T: IntoVal<Env, Vec<Val>>,
+        D: IntoVal<Env, Val>,
+    {
+        let topics: Vec<Val> = topics.into_val(env);
+        Self::decode(env, contract.clone(), topics, data.into_val(env))
+    }
+
+    fn decode(env: &Env, contract: Address, topics: Vec<Val>, data: Val) -> Self {
+        EventRecord {
+            contract,
+            topics: ScVal::try_from_val(env, &topics.to_val()).expect("event topics are not valid XDR"),
+            data: ScVal::try_from_val(env, &data).expect("event data is not valid XDR"),
+        }
+    }
+}
+
+impl fmt::Debug for EventRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {:?} => {:?}", self.contract, self.topics, self.data)
+    }
+}
+
+/// The events published by contracts, in publication order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventSnapshot {
+    pub events: std::vec::Vec<EventRecord>,
+}
+
+impl EventSnapshot {
+    /// Captures the events currently recorded by `env`.
+    ///
+    /// The test environment only keeps the events of the last contract call,
+    /// so capture them right after the call that publishes them.
+    pub fn capture(env: &Env) -> Self {
+        let events = env
+            .events()
+            .all()
+            .iter()
+            .map(|(contract, topics, data)| EventRecord::decode(env, contract, topics, data))
+            .collect();
+        EventSnapshot { events }
+    }
+
+    /// Keeps only the events published by `contract`.
+    pub fn for_contract(self, contract: &Address) -> Self {
+        let events = self.events.into_iter().filter(|event| event.contract == *contract).collect();
+        EventSnapshot { events }
+    }
+
+    /// Asserts that the snapshot holds exactly `expected`, in order.
+    ///
+    /// # Panics
+    /// * With a line diff of expected and captured events when they differ
+    pub fn assert_eq(&self, expected: &[EventRecord]) {
+        if self.events.as_slice() == expected {
+            return;
+        }
+
+        let mut diff = std::string::String::from("event snapshot mismatch (- expected, + captured):\n");
+        for index in 0..expected.len().max(self.events.len()) {
+            match (expected.get(index), self.events.get(index)) {
+                (Some(expected), Some(captured)) if expected == captured => {
+                    diff.push_str(&format!("  {}: {:?}\n", index, captured));
+                }
+                (expected, captured) => {
+                    if let Some(expected) = expected {
+                        diff.push_str(&format!("- {}: {:?}\n", index, expected));
+                    }
+                    if let Some(captured) = captured {
+                        diff.push_str(&format!("+ {}: {:?}\n", index, captured));
+                    }
+                }
+            }
+        }
+        panic!("{}", diff);
+    }
+
+    /// Asserts that every event of `expected` was captured, in any order and
+    /// among other events.
+    ///
+    /// # Panics
+    /// * Listing the missing and captured events when one is missing
+    pub fn assert_contains(&self, expected: &[EventRecord]) {
+        let missing: std::vec::Vec<&EventRecord> =
+            expected.iter().filter(|event| !self.events.contains(event)).collect();
+        if missing.is_empty() {
+            return;
+        }
+
+        let mut message = std::string::String::from("events not found in snapshot:\n");
+        for event in missing {
+            message.push_str(&format!("- {:?}\n", event));
+        }
+        message.push_str("captured events:\n");
+        for (index, event) in self.events.iter().enumerate() {
+            message.push_str(&format!("  {}: {:?}\n", index, event));
+        }
+        panic!("{}", message);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{contract, contractimpl, symbol_short, testutils::Address as _};
+
+    #[contract]
+    struct EmitterContract;
+
+    #[contractimpl]
+    impl EmitterContract {
+        pub fn emit(env: Env, value: u32) {
+            env.events().publish(("emitter", symbol_short!("value")), value);
+        }
+    }
+
+    fn setup() -> (Env, Address) {
+        let env = Env::default();
+        let contract_id = env.register(EmitterContract, ());
+        let client = EmitterContractClient::new(&env, &contract_id);
+        client.emit(&1);
+        client.emit(&2);
+        (env, contract_id)
+    }
+
+    #[test]
+    fn test_snapshot_matches_published_events() {
+        let (env, contract_id) = setup();
+        let snapshot = EventSnapshot::capture(&env).for_contract(&contract_id);
+
+        snapshot.assert_contains(&[EventRecord::new(&env, &contract_id, ("emitter", symbol_short!("value")), 2_u32)]);
+        assert!(snapshot.clone().for_contract(&Address::generate(&env)).events.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "event snapshot mismatch")]
+    fn test_snapshot_mismatch_panics_with_diff() {
+        let (env, contract_id) = setup();
+
+        EventSnapshot::capture(&env).for_contract(&contract_id).assert_eq(&[EventRecord::new(
+            &env,
+            &contract_id,
+            ("emitter", symbol_short!("value")),
+            3_u32,
+        )]);
+    }
+}
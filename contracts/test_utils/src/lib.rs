@@ -0,0 +1,9 @@
+//! Test helpers shared by the Trustful contract crates.
+
+pub mod events;
+pub mod passkey;
+pub mod scorer;
+
+pub use events::{EventRecord, EventSnapshot};
+pub use passkey::Passkey;
+pub use scorer::{scorer_with_extension, ScorerWithExtension};
@@ -0,0 +1,74 @@
+//! A software passkey producing WebAuthn assertions for `scorer_interface::passkey`.
+use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+use scorer_interface::PasskeySignature;
+use sha2::{Digest, Sha256};
+use soroban_sdk::{Bytes, BytesN, Env};
+
+// Hash of the relying party id, followed by the flags and a zero counter
+const RP_ID_HASH: [u8; 32] = [0x11; 32];
+
+/// Flags of the assertions `sign` produces: user present and user verified.
+pub const USER_PRESENT_AND_VERIFIED: u8 = 0x05;
+
+pub struct Passkey {
+    signing_key: SigningKey,
+}
+
+impl Passkey {
+    /// Creates a passkey from a seed, so tests get the same key every run.
+    pub fn new(seed: u8) -> Self {
+        let mut secret = [seed; 32];
+        secret[0] = 1;
+        Self { signing_key: SigningKey::from_slice(&secret).unwrap() }
+    }
+
+    /// Returns the SEC-1 encoded uncompressed public key.
+    pub fn public_key(&self, env: &Env) -> BytesN<65> {
+        let point = self.signing_key.verifying_key().to_encoded_point(false);
+        BytesN::from_array(env, point.as_bytes().try_into().unwrap())
+    }
+
+    /// Signs an assertion of `challenge`, as a browser would.
+    pub fn sign(&self, env: &Env, challenge: &BytesN<32>) -> PasskeySignature {
+        self.sign_assertion(env, &Self::client_data_json(challenge), USER_PRESENT_AND_VERIFIED)
+    }
+
+    /// Returns the client data JSON a browser produces for an assertion of `challenge`.
+    pub fn client_data_json(challenge: &BytesN<32>) -> String {
+        format!(
+            r#"{{"type":"webauthn.get","challenge":"{}","origin":"https://trustful.example"}}"#,
+            base64url(&challenge.to_array()),
+        )
+    }
+
+    /// Signs an assertion with the given client data JSON and authenticator data flags.
+    pub fn sign_assertion(&self, env: &Env, client_data_json: &str, flags: u8) -> PasskeySignature {
+        let mut authenticator_data = RP_ID_HASH.to_vec();
+        authenticator_data.extend_from_slice(&[flags, 0, 0, 0, 0]);
+
+        let mut message = authenticator_data.clone();
+        message.extend_from_slice(&Sha256::digest(client_data_json.as_bytes()));
+        let signature: Signature = self.signing_key.sign(&message);
+        let signature = signature.normalize_s().unwrap_or(signature);
+
+        PasskeySignature {
+            authenticator_data: Bytes::from_slice(env, &authenticator_data),
+            client_data_json: Bytes::from_slice(env, client_data_json.as_bytes()),
+            signature: BytesN::from_array(env, &signature.to_bytes().into()),
+        }
+    }
+}
+
+fn base64url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let bits = (group[0] as u32) << 16 | (group[1] as u32) << 8 | group[2] as u32;
+        for index in 0..=chunk.len() {
+            encoded.push(ALPHABET[(bits >> (18 - 6 * index) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
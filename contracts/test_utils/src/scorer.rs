@@ -0,0 +1,58 @@
+//! A scorer with an extension contract, the fixture of the extension tests.
+
+use scorer_interface::{BadgeId, Extension, ScorerClient};
+use soroban_sdk::{testutils::{Address as _, Register}, Address, Env, Map, String, Vec};
+
+/// A scorer and its extension, as set up by `scorer_with_extension`.
+pub struct ScorerWithExtension {
+    pub env: Env,
+    pub scorer_creator: Address,
+    /// An active user of the scorer.
+    pub user: Address,
+    /// A badge of the creator scored 100 and a badge scored 40, both part of
+    /// the scorer, and a badge that is not.
+    pub badges: Vec<BadgeId>,
+    pub scorer: Address,
+    pub extension: Address,
+}
+
+/// Registers `scorer_contract` with the badges of `ScorerWithExtension` and an
+/// active user, then registers `extension_contract`, initializes it with
+/// `initialize(env, extension, scorer)` and sets it as the scorer's `extension`.
+/// Every authorization is mocked.
+pub fn scorer_with_extension<S, E>(
+    scorer_contract: S,
+    extension: Extension,
+    extension_contract: E,
+    initialize: impl FnOnce(&Env, &Address, &Address),
+) -> ScorerWithExtension
+where
+    S: Register,
+    E: Register,
+{
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let scorer_creator = Address::generate(&env);
+    let badges = Vec::from_array(&env, [
+        BadgeId { name: String::from_str(&env, "Test Badge"), issuer: scorer_creator.clone() },
+        BadgeId { name: String::from_str(&env, "Speaker"), issuer: Address::generate(&env) },
+        BadgeId { name: String::from_str(&env, "Unknown"), issuer: Address::generate(&env) },
+    ]);
+    let scorer = ScorerClient::new(&env, &env.register(scorer_contract, ()));
+    scorer.initialize(
+        &scorer_creator,
+        &Map::from_array(&env, [(badges.get_unchecked(0), 100), (badges.get_unchecked(1), 40)]),
+        &String::from_str(&env, "Community"),
+        &String::from_str(&env, "A community"),
+        &String::from_str(&env, "icon.png"),
+    );
+    let user = Address::generate(&env);
+    scorer.add_user(&user);
+
+    let extension_address = env.register(extension_contract, ());
+    initialize(&env, &extension_address, &scorer.address);
+    scorer.set_extension(&scorer_creator, &extension, &Some(extension_address.clone()));
+
+    ScorerWithExtension { env, scorer_creator, user, badges, scorer: scorer.address, extension: extension_address }
+}
@@ -0,0 +1,302 @@
+#![no_std]
+use scorer_interface::events::{self, TOPIC_TIMELOCK};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address, Env, Symbol, Val, Vec};
+
+/// A privileged call waiting for its delay to pass.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Operation {
+    /// The contract to call.
+    pub contract: Address,
+    /// The function to call.
+    pub fn_name: Symbol,
+    /// The arguments of the call.
+    pub args: Vec<Val>,
+    /// The first ledger the call can be executed at.
+    pub ready_at: u32,
+}
+
+#[contracttype]
+enum DataKey {
+    Admin,
+    MinDelay,
+    OperationCount,
+    Operation(u32),
+}
+
+/// Errors raised by the timelock.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    /// The contract is not initialized.
+    NotInitialized = 2,
+    /// The delay is shorter than the minimum delay.
+    DelayTooShort = 3,
+    /// No pending operation with this id exists.
+    OperationNotFound = 4,
+    /// The delay of the operation has not passed yet.
+    OperationNotReady = 5,
+}
+
+/// Delays privileged calls, so they can be reviewed, and cancelled, before they run.
+///
+/// The timelock holds a privileged role, such as the scorer creator or the
+/// factory creator, and its admin routes the calls of that role through it:
+/// upgrades, WASM hash changes, manager changes and the like are scheduled,
+/// become executable once their delay passes, and can be cancelled by the
+/// admin until then. Anyone can execute a ready call. The contracts called see
+/// the timelock as the invoker, so its `require_auth` passes without
+/// signatures, and calls taking a `sender` take the timelock's address. The
+/// minimum delay is set once; deploy a new timelock to change it.
+#[contract]
+pub struct TimelockContract;
+
+#[contractimpl]
+impl TimelockContract {
+    /// Initializes the timelock
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `admin` - The address allowed to schedule and cancel calls
+    /// * `min_delay` - The minimum delay of a call, in ledgers
+    ///
+    /// # Panics
+    /// * When the contract is already initialized (`Error::ContractAlreadyInitialized`)
+    pub fn initialize(env: Env, admin: Address, min_delay: u32) {
+        if env.storage().persistent().has(&DataKey::Admin) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+        env.storage().persistent().set(&DataKey::MinDelay, &min_delay);
+    }
+
+    /// Schedules a call
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `contract` - The contract to call
+    /// * `fn_name` - The function to call
+    /// * `args` - The arguments of the call
+    /// * `delay` - The number of ledgers before the call can be executed
+    ///
+    /// # Returns
+    /// * `u32` - The id of the operation
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the delay is shorter than the minimum delay (`Error::DelayTooShort`)
+    pub fn schedule(env: Env, contract: Address, fn_name: Symbol, args: Vec<Val>, delay: u32) -> u32 {
+        Self::get_admin(env.clone()).require_auth();
+
+        if delay < Self::get_min_delay(env.clone()) {
+            panic_with_error!(&env, Error::DelayTooShort);
+        }
+
+        let id = env.storage().persistent().get(&DataKey::OperationCount).unwrap_or(0u32) + 1;
+        let ready_at = env.ledger().sequence().saturating_add(delay);
+        env.storage().persistent().set(&DataKey::OperationCount, &id);
+        env.storage().persistent().set(
+            &DataKey::Operation(id),
+            &Operation { contract: contract.clone(), fn_name: fn_name.clone(), args, ready_at },
+        );
+
+        env.events().publish(
+            (TOPIC_TIMELOCK, symbol_short!("schedule")),
+            events::CallScheduled { id, contract, fn_name, ready_at },
+        );
+        id
+    }
+
+    /// Executes a scheduled call once its delay passed
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the operation
+    ///
+    /// # Returns
+    /// * `Val` - The value returned by the call
+    ///
+    /// # Panics
+    /// * When no pending operation has this id (`Error::OperationNotFound`)
+    /// * When the delay has not passed yet (`Error::OperationNotReady`)
+    /// * When the call fails
+    pub fn execute(env: Env, id: u32) -> Val {
+        let operation = Self::get_operation(env.clone(), id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::OperationNotFound));
+        if env.ledger().sequence() < operation.ready_at {
+            panic_with_error!(&env, Error::OperationNotReady);
+        }
+
+        env.storage().persistent().remove(&DataKey::Operation(id));
+        let result = env.invoke_contract(&operation.contract, &operation.fn_name, operation.args);
+
+        env.events().publish((TOPIC_TIMELOCK, symbol_short!("execute")), events::CallExecuted { id });
+        result
+    }
+
+    /// Cancels a scheduled call
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the operation
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When no pending operation has this id (`Error::OperationNotFound`)
+    pub fn cancel(env: Env, id: u32) {
+        Self::get_admin(env.clone()).require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Operation(id)) {
+            panic_with_error!(&env, Error::OperationNotFound);
+        }
+        env.storage().persistent().remove(&DataKey::Operation(id));
+
+        env.events().publish((TOPIC_TIMELOCK, symbol_short!("cancel")), events::CallCancelled { id });
+    }
+
+    /// Hands the admin role to another address
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `admin` - The new admin
+    ///
+    /// # Panics
+    /// * When the current admin fails authentication
+    pub fn set_admin(env: Env, admin: Address) {
+        let previous_admin = Self::get_admin(env.clone());
+        previous_admin.require_auth();
+
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+
+        env.events().publish(
+            (TOPIC_TIMELOCK, symbol_short!("admin")),
+            events::TimelockAdminSet { previous_admin, admin },
+        );
+    }
+
+    /// Returns a pending operation, if any
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the operation
+    pub fn get_operation(env: Env, id: u32) -> Option<Operation> {
+        env.storage().persistent().get(&DataKey::Operation(id))
+    }
+
+    /// Returns the admin
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
+    }
+
+    /// Returns the minimum delay of a call, in ledgers
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    pub fn get_min_delay(env: Env) -> u32 {
+        env.storage().persistent().get(&DataKey::MinDelay).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::{vec, IntoVal, Map, String};
+    use test_utils::{EventRecord, EventSnapshot};
+
+    struct Setup {
+        env: Env,
+        scorer: ScorerContractClient<'static>,
+        client: TimelockContractClient<'static>,
+    }
+
+    // A scorer whose creator is a timelock with a delay of 100 ledgers
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = TimelockContractClient::new(&env, &env.register(TimelockContract, ()));
+        client.initialize(&Address::generate(&env), &100);
+
+        let scorer = ScorerContractClient::new(&env, &env.register(ScorerContract, ()));
+        scorer.initialize(
+            &client.address,
+            &Map::new(&env),
+            &String::from_str(&env, "Community"),
+            &String::from_str(&env, "A community"),
+            &String::from_str(&env, "icon.png"),
+        );
+
+        Setup { env, scorer, client }
+    }
+
+    fn schedule_add_manager(setup: &Setup, manager: &Address, delay: u32) -> u32 {
+        let Setup { env, scorer, client } = setup;
+        let args = vec![env, client.address.into_val(env), manager.into_val(env)];
+        client.schedule(&scorer.address, &Symbol::new(env, "add_manager"), &args, &delay)
+    }
+
+    #[test]
+    fn test_schedule_and_execute() {
+        let setup = setup();
+        let manager = Address::generate(&setup.env);
+        let id = schedule_add_manager(&setup, &manager, 100);
+        let Setup { env, scorer, client } = setup;
+
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_TIMELOCK, symbol_short!("schedule")),
+            events::CallScheduled { id, contract: scorer.address.clone(), fn_name: Symbol::new(&env, "add_manager"), ready_at: 100 },
+        )]);
+
+        // The scorer authorizes the timelock as its invoker, without mocked auths
+        env.set_auths(&[]);
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 99);
+        assert_eq!(client.try_execute(&id).err(), Some(Ok(Error::OperationNotReady.into())));
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 100);
+        client.execute(&id);
+        assert!(scorer.get_managers().contains(&manager));
+        assert_eq!(client.get_operation(&id), None);
+        assert_eq!(client.try_execute(&id).err(), Some(Ok(Error::OperationNotFound.into())));
+    }
+
+    #[test]
+    fn test_cancel() {
+        let setup = setup();
+        let manager = Address::generate(&setup.env);
+        assert_eq!(
+            setup.client.try_schedule(&setup.scorer.address, &Symbol::new(&setup.env, "add_manager"), &vec![&setup.env], &99),
+            Err(Ok(Error::DelayTooShort.into()))
+        );
+        let id = schedule_add_manager(&setup, &manager, 200);
+        let Setup { env, scorer, client } = setup;
+
+        client.cancel(&id);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_TIMELOCK, symbol_short!("cancel")),
+            events::CallCancelled { id },
+        )]);
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 200);
+        assert_eq!(client.try_execute(&id).err(), Some(Ok(Error::OperationNotFound.into())));
+        assert!(!scorer.get_managers().contains(&manager));
+    }
+}
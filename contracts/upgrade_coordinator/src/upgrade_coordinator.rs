@@ -0,0 +1,442 @@
+#![no_std]
+use scorer_interface::{events, ScorerClient};
+use scorer_interface::events::{TOPIC_ROLLOUT};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address, BytesN, Env, Vec};
+
+/// The state of a rollout.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RolloutStatus {
+    /// The next wave can be upgraded.
+    Active,
+    /// A wave failed or the admin halted the rollout; it must be resumed or rolled back.
+    Halted,
+    /// Every wave was upgraded.
+    Completed,
+    /// The upgraded scorers were upgraded back to the previous WASM.
+    RolledBack,
+}
+
+/// The outcome of a rollout for a scorer.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpgradeStatus {
+    /// The scorer runs the new WASM.
+    Upgraded,
+    /// The upgrade of the scorer failed.
+    Failed,
+    /// The scorer was upgraded back to the previous WASM.
+    RolledBack,
+}
+
+/// A new scorer WASM rolled out in waves.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rollout {
+    /// The WASM hash the scorers are upgraded to.
+    pub wasm_hash: BytesN<32>,
+    /// The WASM hash the scorers run before the rollout, restored by a rollback.
+    pub previous_wasm_hash: BytesN<32>,
+    /// The scorers of each wave, canaries first.
+    pub waves: Vec<Vec<Address>>,
+    /// The index of the next wave to upgrade.
+    pub next_wave: u32,
+    pub status: RolloutStatus,
+}
+
+#[contracttype]
+enum DataKey {
+    Admin,
+    RolloutCount,
+    Rollout(u32),
+    UpgradeStatus(u32, Address),
+}
+
+/// Errors raised by the upgrade coordinator.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// The contract was already initialized.
+    ContractAlreadyInitialized = 1,
+    /// The contract is not initialized.
+    NotInitialized = 2,
+    /// The rollout has no wave, or an empty wave.
+    InvalidWaves = 3,
+    /// No rollout with this id exists.
+    RolloutNotFound = 4,
+    /// The rollout is halted, completed or rolled back.
+    RolloutNotActive = 5,
+    /// The rollout is not halted.
+    RolloutNotHalted = 6,
+    /// The rollout was already rolled back.
+    RolloutRolledBack = 7,
+}
+
+/// Rolls a new scorer WASM out in waves, canary scorers first.
+///
+/// The coordinator upgrades the scorers it is the scorer creator of, one wave
+/// per `advance`, and records the outcome for each scorer. A wave with a
+/// failed upgrade, such as a scorer whose upgrade guard does not approve the
+/// hash or whose creator is not the coordinator, halts the rollout; the admin
+/// then resumes it or rolls it back, upgrading the scorers it upgraded back to
+/// the previous WASM.
+#[contract]
+pub struct UpgradeCoordinatorContract;
+
+#[contractimpl]
+impl UpgradeCoordinatorContract {
+    /// Initializes the coordinator
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `admin` - The address allowed to manage rollouts
+    ///
+    /// # Panics
+    /// * When the contract is already initialized (`Error::ContractAlreadyInitialized`)
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().persistent().has(&DataKey::Admin) {
+            panic_with_error!(&env, Error::ContractAlreadyInitialized);
+        }
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+    }
+
+    /// Creates a rollout
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `wasm_hash` - The WASM hash to upgrade the scorers to
+    /// * `previous_wasm_hash` - The WASM hash the scorers run, restored by a rollback
+    /// * `waves` - The scorers of each wave, canaries first
+    ///
+    /// # Returns
+    /// * `u32` - The id of the rollout
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When there is no wave or a wave is empty (`Error::InvalidWaves`)
+    pub fn create_rollout(env: Env, wasm_hash: BytesN<32>, previous_wasm_hash: BytesN<32>, waves: Vec<Vec<Address>>) -> u32 {
+        Self::get_admin(env.clone()).require_auth();
+
+        if waves.is_empty() || waves.iter().any(|wave| wave.is_empty()) {
+            panic_with_error!(&env, Error::InvalidWaves);
+        }
+
+        let id = env.storage().persistent().get(&DataKey::RolloutCount).unwrap_or(0u32) + 1;
+        env.storage().persistent().set(&DataKey::RolloutCount, &id);
+        let rollout = Rollout {
+            wasm_hash: wasm_hash.clone(),
+            previous_wasm_hash: previous_wasm_hash.clone(),
+            waves: waves.clone(),
+            next_wave: 0,
+            status: RolloutStatus::Active,
+        };
+        env.storage().persistent().set(&DataKey::Rollout(id), &rollout);
+
+        env.events().publish(
+            (TOPIC_ROLLOUT, symbol_short!("create")),
+            events::RolloutCreated { id, wasm_hash, previous_wasm_hash, waves: waves.len() },
+        );
+        id
+    }
+
+    /// Upgrades the next wave of a rollout
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the rollout
+    ///
+    /// # Returns
+    /// * `RolloutStatus` - The status of the rollout after the wave
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the rollout does not exist (`Error::RolloutNotFound`)
+    /// * When the rollout is not active (`Error::RolloutNotActive`)
+    pub fn advance(env: Env, id: u32) -> RolloutStatus {
+        Self::get_admin(env.clone()).require_auth();
+
+        let mut rollout = Self::rollout(&env, id);
+        if rollout.status != RolloutStatus::Active {
+            panic_with_error!(&env, Error::RolloutNotActive);
+        }
+
+        let (mut upgraded, mut failed) = (0, 0);
+        for scorer in rollout.waves.get_unchecked(rollout.next_wave).iter() {
+            let status = match ScorerClient::new(&env, &scorer).try_upgrade(&rollout.wasm_hash) {
+                Ok(_) => {
+                    upgraded += 1;
+                    UpgradeStatus::Upgraded
+                }
+                Err(_) => {
+                    failed += 1;
+                    UpgradeStatus::Failed
+                }
+            };
+            env.storage().persistent().set(&DataKey::UpgradeStatus(id, scorer), &status);
+        }
+
+        let wave = rollout.next_wave;
+        rollout.next_wave += 1;
+        rollout.status = if failed > 0 {
+            RolloutStatus::Halted
+        } else if rollout.next_wave == rollout.waves.len() {
+            RolloutStatus::Completed
+        } else {
+            RolloutStatus::Active
+        };
+        env.storage().persistent().set(&DataKey::Rollout(id), &rollout);
+
+        env.events().publish(
+            (TOPIC_ROLLOUT, symbol_short!("wave")),
+            events::WaveUpgraded { id, wave, upgraded, failed },
+        );
+        rollout.status
+    }
+
+    /// Halts a rollout before its next wave
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the rollout
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the rollout does not exist (`Error::RolloutNotFound`)
+    /// * When the rollout is not active (`Error::RolloutNotActive`)
+    pub fn halt(env: Env, id: u32) {
+        Self::get_admin(env.clone()).require_auth();
+
+        let mut rollout = Self::rollout(&env, id);
+        if rollout.status != RolloutStatus::Active {
+            panic_with_error!(&env, Error::RolloutNotActive);
+        }
+        rollout.status = RolloutStatus::Halted;
+        env.storage().persistent().set(&DataKey::Rollout(id), &rollout);
+
+        env.events().publish((TOPIC_ROLLOUT, symbol_short!("halt")), events::RolloutHalted { id });
+    }
+
+    /// Resumes a halted rollout, leaving the scorers that failed on the previous WASM
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the rollout
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the rollout does not exist (`Error::RolloutNotFound`)
+    /// * When the rollout is not halted (`Error::RolloutNotHalted`)
+    pub fn resume(env: Env, id: u32) {
+        Self::get_admin(env.clone()).require_auth();
+
+        let mut rollout = Self::rollout(&env, id);
+        if rollout.status != RolloutStatus::Halted {
+            panic_with_error!(&env, Error::RolloutNotHalted);
+        }
+        rollout.status = if rollout.next_wave == rollout.waves.len() {
+            RolloutStatus::Completed
+        } else {
+            RolloutStatus::Active
+        };
+        env.storage().persistent().set(&DataKey::Rollout(id), &rollout);
+
+        env.events().publish((TOPIC_ROLLOUT, symbol_short!("resume")), events::RolloutResumed { id });
+    }
+
+    /// Upgrades the scorers a rollout upgraded back to the previous WASM, ending the rollout
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the rollout
+    ///
+    /// # Panics
+    /// * When the admin fails authentication
+    /// * When the rollout does not exist (`Error::RolloutNotFound`)
+    /// * When the rollout was already rolled back (`Error::RolloutRolledBack`)
+    pub fn rollback(env: Env, id: u32) {
+        Self::get_admin(env.clone()).require_auth();
+
+        let mut rollout = Self::rollout(&env, id);
+        if rollout.status == RolloutStatus::RolledBack {
+            panic_with_error!(&env, Error::RolloutRolledBack);
+        }
+
+        let (mut rolled_back, mut failed) = (0, 0);
+        for wave in rollout.waves.iter().take(rollout.next_wave as usize) {
+            for scorer in wave.iter() {
+                if Self::get_upgrade_status(env.clone(), id, scorer.clone()) != Some(UpgradeStatus::Upgraded) {
+                    continue;
+                }
+                match ScorerClient::new(&env, &scorer).try_upgrade(&rollout.previous_wasm_hash) {
+                    Ok(_) => {
+                        rolled_back += 1;
+                        env.storage().persistent().set(&DataKey::UpgradeStatus(id, scorer), &UpgradeStatus::RolledBack);
+                    }
+                    Err(_) => failed += 1,
+                }
+            }
+        }
+
+        rollout.status = RolloutStatus::RolledBack;
+        env.storage().persistent().set(&DataKey::Rollout(id), &rollout);
+
+        env.events().publish(
+            (TOPIC_ROLLOUT, symbol_short!("rollback")),
+            events::RolloutRolledBack { id, rolled_back, failed },
+        );
+    }
+
+    /// Returns a rollout, if any
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the rollout
+    pub fn get_rollout(env: Env, id: u32) -> Option<Rollout> {
+        env.storage().persistent().get(&DataKey::Rollout(id))
+    }
+
+    /// Returns the outcome of a rollout for a scorer, if its wave was upgraded
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `id` - The id of the rollout
+    /// * `scorer` - The scorer to look up
+    pub fn get_upgrade_status(env: Env, id: u32, scorer: Address) -> Option<UpgradeStatus> {
+        env.storage().persistent().get(&DataKey::UpgradeStatus(id, scorer))
+    }
+
+    /// Returns the admin
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Panics
+    /// * When the contract is not initialized (`Error::NotInitialized`)
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
+    }
+
+    fn rollout(env: &Env, id: u32) -> Rollout {
+        Self::get_rollout(env.clone(), id).unwrap_or_else(|| panic_with_error!(env, Error::RolloutNotFound))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scorer::{ScorerContract, ScorerContractClient};
+    use soroban_sdk::testutils::{Address as _, MockAuth, MockAuthInvoke};
+    use soroban_sdk::{vec, IntoVal, Map, String};
+    use test_utils::{EventRecord, EventSnapshot};
+
+    mod old_scorer {
+        soroban_sdk::contractimport!(file = "../../wasm/trustful_stellar_v1_test_upgradable.wasm");
+    }
+
+    mod current_scorer {
+        soroban_sdk::contractimport!(file = "../../wasm/scorer.wasm");
+    }
+
+    struct Setup {
+        env: Env,
+        admin: Address,
+        new_hash: BytesN<32>,
+        previous_hash: BytesN<32>,
+        client: UpgradeCoordinatorContractClient<'static>,
+    }
+
+    // A coordinator, the hash of a scorer WASM reporting version 0 to upgrade to,
+    // and the hash of the current scorer WASM
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.cost_estimate().budget().reset_unlimited();
+
+        let admin = Address::generate(&env);
+        let client = UpgradeCoordinatorContractClient::new(&env, &env.register(UpgradeCoordinatorContract, ()));
+        client.initialize(&admin);
+
+        let new_hash = env.deployer().upload_contract_wasm(old_scorer::WASM);
+        let previous_hash = env.deployer().upload_contract_wasm(current_scorer::WASM);
+        Setup { env, admin, new_hash, previous_hash, client }
+    }
+
+    fn create_scorer(env: &Env, creator: &Address) -> ScorerContractClient<'static> {
+        let scorer = ScorerContractClient::new(env, &env.register(ScorerContract, ()));
+        scorer.initialize(
+            creator,
+            &Map::new(env),
+            &String::from_str(env, "Community"),
+            &String::from_str(env, "A community"),
+            &String::from_str(env, "icon.png"),
+        );
+        scorer
+    }
+
+    fn mock_admin(env: &Env, admin: &Address, client: &UpgradeCoordinatorContractClient, fn_name: &str, id: u32) {
+        env.mock_auths(&[MockAuth {
+            address: admin,
+            invoke: &MockAuthInvoke { contract: &client.address, fn_name, args: (id,).into_val(env), sub_invokes: &[] },
+        }]);
+    }
+
+    #[test]
+    fn test_rollout_in_waves() {
+        let Setup { env, new_hash, previous_hash, client, .. } = setup();
+        let canary = create_scorer(&env, &client.address);
+        let fleet = [create_scorer(&env, &client.address), create_scorer(&env, &client.address)];
+        let waves = vec![&env, vec![&env, canary.address.clone()], vec![&env, fleet[0].address.clone(), fleet[1].address.clone()]];
+
+        let id = client.create_rollout(&new_hash, &previous_hash, &waves);
+        assert_eq!(client.advance(&id), RolloutStatus::Active);
+        assert_eq!(canary.contract_version(), 0);
+        assert_eq!(fleet[0].contract_version(), 1);
+
+        assert_eq!(client.advance(&id), RolloutStatus::Completed);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_ROLLOUT, symbol_short!("wave")),
+            events::WaveUpgraded { id, wave: 1, upgraded: 2, failed: 0 },
+        )]);
+        assert_eq!(fleet[1].contract_version(), 0);
+        assert_eq!(client.get_upgrade_status(&id, &fleet[1].address), Some(UpgradeStatus::Upgraded));
+
+        assert_eq!(client.try_advance(&id), Err(Ok(Error::RolloutNotActive.into())));
+    }
+
+    #[test]
+    fn test_failed_wave_halts_and_rolls_back() {
+        let Setup { env, admin, new_hash, previous_hash, client } = setup();
+        let upgraded = create_scorer(&env, &client.address);
+        // Not owned by the coordinator, so its upgrade fails
+        let foreign = create_scorer(&env, &Address::generate(&env));
+        let waves = vec![&env, vec![&env, upgraded.address.clone(), foreign.address.clone()], vec![&env, Address::generate(&env)]];
+
+        let id = client.create_rollout(&new_hash, &previous_hash, &waves);
+        // Only the admin is mocked, so scorers check the coordinator's authorization
+        mock_admin(&env, &admin, &client, "advance", id);
+        assert_eq!(client.advance(&id), RolloutStatus::Halted);
+        assert_eq!(client.get_upgrade_status(&id, &foreign.address), Some(UpgradeStatus::Failed));
+        assert_eq!(upgraded.contract_version(), 0);
+
+        mock_admin(&env, &admin, &client, "rollback", id);
+        client.rollback(&id);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &client.address,
+            (TOPIC_ROLLOUT, symbol_short!("rollback")),
+            events::RolloutRolledBack { id, rolled_back: 1, failed: 0 },
+        )]);
+        assert_eq!(upgraded.contract_version(), 1);
+        assert_eq!(client.get_upgrade_status(&id, &upgraded.address), Some(UpgradeStatus::RolledBack));
+        assert_eq!(client.get_rollout(&id).unwrap().status, RolloutStatus::RolledBack);
+
+        env.mock_all_auths();
+        assert_eq!(client.try_rollback(&id), Err(Ok(Error::RolloutRolledBack.into())));
+    }
+}
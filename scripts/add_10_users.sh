@@ -6,6 +6,8 @@ GREEN='\033[0;32m'
 YELLOW='\033[1;33m'
 NC='\033[0m'
 
+source "$(dirname "$0")/config.sh"
+
 # Default values
 NETWORK="testnet"
 SOURCE_KEY=""
@@ -32,7 +34,7 @@ usage() {
     echo "Usage: $0 [OPTIONS]"
     echo
     echo "Options:"
-    echo "  -n, --network <testnet|mainnet>  Network to deploy to (default: testnet)"
+    echo "  -n, --network <network>          Network to deploy to (default: testnet)"
     echo "  -s, --source <key_name>          Source account key name (required)"
     echo "  -h, --help                       Show this help message"
 }
@@ -67,8 +69,7 @@ if [[ -z "$SOURCE_KEY" ]]; then
     exit 1
 fi
 
-if [[ "$NETWORK" != "testnet" && "$NETWORK" != "mainnet" ]]; then
-    echo -e "${RED}Error: Network must be either 'testnet' or 'mainnet'${NC}"
+if ! load_config "$NETWORK"; then
     usage
     exit 1
 fi
@@ -79,12 +80,12 @@ ensure_account_funded() {
     local keyname=$2
     
     # First, try to see if account already exists
-    local query_result=$(stellar query account "$address" --network "$NETWORK" 2>&1)
+    local query_result=$(stellar query account "$address" --rpc-url "$TRUSTFUL_RPC_URL" --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" 2>&1)
     
     if [[ "$query_result" == *"Account not found"* ]]; then
         echo -e "${YELLOW}Account doesn't exist on $NETWORK. Funding it...${NC}"
         # Account doesn't exist, fund it
-        stellar keys fund "$address" --network "$NETWORK"
+        stellar keys fund "$address" --rpc-url "$TRUSTFUL_RPC_URL" --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE"
         if [ $? -ne 0 ]; then
             echo -e "${RED}Error: Failed to fund new account $address on $NETWORK${NC}"
             exit 1
@@ -94,7 +95,7 @@ ensure_account_funded() {
         # Account exists, but let's make sure it has enough funds
         # We'll fund it again anyway to ensure it has enough XLM
         echo -e "${YELLOW}Account exists but might need more funds. Adding more funds...${NC}"
-        stellar keys fund "$address" --network "$NETWORK" 2>/dev/null || true
+        stellar keys fund "$address" --rpc-url "$TRUSTFUL_RPC_URL" --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" 2>/dev/null || true
         echo -e "${GREEN}Added funds to existing account on $NETWORK${NC}"
     fi
 }
@@ -134,7 +135,8 @@ add_user() {
     ADD_USER_RESULT=$(stellar contract invoke \
         --id "$SCORER_ADDRESS" \
         --source "$user_key" \
-        --network "$NETWORK" \
+        --rpc-url "$TRUSTFUL_RPC_URL" \
+        --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
         -- \
         add_user \
         --user "$user_key")
@@ -155,7 +157,8 @@ add_manager() {
     ADD_MANAGER_RESULT=$(stellar contract invoke \
         --id "$SCORER_ADDRESS" \
         --source "$SOURCE_KEY" \
-        --network "$NETWORK" \
+        --rpc-url "$TRUSTFUL_RPC_URL" \
+        --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
         -- \
         add_manager \
         --new_manager "$manager_key" \
@@ -6,6 +6,8 @@ GREEN='\033[0;32m'
 YELLOW='\033[1;33m'
 NC='\033[0m'
 
+source "$(dirname "$0")/config.sh"
+
 # Default values
 NETWORK="testnet"
 SOURCE_KEY=""
@@ -30,7 +32,7 @@ usage() {
     echo "Usage: $0 [OPTIONS]"
     echo
     echo "Options:"
-    echo "  -n, --network <testnet|mainnet>  Network to deploy to (default: testnet)"
+    echo "  -n, --network <network>          Network to deploy to (default: testnet)"
     echo "  -s, --source <key_name>          Source account key name (required)"
     echo "  -h, --help                       Show this help message"
 }
@@ -65,6 +67,11 @@ if [ -z "$SOURCE_KEY" ]; then
     exit 1
 fi
 
+if ! load_config "$NETWORK"; then
+    usage
+    exit 1
+fi
+
 # Function to add a manager
 add_manager() {
     local manager_key=$1
@@ -72,7 +79,8 @@ add_manager() {
     ADD_MANAGER_RESULT=$(stellar contract invoke \
         --id "$SCORER_ADDRESS" \
         --source "$SOURCE_KEY" \
-        --network "$NETWORK" \
+        --rpc-url "$TRUSTFUL_RPC_URL" \
+        --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
         -- \
         add_manager \
         --new_manager "$manager_key" \
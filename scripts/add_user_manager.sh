@@ -6,6 +6,8 @@ GREEN='\033[0;32m'
 YELLOW='\033[1;33m'
 NC='\033[0m'
 
+source "$(dirname "$0")/config.sh"
+
 # Default values
 NETWORK="testnet"
 SOURCE_KEY=""
@@ -31,7 +33,7 @@ usage() {
     echo "Usage: $0 [OPTIONS]"
     echo
     echo "Options:"
-    echo "  -n, --network <testnet|mainnet>  Network to deploy to (default: testnet)"
+    echo "  -n, --network <network>          Network to deploy to (default: testnet)"
     echo "  -s, --source <key_name>          Source account key name (required)"
     echo "  -t, --target <key_name>          Target account key name to add as user/manager (required)"
     echo "  -h, --help                       Show this help message"
@@ -77,8 +79,7 @@ if [[ -z "$TARGET_KEY" ]]; then
     exit 1
 fi
 
-if [[ "$NETWORK" != "testnet" && "$NETWORK" != "mainnet" ]]; then
-    echo -e "${RED}Error: Network must be either 'testnet' or 'mainnet'${NC}"
+if ! load_config "$NETWORK"; then
     usage
     exit 1
 fi
@@ -94,7 +95,8 @@ echo -e "${YELLOW}Adding manager to scorer contract...${NC}"
 ADD_MANAGER_RESULT=$(stellar contract invoke \
     --id "$SCORER_ADDRESS" \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     -- \
     add_manager \
     --sender "$ADMIN_ADDRESS" \
@@ -113,7 +115,8 @@ echo -e "${YELLOW}Adding user to scorer contract...${NC}"
 ADD_USER_RESULT=$(stellar contract invoke \
     --id "$SCORER_ADDRESS" \
     --source "$TARGET_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     -- \
     add_user \
     --user "$TARGET_ADDRESS")
@@ -131,7 +134,8 @@ echo -e "${YELLOW}Removing manager from scorer contract...${NC}"
 REMOVE_MANAGER_RESULT=$(stellar contract invoke \
     --id "$SCORER_ADDRESS" \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     -- \
     remove_manager \
     --sender "$ADMIN_ADDRESS" \
@@ -150,7 +154,8 @@ echo -e "${YELLOW}Removing user from scorer contract...${NC}"
 REMOVE_USER_RESULT=$(stellar contract invoke \
     --id "$SCORER_ADDRESS" \
     --source "$TARGET_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     -- \
     remove_user \
     --user "$TARGET_ADDRESS")
@@ -0,0 +1,34 @@
+#!/bin/bash
+
+# Network configuration shared by the scripts.
+#
+# load_config <network> reads config/<network>.env (or the file given by
+# TRUSTFUL_CONFIG) and exports its TRUSTFUL_* values. Variables already set in
+# the environment take precedence over the file.
+
+CONFIG_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")/../config" && pwd)"
+
+load_config() {
+    local network="$1"
+    local file="${TRUSTFUL_CONFIG:-$CONFIG_DIR/$network.env}"
+
+    if [[ ! -f "$file" ]]; then
+        echo -e "${RED}Error: No configuration found for network '$network' ($file)${NC}"
+        echo "Available networks: $(ls "$CONFIG_DIR" | sed 's/\.env$//' | tr '\n' ' ')"
+        return 1
+    fi
+
+    local key value
+    while IFS='=' read -r key value; do
+        [[ -z "$key" || "$key" == \#* ]] && continue
+        if [[ -z "${!key}" ]]; then
+            value="${value%\"}"
+            export "$key=${value#\"}"
+        fi
+    done < "$file"
+
+    if [[ -z "$TRUSTFUL_RPC_URL" || -z "$TRUSTFUL_NETWORK_PASSPHRASE" ]]; then
+        echo -e "${RED}Error: TRUSTFUL_RPC_URL and TRUSTFUL_NETWORK_PASSPHRASE must be set for network '$network'${NC}"
+        return 1
+    fi
+}
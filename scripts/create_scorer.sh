@@ -6,6 +6,8 @@ GREEN='\033[0;32m'
 YELLOW='\033[1;33m'
 NC='\033[0m'
 
+source "$(dirname "$0")/config.sh"
+
 # Default values
 NETWORK="testnet"
 SOURCE_KEY=""
@@ -30,7 +32,7 @@ usage() {
     echo "Usage: $0 [OPTIONS]"
     echo
     echo "Options:"
-    echo "  -n, --network <testnet|mainnet>  Network to deploy to (default: testnet)"
+    echo "  -n, --network <network>          Network to deploy to (default: testnet)"
     echo "  -s, --source <key_name>          Source account key name (required)"
     echo "  -h, --help                       Show this help message"
 }
@@ -65,8 +67,7 @@ if [[ -z "$SOURCE_KEY" ]]; then
     exit 1
 fi
 
-if [[ "$NETWORK" != "testnet" && "$NETWORK" != "mainnet" ]]; then
-    echo -e "${RED}Error: Network must be either 'testnet' or 'mainnet'${NC}"
+if ! load_config "$NETWORK"; then
     usage
     exit 1
 fi
@@ -114,7 +115,8 @@ echo -e "${YELLOW}Creating scorer contract...${NC}"
 CREATE_RESULT=$(stellar contract invoke \
     --id "$FACTORY_ADDRESS" \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     -- \
     create_scorer \
     --deployer "$ADMIN_ADDRESS" \
@@ -6,6 +6,8 @@ GREEN='\033[0;32m'
 YELLOW='\033[1;33m'
 NC='\033[0m' # No Color
 
+source "$(dirname "$0")/config.sh"
+
 # Default values
 NETWORK="testnet"
 SOURCE_KEY=""
@@ -16,9 +18,9 @@ usage() {
     echo "Usage: $0 [OPTIONS]"
     echo
     echo "Options:"
-    echo "  -n, --network <testnet|mainnet>  Network to deploy to (default: testnet)"
+    echo "  -n, --network <network>          Network to deploy to (default: testnet)"
     echo "  -s, --source <key_name>          Source account key name"
-    echo "  -f, --fund                       Fund the account using friendbot (testnet only)"
+    echo "  -f, --fund                       Fund the account using friendbot (not on mainnet)"
     echo "  -h, --help                       Show this help message"
     echo
     echo "Example:"
@@ -60,8 +62,7 @@ if [[ -z "$SOURCE_KEY" ]]; then
     exit 1
 fi
 
-if [[ "$NETWORK" != "testnet" && "$NETWORK" != "mainnet" ]]; then
-    echo -e "${RED}Error: Network must be either 'testnet' or 'mainnet'${NC}"
+if ! load_config "$NETWORK"; then
     usage
     exit 1
 fi
@@ -96,9 +97,9 @@ fi
 mkdir -p .deploy
 
 # Generate and fund account if requested
-if [[ "$FUND_ACCOUNT" == true && "$NETWORK" == "testnet" ]]; then
+if [[ "$FUND_ACCOUNT" == true && "$NETWORK" != "mainnet" ]]; then
     echo -e "${YELLOW}Generating and funding account for $SOURCE_KEY...${NC}"
-    stellar keys generate --global "$SOURCE_KEY" --network testnet --fund
+    stellar keys generate --global "$SOURCE_KEY" --rpc-url "$TRUSTFUL_RPC_URL" --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" --fund
     
     if [ $? -ne 0 ]; then
         echo -e "${RED}Error: Failed to generate and fund account${NC}"
@@ -115,7 +116,8 @@ echo -e "${YELLOW}Deploying deployer contract to $NETWORK...${NC}"
 RESULT=$(stellar contract deploy \
     --wasm target/wasm32-unknown-unknown/release/deployer.wasm \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK")
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE")
 
 if [ $? -ne 0 ]; then
     echo -e "${RED}Error: Deployment failed${NC}"
@@ -6,9 +6,12 @@ GREEN='\033[0;32m'
 YELLOW='\033[1;33m'
 NC='\033[0m'
 
+source "$(dirname "$0")/config.sh"
+
 # Default values
 NETWORK="testnet"
 SOURCE_KEY=""
+LABEL=""
 
 # Load deployer contract info
 if [ ! -f .deploy/deployer.env ]; then
@@ -30,8 +33,9 @@ usage() {
     echo "Usage: $0 [OPTIONS]"
     echo
     echo "Options:"
-    echo "  -n, --network <testnet|mainnet>  Network to deploy to (default: testnet)"
+    echo "  -n, --network <network>          Network to deploy to (default: testnet)"
     echo "  -s, --source <key_name>          Source account key name (required)"
+    echo "  -l, --label <label>              Derive the factory address from a label like factory-v2 (default: random salt)"
     echo "  -h, --help                       Show this help message"
 }
 
@@ -46,6 +50,10 @@ while [[ $# -gt 0 ]]; do
             SOURCE_KEY="$2"
             shift 2
             ;;
+        -l|--label)
+            LABEL="$2"
+            shift 2
+            ;;
         -h|--help)
             usage
             exit 0
@@ -65,8 +73,7 @@ if [[ -z "$SOURCE_KEY" ]]; then
     exit 1
 fi
 
-if [[ "$NETWORK" != "testnet" && "$NETWORK" != "mainnet" ]]; then
-    echo -e "${RED}Error: Network must be either 'testnet' or 'mainnet'${NC}"
+if ! load_config "$NETWORK"; then
     usage
     exit 1
 fi
@@ -96,7 +103,8 @@ echo -e "${YELLOW}Installing scorer WASM...${NC}"
 SCORER_RESULT=$(stellar contract install \
     --wasm wasm/scorer.wasm \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     --ignore-checks)
 
 if [ $? -ne 0 ]; then
@@ -113,7 +121,8 @@ echo -e "${YELLOW}Installing factory WASM...${NC}"
 FACTORY_RESULT=$(stellar contract install \
     --wasm wasm/scorer_factory.wasm \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     --ignore-checks)
 
 if [ $? -ne 0 ]; then
@@ -124,17 +133,27 @@ fi
 
 FACTORY_HASH=$FACTORY_RESULT
 
-# Finally deploy the factory using the deployer
+# Finally deploy the factory using the deployer, at the address derived from
+# the label when one is given so it is the same after a testnet reset
+if [ -n "$LABEL" ]; then
+    DEPLOY_FN="deploy_labeled"
+    SALT_ARGS=(--label "$LABEL")
+else
+    DEPLOY_FN="deploy"
+    SALT_ARGS=(--salt "$(openssl rand -hex 32)")
+fi
+
 echo -e "${YELLOW}Deploying factory contract...${NC}"
 DEPLOY_RESULT=$(stellar contract invoke \
     --id "$CONTRACT_ID" \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     -- \
-    deploy \
+    "$DEPLOY_FN" \
     --deployer "$ADMIN_ADDRESS" \
     --wasm-hash "$FACTORY_HASH" \
-    --salt "$(openssl rand -hex 32)" \
+    "${SALT_ARGS[@]}" \
     --init-fn "initialize" \
     --init-args "[{\"address\":\"$ADMIN_ADDRESS\"},{\"bytes\":\"$SCORER_HASH\"}]")
 
@@ -144,8 +163,8 @@ if [ $? -ne 0 ]; then
     exit 1
 fi
 
-# Extract the factory address from the result array
-FACTORY_ADDRESS=$(echo $DEPLOY_RESULT | jq -r '.[0]')
+# The result is the factory address
+FACTORY_ADDRESS=$(echo $DEPLOY_RESULT | jq -r '.')
 
 # Save factory deployment information
 echo "NETWORK=$NETWORK" > .deploy/factory.env
@@ -162,7 +181,8 @@ echo -e "${YELLOW}Adding manager...${NC}"
 ADD_MANAGER_RESULT=$(stellar contract invoke \
     --id "$FACTORY_ADDRESS" \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     -- \
     add_manager \
     --caller "$ADMIN_ADDRESS" \
@@ -181,7 +201,8 @@ echo -e "${YELLOW}Removing manager...${NC}"
 REMOVE_MANAGER_RESULT=$(stellar contract invoke \
     --id "$FACTORY_ADDRESS" \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     -- \
     remove_manager \
     --caller "$ADMIN_ADDRESS" \
@@ -201,7 +222,8 @@ echo -e "${YELLOW}Adding manager...${NC}"
 ADD_MANAGER_RESULT=$(stellar contract invoke \
     --id "$FACTORY_ADDRESS" \
     --source "$SOURCE_KEY" \
-    --network "$NETWORK" \
+    --rpc-url "$TRUSTFUL_RPC_URL" \
+    --network-passphrase "$TRUSTFUL_NETWORK_PASSPHRASE" \
     -- \
     add_manager \
     --caller "$ADMIN_ADDRESS" \
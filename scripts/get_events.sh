@@ -6,11 +6,23 @@ GREEN='\033[0;32m'
 YELLOW='\033[1;33m'
 NC='\033[0m'
 
-# Contract ID to query
-CONTRACT_ID="CD64GYZHU57XUKUH2FUOOAWM4TWSQ4NCDE2NGJMOQEV3N5VSYPEQWWYK"
+source "$(dirname "$0")/config.sh"
 
-# Soroban RPC endpoint for testnet
-RPC_URL="https://soroban-testnet.stellar.org"
+# Usage: get_events.sh [contract_id], on the network given by NETWORK (default: testnet)
+if ! load_config "${NETWORK:-testnet}"; then
+    exit 1
+fi
+
+# Contract ID to query, defaults to the configured scorer
+CONTRACT_ID="${1:-$TRUSTFUL_SCORER_ID}"
+if [ -z "$CONTRACT_ID" ]; then
+    echo -e "${RED}Error: No contract ID given and TRUSTFUL_SCORER_ID is not configured${NC}"
+    echo "Usage: $0 [contract_id]"
+    exit 1
+fi
+
+# Soroban RPC endpoint
+RPC_URL="$TRUSTFUL_RPC_URL"
 
 # Updated valid start ledger from error message
 START_LEDGER=498000
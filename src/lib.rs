@@ -1,3 +1,3 @@
 
-#[cfg(test)]
-mod test_utils;
\ No newline at end of file
+#[cfg(feature = "testutils")]
+pub mod test_utils;
\ No newline at end of file
@@ -1,3 +1,9 @@
+use core::cell::Cell;
+use deployer::{Deployer, DeployerClient};
+use scorer::{BadgeId, ScorerContractClient};
+use scorer_factory::ScorerFactoryContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec};
+
 pub mod old_contract {
     soroban_sdk::contractimport!(
         file = "wasm/trustful_stellar_v1_test_upgradable.wasm"
@@ -8,4 +14,179 @@ pub mod new_contract {
     soroban_sdk::contractimport!(
         file = "wasm/trustful_stellar_v1.wasm"
     );
-} 
\ No newline at end of file
+}
+
+pub mod scorer_contract {
+    soroban_sdk::contractimport!(
+        file = "wasm/scorer.wasm"
+    );
+}
+
+pub mod scorer_factory_contract {
+    soroban_sdk::contractimport!(
+        file = "wasm/scorer_factory.wasm"
+    );
+}
+
+/// Sizes of the state seeded by `TestWorld::with_config`.
+#[derive(Clone, Copy, Debug)]
+pub struct TestWorldConfig {
+    /// Number of scorers created through the factory.
+    pub scorers: u32,
+    /// Number of badges each scorer is initialized with, issued by the admin.
+    pub badges: u32,
+    /// Number of users that join every scorer.
+    pub users: u32,
+    /// Number of managers added to every scorer, besides the admin.
+    pub managers: u32,
+}
+
+impl Default for TestWorldConfig {
+    fn default() -> Self {
+        TestWorldConfig {
+            scorers: 1,
+            badges: 1,
+            users: 0,
+            managers: 0,
+        }
+    }
+}
+
+/// A deployed Trustful stack: the deployer, a factory deployed through it and
+/// scorers created through the factory, all owned by `admin`.
+///
+/// All auths are mocked and the budget is unlimited. Badge `i` of each scorer is named `Badge {i}` and
+/// scores `(i + 1) * 100`; scorer `i` is named `Scorer {i}`.
+pub struct TestWorld {
+    pub env: Env,
+    pub admin: Address,
+    pub scorer_wasm_hash: BytesN<32>,
+    pub deployer: DeployerClient<'static>,
+    pub factory: ScorerFactoryContractClient<'static>,
+    pub scorers: std::vec::Vec<ScorerContractClient<'static>>,
+    pub managers: std::vec::Vec<Address>,
+    pub users: std::vec::Vec<Address>,
+    salt_nonce: Cell<u32>,
+}
+
+impl TestWorld {
+    /// Deploys the stack with the default config: one scorer with one badge.
+    pub fn new() -> Self {
+        Self::with_config(TestWorldConfig::default())
+    }
+
+    /// Deploys the stack and seeds it as described by `config`.
+    pub fn with_config(config: TestWorldConfig) -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        // The factory runs as WASM, which quickly exceeds the default budget.
+        env.cost_estimate().budget().reset_unlimited();
+
+        let admin = Address::generate(&env);
+        let deployer = DeployerClient::new(&env, &env.register(Deployer, ()));
+
+        let factory_wasm_hash = env.deployer().upload_contract_wasm(scorer_factory_contract::WASM);
+        let scorer_wasm_hash = env.deployer().upload_contract_wasm(scorer_contract::WASM);
+
+        let mut init_args: Vec<Val> = Vec::new(&env);
+        init_args.push_back(admin.clone().into_val(&env));
+        init_args.push_back(scorer_wasm_hash.clone().into_val(&env));
+        let factory_address = deployer.deploy(
+            &admin,
+            &factory_wasm_hash,
+            &BytesN::from_array(&env, &[0; 32]),
+            &Symbol::new(&env, "initialize"),
+            &init_args,
+        );
+        let factory = ScorerFactoryContractClient::new(&env, &factory_address);
+
+        let managers = (0..config.managers).map(|_| Address::generate(&env)).collect();
+        let users = (0..config.users).map(|_| Address::generate(&env)).collect();
+
+        let mut world = TestWorld {
+            env,
+            admin,
+            scorer_wasm_hash,
+            deployer,
+            factory,
+            scorers: std::vec::Vec::new(),
+            managers,
+            users,
+            salt_nonce: Cell::new(0),
+        };
+
+        for index in 0..config.scorers {
+            let name = format!("Scorer {}", index);
+            let scorer = world.create_scorer(&name, world.badges(config.badges));
+            for manager in world.managers.iter() {
+                scorer.add_manager(&world.admin, manager);
+            }
+            for user in world.users.iter() {
+                scorer.add_user(user);
+            }
+            world.scorers.push(scorer);
+        }
+
+        world
+    }
+
+    /// Returns `count` badges issued by the admin, following the world's
+    /// naming and scoring scheme.
+    pub fn badges(&self, count: u32) -> Map<BadgeId, u32> {
+        let mut badges = Map::new(&self.env);
+        for index in 0..count {
+            let badge_id = BadgeId {
+                name: String::from_str(&self.env, &format!("Badge {}", index)),
+                issuer: self.admin.clone(),
+            };
+            badges.set(badge_id, (index + 1) * 100);
+        }
+        badges
+    }
+
+    /// Returns the factory `initialize` arguments of a scorer owned by the
+    /// admin.
+    pub fn scorer_init_args(&self, name: &str, badges: Map<BadgeId, u32>) -> Vec<Val> {
+        let mut init_args: Vec<Val> = Vec::new(&self.env);
+        init_args.push_back(self.admin.clone().into_val(&self.env));
+        init_args.push_back(badges.into_val(&self.env));
+        init_args.push_back(String::from_str(&self.env, name).into_val(&self.env));
+        init_args.push_back(String::from_str(&self.env, "A test scorer").into_val(&self.env));
+        init_args.push_back(String::from_str(&self.env, "icon.png").into_val(&self.env));
+        init_args
+    }
+
+    /// Creates a scorer owned by the admin through the factory. It is not
+    /// added to `scorers`.
+    pub fn create_scorer(&self, name: &str, badges: Map<BadgeId, u32>) -> ScorerContractClient<'static> {
+        let salt = BytesN::from_array(&self.env, &self.next_salt());
+        let scorer_address = self.factory.create_scorer(
+            &self.admin,
+            &salt,
+            &Symbol::new(&self.env, "initialize"),
+            &self.scorer_init_args(name, badges),
+        );
+        ScorerContractClient::new(&self.env, &scorer_address)
+    }
+
+    /// Returns the first scorer created with the world.
+    pub fn scorer(&self) -> &ScorerContractClient<'static> {
+        self.scorers.first().expect("TestWorld has no scorers")
+    }
+
+    // Salts only need to be unique per factory, so a counter is enough.
+    fn next_salt(&self) -> [u8; 32] {
+        let nonce = self.salt_nonce.get() + 1;
+        self.salt_nonce.set(nonce);
+
+        let mut salt = [0; 32];
+        salt[..4].copy_from_slice(&nonce.to_be_bytes());
+        salt
+    }
+}
+
+impl Default for TestWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
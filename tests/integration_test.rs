@@ -3,38 +3,51 @@ use soroban_sdk::{
     Address, Env, BytesN, Map, String, Vec, Val, Symbol, symbol_short
  };
  use deployer::{Deployer, DeployerClient as DeployerContractClient}; 
- use scorer_factory::{ScorerFactoryContractClient, ScorerFactoryContract};
+ use scorer_factory::{events, ScorerFactoryContractClient, ScorerFactoryContract};
  use scorer::ScorerContractClient;
- use scorer::BadgeId;
+ use scorer::{BadgeId, Extension};
+ use issuer_registry::{IssuerRegistryContract, IssuerRegistryContractClient};
+ use trustful_stellar_v1::test_utils::{TestWorld, TestWorldConfig};
+ use test_utils::{EventRecord, EventSnapshot};
+ use mock_issuer::{MockIssuerContract, MockIssuerContractClient};
  
- soroban_sdk::contractimport!(
-    file = "wasm/deployer.wasm"
- );
+ mod deployer_wasm {
+    soroban_sdk::contractimport!(
+        file = "wasm/deployer.wasm"
+    );
+ }
  
  fn install_scorer_wasm(e: &Env) -> BytesN<32> {
     soroban_sdk::contractimport!(
         file = "wasm/scorer.wasm"
     );
-    e.deployer().upload_contract_wasm(WASM)
+    // Validating the upload takes about the whole default budget, which on the
+    // network would be spent by a transaction of its own
+    e.cost_estimate().budget().reset_unlimited();
+    let wasm_hash = e.deployer().upload_contract_wasm(WASM);
+    e.cost_estimate().budget().reset_default();
+    wasm_hash
  }
  
  fn install_scorer_factory_wasm(e: &Env) -> BytesN<32> {
     soroban_sdk::contractimport!(
         file = "wasm/scorer_factory.wasm"
     );
-    e.deployer().upload_contract_wasm(WASM)
+    let wasm_hash = e.deployer().upload_contract_wasm(WASM);
+    e.cost_estimate().budget().reset_default();
+    wasm_hash
  }
  
  mod factory_tests {
     use super::*;
-    use soroban_sdk::{testutils::{Address as _, Events}, IntoVal};
+    use soroban_sdk::IntoVal;
  
 
     fn setup_contract() -> (Env, Address, ScorerFactoryContractClient<'static>) {
         let env = Env::default();
         env.mock_all_auths();
         let scorer_factory_creator = Address::generate(&env);
-        let scorer_factory_contract_id = env.register_contract(None, ScorerFactoryContract);
+        let scorer_factory_contract_id = env.register(ScorerFactoryContract, ());
         let scorer_factory_client = ScorerFactoryContractClient::new(&env, &scorer_factory_contract_id);
         
         // Upload the real scorer WASM and get its hash
@@ -83,14 +96,14 @@ use soroban_sdk::{
             
             assert!(!scorer_address.to_string().is_empty());
             
-            let expected_event = (
-                scorer_factory_client.address.clone(),
-                (String::from_str(&env, "scorer"), symbol_short!("create")).into_val(&env),
-                (scorer_factory_creator, scorer_address, name, description, icon).into_val(&env)
+            let expected_event = EventRecord::new(
+                &env,
+                &scorer_factory_client.address,
+                (symbol_short!("scorer"), symbol_short!("create")),
+                events::ScorerCreated { creator: scorer_factory_creator, scorer: scorer_address, name, description, icon },
             );
 
-            assert!(env.events().all().contains(&expected_event), 
-                "Expected scorer creation event not found in events list");
+            EventSnapshot::capture(&env).assert_contains(&[expected_event]);
         }
     
     #[test]
@@ -99,7 +112,7 @@ use soroban_sdk::{
         env.mock_all_auths();
         
         let admin = Address::generate(&env);
-        let deployer_id = env.register_contract(None, Deployer);
+        let deployer_id = env.register(Deployer, ());
         let deployer_client = DeployerContractClient::new(&env, &deployer_id);
  
         let factory_wasm_hash = install_scorer_factory_wasm(&env);
@@ -117,7 +130,7 @@ use soroban_sdk::{
             &init_args
         );
  
-        let factory_client = ScorerFactoryContractClient::new(&env, &factory_id.0);
+        let factory_client = ScorerFactoryContractClient::new(&env, &factory_id);
         assert!(factory_client.is_initialized());
         assert!(factory_client.is_manager(&admin));
     }
@@ -126,7 +139,7 @@ use soroban_sdk::{
     fn test_get_scorers() {
         let (env, scorer_factory_creator, scorer_factory_client) = setup_contract();
         let scorers = scorer_factory_client.get_scorers();
-        assert!(scorers.len() == 0);
+        assert!(scorers.is_empty());
 
         let salt = BytesN::from_array(&env, &[1; 32]);
         let init_fn = Symbol::new(&env, "initialize");
@@ -166,13 +179,14 @@ use soroban_sdk::{
  
  mod integration_tests {
     use super::*;
-    use soroban_sdk::{testutils::{Address as _, Events}, IntoVal};
+    use scorer::UserStatus;
+    use soroban_sdk::IntoVal;
  
     fn setup_contract() -> (Env, Address, ScorerFactoryContractClient<'static>) {
         let env = Env::default();
         env.mock_all_auths();
         let scorer_factory_creator = Address::generate(&env);
-        let scorer_factory_contract_id = env.register_contract(None, ScorerFactoryContract);
+        let scorer_factory_contract_id = env.register(ScorerFactoryContract, ());
         let scorer_factory_client = ScorerFactoryContractClient::new(&env, &scorer_factory_contract_id);
         
         // Upload the real scorer WASM and get its hash
@@ -190,10 +204,9 @@ use soroban_sdk::{
         // Generate addresses for testing
         let admin = Address::generate(&env);
         let new_manager = Address::generate(&env);
-        let user = Address::generate(&env);
         
         // Step 1: Deploy deployer contract
-        let deployer_id: Address = env.register_contract(None, Deployer);
+        let deployer_id: Address = env.register(Deployer, ());
         let deployer_client = DeployerContractClient::new(&env, &deployer_id);
 
         // Step 2: Call deploy to deploy the factory contract
@@ -202,7 +215,7 @@ use soroban_sdk::{
         
         let mut init_args: Vec<Val> = Vec::new(&env);   
         init_args.push_back(admin.clone().into_val(&env));
-        init_args.push_back(scorer_wasm_hash.into_val(&env));
+        init_args.push_back(scorer_wasm_hash.clone().into_val(&env));
 
         let factory_id = deployer_client.deploy(
             &admin,
@@ -212,7 +225,19 @@ use soroban_sdk::{
             &init_args
         );
 
-        let factory_client = ScorerFactoryContractClient::new(&env, &factory_id.0);
+        let factory_client = ScorerFactoryContractClient::new(&env, &factory_id);
+        EventSnapshot::capture(&env).for_contract(&factory_client.address).assert_eq(&[EventRecord::new(
+            &env,
+            &factory_client.address,
+            (symbol_short!("init"), symbol_short!("contract")),
+            events::FactoryInitialized {
+                creator: admin.clone(),
+                managers: soroban_sdk::vec![&env, admin.clone()],
+                scorer_wasm_hash: scorer_wasm_hash.clone(),
+            },
+        )]);
+
+        env.cost_estimate().budget().reset_default();
 
         // Step 3: Check if factory is initialized
         assert!(factory_client.is_initialized());
@@ -222,18 +247,15 @@ use soroban_sdk::{
 
         // Step 5: Add a new manager and verify event
         factory_client.add_manager(&admin, &new_manager);
+        EventSnapshot::capture(&env).for_contract(&factory_client.address).assert_eq(&[EventRecord::new(
+            &env,
+            &factory_client.address,
+            (symbol_short!("manager"), symbol_short!("add")),
+            events::ManagerAdded { sender: admin.clone(), manager: new_manager.clone() },
+        )]);
         assert!(factory_client.is_manager(&new_manager));
-        assert_eq!(
-            env.events().all(),
-            soroban_sdk::vec![
-                &env,
-                (
-                    factory_client.address.clone(),
-                    (String::from_str(&env, "manager"), symbol_short!("add")).into_val(&env),
-                    (admin.clone(), new_manager.clone()).into_val(&env)
-                )
-            ]
-        );
+
+        env.cost_estimate().budget().reset_default();
 
         // Step 6: Create a scorer contract
         let salt = BytesN::from_array(&env, &[1; 32]);
@@ -278,7 +300,7 @@ use soroban_sdk::{
         assert_eq!(scorers.len(), 1);
         assert_eq!(scorers.get(scorer_address.clone()).unwrap(), (name, description, icon));
 
-        env.budget().reset_default();
+        env.cost_estimate().budget().reset_default();
 
         // Step 9: Create scorer client and verify badges
         let scorer_client = ScorerContractClient::new(&env, &scorer_address);
@@ -307,7 +329,7 @@ use soroban_sdk::{
         let mut new_scorer_init_args: Vec<Val> = Vec::new(&env);
         new_scorer_init_args.push_back(new_manager.clone().into_val(&env));
         new_scorer_init_args.push_back(new_scorer_badges.into_val(&env));
-        new_scorer_init_args.push_back(String::from_str(&env, "new_scorer").into_val(&env));
+        new_scorer_init_args.push_back(String::from_str(&env, "second_scorer").into_val(&env));
         new_scorer_init_args.push_back(String::from_str(&env, "scorer's description").into_val(&env));
         new_scorer_init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
 
@@ -321,20 +343,23 @@ use soroban_sdk::{
         // Step 11: Verify second scorer
         let scorers = factory_client.get_scorers();
         assert_eq!(scorers.len(), 2);
-        let name = String::from_str(&env, "new_scorer");
+        let name = String::from_str(&env, "second_scorer");
         let description = String::from_str(&env, "scorer's description");
         let icon = String::from_str(&env, "icon.png");
         assert_eq!(scorers.get(new_scorer_address.clone()).unwrap(), (name, description, icon));
 
         // Step 12: Remove manager and verify event
         factory_client.remove_manager(&admin, &new_manager);
+        EventSnapshot::capture(&env).assert_contains(&[EventRecord::new(
+            &env,
+            &factory_client.address,
+            (symbol_short!("manager"), symbol_short!("remove")),
+            events::ManagerRemoved { sender: admin.clone(), manager: new_manager.clone() },
+        )]);
 
         assert!(!factory_client.is_manager(&new_manager));
-        assert!(env.events().all().contains(&(
-            factory_client.address.clone(),
-            (String::from_str(&env, "manager"), symbol_short!("remove")).into_val(&env),
-            (admin.clone(), new_manager.clone()).into_val(&env)
-        )));
+
+        env.cost_estimate().budget().reset_default();
 
         // Step 13: Add user to scorer
         let user = Address::generate(&env);
@@ -346,99 +371,68 @@ use soroban_sdk::{
         new_scorer_client.add_user(&user);
         assert!(new_scorer_client.get_users().contains_key(user.clone()));
 
+        env.cost_estimate().budget().reset_default();
+
         // Step 15: Remove user from first scorer
         scorer_client.remove_user(&user);
-        assert_eq!(scorer_client.get_users().get(user.clone()), Some(false));
+        assert_eq!(scorer_client.get_users().get(user.clone()), Some(UserStatus::Left));
 
         // Step 16: Verify user can remove themselves from second scorer
         new_scorer_client.remove_user(&user);
-        assert_eq!(new_scorer_client.get_users().get(user.clone()), Some(false));
+        assert_eq!(new_scorer_client.get_users().get(user.clone()), Some(UserStatus::Left));
 
     }
 
     #[test]
     fn test_remove_scorer() {
-        let (env, admin, factory_client) = setup_contract();
-        let manager = Address::generate(&env);
-        
-        // Add manager
-        factory_client.add_manager(&admin, &manager);
-        assert!(factory_client.is_manager(&manager));
+        let world = TestWorld::new();
+        let manager = Address::generate(&world.env);
 
-        // Create a scorer with new badge structure
-        let mut scorer_badges = Map::new(&env);
-        let badge_id = BadgeId {
-            name: String::from_str(&env, "Test Badge"),
-            issuer: admin.clone(),
-        };
-        
-        let score: u32 = 100;
-        
-        scorer_badges.set(badge_id, score);
-        
-        let mut init_args: Vec<Val> = Vec::new(&env);
-        init_args.push_back(admin.clone().into_val(&env));
-        init_args.push_back(scorer_badges.into_val(&env));
-        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
-        init_args.push_back(String::from_str(&env, "A test scorer").into_val(&env));
-        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
-
-        let scorer_address = factory_client.create_scorer(
-            &admin,
-            &BytesN::from_array(&env, &[1_u8; 32]),
-            &Symbol::new(&env, "initialize"),
-            &init_args
-        );
+        // Add manager
+        world.factory.add_manager(&world.admin, &manager);
+        assert!(world.factory.is_manager(&manager));
 
         // Verify scorer was created
-        let scorers = factory_client.get_scorers();
+        let scorer_address = world.scorer().address.clone();
+        let scorers = world.factory.get_scorers();
         assert!(scorers.contains_key(scorer_address.clone()));
 
         // Remove the scorer using the manager
-        factory_client.remove_scorer(&manager, &scorer_address);
+        world.factory.remove_scorer(&manager, &scorer_address);
 
         // Verify scorer was removed
-        let scorers_after = factory_client.get_scorers();
-        assert!(!scorers_after.contains_key(scorer_address));
+        let scorers_after = world.factory.get_scorers();
+        assert!(!scorers_after.contains_key(scorer_address.clone()));
+
+        // The scorer did not list the factory, so it was not archived
+        assert!(!world.scorer().is_archived());
     }
 
     #[test]
-    #[should_panic(expected = "Unauthorized")]
-    fn test_remove_scorer_unauthorized() {
-        let (env, admin, factory_client) = setup_contract();
-        let non_manager = Address::generate(&env);
-        
-        // Create a scorer with new badge structure
-        let mut scorer_badges = Map::new(&env);
-        let badge_id = BadgeId {
-            name: String::from_str(&env, "Test Badge"),
-            issuer: admin.clone(),
-        };
-        
-        let score: u32 = 100;
-        
-        scorer_badges.set(badge_id, score);
-        
-        let mut init_args: Vec<Val> = Vec::new(&env);
-        init_args.push_back(admin.clone().into_val(&env));
-        init_args.push_back(scorer_badges.into_val(&env));
-        init_args.push_back(String::from_str(&env, "Test Scorer").into_val(&env));
-        init_args.push_back(String::from_str(&env, "A test scorer").into_val(&env));
-        init_args.push_back(String::from_str(&env, "icon.png").into_val(&env));
+    fn test_remove_scorer_archives_linked_scorer() {
+        let world = TestWorld::new();
+        let scorer_client = world.scorer();
+        scorer_client.set_extension(&world.admin, &Extension::Factory, &Some(world.factory.address.clone()));
 
-        let scorer_address = factory_client.create_scorer(
-            &admin,
-            &BytesN::from_array(&env, &[1_u8; 32]),
-            &Symbol::new(&env, "initialize"),
-            &init_args
-        );
+        world.factory.remove_scorer(&world.admin, &scorer_client.address);
+
+        assert!(scorer_client.is_archived());
+        assert!(scorer_client.try_add_user(&Address::generate(&world.env)).is_err());
+    }
 
-        // Attempt to remove the scorer with a non-manager (should panic)
-        factory_client.remove_scorer(&non_manager, &scorer_address);
+    #[test]
+    fn test_remove_scorer_unauthorized() {
+        let world = TestWorld::new();
+        let non_manager = Address::generate(&world.env);
+
+        // Attempt to remove the scorer with a non-manager
+        let result = world.factory.try_remove_scorer(&non_manager, &world.scorer().address);
+        assert_eq!(result, Err(Ok(scorer_factory::Error::Unauthorized.into())));
+        assert!(world.factory.get_scorers().contains_key(world.scorer().address.clone()));
     }
 
     #[test]
-    #[should_panic(expected = "ScorerNotFound")]
+    #[should_panic(expected = "Error(Contract, #8)")]
     fn test_remove_nonexistent_scorer() {
         let (env, admin, factory_client) = setup_contract();
 
@@ -446,4 +440,103 @@ use soroban_sdk::{
         let nonexistent_scorer = Address::generate(&env);
         factory_client.remove_scorer(&admin, &nonexistent_scorer);
     }
- }
\ No newline at end of file
+
+    #[test]
+    fn test_upgrade_guarded_by_factory_allowlist() {
+        let world = TestWorld::new();
+        let scorer_client = world.scorer();
+        scorer_client.enable_upgrade_guard(&world.admin, &world.factory.address);
+
+        // An unapproved hash is rejected by the scorer
+        let new_wasm_hash = install_scorer_wasm(&world.env);
+        assert!(scorer_client.try_upgrade(&new_wasm_hash).is_err());
+
+        // Once approved on the factory, the upgrade goes through
+        world.factory.approve_wasm_hash(&world.admin, &new_wasm_hash);
+        scorer_client.upgrade(&new_wasm_hash);
+        assert_eq!(scorer_client.contract_version(), 1);
+    }
+
+    #[test]
+    fn test_issuer_badges_require_verified_issuer() {
+        let world = TestWorld::new();
+        let scorer_client = world.scorer();
+        let registry = IssuerRegistryContractClient::new(&world.env, &world.env.register(IssuerRegistryContract, ()));
+        registry.initialize(&world.admin);
+        scorer_client.set_extension(&world.admin, &Extension::IssuerRegistry, &Some(registry.address.clone()));
+
+        let issuer_address = world.env.register(MockIssuerContract, (&world.admin, BytesN::from_array(&world.env, &[0; 32])));
+        let issuer = MockIssuerContractClient::new(&world.env, &issuer_address);
+        scorer_client.add_manager(&world.admin, &issuer.address);
+        let badge = String::from_str(&world.env, "Issued Badge");
+
+        // The scorer rejects badges of issuers the registry has not listed
+        assert!(issuer.try_register_badge(&scorer_client.address, &badge, &300).is_err());
+
+        registry.list_issuer(&issuer.address);
+        issuer.register_badge(&scorer_client.address, &badge, &300);
+
+        let badge_id = BadgeId { name: badge, issuer: issuer.address.clone() };
+        assert_eq!(scorer_client.get_badges().get(badge_id), Some(300));
+    }
+
+    #[test]
+    fn test_factory_verifies_issuers_through_issuer_registry() {
+        let world = TestWorld::new();
+        let scorer_client = world.scorer();
+        let registry = IssuerRegistryContractClient::new(&world.env, &world.env.register(IssuerRegistryContract, ()));
+        registry.initialize(&world.admin);
+
+        // The scorer trusts the factory, which answers from the shared issuer registry
+        world.factory.set_issuer_registry(&world.admin, &Some(registry.address.clone()));
+        scorer_client.set_extension(&world.admin, &Extension::IssuerRegistry, &Some(world.factory.address.clone()));
+
+        let issuer = Address::generate(&world.env);
+        let badge = String::from_str(&world.env, "Registry Badge");
+        assert!(scorer_client.try_add_badge(&world.admin, &badge, &issuer, &100).is_err());
+
+        registry.list_issuer(&issuer);
+        assert!(world.factory.is_verified_issuer(&issuer));
+        scorer_client.add_badge(&world.admin, &badge, &issuer, &100);
+        assert_eq!(scorer_client.get_badges().get(BadgeId { name: badge, issuer }), Some(100));
+    }
+
+    #[test]
+    fn test_factory_verifies_issuers_it_lists() {
+        let world = TestWorld::new();
+        let scorer_client = world.scorer();
+        scorer_client.set_extension(&world.admin, &Extension::IssuerRegistry, &Some(world.factory.address.clone()));
+
+        // Without an issuer registry, the factory only verifies the issuers it lists itself
+        let issuer = Address::generate(&world.env);
+        let badge = String::from_str(&world.env, "Listed Badge");
+        assert!(scorer_client.try_add_badge(&world.admin, &badge, &issuer, &100).is_err());
+
+        world.factory.add_verified_issuer(&world.admin, &issuer);
+        scorer_client.add_badge(&world.admin, &badge, &issuer, &100);
+        assert_eq!(scorer_client.get_badges().get(BadgeId { name: badge.clone(), issuer: issuer.clone() }), Some(100));
+
+        // Removing the issuer keeps its badges but stops new ones
+        world.factory.remove_verified_issuer(&world.admin, &issuer);
+        let other = String::from_str(&world.env, "Other Badge");
+        assert!(scorer_client.try_add_badge(&world.admin, &other, &issuer, &100).is_err());
+        assert!(scorer_client.get_badges().contains_key(BadgeId { name: badge, issuer }));
+    }
+
+    #[test]
+    fn test_world_seeds_state() {
+        let world = TestWorld::with_config(TestWorldConfig {
+            scorers: 2,
+            badges: 3,
+            users: 2,
+            managers: 1,
+        });
+
+        assert_eq!(world.factory.get_scorers().len(), 2);
+        for scorer_client in world.scorers.iter() {
+            assert_eq!(scorer_client.get_badges().len(), 3);
+            assert_eq!(scorer_client.get_users().len(), 2);
+            assert!(scorer_client.get_managers().contains(&world.managers[0]));
+        }
+    }
+ }
@@ -0,0 +1,242 @@
+//! Declarative scenario runner for end-to-end flows.
+//!
+//! A scenario is a list of [`Step`]s run in order against a fresh
+//! [`TestWorld`]: a deployer, a factory deployed through it and the scorers the
+//! scenario creates, and an issuer registry the scorers can verify issuers
+//! against. Actors are referred to by name and created on first use:
+//! accounts are generated addresses (`"admin"` is the world admin, who owns the
+//! factory and every scorer) and issuers are mock issuer contracts administered
+//! by the admin. Every step must succeed unless it is wrapped in
+//! [`Step::Fails`]; the first step that does not behave as scripted panics with
+//! the scenario name, the step index and the step.
+use std::collections::BTreeMap;
+
+use issuer_registry::{IssuerRegistryContract, IssuerRegistryContractClient};
+use mock_issuer::{MockIssuerContract, MockIssuerContractClient};
+use scorer::{Extension, ScorerContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Map, String, Symbol, Vec};
+use trustful_stellar_v1::test_utils::{TestWorld, TestWorldConfig};
+
+/// Name of the world admin account.
+pub const ADMIN: &str = "admin";
+
+#[derive(Clone, Copy, Debug)]
+pub enum Step {
+    /// The admin creates a scorer with no badges through the factory and lets
+    /// the factory archive it.
+    CreateScorer { scorer: &'static str },
+    /// The scorer only accepts badges of issuers listed in the issuer registry.
+    RequireVerifiedIssuers { scorer: &'static str },
+    /// The admin lists an issuer in the issuer registry.
+    VerifyIssuer { issuer: &'static str },
+    /// The admin adds a manager to a scorer.
+    AddManager { scorer: &'static str, manager: &'static str },
+    /// `sender` adds a badge of `issuer` to a scorer.
+    AddBadge { scorer: &'static str, sender: &'static str, badge: &'static str, issuer: &'static str, score: u32 },
+    /// `sender` removes a badge of `issuer` from a scorer.
+    RemoveBadge { scorer: &'static str, sender: &'static str, badge: &'static str, issuer: &'static str },
+    /// A user joins a scorer.
+    Join { scorer: &'static str, user: &'static str },
+    /// A user leaves a scorer.
+    Leave { scorer: &'static str, user: &'static str },
+    /// An issuer attests that a user holds a badge.
+    Attest { issuer: &'static str, user: &'static str, badge: &'static str },
+    /// An issuer revokes a badge from a user.
+    Revoke { issuer: &'static str, user: &'static str, badge: &'static str },
+    /// The admin removes a scorer from the factory, which archives it.
+    Archive { scorer: &'static str },
+    /// Checks the score a user earns in a scorer, as computed by its `get_score`
+    /// from the scorer's badges the user holds.
+    ExpectScore { scorer: &'static str, user: &'static str, score: u32 },
+    /// Checks the number of badges of a scorer.
+    ExpectBadges { scorer: &'static str, count: u32 },
+    /// Checks whether a scorer is archived.
+    ExpectArchived { scorer: &'static str, archived: bool },
+    /// The wrapped step must fail.
+    Fails(&'static Step),
+}
+
+/// State of a running scenario.
+pub struct Scenario {
+    pub world: TestWorld,
+    pub registry: IssuerRegistryContractClient<'static>,
+    accounts: BTreeMap<&'static str, Address>,
+    issuers: BTreeMap<&'static str, Address>,
+    scorers: BTreeMap<&'static str, ScorerContractClient<'static>>,
+}
+
+/// Runs the steps of the scenario `name` and returns its final state.
+///
+/// # Panics
+/// * When a step does not succeed, or a step wrapped in `Step::Fails` does not fail
+pub fn run(name: &str, steps: &[Step]) -> Scenario {
+    let world = TestWorld::with_config(TestWorldConfig { scorers: 0, ..Default::default() });
+    let mut accounts = BTreeMap::new();
+    accounts.insert(ADMIN, world.admin.clone());
+    let registry = IssuerRegistryContractClient::new(&world.env, &world.env.register(IssuerRegistryContract, ()));
+    registry.initialize(&world.admin);
+
+    let mut scenario = Scenario {
+        world,
+        registry,
+        accounts,
+        issuers: BTreeMap::new(),
+        scorers: BTreeMap::new(),
+    };
+
+    for (index, step) in steps.iter().enumerate() {
+        let result = match step {
+            Step::Fails(step) => match scenario.apply(step) {
+                Ok(()) => Err("expected to fail, but succeeded".to_string()),
+                Err(_) => Ok(()),
+            },
+            step => scenario.apply(step),
+        };
+        if let Err(error) = result {
+            panic!("scenario `{}` step {} ({:?}): {}", name, index, step, error);
+        }
+    }
+
+    scenario
+}
+
+impl Scenario {
+    /// Returns the address of the account `name`, generating it on first use.
+    pub fn account(&mut self, name: &'static str) -> Address {
+        let env = &self.world.env;
+        self.accounts.entry(name).or_insert_with(|| Address::generate(env)).clone()
+    }
+
+    /// Returns the mock issuer `name`, deploying it on first use.
+    pub fn issuer(&mut self, name: &'static str) -> MockIssuerContractClient<'static> {
+        let env = &self.world.env;
+        let admin = &self.world.admin;
+        let address = self.issuers.entry(name).or_insert_with(|| {
+            env.register(MockIssuerContract, (admin, BytesN::from_array(env, &[0; 32])))
+        });
+        MockIssuerContractClient::new(env, address)
+    }
+
+    /// Returns the scorer `name` created by the scenario.
+    pub fn scorer(&self, name: &str) -> Result<&ScorerContractClient<'static>, std::string::String> {
+        self.scorers.get(name).ok_or_else(|| format!("unknown scorer `{}`", name))
+    }
+
+    fn apply(&mut self, step: &Step) -> Result<(), std::string::String> {
+        let env = self.world.env.clone();
+        let admin = self.world.admin.clone();
+        let string = |value: &str| String::from_str(&env, value);
+
+        let succeeded = match *step {
+            Step::CreateScorer { scorer } => {
+                // Salts only need to be unique per factory
+                let mut salt = [0; 32];
+                salt[..4].copy_from_slice(&(self.scorers.len() as u32 + 1).to_be_bytes());
+                let created = self.world.factory.try_create_scorer(
+                    &admin,
+                    &BytesN::from_array(&env, &salt),
+                    &Symbol::new(&env, "initialize"),
+                    &self.world.scorer_init_args(scorer, Map::new(&env)),
+                );
+                let Ok(Ok(address)) = created else {
+                    return Err("call failed".to_string());
+                };
+
+                let client = ScorerContractClient::new(&env, &address);
+                let factory = Some(self.world.factory.address.clone());
+                let succeeded = is_ok(client.try_set_extension(&admin, &Extension::Factory, &factory));
+                self.scorers.insert(scorer, client);
+                succeeded
+            }
+            Step::RequireVerifiedIssuers { scorer } => {
+                let registry = Some(self.registry.address.clone());
+                is_ok(self.scorer(scorer)?.try_set_extension(&admin, &Extension::IssuerRegistry, &registry))
+            }
+            Step::VerifyIssuer { issuer } => {
+                let issuer = self.issuer(issuer).address;
+                is_ok(self.registry.try_list_issuer(&issuer))
+            }
+            Step::AddManager { scorer, manager } => {
+                let manager = self.account(manager);
+                is_ok(self.scorer(scorer)?.try_add_manager(&admin, &manager))
+            }
+            Step::AddBadge { scorer, sender, badge, issuer, score } => {
+                let sender = self.account(sender);
+                let issuer = self.issuer(issuer).address;
+                is_ok(self.scorer(scorer)?.try_add_badge(&sender, &string(badge), &issuer, &score))
+            }
+            Step::RemoveBadge { scorer, sender, badge, issuer } => {
+                let sender = self.account(sender);
+                let issuer = self.issuer(issuer).address;
+                is_ok(self.scorer(scorer)?.try_remove_badge(&sender, &string(badge), &issuer))
+            }
+            Step::Join { scorer, user } => {
+                let user = self.account(user);
+                is_ok(self.scorer(scorer)?.try_add_user(&user))
+            }
+            Step::Leave { scorer, user } => {
+                let user = self.account(user);
+                is_ok(self.scorer(scorer)?.try_remove_user(&user))
+            }
+            Step::Attest { issuer, user, badge } => {
+                let user = self.account(user);
+                is_ok(self.issuer(issuer).try_issue(&user, &string(badge)))
+            }
+            Step::Revoke { issuer, user, badge } => {
+                let user = self.account(user);
+                is_ok(self.issuer(issuer).try_revoke(&user, &string(badge)))
+            }
+            Step::Archive { scorer } => {
+                let scorer = self.scorer(scorer)?.address.clone();
+                is_ok(self.world.factory.try_remove_scorer(&admin, &scorer))
+            }
+            Step::ExpectScore { scorer, user, score } => {
+                let user = self.account(user);
+                let actual = self.score(scorer, &user)?;
+                return expect("score", score, actual);
+            }
+            Step::ExpectBadges { scorer, count } => {
+                return expect("badge count", count, self.scorer(scorer)?.get_badges().len());
+            }
+            Step::ExpectArchived { scorer, archived } => {
+                return expect("archived", archived, self.scorer(scorer)?.is_archived());
+            }
+            Step::Fails(step) => return self.apply(step),
+        };
+
+        if succeeded {
+            Ok(())
+        } else {
+            Err("call failed".to_string())
+        }
+    }
+
+    // Badges of issuers the scenario did not deploy cannot be attested, so
+    // they never count towards a score.
+    fn score(&self, scorer: &str, user: &Address) -> Result<u32, std::string::String> {
+        let scorer = self.scorer(scorer)?;
+        let mut held = Vec::new(&self.world.env);
+        for (badge, _) in scorer.get_badges().iter() {
+            let attested = self.issuers.values().any(|issuer| {
+                *issuer == badge.issuer
+                    && MockIssuerContractClient::new(&self.world.env, issuer).has_badge(user, &badge.name)
+            });
+            if attested {
+                held.push_back(badge);
+            }
+        }
+        Ok(scorer.get_score(user, &held))
+    }
+}
+
+fn is_ok<T, E, F, G>(result: Result<Result<T, F>, Result<E, G>>) -> bool {
+    matches!(result, Ok(Ok(_)))
+}
+
+fn expect<T: PartialEq + std::fmt::Debug>(what: &str, expected: T, actual: T) -> Result<(), std::string::String> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!("expected {} {:?}, got {:?}", what, expected, actual))
+    }
+}
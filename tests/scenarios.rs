@@ -0,0 +1,103 @@
+//! End-to-end flows across the deployer, factory, scorers and issuers,
+//! scripted with the runner in `scenario/`.
+mod scenario;
+
+use scenario::{run, Step::*, ADMIN};
+
+#[test]
+fn test_community_lifecycle() {
+    run("community lifecycle", &[
+        // Create the community, accepting only verified issuers
+        CreateScorer { scorer: "dao" },
+        RequireVerifiedIssuers { scorer: "dao" },
+        VerifyIssuer { issuer: "quests" },
+        AddBadge { scorer: "dao", sender: ADMIN, badge: "Onboarding", issuer: "quests", score: 100 },
+        AddBadge { scorer: "dao", sender: ADMIN, badge: "Governance", issuer: "quests", score: 250 },
+        ExpectBadges { scorer: "dao", count: 2 },
+        // Users join and earn badges
+        Join { scorer: "dao", user: "alice" },
+        Join { scorer: "dao", user: "bob" },
+        Attest { issuer: "quests", user: "alice", badge: "Onboarding" },
+        Attest { issuer: "quests", user: "alice", badge: "Governance" },
+        Attest { issuer: "quests", user: "bob", badge: "Onboarding" },
+        ExpectScore { scorer: "dao", user: "alice", score: 350 },
+        ExpectScore { scorer: "dao", user: "bob", score: 100 },
+        // Retire the community
+        Archive { scorer: "dao" },
+        ExpectArchived { scorer: "dao", archived: true },
+        Fails(&Join { scorer: "dao", user: "carol" }),
+        ExpectScore { scorer: "dao", user: "alice", score: 350 },
+    ]);
+}
+
+#[test]
+fn test_unverified_issuer_is_rejected() {
+    run("unverified issuer", &[
+        CreateScorer { scorer: "dao" },
+        RequireVerifiedIssuers { scorer: "dao" },
+        Fails(&AddBadge { scorer: "dao", sender: ADMIN, badge: "Spam", issuer: "unknown", score: 500 }),
+        VerifyIssuer { issuer: "unknown" },
+        AddBadge { scorer: "dao", sender: ADMIN, badge: "Spam", issuer: "unknown", score: 500 },
+        ExpectBadges { scorer: "dao", count: 1 },
+    ]);
+}
+
+#[test]
+fn test_scores_follow_membership_and_attestations() {
+    run("membership and attestations", &[
+        CreateScorer { scorer: "dao" },
+        AddBadge { scorer: "dao", sender: ADMIN, badge: "Contributor", issuer: "github", score: 300 },
+        AddBadge { scorer: "dao", sender: ADMIN, badge: "Speaker", issuer: "events", score: 200 },
+        Attest { issuer: "github", user: "alice", badge: "Contributor" },
+        Attest { issuer: "events", user: "alice", badge: "Speaker" },
+        // Badges only count while the user is a member
+        ExpectScore { scorer: "dao", user: "alice", score: 0 },
+        Join { scorer: "dao", user: "alice" },
+        ExpectScore { scorer: "dao", user: "alice", score: 500 },
+        Leave { scorer: "dao", user: "alice" },
+        ExpectScore { scorer: "dao", user: "alice", score: 0 },
+        Fails(&Leave { scorer: "dao", user: "alice" }),
+        Join { scorer: "dao", user: "alice" },
+        // Revoked attestations and removed badges stop counting
+        Revoke { issuer: "events", user: "alice", badge: "Speaker" },
+        ExpectScore { scorer: "dao", user: "alice", score: 300 },
+        RemoveBadge { scorer: "dao", sender: ADMIN, badge: "Contributor", issuer: "github" },
+        ExpectScore { scorer: "dao", user: "alice", score: 0 },
+        Fails(&Revoke { issuer: "events", user: "alice", badge: "Speaker" }),
+    ]);
+}
+
+#[test]
+fn test_communities_share_issuers() {
+    run("shared issuers", &[
+        CreateScorer { scorer: "dao" },
+        CreateScorer { scorer: "guild" },
+        AddBadge { scorer: "dao", sender: ADMIN, badge: "Builder", issuer: "quests", score: 100 },
+        AddBadge { scorer: "guild", sender: ADMIN, badge: "Builder", issuer: "quests", score: 400 },
+        Join { scorer: "dao", user: "alice" },
+        Join { scorer: "guild", user: "alice" },
+        Attest { issuer: "quests", user: "alice", badge: "Builder" },
+        // Each community weighs the same attestation differently
+        ExpectScore { scorer: "dao", user: "alice", score: 100 },
+        ExpectScore { scorer: "guild", user: "alice", score: 400 },
+        // Archiving one community leaves the other untouched
+        Archive { scorer: "dao" },
+        ExpectArchived { scorer: "guild", archived: false },
+        Join { scorer: "guild", user: "bob" },
+    ]);
+}
+
+#[test]
+fn test_managers_curate_badges() {
+    run("manager curation", &[
+        CreateScorer { scorer: "dao" },
+        Fails(&AddBadge { scorer: "dao", sender: "curator", badge: "Mentor", issuer: "quests", score: 150 }),
+        AddManager { scorer: "dao", manager: "curator" },
+        Fails(&AddManager { scorer: "dao", manager: "curator" }),
+        AddBadge { scorer: "dao", sender: "curator", badge: "Mentor", issuer: "quests", score: 150 },
+        Fails(&AddBadge { scorer: "dao", sender: "curator", badge: "Mentor", issuer: "quests", score: 150 }),
+        Fails(&RemoveBadge { scorer: "dao", sender: "stranger", badge: "Mentor", issuer: "quests" }),
+        RemoveBadge { scorer: "dao", sender: "curator", badge: "Mentor", issuer: "quests" },
+        ExpectBadges { scorer: "dao", count: 0 },
+    ]);
+}
@@ -0,0 +1,275 @@
+//! Upgrade compatibility of the scorer and the scorer factory.
+//!
+//! Contracts are deployed from the previous release (`wasm/scorer_previous.wasm`,
+//! `wasm/scorer_factory_previous.wasm`), populated through that release's own
+//! interface, then upgraded to the current code and migrated. Their state must
+//! survive unchanged.
+use scorer::{BadgeId, Extension, ScorerContractClient, UserStatus};
+use scorer_factory::ScorerFactoryContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec};
+use trustful_stellar_v1::test_utils::{scorer_contract, scorer_factory_contract};
+
+mod previous_scorer {
+    soroban_sdk::contractimport!(
+        file = "wasm/scorer_previous.wasm"
+    );
+}
+
+mod previous_scorer_factory {
+    soroban_sdk::contractimport!(
+        file = "wasm/scorer_factory_previous.wasm"
+    );
+}
+
+fn setup() -> Env {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    env
+}
+
+/// State written by the previous release, as read back through it.
+struct PreviousState {
+    owner: Address,
+    badges: Map<BadgeId, u32>,
+    users: Map<Address, bool>,
+    managers: Vec<Address>,
+}
+
+/// Deploys a scorer from the previous release and populates it with badges from
+/// several issuers, active and removed users, and managers.
+fn deploy_previous(env: &Env) -> (ScorerContractClient<'static>, PreviousState) {
+    let owner = Address::generate(env);
+    let issuers = [Address::generate(env), Address::generate(env)];
+
+    let mut badges = Map::new(env);
+    for (i, issuer) in issuers.iter().enumerate() {
+        badges.set(
+            previous_scorer::BadgeId { name: String::from_str(env, "Initial"), issuer: issuer.clone() },
+            (i as u32 + 1) * 100,
+        );
+    }
+
+    let address = env.register(previous_scorer::WASM, ());
+    let previous = previous_scorer::Client::new(env, &address);
+    previous.initialize(
+        &owner,
+        &badges,
+        &String::from_str(env, "Previous Scorer"),
+        &String::from_str(env, "Deployed from the previous release"),
+        &String::from_str(env, "https://trustful.example/icon.png"),
+    );
+
+    let managers = [Address::generate(env), Address::generate(env), Address::generate(env)];
+    for manager in managers.iter() {
+        previous.add_manager(&owner, manager);
+    }
+    previous.remove_manager(&owner, &managers[2]);
+
+    previous.add_badge(&managers[0], &String::from_str(env, "Added"), &issuers[0], &250);
+    previous.add_badge(&managers[1], &String::from_str(env, "Removed"), &issuers[1], &50);
+    previous.remove_badge(&managers[1], &String::from_str(env, "Removed"), &issuers[1]);
+
+    let users: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(env)).collect();
+    for user in users.iter() {
+        previous.add_user(user);
+    }
+    previous.remove_user(&users[4]);
+
+    // Read the state back through the previous release, in the current types
+    let mut badges = Map::new(env);
+    for (badge, score) in previous.get_badges().iter() {
+        badges.set(BadgeId { name: badge.name, issuer: badge.issuer }, score);
+    }
+    let state = PreviousState {
+        owner: previous.get_contract_owner(),
+        badges,
+        users: previous.get_users(),
+        managers: previous.get_managers(),
+    };
+    assert_eq!(state.badges.len(), 3);
+    assert_eq!(state.users.len(), 5);
+    assert_eq!(state.managers.len(), 3);
+
+    (ScorerContractClient::new(env, &address), state)
+}
+
+/// Converts the users read through the previous release to their statuses.
+fn statuses(env: &Env, users: &Map<Address, bool>) -> Map<Address, UserStatus> {
+    let mut statuses = Map::new(env);
+    for (user, active) in users.iter() {
+        statuses.set(user, if active { UserStatus::Active } else { UserStatus::Left });
+    }
+    statuses
+}
+
+/// Upgrades the scorer to the current code and migrates its storage.
+fn upgrade_and_migrate(env: &Env, client: &ScorerContractClient, owner: &Address) {
+    let wasm_hash = env.deployer().upload_contract_wasm(scorer_contract::WASM);
+    client.upgrade(&wasm_hash);
+
+    assert_eq!(client.get_storage_version(), 1);
+    // Batches of two users cover the five users in three calls
+    assert_eq!(client.migrate(owner, &0, &2), 2);
+    assert_eq!(client.migrate(owner, &2, &2), 4);
+    assert_eq!(client.get_storage_version(), 1);
+    assert_eq!(client.migrate(owner, &4, &2), 0);
+    assert_eq!(client.get_storage_version(), 2);
+}
+
+#[test]
+fn test_upgrade_preserves_state() {
+    let env = setup();
+
+    let (client, state) = deploy_previous(&env);
+    upgrade_and_migrate(&env, &client, &state.owner);
+
+    assert_eq!(client.get_contract_owner(), state.owner);
+    assert_eq!(client.get_badges(), state.badges);
+    assert_eq!(client.get_users(), statuses(&env, &state.users));
+    assert_eq!(client.get_managers(), state.managers);
+    assert_eq!(
+        client.get_metadata(),
+        (
+            String::from_str(&env, "Previous Scorer"),
+            String::from_str(&env, "Deployed from the previous release"),
+            String::from_str(&env, "https://trustful.example/icon.png"),
+        )
+    );
+
+    // Keys added since the previous release read as their defaults
+    assert!(!client.is_archived());
+    assert_eq!(client.get_extension(&Extension::Factory), None);
+    assert_eq!(client.get_upgrade_guard(), None);
+    assert_eq!(client.get_extension(&Extension::IssuerRegistry), None);
+}
+
+#[test]
+fn test_upgraded_scorer_keeps_working() {
+    let env = setup();
+
+    let (client, state) = deploy_previous(&env);
+    upgrade_and_migrate(&env, &client, &state.owner);
+
+    // Data written by the previous release is still enforced
+    let (user, _) = state.users.iter().find(|(_, active)| *active).unwrap();
+    assert!(client.try_add_user(&user).is_err());
+    let (badge, _) = state.badges.iter().next().unwrap();
+    assert!(client.try_add_badge(&state.owner, &badge.name, &badge.issuer, &1).is_err());
+
+    let manager = state.managers.get(1).unwrap();
+    let issuer = Address::generate(&env);
+    client.add_badge(&manager, &String::from_str(&env, "After Upgrade"), &issuer, &400);
+    assert_eq!(client.get_badges().len(), state.badges.len() + 1);
+
+    let new_user = Address::generate(&env);
+    client.add_user(&new_user);
+    assert_eq!(client.get_users().get(new_user), Some(UserStatus::Active));
+
+    client.set_archived(&state.owner, &true);
+    assert!(client.try_add_user(&Address::generate(&env)).is_err());
+}
+
+#[test]
+fn test_migrate_twice() {
+    let env = setup();
+
+    let (client, state) = deploy_previous(&env);
+    upgrade_and_migrate(&env, &client, &state.owner);
+    assert!(client.try_migrate(&state.owner, &0, &10).is_err());
+}
+
+#[test]
+fn test_migrate_requires_owner() {
+    let env = setup();
+
+    let (client, state) = deploy_previous(&env);
+    let wasm_hash = env.deployer().upload_contract_wasm(scorer_contract::WASM);
+    client.upgrade(&wasm_hash);
+
+    let manager = state.managers.get(1).unwrap();
+    assert!(client.try_migrate(&manager, &0, &10).is_err());
+    assert_eq!(client.get_storage_version(), 1);
+}
+
+#[test]
+fn test_users_readable_before_migrate() {
+    let env = setup();
+
+    let (client, state) = deploy_previous(&env);
+    let wasm_hash = env.deployer().upload_contract_wasm(scorer_contract::WASM);
+    client.upgrade(&wasm_hash);
+
+    // The booleans stored by the previous release are read as statuses
+    assert_eq!(client.get_users(), statuses(&env, &state.users));
+    let (user, _) = state.users.iter().find(|(_, active)| *active).unwrap();
+    client.remove_user(&user);
+    assert_eq!(client.get_user_status(&user), Some(UserStatus::Left));
+
+    // The migration converts the remaining booleans
+    client.migrate(&state.owner, &0, &10);
+    let mut expected = statuses(&env, &state.users);
+    expected.set(user, UserStatus::Left);
+    assert_eq!(client.get_users(), expected);
+}
+
+/// Creates a scorer named `name` through a factory from the previous release.
+fn create_previous_scorer(env: &Env, factory: &previous_scorer_factory::Client, creator: &Address, salt: u8, name: &str) -> Address {
+    let mut init_args: Vec<Val> = Vec::new(env);
+    init_args.push_back(creator.into_val(env));
+    init_args.push_back(Map::<previous_scorer::BadgeId, u32>::new(env).into_val(env));
+    init_args.push_back(String::from_str(env, name).into_val(env));
+    init_args.push_back(String::from_str(env, "Created by the previous release").into_val(env));
+    init_args.push_back(String::from_str(env, "https://trustful.example/icon.png").into_val(env));
+    factory.create_scorer(
+        creator,
+        &BytesN::from_array(env, &[salt; 32]),
+        &Symbol::new(env, "initialize"),
+        &init_args,
+    )
+}
+
+#[test]
+fn test_upgraded_factory_resolves_names() {
+    let env = setup();
+
+    let creator = Address::generate(&env);
+    let address = env.register(previous_scorer_factory::WASM, ());
+    let previous = previous_scorer_factory::Client::new(&env, &address);
+    let scorer_wasm_hash = env.deployer().upload_contract_wasm(previous_scorer::WASM);
+    previous.initialize(&creator, &scorer_wasm_hash);
+
+    // The previous release did not check names, so two scorers share one
+    let alpha = create_previous_scorer(&env, &previous, &creator, 1, "Alpha");
+    let shared = [
+        create_previous_scorer(&env, &previous, &creator, 2, "Shared"),
+        create_previous_scorer(&env, &previous, &creator, 3, "shared "),
+    ];
+    let scorers = previous.get_scorers();
+    assert_eq!(scorers.len(), 3);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(scorer_factory_contract::WASM);
+    env.as_contract(&address, || env.deployer().update_current_contract_wasm(wasm_hash));
+    let client = ScorerFactoryContractClient::new(&env, &address);
+
+    // The previous layout is only readable through `migrate`
+    assert_eq!(client.try_get_scorers(), Err(Ok(scorer_factory::Error::MigrationRequired.into())));
+    assert_eq!(client.migrate(&creator, &0, &2), 2);
+    assert_eq!(client.migrate(&creator, &2, &2), 0);
+    assert_eq!(client.get_storage_version(), 2);
+    assert_eq!(client.get_scorers(), scorers);
+
+    // Of the scorers sharing a name, the first in the previous registry keeps it
+    let first = scorers.keys().iter().find(|scorer| shared.contains(scorer)).unwrap();
+    let other = shared.iter().find(|scorer| **scorer != first).unwrap();
+    assert_eq!(client.get_scorer_by_name(&String::from_str(&env, "Alpha")), alpha);
+    assert_eq!(client.get_scorer_by_name(&String::from_str(&env, "SHARED")), first);
+    assert!(!client.is_name_available(&String::from_str(&env, "Shared")));
+
+    // The others are still registered and reachable by address
+    assert_eq!(client.export_registration(other).address, *other);
+
+    // Names freed after the migration can be taken again
+    client.remove_scorer(&creator, &first);
+    assert!(client.is_name_available(&String::from_str(&env, "Shared")));
+}
@@ -0,0 +1,63 @@
+//! Size limit of the release WASM in `wasm/`.
+//!
+//! The network rejects uploads of contract code over its size limit, 64 KiB by
+//! default, so every contract must stay under it to be deployable. The limit is
+//! the network's, not a budget per contract: a contract that outgrows it has to
+//! be trimmed or have features moved to another contract. Each contract must
+//! also leave `HEADROOM` under the limit, so a fix can still be shipped without
+//! first trimming the contract. The WASM are rebuilt from source by the build
+//! script.
+
+// Default maximum size of contract code on the network, in bytes
+const MAX_WASM_SIZE: usize = 65_536;
+
+// Room each contract leaves under the limit, in bytes
+const HEADROOM: usize = 4_096;
+
+const CONTRACTS: &[&str] = &[
+    "analytics",
+    "attestation_bridge",
+    "badge_governance",
+    "badge_token",
+    "delegation_registry",
+    "deployer",
+    "disputes",
+    "factory_governance",
+    "issuer_registry",
+    "membership",
+    "mock_issuer",
+    "multisig",
+    "passkey_wallet",
+    "policy_account",
+    "price_adapter",
+    "quests",
+    "reward_distributor",
+    "scorer",
+    "scorer_factory",
+    "scoreboard",
+    "staking",
+    "timelock",
+    "upgrade_coordinator",
+];
+
+#[test]
+fn test_wasm_sizes() {
+    let mut oversized = std::vec::Vec::new();
+    for contract in CONTRACTS {
+        let path = format!("{}/wasm/{}.wasm", env!("CARGO_MANIFEST_DIR"), contract);
+        let size = std::fs::metadata(&path)
+            .unwrap_or_else(|error| panic!("cannot read {}: {}", path, error))
+            .len() as usize;
+        std::println!("{:>19}: {:>6} bytes", contract, size);
+        if size > MAX_WASM_SIZE - HEADROOM {
+            oversized.push(format!("{} is {} bytes", contract, size));
+        }
+    }
+    assert!(
+        oversized.is_empty(),
+        "WASM leaving less than {} bytes under the network limit of {} bytes: {}",
+        HEADROOM,
+        MAX_WASM_SIZE,
+        oversized.join(", "),
+    );
+}